@@ -88,7 +88,7 @@ async fn main() -> Result<()> {
                               opportunity.expected_profit);
 
                         // Execute profitable opportunities
-                        if opportunity.spread_bps >= config.strategy.min_spread_bps as f64 && opportunity.expected_profit > 1.0 {
+                        if opportunity.spread_bps >= config.strategy.min_spread_bps && opportunity.expected_profit > 1.0 {
                             let buy_order = cross_exchange_arbitrage::connectors::LimitOrder {
                                 symbol: opportunity.symbol.clone(),
                                 side: cross_exchange_arbitrage::connectors::OrderSide::Buy,
@@ -28,7 +28,7 @@ async fn main() -> Result<()> {
     info!("Dry-run executor created");
     
     // Create arbitrage strategy
-    let mut strategy = ArbitrageStrategy::new(config).await?;
+    let strategy = ArbitrageStrategy::new(config).await?;
     info!("Arbitrage strategy created");
     
     // Run the strategy
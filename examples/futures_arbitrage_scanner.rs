@@ -33,7 +33,7 @@ async fn main() -> Result<()> {
 
     // Create configuration
     let mut config = ArbitrageConfig::default();
-    config.strategy.min_spread_bps = 3; // Lower threshold for futures (higher leverage)
+    config.strategy.min_spread_bps = 3.0; // Lower threshold for futures (higher leverage)
     config.strategy.max_position_size = 0.5; // Conservative position size
     config.execution.min_order_size = 0.001;
 
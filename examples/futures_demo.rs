@@ -17,7 +17,7 @@ async fn main() -> Result<()> {
     // Configuration for futures arbitrage
     let mut config = ArbitrageConfig::default();
     config.strategy.symbol = "BTCUSDT".to_string();
-    config.strategy.min_spread_bps = 3; // Lower threshold for futures
+    config.strategy.min_spread_bps = 3.0; // Lower threshold for futures
     config.strategy.max_position_size = 0.5; // Conservative size
     config.execution.min_order_size = 0.001;
 
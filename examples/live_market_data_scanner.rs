@@ -381,7 +381,7 @@ impl LiveMarketDataManager {
 fn create_live_config() -> ArbitrageConfig {
     let mut config = ArbitrageConfig::default();
     config.strategy.symbol = "BTCUSDT".to_string();
-    config.strategy.min_spread_bps = 2; // Lower threshold for futures
+    config.strategy.min_spread_bps = 2.0; // Lower threshold for futures
     config.strategy.max_position_size = 1.0;
     config.execution.min_order_size = 0.001;
     config
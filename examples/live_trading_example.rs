@@ -28,7 +28,7 @@ async fn main() -> Result<()> {
     info!("Live trading executor created");
     
     // Create arbitrage strategy
-    let mut strategy = ArbitrageStrategy::new(config).await?;
+    let strategy = ArbitrageStrategy::new(config).await?;
     info!("Arbitrage strategy created");
     
     // Perform pre-flight checks
@@ -31,7 +31,7 @@ async fn main() -> Result<()> {
 
     // Configuration
     let mut config = ArbitrageConfig::default();
-    config.strategy.min_spread_bps = 3;
+    config.strategy.min_spread_bps = 3.0;
     config.strategy.max_position_size = 0.1; // Small size for testing
     config.execution.enable_fees = true;
     config.execution.maker_fee = -0.00025; // Bybit rebate
@@ -400,9 +400,9 @@ async fn scan_arbitrage_opportunities(
             if let (Some(bybit_bid), Some(binance_ask)) = (bybit_book.best_bid(), binance_book.best_ask()) {
                 if bybit_bid > binance_ask {
                     let spread = bybit_bid - binance_ask;
-                    let spread_bps = (spread / binance_ask * 10000.0).round();
+                    let spread_bps = spread / binance_ask * 10000.0;
                     
-                    if spread_bps >= config.strategy.min_spread_bps as f64 {
+                    if spread_bps >= config.strategy.min_spread_bps {
                         let quantity = 0.01; // Small test size
                         let maker_rebate = bybit_bid * quantity * 0.00025; // Bybit rebate
                         let taker_cost = binance_ask * quantity * 0.0004;  // Binance fee
@@ -421,9 +421,9 @@ async fn scan_arbitrage_opportunities(
             if let (Some(bybit_ask), Some(binance_bid)) = (bybit_book.best_ask(), binance_book.best_bid()) {
                 if binance_bid > bybit_ask {
                     let spread = binance_bid - bybit_ask;
-                    let spread_bps = (spread / bybit_ask * 10000.0).round();
+                    let spread_bps = spread / bybit_ask * 10000.0;
                     
-                    if spread_bps >= config.strategy.min_spread_bps as f64 {
+                    if spread_bps >= config.strategy.min_spread_bps {
                         let quantity = 0.01; // Small test size
                         let maker_rebate = bybit_ask * quantity * 0.00025; // Bybit rebate
                         let taker_cost = binance_bid * quantity * 0.0004;  // Binance fee
@@ -15,7 +15,7 @@ async fn main() -> Result<()> {
     // Create configuration
     let mut config = ArbitrageConfig::default();
     config.strategy.symbol = "BTCUSDT".to_string();
-    config.strategy.min_spread_bps = 5;
+    config.strategy.min_spread_bps = 5.0;
     config.execution.enable_fees = true;
     config.execution.maker_fee = 0.001; // 0.1%
     config.execution.taker_fee = 0.001; // 0.1%
@@ -4,7 +4,7 @@ pub mod settings;
 
 pub use settings::*;
 
-use crate::{ArbitrageError, Result};
+use crate::{connectors::{futures::{MarginMode, PositionMode}, Exchange, OrderSide}, trading::LatencyModel, ArbitrageError, Result};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
@@ -28,8 +28,11 @@ pub struct ArbitrageConfig {
 pub struct StrategyConfig {
     /// Trading symbol
     pub symbol: String,
-    /// Minimum spread in basis points
-    pub min_spread_bps: u32,
+    /// Minimum spread in basis points; fractional values are compared at full precision
+    pub min_spread_bps: f64,
+    /// Per-symbol minimum spread in basis points, overriding `min_spread_bps`
+    #[serde(default)]
+    pub min_spread_bps_overrides: std::collections::HashMap<String, f64>,
     /// Maximum position size per exchange
     pub max_position_size: f64,
     /// Rebalance threshold
@@ -38,6 +41,141 @@ pub struct StrategyConfig {
     pub min_profit_usd: f64,
     /// Maximum concurrent positions
     pub max_concurrent_positions: u32,
+    /// Hedge leg size as a multiple of the maker leg size (1.0 for same-instrument arbitrage)
+    #[serde(default = "default_hedge_ratio")]
+    pub hedge_ratio: f64,
+    /// Risk score penalty per millisecond the critical leg's venue is slower than the other
+    #[serde(default = "default_latency_penalty_per_ms")]
+    pub latency_penalty_per_ms: f64,
+    /// Symbols scanned per detection tick, weighted by update rate (0 scans every symbol)
+    #[serde(default)]
+    pub scans_per_tick: usize,
+    /// Minimum realized profit per hour in USD before alerting (0 disables)
+    #[serde(default)]
+    pub min_profit_per_hour_usd: f64,
+    /// Rolling window for the profit-rate metric in seconds
+    #[serde(default = "default_profit_rate_window_secs")]
+    pub profit_rate_window_secs: u64,
+    /// Pause the strategy when the profit rate falls below the floor
+    #[serde(default)]
+    pub pause_on_low_profit_rate: bool,
+    /// Alert when no opportunity has been detected for this many seconds (0 disables)
+    #[serde(default)]
+    pub zero_opportunity_alert_secs: u64,
+    /// Most opportunity lines logged per `opportunity_log_interval_ms`, most profitable first (0 logs every one)
+    #[serde(default)]
+    pub opportunity_log_limit: usize,
+    /// Interval the opportunity log limit applies to in milliseconds
+    #[serde(default = "default_opportunity_log_interval_ms")]
+    pub opportunity_log_interval_ms: u64,
+    /// Window in milliseconds over which orderbook updates are coalesced before detection (0 disables)
+    #[serde(default)]
+    pub coalesce_ms: u64,
+    /// Minimum time between any two executions in milliseconds (0 disables)
+    #[serde(default)]
+    pub min_execution_interval_ms: u64,
+    /// Quote currencies traded in this session; PnL and balances are tracked per quote
+    #[serde(default = "default_quote_currencies")]
+    pub quote_currencies: Vec<String>,
+    /// Sides each venue may trade (exchange -> direction); unlisted venues trade both
+    #[serde(default)]
+    pub allowed_directions: std::collections::HashMap<String, TradeDirection>,
+    /// Time after a fill during which a symbol is not executed again in milliseconds (0 disables)
+    #[serde(default)]
+    pub post_fill_cooldown_ms: u64,
+    /// Maximum bid/ask spread on either leg's own book in basis points (0 disables)
+    #[serde(default)]
+    pub max_source_spread_bps: f64,
+    /// Number of book levels summed for the depth requirement (0 disables)
+    #[serde(default)]
+    pub min_depth_top_n: usize,
+    /// Quantity the top `min_depth_top_n` levels of each leg's traded side must exceed
+    #[serde(default)]
+    pub min_depth_quantity: f64,
+    /// Price band width levels are grouped into before the depth requirement is checked (0 disables)
+    #[serde(default)]
+    pub min_depth_band: f64,
+    /// Time after detection within which an opportunity must start executing in milliseconds (0 disables)
+    #[serde(default)]
+    pub max_decision_latency_ms: u64,
+    /// Maximum notional committed to a single opportunity in USD (0 disables)
+    #[serde(default)]
+    pub max_capital_per_opportunity_usd: f64,
+    /// Pause a quote currency when its USD reference price drifts from $1 by more than this (0 disables)
+    #[serde(default)]
+    pub max_stablecoin_depeg_bps: f64,
+    /// Book whose mid prices a quote stablecoin in another stablecoin (currency -> symbol, e.g. USDC -> USDCUSDT)
+    #[serde(default)]
+    pub stablecoin_reference_symbols: std::collections::HashMap<String, String>,
+    /// Drop symbols not listed on every exchange at startup instead of failing
+    #[serde(default = "default_exclude_unlisted_symbols")]
+    pub exclude_unlisted_symbols: bool,
+    /// Scale opportunity size by confidence, volatility and risk
+    #[serde(default)]
+    pub confidence_sizing: ConfidenceSizingConfig,
+    /// How far ahead of the local wall clock a book timestamp may be before it is logged as a clock jump
+    #[serde(default = "default_clock_skew_tolerance_ms")]
+    pub clock_skew_tolerance_ms: u64,
+    /// Currency PnL, fees and volume are aggregated into
+    #[serde(default = "default_reporting_currency")]
+    pub reporting_currency: String,
+    /// Value of one unit of a currency in the reporting currency (currency -> rate)
+    #[serde(default)]
+    pub conversion_rates: std::collections::HashMap<String, f64>,
+    /// Which strategies to run; the `--strategy` flag overrides it
+    #[serde(default)]
+    pub mode: StrategyMode,
+    /// Size at which spot spreads are priced by sweeping both books' depth (0 uses the touch)
+    #[serde(default)]
+    pub executable_spread_size: f64,
+}
+
+impl StrategyConfig {
+    /// Minimum spread for a symbol: its override if set, the global threshold otherwise
+    pub fn min_spread_bps_for(&self, symbol: &str) -> f64 {
+        self.min_spread_bps_overrides.get(symbol).copied().unwrap_or(self.min_spread_bps)
+    }
+    
+    /// Quote currency of a symbol, matching the longest configured suffix
+    pub fn quote_currency(&self, symbol: &str) -> Option<&str> {
+        self.quote_currencies
+            .iter()
+            .filter(|quote| symbol.ends_with(quote.as_str()) && symbol.len() > quote.len())
+            .max_by_key(|quote| quote.len())
+            .map(|quote| quote.as_str())
+    }
+    
+    /// Stablecoin a reference book prices and its USD price at `mid`, if `symbol` is a reference pair.
+    ///
+    /// The other leg of the pair is taken as $1, so a currency quoting the
+    /// pair (USDT in USDCUSDT) is priced at the inverse of the mid.
+    pub fn stablecoin_reference_price(&self, symbol: &str, mid: f64) -> Option<(&str, f64)> {
+        if mid <= 0.0 {
+            return None;
+        }
+        let (currency, _) = self.stablecoin_reference_symbols.iter().find(|(_, reference)| reference.as_str() == symbol)?;
+        if symbol.starts_with(currency.as_str()) {
+            Some((currency.as_str(), mid))
+        } else if symbol.ends_with(currency.as_str()) {
+            Some((currency.as_str(), 1.0 / mid))
+        } else {
+            None
+        }
+    }
+    
+    /// Whether a stablecoin's USD price has drifted from $1 by more than `max_stablecoin_depeg_bps`
+    pub fn is_depegged(&self, usd_price: f64) -> bool {
+        self.max_stablecoin_depeg_bps > 0.0 && (usd_price - 1.0).abs() * 10000.0 > self.max_stablecoin_depeg_bps
+    }
+    
+    /// Whether an exchange may trade the given side
+    pub fn allows_side(&self, exchange: &str, side: OrderSide) -> bool {
+        self.allowed_directions
+            .get(&exchange.to_lowercase())
+            .copied()
+            .unwrap_or_default()
+            .allows(side)
+    }
 }
 
 /// Risk management configuration
@@ -53,6 +191,176 @@ pub struct RiskConfig {
     pub daily_loss_limit: f64,
     /// Volatility threshold
     pub volatility_threshold: f64,
+    /// Profit-taking ladder, in ascending order of `profit_bps`
+    #[serde(default)]
+    pub profit_targets: Vec<ProfitTarget>,
+    /// Maximum absolute net position across exchanges after a pair executes
+    #[serde(default = "default_net_position_tolerance")]
+    pub net_position_tolerance: f64,
+    /// Flatten residual net exposure with a reduce-only market order
+    #[serde(default)]
+    pub flatten_residual: bool,
+    /// Cap on the combined USD notional of all open positions (0 disables)
+    #[serde(default)]
+    pub max_total_notional_usd: f64,
+    /// Balance kept off-limits to trading (exchange -> asset -> amount)
+    #[serde(default)]
+    pub min_reserves: std::collections::HashMap<String, std::collections::HashMap<String, f64>>,
+    /// Positions open longer than this many seconds are flagged as stuck (0 disables)
+    #[serde(default)]
+    pub max_position_age_secs: u64,
+    /// Flatten positions that exceed `max_position_age_secs` with an IOC order
+    #[serde(default)]
+    pub flatten_aged_positions: bool,
+    /// Flatten open positions with IOC orders during emergency shutdown, after resting orders are cancelled
+    #[serde(default)]
+    pub flatten_on_shutdown: bool,
+    /// Manual stop triggered by a control file or signal
+    #[serde(default)]
+    pub kill_switch: KillSwitchConfig,
+    /// Take-profit distance from entry in bps for OCO brackets on directional positions (0 disables)
+    #[serde(default)]
+    pub bracket_take_profit_bps: f64,
+    /// Stop distance from entry in bps for OCO brackets on directional positions (0 disables)
+    #[serde(default)]
+    pub bracket_stop_bps: f64,
+    /// Cap on a single symbol's position value in USD (0 disables)
+    #[serde(default)]
+    pub max_position_value_usd: f64,
+    /// Per-symbol limits taking precedence over the global ones (canonical symbol -> overrides)
+    #[serde(default)]
+    pub symbol_overrides: std::collections::HashMap<String, SymbolRiskOverride>,
+}
+
+/// Per-symbol risk limits; unset fields fall back to the global values
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SymbolRiskOverride {
+    /// Maximum position size for the symbol
+    #[serde(default)]
+    pub max_position_size: Option<f64>,
+    /// Stop loss in basis points for the symbol
+    #[serde(default)]
+    pub stop_loss_bps: Option<u32>,
+    /// Cap on the symbol's position value in USD (0 disables)
+    #[serde(default)]
+    pub max_position_value_usd: Option<f64>,
+}
+
+/// Risk limits in effect for one symbol after applying overrides
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SymbolRiskLimits {
+    /// Maximum position size
+    pub max_position_size: f64,
+    /// Stop loss in basis points
+    pub stop_loss_bps: u32,
+    /// Cap on position value in USD (0 disables)
+    pub max_position_value_usd: f64,
+}
+
+fn default_exclude_unlisted_symbols() -> bool {
+    true
+}
+
+fn default_clock_skew_tolerance_ms() -> u64 {
+    1000
+}
+
+/// Confidence-scaled sizing configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfidenceSizingConfig {
+    /// Scale quantities below the hard limits; when off opportunities size to the cap
+    #[serde(default)]
+    pub enabled: bool,
+    /// Book age in milliseconds at which confidence reaches zero (0 ignores age)
+    #[serde(default = "default_confidence_max_book_age_ms")]
+    pub max_book_age_ms: u64,
+    /// Volatility in basis points at which size is halved (0 ignores volatility)
+    #[serde(default = "default_confidence_volatility_reference_bps")]
+    pub volatility_reference_bps: f64,
+}
+
+impl Default for ConfidenceSizingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_book_age_ms: default_confidence_max_book_age_ms(),
+            volatility_reference_bps: default_confidence_volatility_reference_bps(),
+        }
+    }
+}
+
+fn default_confidence_max_book_age_ms() -> u64 {
+    2000
+}
+
+fn default_confidence_volatility_reference_bps() -> f64 {
+    10.0
+}
+
+/// Kill switch configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KillSwitchConfig {
+    /// Trigger emergency shutdown when this file exists
+    #[serde(default)]
+    pub control_file: Option<String>,
+    /// Trigger emergency shutdown on SIGUSR1 (Unix only)
+    #[serde(default)]
+    pub sigusr1: bool,
+    /// How often the control file is checked in milliseconds
+    #[serde(default = "default_kill_switch_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// File of operator commands (`disable bybit`, `enable bybit`), one per line; consumed when read
+    #[serde(default)]
+    pub command_file: Option<String>,
+}
+
+impl Default for KillSwitchConfig {
+    fn default() -> Self {
+        Self {
+            control_file: None,
+            sigusr1: false,
+            poll_interval_ms: default_kill_switch_poll_interval_ms(),
+            command_file: None,
+        }
+    }
+}
+
+fn default_kill_switch_poll_interval_ms() -> u64 {
+    500
+}
+
+impl RiskConfig {
+    /// Reserve held back for an asset on an exchange
+    pub fn min_reserve(&self, exchange: &str, asset: &str) -> f64 {
+        self.min_reserves
+            .get(&exchange.to_lowercase())
+            .and_then(|reserves| reserves.get(asset))
+            .copied()
+            .unwrap_or(0.0)
+    }
+    
+    /// Fail unless `available` of an asset on an exchange covers `required` without
+    /// dipping into the configured minimum reserve
+    pub fn check_reserve(&self, exchange: &str, asset: &str, available: f64, required: f64) -> Result<()> {
+        let min_reserve = self.min_reserve(exchange, asset);
+        let spendable = available - min_reserve;
+        if spendable < required {
+            return Err(ArbitrageError::RiskManagement(format!(
+                "Insufficient {} on {}: need {:.2}, available {:.2} after {:.2} reserve",
+                asset, exchange, required, spendable, min_reserve
+            )).into());
+        }
+        Ok(())
+    }
+}
+
+/// Profit-taking ladder level
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfitTarget {
+    /// Unrealized profit in basis points of entry at which this level fires
+    pub profit_bps: f64,
+    /// Fraction of the initial position size to close at this level
+    pub fraction: f64,
 }
 
 /// Execution configuration
@@ -97,12 +405,292 @@ pub struct ExecutionConfig {
     /// Minimum fill ratio for partial fills (dry-run only)
     #[serde(default = "default_min_fill_ratio")]
     pub min_fill_ratio: f64,
+    /// Number of maker orders laddered across successive ticks (1 = single order at the touch)
+    #[serde(default = "default_ladder_levels")]
+    pub ladder_levels: u32,
+    /// Size distribution across ladder levels
+    #[serde(default)]
+    pub ladder_distribution: LadderDistribution,
+    /// Size ratio between consecutive levels for geometric ladders
+    #[serde(default = "default_ladder_geometric_ratio")]
+    pub ladder_geometric_ratio: f64,
+    /// Reserve margin on both legs before placing either order (atomic engine only)
+    #[serde(default = "default_two_phase_commit")]
+    pub two_phase_commit: bool,
+    /// Margin mode applied to futures symbols before placing orders
+    #[serde(default)]
+    pub margin_mode: MarginMode,
+    /// Futures position mode per exchange name; venues not listed are detected from the account
+    #[serde(default)]
+    pub position_modes: std::collections::HashMap<String, PositionMode>,
+    /// File active orders are persisted to and restored from across restarts (unset disables)
+    #[serde(default)]
+    pub active_orders_file: Option<String>,
+    /// Leverage set on every futures symbol at startup
+    #[serde(default = "default_leverage")]
+    pub leverage: u32,
+    /// How leg quantities are rounded to lot size
+    #[serde(default)]
+    pub rounding_mode: RoundingMode,
+    /// Slice the hedge into child orders of this fraction of visible top-of-book depth (0 disables)
+    #[serde(default)]
+    pub hedge_slice_depth_fraction: f64,
+    /// Pause between hedge child orders in milliseconds
+    #[serde(default = "default_hedge_slice_pause_ms")]
+    pub hedge_slice_pause_ms: u64,
+    /// How long fetched balances are reused before querying exchanges again (0 disables caching)
+    #[serde(default = "default_balance_cache_ttl_ms")]
+    pub balance_cache_ttl_ms: u64,
+    /// Size of the canary order sent to a venue before full-size execution (0 disables)
+    #[serde(default)]
+    pub canary_size: f64,
+    /// Venues idle for at least this many seconds are probed with a canary first
+    #[serde(default = "default_canary_idle_secs")]
+    pub canary_idle_secs: u64,
+    /// Send same-venue orders as one batch request where the exchange supports it
+    #[serde(default)]
+    pub batch_orders: bool,
+    /// Abort before placing the maker order if either leg's book has moved against
+    /// the detected prices by more than this many bps (0 disables)
+    #[serde(default)]
+    pub last_look_tolerance_bps: f64,
+    /// Abort if either leg's price is more than this many bps from its venue's mark price (0 disables)
+    #[serde(default)]
+    pub max_mark_deviation_bps: f64,
+    /// Mark prices older than this are ignored by mark-based guards in milliseconds (0 disables)
+    #[serde(default)]
+    pub max_mark_price_age_ms: u64,
+    /// On a filter reject, refresh the symbol's rules, re-round the order and retry once
+    #[serde(default = "default_retry_on_filter_reject")]
+    pub retry_on_filter_reject: bool,
+    /// Take-profit attached to the maker order, in bps from its price (0 disables)
+    #[serde(default)]
+    pub maker_take_profit_bps: f64,
+    /// Stop-loss attached to the maker order, in bps from its price (0 disables)
+    #[serde(default)]
+    pub maker_stop_loss_bps: f64,
+    /// Ticks to improve the maker price by, within the venue's tick and percent-price filters (0 disables)
+    #[serde(default)]
+    pub maker_improvement_ticks: u32,
+    /// Replay every live fill through the dry-run simulator and record the divergence
+    #[serde(default)]
+    pub shadow_dry_run: bool,
+    /// Refuse orders below the exchange's minimum notional, computed the way the exchange does
+    #[serde(default = "default_enforce_min_notional")]
+    pub enforce_min_notional: bool,
+    /// Log the execution plan of each opportunity instead of placing orders
+    #[serde(default)]
+    pub preview_only: bool,
+    /// How the two legs of an opportunity are placed
+    #[serde(default)]
+    pub execution_engine: ExecutionEngine,
+    /// Simulated fill latency per exchange name (dry-run only); venues not listed use a jittered fraction of the order timeout
+    #[serde(default)]
+    pub fill_latency: std::collections::HashMap<String, LatencyModel>,
+}
+
+/// Markets traded by a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StrategyMode {
+    /// Spot cross-exchange arbitrage only
+    #[default]
+    Spot,
+    /// Futures maker/taker arbitrage only
+    Futures,
+    /// Spot and futures concurrently, under one portfolio risk limit
+    Both,
+}
+
+impl StrategyMode {
+    /// Whether the spot strategy runs in this mode
+    pub fn runs_spot(self) -> bool {
+        matches!(self, StrategyMode::Spot | StrategyMode::Both)
+    }
+
+    /// Whether the futures strategy runs in this mode
+    pub fn runs_futures(self) -> bool {
+        matches!(self, StrategyMode::Futures | StrategyMode::Both)
+    }
+}
+
+impl std::fmt::Display for StrategyMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StrategyMode::Spot => write!(f, "spot"),
+            StrategyMode::Futures => write!(f, "futures"),
+            StrategyMode::Both => write!(f, "both"),
+        }
+    }
+}
+
+impl std::str::FromStr for StrategyMode {
+    type Err = ArbitrageError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "spot" => Ok(StrategyMode::Spot),
+            "futures" => Ok(StrategyMode::Futures),
+            "both" => Ok(StrategyMode::Both),
+            _ => Err(ArbitrageError::Config(format!("Unknown strategy mode: {} (expected spot, futures or both)", s))),
+        }
+    }
+}
+
+/// Placement strategy for the two legs of an opportunity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionEngine {
+    /// Reserve margin on both legs, place the maker, then the hedge; a failed
+    /// hedge cancels the maker and a failed maker sends no hedge
+    #[default]
+    Atomic,
+    /// Send both legs at once without reserving margin; whichever leg succeeded
+    /// is unwound when the other fails
+    Concurrent,
+}
+
+fn default_enforce_min_notional() -> bool {
+    true
+}
+
+fn default_retry_on_filter_reject() -> bool {
+    true
+}
+
+fn default_max_rate_limit_retries() -> u32 {
+    3
+}
+
+fn default_ws_subscriptions_per_sec() -> f64 {
+    // Binance allows 5 incoming messages per second per connection
+    5.0
+}
+
+fn default_ws_subscription_batch_size() -> usize {
+    10
+}
+
+fn default_ws_subscription_ack_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_quote_currencies() -> Vec<String> {
+    vec!["USDT".to_string()]
+}
+
+fn default_reporting_currency() -> String {
+    "USDT".to_string()
+}
+
+fn default_profit_rate_window_secs() -> u64 {
+    3600
+}
+
+fn default_opportunity_log_interval_ms() -> u64 {
+    1000
+}
+
+fn default_hedge_ratio() -> f64 {
+    1.0
+}
+
+fn default_latency_penalty_per_ms() -> f64 {
+    0.1
 }
 
 fn default_min_fill_ratio() -> f64 {
     0.1
 }
 
+fn default_ladder_levels() -> u32 {
+    1
+}
+
+fn default_ladder_geometric_ratio() -> f64 {
+    0.5
+}
+
+fn default_resync_on_gap() -> bool {
+    true
+}
+
+fn default_snapshot_bootstrap() -> bool {
+    true
+}
+
+fn default_two_phase_commit() -> bool {
+    true
+}
+
+fn default_leverage() -> u32 {
+    1
+}
+
+fn default_hedge_slice_pause_ms() -> u64 {
+    50
+}
+
+fn default_balance_cache_ttl_ms() -> u64 {
+    1000
+}
+
+fn default_canary_idle_secs() -> u64 {
+    3600
+}
+
+fn default_net_position_tolerance() -> f64 {
+    0.001
+}
+
+/// Size distribution for laddered maker orders
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LadderDistribution {
+    /// Equal size at every level
+    #[default]
+    Flat,
+    /// Each level is `ladder_geometric_ratio` times the size of the previous one
+    Geometric,
+}
+
+/// Lot-size rounding applied to leg quantities
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// Round both legs down
+    #[default]
+    Down,
+    /// Round both legs to the nearest lot
+    Nearest,
+    /// Round the maker leg down and the hedge leg up so the hedge always covers it
+    TowardHedge,
+}
+
+/// Sides a venue is allowed to trade
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TradeDirection {
+    /// Buy and sell
+    #[default]
+    Both,
+    /// Buy only
+    BuyOnly,
+    /// Sell only
+    SellOnly,
+}
+
+impl TradeDirection {
+    /// Whether this direction permits the given side
+    pub fn allows(self, side: OrderSide) -> bool {
+        match self {
+            TradeDirection::Both => true,
+            TradeDirection::BuyOnly => side == OrderSide::Buy,
+            TradeDirection::SellOnly => side == OrderSide::Sell,
+        }
+    }
+}
+
 /// Monitoring configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MonitoringConfig {
@@ -116,6 +704,157 @@ pub struct MonitoringConfig {
     pub log_rotation_size_mb: u64,
     /// Health check interval in seconds
     pub health_check_interval_secs: u64,
+    /// Health-transition webhook notifications
+    #[serde(default)]
+    pub webhook: WebhookConfig,
+    /// SQLite persistence of opportunities and executed orders
+    #[serde(default)]
+    pub trade_store: TradeStoreConfig,
+    /// Prometheus metrics endpoint
+    #[serde(default)]
+    pub prometheus: PrometheusConfig,
+    /// Hash-chained audit log of order activity
+    #[serde(default)]
+    pub audit_log: AuditLogConfig,
+    /// How often cached order books are checked for corruption in milliseconds (0 disables)
+    #[serde(default)]
+    pub book_integrity_interval_ms: u64,
+}
+
+/// Order audit log configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogConfig {
+    /// Append every order placement, cancel and fill to the audit log
+    #[serde(default)]
+    pub enabled: bool,
+    /// Path of the JSON-lines audit log file
+    #[serde(default = "default_audit_log_path")]
+    pub path: String,
+}
+
+impl Default for AuditLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: default_audit_log_path(),
+        }
+    }
+}
+
+fn default_audit_log_path() -> String {
+    "data/audit.log".to_string()
+}
+
+/// Prometheus metrics endpoint configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrometheusConfig {
+    /// Serve metrics over HTTP
+    #[serde(default)]
+    pub enabled: bool,
+    /// Address the `/metrics` endpoint listens on
+    #[serde(default = "default_prometheus_listen_addr")]
+    pub listen_addr: String,
+    /// Upper bounds of the spread-capture histogram buckets in basis points
+    #[serde(default = "default_spread_capture_buckets_bps")]
+    pub spread_capture_buckets_bps: Vec<f64>,
+}
+
+impl Default for PrometheusConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen_addr: default_prometheus_listen_addr(),
+            spread_capture_buckets_bps: default_spread_capture_buckets_bps(),
+        }
+    }
+}
+
+fn default_prometheus_listen_addr() -> String {
+    "127.0.0.1:9898".to_string()
+}
+
+fn default_spread_capture_buckets_bps() -> Vec<f64> {
+    vec![0.0, 1.0, 2.0, 5.0, 10.0, 20.0, 50.0, 100.0]
+}
+
+/// Trade history persistence configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeStoreConfig {
+    /// Write opportunities and executed orders to SQLite
+    #[serde(default)]
+    pub enabled: bool,
+    /// Path of the SQLite database file
+    #[serde(default = "default_trade_store_path")]
+    pub path: String,
+    /// Buffered writes that trigger a flush (0 disables batching)
+    #[serde(default = "default_trade_store_batch_size")]
+    pub batch_size: usize,
+    /// Longest a buffered write waits before being flushed, in milliseconds
+    #[serde(default = "default_trade_store_flush_interval_ms")]
+    pub flush_interval_ms: u64,
+}
+
+impl Default for TradeStoreConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: default_trade_store_path(),
+            batch_size: default_trade_store_batch_size(),
+            flush_interval_ms: default_trade_store_flush_interval_ms(),
+        }
+    }
+}
+
+fn default_trade_store_path() -> String {
+    "data/trades.db".to_string()
+}
+
+fn default_trade_store_batch_size() -> usize {
+    100
+}
+
+fn default_trade_store_flush_interval_ms() -> u64 {
+    1000
+}
+
+/// Health webhook notification configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    /// URL to POST health events to (disabled when unset)
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Retries after a failed delivery
+    #[serde(default = "default_webhook_max_retries")]
+    pub max_retries: u32,
+    /// Minimum seconds between notifications of the same event type
+    #[serde(default = "default_webhook_min_interval_secs")]
+    pub min_interval_secs: u64,
+    /// Timeout of each delivery attempt in milliseconds
+    #[serde(default = "default_webhook_request_timeout_ms")]
+    pub request_timeout_ms: u64,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            max_retries: default_webhook_max_retries(),
+            min_interval_secs: default_webhook_min_interval_secs(),
+            request_timeout_ms: default_webhook_request_timeout_ms(),
+        }
+    }
+}
+
+fn default_webhook_max_retries() -> u32 {
+    3
+}
+
+fn default_webhook_min_interval_secs() -> u64 {
+    60
+}
+
+fn default_webhook_request_timeout_ms() -> u64 {
+    2000
 }
 
 /// Exchange list configuration
@@ -125,6 +864,91 @@ pub struct ExchangeListConfig {
     pub enabled: Vec<String>,
     /// Primary exchange for reference pricing
     pub primary_exchange: String,
+    /// Fallback reference exchanges, in order, used when the primary's book is stale
+    #[serde(default)]
+    pub reference_exchanges: Vec<String>,
+    /// Age in milliseconds after which a reference book is stale
+    #[serde(default = "default_reference_max_age_ms")]
+    pub reference_max_age_ms: u64,
+    /// Per-exchange tickers for canonical symbols (canonical -> exchange -> ticker)
+    #[serde(default)]
+    pub symbol_aliases: std::collections::HashMap<String, std::collections::HashMap<String, String>>,
+    /// Reconnect-storm protection
+    #[serde(default)]
+    pub reconnect_guard: ReconnectGuardConfig,
+}
+
+/// Reconnect-storm guard configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconnectGuardConfig {
+    /// Reconnect failures within the window that trigger the cool-down
+    #[serde(default = "default_reconnect_max_failures")]
+    pub max_failures: u32,
+    /// Window in seconds over which failures are counted
+    #[serde(default = "default_reconnect_failure_window_secs")]
+    pub failure_window_secs: u64,
+    /// Cool-down in seconds before reconnection is attempted again
+    #[serde(default = "default_reconnect_cooldown_secs")]
+    pub cooldown_secs: u64,
+}
+
+impl Default for ReconnectGuardConfig {
+    fn default() -> Self {
+        Self {
+            max_failures: default_reconnect_max_failures(),
+            failure_window_secs: default_reconnect_failure_window_secs(),
+            cooldown_secs: default_reconnect_cooldown_secs(),
+        }
+    }
+}
+
+fn default_reconnect_max_failures() -> u32 {
+    5
+}
+
+fn default_reconnect_failure_window_secs() -> u64 {
+    60
+}
+
+fn default_reconnect_cooldown_secs() -> u64 {
+    600
+}
+
+fn default_reference_max_age_ms() -> u64 {
+    1000
+}
+
+impl ExchangeListConfig {
+    /// Reference exchanges in priority order: the primary, then the fallbacks
+    pub fn reference_order(&self) -> Vec<String> {
+        let mut order = vec![self.primary_exchange.to_lowercase()];
+        for exchange in &self.reference_exchanges {
+            let exchange = exchange.to_lowercase();
+            if !order.contains(&exchange) {
+                order.push(exchange);
+            }
+        }
+        order
+    }
+
+    /// Ticker an exchange uses for a canonical symbol
+    pub fn exchange_symbol(&self, canonical: &str, exchange: &str) -> String {
+        self.symbol_aliases
+            .get(canonical)
+            .and_then(|aliases| aliases.get(&exchange.to_lowercase()))
+            .cloned()
+            .unwrap_or_else(|| canonical.to_string())
+    }
+
+    /// Canonical symbol for an exchange-specific ticker
+    pub fn canonical_symbol(&self, exchange: &str, symbol: &str) -> String {
+        let exchange = exchange.to_lowercase();
+        self.symbol_aliases
+            .iter()
+            .find(|(_, aliases)| aliases.get(&exchange).map(String::as_str) == Some(symbol))
+            .map(|(canonical, _)| canonical.clone())
+            .unwrap_or_else(|| symbol.to_string())
+    }
 }
 
 /// Individual exchange configuration
@@ -159,6 +983,34 @@ pub struct ConnectionConfig {
     pub max_reconnect_attempts: u32,
     /// Reconnection delay in seconds
     pub reconnect_delay_secs: u64,
+    /// Retries after a rate-limit (429/418) response before giving up
+    #[serde(default = "default_max_rate_limit_retries")]
+    pub max_rate_limit_retries: u32,
+    /// Compression the venue applies to binary WebSocket frames
+    #[serde(default)]
+    pub ws_compression: WsCompression,
+    /// Subscription requests sent per second on one WebSocket (0 disables pacing)
+    #[serde(default = "default_ws_subscriptions_per_sec")]
+    pub ws_subscriptions_per_sec: f64,
+    /// Streams carried by one subscription request
+    #[serde(default = "default_ws_subscription_batch_size")]
+    pub ws_subscription_batch_size: usize,
+    /// How long to wait for a subscription request's ack in milliseconds
+    #[serde(default = "default_ws_subscription_ack_timeout_ms")]
+    pub ws_subscription_ack_timeout_ms: u64,
+}
+
+/// Compression applied to binary WebSocket frames before they are JSON-parsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WsCompression {
+    /// Binary frames are plain UTF-8
+    #[default]
+    None,
+    /// Binary frames are gzip streams
+    Gzip,
+    /// Binary frames are deflate streams, with or without a zlib header
+    Deflate,
 }
 
 /// Authentication configuration
@@ -183,6 +1035,9 @@ pub struct TradingConfig {
     pub default_order_type: String,
     /// Default time in force
     pub default_time_in_force: String,
+    /// Order types the system may place, in addition to the default
+    #[serde(default)]
+    pub order_types: Vec<String>,
     /// Additional trading settings (exchange-specific)
     #[serde(flatten)]
     pub additional: std::collections::HashMap<String, serde_json::Value>,
@@ -226,6 +1081,12 @@ pub struct MarketDataConfig {
     pub topics: Vec<String>,
     /// Order book depth levels
     pub depth_levels: u32,
+    /// Rebuild the book from a REST snapshot when a sequence gap is detected
+    #[serde(default = "default_resync_on_gap")]
+    pub resync_on_gap: bool,
+    /// Buffer deltas until a REST snapshot bootstraps the book, instead of building it from the first delta
+    #[serde(default = "default_snapshot_bootstrap")]
+    pub snapshot_bootstrap: bool,
     /// Additional market data settings
     #[serde(flatten)]
     pub additional: std::collections::HashMap<String, serde_json::Value>,
@@ -243,6 +1104,12 @@ impl ArbitrageConfig {
         // Expand environment variables
         config.expand_env_vars()?;
         
+        // Reserves are looked up by lowercase exchange name
+        config.risk.min_reserves = std::mem::take(&mut config.risk.min_reserves)
+            .into_iter()
+            .map(|(exchange, reserves)| (exchange.to_lowercase(), reserves))
+            .collect();
+        
         Ok(config)
     }
     
@@ -253,24 +1120,134 @@ impl ArbitrageConfig {
             return Err(ArbitrageError::Config("Symbol cannot be empty".to_string()).into());
         }
         
-        if self.strategy.min_spread_bps == 0 {
+        if self.strategy.min_spread_bps <= 0.0 {
             return Err(ArbitrageError::Config("Minimum spread must be greater than 0".to_string()).into());
         }
         
+        for (symbol, min_spread_bps) in &self.strategy.min_spread_bps_overrides {
+            if *min_spread_bps <= 0.0 {
+                return Err(ArbitrageError::Config(format!("Minimum spread override for {} must be greater than 0", symbol)).into());
+            }
+        }
+        
         if self.strategy.max_position_size <= 0.0 {
             return Err(ArbitrageError::Config("Maximum position size must be positive".to_string()).into());
         }
         
+        if self.strategy.hedge_ratio <= 0.0 {
+            return Err(ArbitrageError::Config("Hedge ratio must be positive".to_string()).into());
+        }
+        
+        if self.strategy.latency_penalty_per_ms < 0.0 {
+            return Err(ArbitrageError::Config("Latency penalty cannot be negative".to_string()).into());
+        }
+        
+        if self.strategy.max_source_spread_bps < 0.0 {
+            return Err(ArbitrageError::Config("Max source spread cannot be negative".to_string()).into());
+        }
+        
+        if self.strategy.min_depth_quantity < 0.0 {
+            return Err(ArbitrageError::Config("Min depth quantity cannot be negative".to_string()).into());
+        }
+        
+        if self.strategy.max_capital_per_opportunity_usd < 0.0 {
+            return Err(ArbitrageError::Config("Max capital per opportunity cannot be negative".to_string()).into());
+        }
+        
+        if self.strategy.max_stablecoin_depeg_bps < 0.0 {
+            return Err(ArbitrageError::Config("Max stablecoin depeg cannot be negative".to_string()).into());
+        }
+        
+        if self.strategy.confidence_sizing.volatility_reference_bps < 0.0 {
+            return Err(ArbitrageError::Config("Confidence sizing volatility reference cannot be negative".to_string()).into());
+        }
+        
+        if self.strategy.quote_currencies.is_empty() {
+            return Err(ArbitrageError::Config("At least one quote currency must be configured".to_string()).into());
+        }
+        
+        if self.strategy.reporting_currency.is_empty() {
+            return Err(ArbitrageError::Config("Reporting currency cannot be empty".to_string()).into());
+        }
+        
+        if let Some((currency, symbol)) = self.strategy.stablecoin_reference_symbols.iter()
+            .find(|(currency, symbol)| !symbol.starts_with(currency.as_str()) && !symbol.ends_with(currency.as_str())) {
+            return Err(ArbitrageError::Config(format!("Stablecoin reference {} does not price {}", symbol, currency)).into());
+        }
+        
+        if let Some((currency, _)) = self.strategy.conversion_rates.iter().find(|(_, rate)| **rate <= 0.0) {
+            return Err(ArbitrageError::Config(format!("Conversion rate for {} must be positive", currency)).into());
+        }
+        
         // Validate risk config
         if self.risk.max_drawdown <= 0.0 || self.risk.max_drawdown >= 1.0 {
             return Err(ArbitrageError::Config("Max drawdown must be between 0 and 1".to_string()).into());
         }
         
+        let total_fraction: f64 = self.risk.profit_targets.iter().map(|t| t.fraction).sum();
+        if self.risk.profit_targets.iter().any(|t| t.fraction <= 0.0) || total_fraction > 1.0 + 1e-9 {
+            return Err(ArbitrageError::Config("Profit target fractions must be positive and sum to at most 1".to_string()).into());
+        }
+        
+        if self.risk.min_reserves.values().flat_map(|reserves| reserves.values()).any(|reserve| *reserve < 0.0) {
+            return Err(ArbitrageError::Config("Minimum reserves cannot be negative".to_string()).into());
+        }
+        
+        if self.risk.max_total_notional_usd < 0.0 {
+            return Err(ArbitrageError::Config("Max total notional cannot be negative".to_string()).into());
+        }
+        
+        if self.risk.net_position_tolerance < 0.0 {
+            return Err(ArbitrageError::Config("Net position tolerance cannot be negative".to_string()).into());
+        }
+        
+        if self.risk.profit_targets.windows(2).any(|w| w[1].profit_bps <= w[0].profit_bps) {
+            return Err(ArbitrageError::Config("Profit targets must be in ascending order of profit_bps".to_string()).into());
+        }
+        
         // Validate execution config
         if self.execution.order_timeout_ms == 0 {
             return Err(ArbitrageError::Config("Order timeout must be greater than 0".to_string()).into());
         }
         
+        if self.execution.ladder_levels == 0 {
+            return Err(ArbitrageError::Config("Ladder levels must be at least 1".to_string()).into());
+        }
+        
+        if self.execution.ladder_geometric_ratio <= 0.0 {
+            return Err(ArbitrageError::Config("Ladder geometric ratio must be positive".to_string()).into());
+        }
+        
+        if !(0.0..=1.0).contains(&self.execution.hedge_slice_depth_fraction) {
+            return Err(ArbitrageError::Config("Hedge slice depth fraction must be between 0 and 1".to_string()).into());
+        }
+        
+        if self.execution.leverage == 0 {
+            return Err(ArbitrageError::Config("Leverage must be at least 1".to_string()).into());
+        }
+        
+        for (exchange, model) in &self.execution.fill_latency {
+            exchange.parse::<Exchange>()?;
+            if let LatencyModel::LogNormal { mean_ms, .. } = model {
+                if *mean_ms <= 0.0 {
+                    return Err(ArbitrageError::Config(format!("Log-normal fill latency mean for {} must be positive", exchange)).into());
+                }
+            }
+        }
+        
+        if self.risk.max_position_value_usd < 0.0 {
+            return Err(ArbitrageError::Config("Max position value cannot be negative".to_string()).into());
+        }
+        
+        for (symbol, overrides) in &self.risk.symbol_overrides {
+            if overrides.max_position_size.is_some_and(|size| size <= 0.0) {
+                return Err(ArbitrageError::Config(format!("Max position size override for {} must be positive", symbol)).into());
+            }
+            if overrides.max_position_value_usd.is_some_and(|value| value < 0.0) {
+                return Err(ArbitrageError::Config(format!("Max position value override for {} cannot be negative", symbol)).into());
+            }
+        }
+        
         // Validate exchanges
         if self.exchanges.enabled.is_empty() {
             return Err(ArbitrageError::Config("At least one exchange must be enabled".to_string()).into());
@@ -280,18 +1257,82 @@ impl ArbitrageConfig {
             return Err(ArbitrageError::Config("At least two exchanges required for arbitrage".to_string()).into());
         }
         
+        for exchange in self.exchanges.reference_order() {
+            exchange.parse::<crate::connectors::Exchange>()?;
+        }
+        
         Ok(())
     }
     
-    /// Expand environment variables in configuration
+    /// Risk limits for a symbol: its overrides where set, the globals otherwise
+    pub fn risk_limits(&self, symbol: &str) -> SymbolRiskLimits {
+        let overrides = self.risk.symbol_overrides.get(symbol).cloned().unwrap_or_default();
+        SymbolRiskLimits {
+            max_position_size: overrides.max_position_size.unwrap_or(self.strategy.max_position_size),
+            stop_loss_bps: overrides.stop_loss_bps.unwrap_or(self.risk.stop_loss_bps),
+            max_position_value_usd: overrides.max_position_value_usd.unwrap_or(self.risk.max_position_value_usd),
+        }
+    }
+    
+    /// Expand `${VAR}` and `${VAR:-default}` references in every string field
     fn expand_env_vars(&mut self) -> Result<()> {
-        // This is a simplified implementation
-        // In a real implementation, you would recursively traverse all string fields
-        // and expand ${VAR_NAME} patterns
+        *self = expand_env_vars_in(self)?;
         Ok(())
     }
 }
 
+/// Copy of `config` with environment references expanded in every string,
+/// including those inside free-form `additional` maps
+fn expand_env_vars_in<T: Serialize + serde::de::DeserializeOwned>(config: &T) -> Result<T> {
+    let mut value = serde_json::to_value(config)
+        .map_err(|e| ArbitrageError::Config(format!("Failed to expand environment variables: {}", e)))?;
+    expand_value(&mut value)?;
+    serde_json::from_value(value)
+        .map_err(|e| ArbitrageError::Config(format!("Failed to expand environment variables: {}", e)).into())
+}
+
+fn expand_value(value: &mut serde_json::Value) -> Result<()> {
+    match value {
+        serde_json::Value::String(s) => *s = expand_str(s)?,
+        serde_json::Value::Array(items) => items.iter_mut().try_for_each(expand_value)?,
+        serde_json::Value::Object(fields) => fields.values_mut().try_for_each(expand_value)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Substitute each `${VAR}` with the variable's value; `${VAR:-default}` falls
+/// back to `default` when the variable is unset or empty. An unterminated `${`
+/// is kept as written.
+fn expand_str(s: &str) -> Result<String> {
+    let mut expanded = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        expanded.push_str(&rest[..start]);
+        let reference = &rest[start + 2..start + 2 + len];
+        let (name, default) = match reference.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (reference, None),
+        };
+        match (std::env::var(name), default) {
+            (Ok(value), Some(default)) if value.is_empty() => expanded.push_str(default),
+            (Ok(value), _) => expanded.push_str(&value),
+            (Err(_), Some(default)) => expanded.push_str(default),
+            (Err(_), None) => {
+                return Err(ArbitrageError::Config(
+                    format!("Environment variable '{}' referenced in config is not set", name)
+                ).into());
+            }
+        }
+        rest = &rest[start + 2 + len + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
 impl ExchangeConfig {
     /// Load exchange configuration from a TOML file
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
@@ -301,8 +1342,46 @@ impl ExchangeConfig {
         let config: ExchangeConfig = toml::from_str(&content)
             .map_err(|e| ArbitrageError::Config(format!("Failed to parse exchange config: {}", e)))?;
         
+        expand_env_vars_in(&config)
+    }
+    
+    /// Load and validate the configuration for a named exchange
+    pub fn from_file_for_exchange<P: AsRef<Path>>(path: P, exchange: &str) -> Result<Self> {
+        let config = Self::from_file(path)?;
+        config.validate_order_types(exchange)?;
         Ok(config)
     }
+    
+    /// Check that every configured order type is supported by the exchange
+    pub fn validate_order_types(&self, exchange: &str) -> Result<()> {
+        let supported = match supported_order_types(exchange) {
+            Some(supported) => supported,
+            None => return Ok(()),
+        };
+        
+        let configured = std::iter::once(&self.trading.default_order_type).chain(&self.trading.order_types);
+        for order_type in configured {
+            if !supported.contains(&order_type.as_str()) {
+                return Err(ArbitrageError::Config(format!(
+                    "Order type '{}' is not supported on {} (supported: {})",
+                    order_type, exchange, supported.join(", ")
+                )).into());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Spot order types each exchange accepts, using the exchange's own spelling
+fn supported_order_types(exchange: &str) -> Option<&'static [&'static str]> {
+    match exchange.to_lowercase().as_str() {
+        "binance" => Some(&[
+            "LIMIT", "MARKET", "STOP_LOSS", "STOP_LOSS_LIMIT",
+            "TAKE_PROFIT", "TAKE_PROFIT_LIMIT", "LIMIT_MAKER",
+        ]),
+        "bybit" => Some(&["Limit", "Market"]),
+        _ => None,
+    }
 }
 
 impl Default for ArbitrageConfig {
@@ -310,11 +1389,41 @@ impl Default for ArbitrageConfig {
         Self {
             strategy: StrategyConfig {
                 symbol: "BTCUSDT".to_string(),
-                min_spread_bps: 10,
+                min_spread_bps: 10.0,
+                min_spread_bps_overrides: std::collections::HashMap::new(),
                 max_position_size: 1.0,
                 rebalance_threshold: 0.1,
                 min_profit_usd: 5.0,
                 max_concurrent_positions: 3,
+                hedge_ratio: 1.0,
+                latency_penalty_per_ms: 0.1,
+                scans_per_tick: 0,
+                min_profit_per_hour_usd: 0.0,
+                profit_rate_window_secs: 3600,
+                pause_on_low_profit_rate: false,
+                zero_opportunity_alert_secs: 0,
+                opportunity_log_limit: 0,
+                opportunity_log_interval_ms: default_opportunity_log_interval_ms(),
+                coalesce_ms: 0,
+                min_execution_interval_ms: 0,
+                quote_currencies: default_quote_currencies(),
+                allowed_directions: std::collections::HashMap::new(),
+                post_fill_cooldown_ms: 0,
+                max_source_spread_bps: 0.0,
+                min_depth_top_n: 0,
+                min_depth_quantity: 0.0,
+                min_depth_band: 0.0,
+                max_decision_latency_ms: 0,
+                max_capital_per_opportunity_usd: 0.0,
+                max_stablecoin_depeg_bps: 0.0,
+                stablecoin_reference_symbols: std::collections::HashMap::new(),
+                exclude_unlisted_symbols: default_exclude_unlisted_symbols(),
+                confidence_sizing: ConfidenceSizingConfig::default(),
+                clock_skew_tolerance_ms: default_clock_skew_tolerance_ms(),
+                reporting_currency: default_reporting_currency(),
+                conversion_rates: std::collections::HashMap::new(),
+                mode: StrategyMode::Spot,
+                executable_spread_size: 0.0,
             },
             risk: RiskConfig {
                 max_drawdown: 0.05,
@@ -322,6 +1431,19 @@ impl Default for ArbitrageConfig {
                 position_limit: 10.0,
                 daily_loss_limit: 1000.0,
                 volatility_threshold: 0.1,
+                profit_targets: Vec::new(),
+                net_position_tolerance: default_net_position_tolerance(),
+                flatten_residual: false,
+                max_total_notional_usd: 0.0,
+                max_position_age_secs: 0,
+                flatten_aged_positions: false,
+                flatten_on_shutdown: false,
+                kill_switch: KillSwitchConfig::default(),
+                bracket_take_profit_bps: 0.0,
+                bracket_stop_bps: 0.0,
+                max_position_value_usd: 0.0,
+                symbol_overrides: std::collections::HashMap::new(),
+                min_reserves: std::collections::HashMap::new(),
             },
             execution: ExecutionConfig {
                 order_timeout_ms: 5000,
@@ -339,6 +1461,33 @@ impl Default for ArbitrageConfig {
                 partial_fill_probability: 0.0,
                 rejection_probability: 0.0,
                 min_fill_ratio: 0.1,
+                ladder_levels: 1,
+                ladder_distribution: LadderDistribution::Flat,
+                ladder_geometric_ratio: 0.5,
+                two_phase_commit: true,
+                margin_mode: MarginMode::default(),
+                position_modes: std::collections::HashMap::new(),
+                active_orders_file: None,
+                leverage: default_leverage(),
+                rounding_mode: RoundingMode::Down,
+                hedge_slice_depth_fraction: 0.0,
+                hedge_slice_pause_ms: default_hedge_slice_pause_ms(),
+                balance_cache_ttl_ms: default_balance_cache_ttl_ms(),
+                canary_size: 0.0,
+                canary_idle_secs: default_canary_idle_secs(),
+                batch_orders: false,
+                last_look_tolerance_bps: 0.0,
+                max_mark_deviation_bps: 0.0,
+                max_mark_price_age_ms: 0,
+                retry_on_filter_reject: default_retry_on_filter_reject(),
+                maker_take_profit_bps: 0.0,
+                maker_stop_loss_bps: 0.0,
+                maker_improvement_ticks: 0,
+                shadow_dry_run: false,
+                enforce_min_notional: default_enforce_min_notional(),
+                preview_only: false,
+                execution_engine: ExecutionEngine::Atomic,
+                fill_latency: std::collections::HashMap::new(),
             },
             monitoring: MonitoringConfig {
                 enable_metrics: true,
@@ -346,10 +1495,19 @@ impl Default for ArbitrageConfig {
                 enable_trade_logging: true,
                 log_rotation_size_mb: 100,
                 health_check_interval_secs: 30,
+                webhook: WebhookConfig::default(),
+                trade_store: TradeStoreConfig::default(),
+                prometheus: PrometheusConfig::default(),
+                audit_log: AuditLogConfig::default(),
+                book_integrity_interval_ms: 0,
             },
             exchanges: ExchangeListConfig {
                 enabled: vec!["binance".to_string(), "bybit".to_string()],
                 primary_exchange: "binance".to_string(),
+                reference_exchanges: Vec::new(),
+                reference_max_age_ms: default_reference_max_age_ms(),
+                symbol_aliases: std::collections::HashMap::new(),
+                reconnect_guard: ReconnectGuardConfig::default(),
             },
         }
     }
@@ -370,11 +1528,22 @@ mod tests {
     #[test]
     fn test_invalid_config_validation() {
         let mut config = ArbitrageConfig::default();
-        config.strategy.min_spread_bps = 0;
+        config.strategy.min_spread_bps = 0.0;
         
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_fill_latency_validation() {
+        let mut config = ArbitrageConfig::default();
+        config.execution.fill_latency.insert("binance".to_string(), LatencyModel::LogNormal { mean_ms: 0.0, std_dev_ms: 5.0 });
+        assert!(config.validate().is_err());
+        
+        let mut config = ArbitrageConfig::default();
+        config.execution.fill_latency.insert("kraken".to_string(), LatencyModel::Fixed { ms: 5.0 });
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_config_serialization() {
         let config = ArbitrageConfig::default();
@@ -396,4 +1565,77 @@ mod tests {
         let loaded_config = ArbitrageConfig::from_file(temp_file.path()).unwrap();
         assert_eq!(config.strategy.symbol, loaded_config.strategy.symbol);
     }
+
+    #[test]
+    fn test_min_reserve_keys_lowercased_on_load() {
+        let mut config = ArbitrageConfig::default();
+        config.risk.min_reserves.insert(
+            "Bybit".to_string(),
+            std::collections::HashMap::from([("USDT".to_string(), 500.0)]),
+        );
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(toml::to_string(&config).unwrap().as_bytes()).unwrap();
+        
+        let loaded_config = ArbitrageConfig::from_file(temp_file.path()).unwrap();
+        assert!(loaded_config.risk.min_reserves.contains_key("bybit"));
+        assert_eq!(loaded_config.risk.min_reserve("bybit", "USDT"), 500.0);
+    }
+
+    #[test]
+    fn test_env_vars_expanded_on_load() {
+        std::env::set_var("ARB_TEST_EXPAND_SYMBOL", "ETHUSDT");
+        std::env::remove_var("ARB_TEST_EXPAND_UNSET");
+        let mut config = ArbitrageConfig::default();
+        config.strategy.symbol = "${ARB_TEST_EXPAND_SYMBOL}".to_string();
+        config.strategy.reporting_currency = "${ARB_TEST_EXPAND_UNSET:-USDT}".to_string();
+        config.monitoring.audit_log.path = "logs/${ARB_TEST_EXPAND_SYMBOL}/audit.log".to_string();
+        
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(toml::to_string(&config).unwrap().as_bytes()).unwrap();
+        
+        let loaded = ArbitrageConfig::from_file(temp_file.path()).unwrap();
+        assert_eq!(loaded.strategy.symbol, "ETHUSDT");
+        assert_eq!(loaded.strategy.reporting_currency, "USDT");
+        assert_eq!(loaded.monitoring.audit_log.path, "logs/ETHUSDT/audit.log");
+    }
+
+    #[test]
+    fn test_exchange_env_vars_expanded_including_additional_settings() {
+        std::env::set_var("ARB_TEST_EXPAND_API_KEY", "key-123");
+        let mut config = crate::connectors::test_exchange_config("https://api.binance.com");
+        config.auth.api_key = "${ARB_TEST_EXPAND_API_KEY}".to_string();
+        config.trading.additional.insert(
+            "sub_account".to_string(),
+            serde_json::json!({ "names": ["${ARB_TEST_EXPAND_API_KEY}-sub", "${ARB_TEST_EXPAND_MISSING:-}"] }),
+        );
+        
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(toml::to_string(&config).unwrap().as_bytes()).unwrap();
+        
+        let loaded = ExchangeConfig::from_file(temp_file.path()).unwrap();
+        assert_eq!(loaded.auth.api_key, "key-123");
+        assert_eq!(loaded.trading.additional["sub_account"], serde_json::json!({ "names": ["key-123-sub", ""] }));
+        
+        config.auth.secret_key = "${ARB_TEST_EXPAND_MISSING}".to_string();
+        let err = expand_env_vars_in(&config).unwrap_err();
+        assert!(matches!(err.downcast_ref::<ArbitrageError>(), Some(ArbitrageError::Config(_))));
+        assert!(err.to_string().contains("ARB_TEST_EXPAND_MISSING"), "{}", err);
+    }
+
+    #[test]
+    fn test_unsupported_order_type_fails_load() {
+        let mut config = crate::connectors::test_exchange_config("https://api.bybit.com");
+        config.trading.default_order_type = "Limit".to_string();
+        config.trading.order_types = vec!["STOP_LIMIT".to_string()];
+        
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(toml::to_string(&config).unwrap().as_bytes()).unwrap();
+        
+        let err = ExchangeConfig::from_file_for_exchange(temp_file.path(), "bybit").unwrap_err();
+        assert!(matches!(err.downcast_ref::<ArbitrageError>(), Some(ArbitrageError::Config(_))));
+        assert!(err.to_string().contains("'STOP_LIMIT' is not supported on bybit"), "{}", err);
+        
+        config.trading.order_types = vec!["Market".to_string()];
+        assert!(config.validate_order_types("bybit").is_ok());
+    }
 }
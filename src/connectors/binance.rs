@@ -1,78 +1,135 @@
 //! Binance exchange connector implementation
 
 use crate::{
-    config::ExchangeConfig,
+    config::{ConnectionConfig, ExchangeConfig, WsCompression},
     connectors::{
+        compression::decode_ws_frame,
+        http::{get_with_backoff, send_with_backoff},
+        sequence::{DepthDelta, DepthFeed, DepthSnapshot, DepthSnapshotSource},
+        serde_helpers::{f64_from_str_or_num, parse_level},
         traits::*,
         ConnectionStatus,
     },
     data::OrderBook,
+    utils::time::{ms_to_ns, now_ns},
     ArbitrageError,
     Result,
 };
 use async_trait::async_trait;
-use serde::{Deserialize, Serialize};
+use futures_util::stream::{SplitSink, SplitStream};
+use futures_util::{SinkExt, StreamExt};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Deserializer, Serialize};
+use sha2::Sha256;
 use std::collections::HashMap;
-use tokio::sync::mpsc;
+use std::sync::Arc;
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, oneshot};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
 use tracing::{debug, error, info, warn};
 use url::Url;
 
+/// Binance error code for an order failing a symbol filter
+const FILTER_FAILURE: i64 = -1013;
+
+/// Binance error code for a price or quantity with more decimals than allowed
+const TOO_MUCH_PRECISION: i64 = -1111;
+
+/// Market data updates buffered between the WebSocket task and the consumer
+const MARKET_DATA_CHANNEL_CAPACITY: usize = 1000;
+
+type BinanceWsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Subscription requests awaiting their ack, by request ID; errors carry the venue's message
+type PendingAcks = Arc<std::sync::Mutex<HashMap<u64, oneshot::Sender<std::result::Result<(), String>>>>>;
+
 /// Binance exchange connector
 pub struct BinanceConnector {
     config: ExchangeConfig,
     connection_status: ConnectionStatus,
     market_data_tx: Option<mpsc::Sender<MarketDataUpdate>>,
+    /// Receiver paired with `market_data_tx`, handed out once
+    market_data_rx: std::sync::Mutex<Option<mpsc::Receiver<MarketDataUpdate>>>,
     order_update_tx: Option<mpsc::Sender<OrderUpdate>>,
     websocket_client: Option<BinanceWebSocketClient>,
     rest_client: BinanceRestClient,
+    symbol_rules: HashMap<String, SymbolRules>,
+    /// Books built from the depth diff stream, shared with the reader task
+    depth_feed: DepthFeed,
 }
 
 impl BinanceConnector {
     /// Create a new Binance connector
     pub async fn new(config: ExchangeConfig) -> Result<Self> {
         let rest_client = BinanceRestClient::new(&config)?;
+        let depth_feed = DepthFeed::new(
+            crate::connectors::Exchange::Binance,
+            &config.market_data,
+            Arc::new(rest_client.clone()),
+        );
         
         Ok(Self {
             config,
             connection_status: ConnectionStatus::Disconnected,
             market_data_tx: None,
+            market_data_rx: std::sync::Mutex::new(None),
             order_update_tx: None,
             websocket_client: None,
             rest_client,
+            symbol_rules: HashMap::new(),
+            depth_feed,
+        })
+    }
+    
+    /// Map a full (`RESULT`) order response onto the shared order type
+    fn order_response(response: BinanceOrderResponse) -> Result<OrderResponse> {
+        Ok(OrderResponse {
+            order_id: response.order_id.to_string(),
+            client_order_id: Some(response.client_order_id),
+            symbol: response.symbol,
+            side: response.side.parse()?,
+            quantity: response.orig_qty,
+            price: response.price,
+            status: response.status,
+            filled_quantity: response.executed_qty,
+            average_price: if response.executed_qty > 0.0 {
+                Some(response.cummulative_quote_qty / response.executed_qty)
+            } else {
+                None
+            },
+            timestamp: ms_to_ns(response.transact_time),
         })
     }
     
-    /// Parse a depth message from Binance WebSocket
-    pub fn parse_depth_message(message: &str) -> Result<OrderBook> {
+    /// Fetch a REST depth snapshot together with its `lastUpdateId`
+    pub async fn get_depth_snapshot(&self, symbol: &str) -> Result<DepthSnapshot> {
+        self.rest_client.depth_snapshot(symbol).await
+    }
+    
+    /// Parse a diff depth message into a delta carrying its `U`/`u` update ID range
+    pub fn parse_depth_delta(message: &str) -> Result<DepthDelta> {
         let data: BinanceDepthMessage = serde_json::from_str(message)
             .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse depth message: {}", e)))?;
         
-        let mut orderbook = OrderBook::new(
-            data.stream.split('@').next().unwrap_or("").to_uppercase(),
-            crate::connectors::Exchange::Binance,
-        );
-        
-        // Update bids
-        for bid in data.data.bids {
-            let price: f64 = bid[0].parse()
-                .map_err(|e| ArbitrageError::DataParsing(format!("Invalid bid price: {}", e)))?;
-            let quantity: f64 = bid[1].parse()
-                .map_err(|e| ArbitrageError::DataParsing(format!("Invalid bid quantity: {}", e)))?;
-            orderbook.update_bid(price, quantity);
-        }
-        
-        // Update asks
-        for ask in data.data.asks {
-            let price: f64 = ask[0].parse()
-                .map_err(|e| ArbitrageError::DataParsing(format!("Invalid ask price: {}", e)))?;
-            let quantity: f64 = ask[1].parse()
-                .map_err(|e| ArbitrageError::DataParsing(format!("Invalid ask quantity: {}", e)))?;
-            orderbook.update_ask(price, quantity);
-        }
-        
-        orderbook.set_timestamp(chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0));
+        let (first_update_id, final_update_id) = match (data.data.first_update_id, data.data.final_update_id) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Err(ArbitrageError::DataParsing(
+                format!("Depth message on {} has no update IDs", data.stream)
+            ).into()),
+        };
+        let parse_levels = |levels: Vec<[String; 2]>| -> Result<Vec<(f64, f64)>> {
+            levels.iter().map(|level| parse_level(&level[0], &level[1])).collect()
+        };
         
-        Ok(orderbook)
+        Ok(DepthDelta {
+            first_update_id,
+            final_update_id,
+            bids: parse_levels(data.data.bids)?,
+            asks: parse_levels(data.data.asks)?,
+            exchange_timestamp: data.data.event_time.map(ms_to_ns),
+            local_recv_timestamp: now_ns(),
+        })
     }
     
     /// Parse a trade message from Binance WebSocket
@@ -81,15 +138,54 @@ impl BinanceConnector {
             .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse trade message: {}", e)))?;
         
         let symbol = data.stream.split('@').next().unwrap_or("").to_uppercase();
-        let price: f64 = data.data.price.parse()
-            .map_err(|e| ArbitrageError::DataParsing(format!("Invalid trade price: {}", e)))?;
-        let quantity: f64 = data.data.quantity.parse()
-            .map_err(|e| ArbitrageError::DataParsing(format!("Invalid trade quantity: {}", e)))?;
+        let (price, quantity) = parse_level(&data.data.price, &data.data.quantity)?;
         let side = if data.data.is_buyer_maker { OrderSide::Sell } else { OrderSide::Buy };
-        let timestamp = data.data.trade_time;
+        let timestamp = ms_to_ns(data.data.trade_time);
         
         Ok((symbol, price, quantity, side, timestamp))
     }
+    
+    /// Subscribe to raw stream names in paced, acknowledged batches
+    pub async fn subscribe_streams(&mut self, streams: &[String]) -> Result<()> {
+        match &mut self.websocket_client {
+            Some(client) => client.subscribe_many(streams).await,
+            None => Err(ArbitrageError::Connection("Not connected to Binance".to_string()).into()),
+        }
+    }
+    
+    /// Parse a combined-stream frame into a market data update.
+    ///
+    /// Depth diffs only become updates once applied to a [`DepthFeed`], so
+    /// they yield `None` here, as do frames without a `stream` envelope, such
+    /// as subscription acks, and streams other than trades.
+    pub fn parse_market_data(message: &str) -> Result<Option<MarketDataUpdate>> {
+        let stream = match stream_name(message)? {
+            Some(stream) => stream,
+            None => return Ok(None),
+        };
+        
+        if stream.ends_with("@trade") {
+            let (symbol, price, quantity, side, timestamp) = Self::parse_trade_message(message)?;
+            Ok(Some(MarketDataUpdate::Trade {
+                exchange: crate::connectors::Exchange::Binance.to_string(),
+                symbol,
+                price,
+                quantity,
+                side,
+                timestamp,
+            }))
+        } else {
+            debug!("Ignoring Binance stream {}", stream);
+            Ok(None)
+        }
+    }
+}
+
+#[async_trait]
+impl DepthSnapshotSource for BinanceConnector {
+    async fn depth_snapshot(&self, symbol: &str) -> Result<DepthSnapshot> {
+        self.rest_client.depth_snapshot(symbol).await
+    }
 }
 
 #[async_trait]
@@ -106,9 +202,12 @@ impl ExchangeConnector for BinanceConnector {
             &self.config.connection.websocket_url
         };
         
-        match BinanceWebSocketClient::new(ws_url).await {
+        let (tx, rx) = mpsc::channel(MARKET_DATA_CHANNEL_CAPACITY);
+        match BinanceWebSocketClient::connect(ws_url, &self.config.connection, tx.clone(), self.depth_feed.clone()).await {
             Ok(client) => {
                 self.websocket_client = Some(client);
+                self.market_data_tx = Some(tx);
+                *self.market_data_rx.lock().unwrap() = Some(rx);
                 self.connection_status = ConnectionStatus::Connected;
                 info!("Successfully connected to Binance");
                 Ok(())
@@ -127,6 +226,7 @@ impl ExchangeConnector for BinanceConnector {
         if let Some(mut client) = self.websocket_client.take() {
             client.disconnect().await?;
         }
+        self.market_data_tx = None;
         
         self.connection_status = ConnectionStatus::Disconnected;
         info!("Disconnected from Binance");
@@ -141,6 +241,11 @@ impl ExchangeConnector for BinanceConnector {
         self.connection_status
     }
     
+    /// Subscribe to the symbol's diff depth stream and seed its book from a REST snapshot.
+    ///
+    /// Diffs arriving before the snapshot are buffered by the feed and those it
+    /// already covers are dropped. If the snapshot cannot seed the book, the
+    /// feed fetches another on the next diff.
     async fn subscribe_orderbook(&mut self, symbol: &str) -> Result<()> {
         debug!("Subscribing to orderbook for symbol: {}", symbol);
         
@@ -148,10 +253,14 @@ impl ExchangeConnector for BinanceConnector {
             let stream = format!("{}@depth@100ms", symbol.to_lowercase());
             client.subscribe(&stream).await?;
             info!("Subscribed to Binance orderbook for {}", symbol);
-            Ok(())
         } else {
-            Err(ArbitrageError::Connection("Not connected to Binance".to_string()).into())
+            return Err(ArbitrageError::Connection("Not connected to Binance".to_string()).into());
         }
+        
+        if let Err(e) = self.depth_feed.seed(symbol).await {
+            warn!("Could not seed Binance {} book from a snapshot yet: {}", symbol, e);
+        }
+        Ok(())
     }
     
     async fn subscribe_trades(&mut self, symbol: &str) -> Result<()> {
@@ -181,33 +290,7 @@ impl ExchangeConnector for BinanceConnector {
     }
     
     async fn get_orderbook(&self, symbol: &str) -> Result<OrderBook> {
-        debug!("Getting orderbook snapshot for symbol: {}", symbol);
-        
-        let snapshot = self.rest_client.get_orderbook_snapshot(symbol).await?;
-        
-        let mut orderbook = OrderBook::new(symbol.to_string(), crate::connectors::Exchange::Binance);
-        
-        // Update bids
-        for bid in snapshot.bids {
-            let price: f64 = bid[0].parse()
-                .map_err(|e| ArbitrageError::DataParsing(format!("Invalid bid price: {}", e)))?;
-            let quantity: f64 = bid[1].parse()
-                .map_err(|e| ArbitrageError::DataParsing(format!("Invalid bid quantity: {}", e)))?;
-            orderbook.update_bid(price, quantity);
-        }
-        
-        // Update asks
-        for ask in snapshot.asks {
-            let price: f64 = ask[0].parse()
-                .map_err(|e| ArbitrageError::DataParsing(format!("Invalid ask price: {}", e)))?;
-            let quantity: f64 = ask[1].parse()
-                .map_err(|e| ArbitrageError::DataParsing(format!("Invalid ask quantity: {}", e)))?;
-            orderbook.update_ask(price, quantity);
-        }
-        
-        orderbook.set_timestamp(chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0));
-        
-        Ok(orderbook)
+        Ok(self.get_depth_snapshot(symbol).await?.book)
     }
     
     async fn get_balances(&self) -> Result<HashMap<String, Balance>> {
@@ -233,22 +316,24 @@ impl ExchangeConnector for BinanceConnector {
         debug!("Placing limit order: {:?}", order);
         
         let response = self.rest_client.place_order(order).await?;
+        Self::order_response(response)
+    }
+    
+    async fn place_market_order(&self, order: &MarketOrder) -> Result<OrderResponse> {
+        debug!("Placing market order: {:?}", order);
         
-        Ok(OrderResponse {
-            order_id: response.order_id.to_string(),
-            client_order_id: Some(response.client_order_id),
-            symbol: response.symbol,
-            side: response.side,
-            quantity: response.orig_qty,
-            price: response.price,
-            status: response.status,
-            filled_quantity: response.executed_qty,
-            average_price: if response.executed_qty > 0.0 {
-                Some(response.cummulative_quote_qty / response.executed_qty)
-            } else {
-                None
-            },
-            timestamp: response.transact_time,
+        let response = self.rest_client.place_market_order(order).await?;
+        Self::order_response(response)
+    }
+    
+    async fn place_oco(&self, order: &OcoOrder) -> Result<OcoResponse> {
+        debug!("Placing OCO order: {:?}", order);
+        
+        let response = self.rest_client.place_oco(order).await?;
+        
+        Ok(OcoResponse {
+            list_id: response.order_list_id.to_string(),
+            order_ids: response.orders.iter().map(|order| order.order_id.to_string()).collect(),
         })
     }
     
@@ -261,7 +346,7 @@ impl ExchangeConnector for BinanceConnector {
             order_id: response.order_id.to_string(),
             client_order_id: Some(response.client_order_id),
             symbol: response.symbol,
-            side: response.side,
+            side: response.side.parse()?,
             quantity: response.orig_qty,
             price: response.price,
             status: response.status,
@@ -271,7 +356,7 @@ impl ExchangeConnector for BinanceConnector {
             } else {
                 None
             },
-            timestamp: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
+            timestamp: now_ns(),
         })
     }
     
@@ -282,10 +367,78 @@ impl ExchangeConnector for BinanceConnector {
         Ok(order.status)
     }
     
+    async fn get_order(&self, symbol: &str, order_id: &str) -> Result<OrderResponse> {
+        debug!("Getting order: {} for symbol: {}", order_id, symbol);
+        
+        Self::order_response(self.rest_client.get_order(symbol, order_id).await?)
+    }
+    
+    async fn get_open_orders(&self, symbol: Option<&str>) -> Result<Vec<OrderResponse>> {
+        debug!("Getting open orders for {:?}", symbol);
+        
+        let orders = self.rest_client.get_open_orders(symbol).await?;
+        orders.into_iter().map(BinanceOpenOrder::into_order_response).collect()
+    }
+    
     fn get_market_data_receiver(&self) -> Option<mpsc::Receiver<MarketDataUpdate>> {
-        // This would return a receiver for market data updates
-        // Implementation depends on how we structure the message handling
-        None
+        // Each connection's receiver is handed out once
+        self.market_data_rx.lock().unwrap().take()
+    }
+    
+    async fn refresh_symbol_rules(&mut self) -> Result<()> {
+        let exchange_info = self.rest_client.get_exchange_info().await?;
+        
+        for symbol_info in exchange_info.symbols {
+            let mut tick_size = None;
+            let mut lot = None;
+            let mut min_notional = "0";
+            let mut apply_min_to_market = true;
+            for filter in &symbol_info.filters {
+                match filter {
+                    BinanceSymbolFilter::Price { tick_size: tick } => tick_size = Some(tick.as_str()),
+                    BinanceSymbolFilter::LotSize { step_size, min_qty } => lot = Some((step_size.as_str(), min_qty.as_str())),
+                    BinanceSymbolFilter::Notional { min_notional: notional, apply_min_to_market: to_market } => {
+                        min_notional = notional.as_str();
+                        apply_min_to_market = *to_market;
+                    }
+                    BinanceSymbolFilter::Other => {}
+                }
+            }
+            
+            let (tick_size, (step_size, min_qty)) = match (tick_size, lot) {
+                (Some(tick_size), Some(lot)) => (tick_size, lot),
+                _ => {
+                    debug!("Skipping {} without price/lot filters", symbol_info.symbol);
+                    continue;
+                }
+            };
+            
+            let rules = SymbolRules {
+                apply_min_to_market,
+                ..SymbolRules::from_decimal_strings(&symbol_info.symbol, tick_size, step_size, min_qty, min_notional)?
+            };
+            self.config.limits.tick_sizes.insert(rules.symbol.clone(), rules.tick_size);
+            self.config.limits.min_order_sizes.insert(rules.symbol.clone(), rules.min_quantity);
+            self.symbol_rules.insert(rules.symbol.clone(), rules);
+        }
+        
+        info!("Loaded trading rules for {} Binance symbols", self.symbol_rules.len());
+        Ok(())
+    }
+    
+    fn symbol_rules(&self, symbol: &str) -> Option<SymbolRules> {
+        self.symbol_rules.get(symbol).cloned()
+    }
+    
+    async fn update_credentials(&mut self, api_key: &str, secret_key: &str) -> Result<()> {
+        validate_credentials(api_key, secret_key)?;
+        
+        // Market data streams are unauthenticated, so only the REST signer changes
+        self.rest_client.set_credentials(api_key, secret_key);
+        self.config.auth.api_key = api_key.to_string();
+        self.config.auth.secret_key = secret_key.to_string();
+        info!("Binance API credentials rotated");
+        Ok(())
     }
     
     fn get_order_update_receiver(&self) -> Option<mpsc::Receiver<OrderUpdate>> {
@@ -295,44 +448,236 @@ impl ExchangeConnector for BinanceConnector {
     }
 }
 
-/// Binance WebSocket client
+/// Binance WebSocket client on the combined-stream endpoint.
+///
+/// The write half stays here for subscriptions; a background task owns the
+/// read half, forwards parsed updates to the connector's channel and routes
+/// subscription acks back to the request waiting on them.
 struct BinanceWebSocketClient {
     url: String,
-    // WebSocket connection would be stored here
-    // For now, we'll use a placeholder
+    sink: SplitSink<BinanceWsStream, Message>,
+    reader: tokio::task::JoinHandle<()>,
+    pending_acks: PendingAcks,
+    next_request_id: u64,
+    /// Minimum gap between subscription requests, if paced
+    request_interval: Option<std::time::Duration>,
+    batch_size: usize,
+    ack_timeout: std::time::Duration,
+    last_request_at: Option<tokio::time::Instant>,
 }
 
 impl BinanceWebSocketClient {
-    async fn new(url: &str) -> Result<Self> {
-        // Validate URL
-        Url::parse(url)
-            .map_err(|e| ArbitrageError::Connection(format!("Invalid WebSocket URL: {}", e)))?;
+    /// Open the connection and start forwarding market data to `tx`, building
+    /// depth books in `depth_feed`
+    async fn connect(
+        url: &str,
+        connection: &ConnectionConfig,
+        tx: mpsc::Sender<MarketDataUpdate>,
+        depth_feed: DepthFeed,
+    ) -> Result<Self> {
+        let url = combined_stream_url(url)?;
+        let (ws_stream, _) = connect_async(&url)
+            .await
+            .map_err(|e| ArbitrageError::Connection(format!("WebSocket connection to {} failed: {}", url, e)))?;
+        let (sink, stream) = ws_stream.split();
+        let pending_acks = PendingAcks::default();
+        let reader = tokio::spawn(read_market_data(stream, connection.ws_compression, tx, pending_acks.clone(), depth_feed));
         
         Ok(Self {
-            url: url.to_string(),
+            url,
+            sink,
+            reader,
+            pending_acks,
+            next_request_id: 1,
+            request_interval: (connection.ws_subscriptions_per_sec > 0.0)
+                .then(|| std::time::Duration::from_secs_f64(1.0 / connection.ws_subscriptions_per_sec)),
+            batch_size: connection.ws_subscription_batch_size.max(1),
+            ack_timeout: std::time::Duration::from_millis(connection.ws_subscription_ack_timeout_ms),
+            last_request_at: None,
         })
     }
     
     async fn subscribe(&mut self, stream: &str) -> Result<()> {
-        debug!("Subscribing to stream: {}", stream);
-        // WebSocket subscription logic would go here
-        // For now, we'll just log the subscription
+        self.subscribe_many(&[stream.to_string()]).await
+    }
+    
+    /// Subscribe in batches, each sent no sooner than the configured rate
+    /// allows and only after the previous batch was acknowledged
+    async fn subscribe_many(&mut self, streams: &[String]) -> Result<()> {
+        for batch in streams.chunks(self.batch_size) {
+            if let (Some(interval), Some(last)) = (self.request_interval, self.last_request_at) {
+                tokio::time::sleep_until(last + interval).await;
+            }
+            
+            let id = self.next_request_id;
+            self.next_request_id += 1;
+            let (ack_tx, ack_rx) = oneshot::channel();
+            self.pending_acks.lock().unwrap().insert(id, ack_tx);
+            
+            debug!("Subscribing to streams: {}", batch.join(", "));
+            let request = serde_json::json!({ "method": "SUBSCRIBE", "params": batch, "id": id });
+            self.last_request_at = Some(tokio::time::Instant::now());
+            if let Err(e) = self.sink.send(Message::Text(request.to_string())).await {
+                self.pending_acks.lock().unwrap().remove(&id);
+                return Err(ArbitrageError::Connection(
+                    format!("Failed to subscribe to {} on {}: {}", batch.join(", "), self.url, e)
+                ).into());
+            }
+            
+            match tokio::time::timeout(self.ack_timeout, ack_rx).await {
+                Ok(Ok(Ok(()))) => {}
+                Ok(Ok(Err(message))) => {
+                    return Err(ArbitrageError::Connection(
+                        format!("Binance rejected subscription to {}: {}", batch.join(", "), message)
+                    ).into());
+                }
+                Ok(Err(_)) => {
+                    return Err(ArbitrageError::Connection(
+                        format!("WebSocket closed before acknowledging subscription to {}", batch.join(", "))
+                    ).into());
+                }
+                Err(_) => {
+                    self.pending_acks.lock().unwrap().remove(&id);
+                    return Err(ArbitrageError::Timeout(
+                        format!("No ack for subscription to {} within {:?}", batch.join(", "), self.ack_timeout)
+                    ).into());
+                }
+            }
+        }
         Ok(())
     }
     
     async fn disconnect(&mut self) -> Result<()> {
         debug!("Disconnecting WebSocket client");
-        // WebSocket disconnection logic would go here
+        self.reader.abort();
+        if let Err(e) = self.sink.close().await {
+            debug!("WebSocket close failed: {}", e);
+        }
         Ok(())
     }
 }
 
+/// Combined-stream form of a Binance WebSocket URL: `.../ws` becomes `.../stream`,
+/// so every frame arrives wrapped in a `{"stream", "data"}` envelope
+fn combined_stream_url(url: &str) -> Result<String> {
+    Url::parse(url)
+        .map_err(|e| ArbitrageError::Connection(format!("Invalid WebSocket URL: {}", e)))?;
+    let base = url.trim_end_matches('/');
+    Ok(match base.strip_suffix("/ws") {
+        Some(root) => format!("{}/stream", root),
+        None => base.to_string(),
+    })
+}
+
+/// Request ID and outcome of a subscription response, or `None` for stream data.
+///
+/// Success is `{"result":null,"id":1}`; errors carry a code and message,
+/// either at the top level or in an `error` object.
+fn parse_subscription_ack(message: &str) -> Option<(u64, std::result::Result<(), String>)> {
+    let response: serde_json::Value = serde_json::from_str(message).ok()?;
+    if response.get("stream").is_some() {
+        return None;
+    }
+    let id = response.get("id")?.as_u64()?;
+    let error = response.get("error").unwrap_or(&response);
+    match error.get("code") {
+        Some(code) => {
+            let message = error.get("msg").and_then(|msg| msg.as_str()).unwrap_or("unknown error");
+            Some((id, Err(format!("{} (code {})", message, code))))
+        }
+        None => Some((id, Ok(()))),
+    }
+}
+
+/// Forward market data frames until the socket closes or the receiver is dropped
+async fn read_market_data(
+    mut stream: SplitStream<BinanceWsStream>,
+    compression: WsCompression,
+    tx: mpsc::Sender<MarketDataUpdate>,
+    pending_acks: PendingAcks,
+    depth_feed: DepthFeed,
+) {
+    while let Some(frame) = stream.next().await {
+        match frame {
+            Ok(Message::Close(_)) => {
+                info!("Binance WebSocket closed by server");
+                break;
+            }
+            Ok(frame) => {
+                if let Err(e) = forward_frame(frame, compression, &tx, &pending_acks, &depth_feed).await {
+                    if tx.is_closed() {
+                        debug!("Market data receiver dropped, stopping Binance reader");
+                        break;
+                    }
+                    warn!("Failed to process Binance message: {}", e);
+                }
+            }
+            Err(e) => {
+                error!("Binance WebSocket error: {}", e);
+                break;
+            }
+        }
+    }
+}
+
+/// Stream name of a combined-stream frame, or `None` for frames without the envelope
+fn stream_name(message: &str) -> Result<Option<String>> {
+    let envelope: serde_json::Value = serde_json::from_str(message)
+        .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse WebSocket message: {}", e)))?;
+    Ok(envelope.get("stream").and_then(|stream| stream.as_str()).map(str::to_string))
+}
+
+/// Parse one frame and send its update, if any; ping and pong frames carry none
+/// and subscription acks go to the request waiting on them.
+///
+/// Depth diffs are applied to the symbol's book in `depth_feed`, which
+/// bootstraps and resyncs it from REST, and the resulting book is sent.
+async fn forward_frame(
+    frame: Message,
+    compression: WsCompression,
+    tx: &mpsc::Sender<MarketDataUpdate>,
+    pending_acks: &PendingAcks,
+    depth_feed: &DepthFeed,
+) -> Result<()> {
+    let text = match decode_ws_frame(frame, compression)? {
+        Some(text) => text,
+        None => return Ok(()),
+    };
+    if let Some((id, outcome)) = parse_subscription_ack(&text) {
+        match pending_acks.lock().unwrap().remove(&id) {
+            Some(waiter) => {
+                let _ = waiter.send(outcome);
+            }
+            None => warn!("Unexpected Binance response for request {}: {}", id, text),
+        }
+        return Ok(());
+    }
+    if let Some(stream) = stream_name(&text)?.filter(|stream| stream.contains("@depth")) {
+        let symbol = stream.split('@').next().unwrap_or("").to_uppercase();
+        let delta = BinanceConnector::parse_depth_delta(&text)?;
+        if let Some(update) = depth_feed.apply(&symbol, delta).await? {
+            tx.send(update)
+                .await
+                .map_err(|_| ArbitrageError::Connection("Market data receiver dropped".to_string()))?;
+        }
+        return Ok(());
+    }
+    if let Some(update) = BinanceConnector::parse_market_data(&text)? {
+        tx.send(update)
+            .await
+            .map_err(|_| ArbitrageError::Connection("Market data receiver dropped".to_string()))?;
+    }
+    Ok(())
+}
+
 /// Binance REST client
+#[derive(Clone)]
 struct BinanceRestClient {
     base_url: String,
     api_key: String,
     secret_key: String,
     client: reqwest::Client,
+    max_rate_limit_retries: u32,
 }
 
 impl BinanceRestClient {
@@ -354,17 +699,20 @@ impl BinanceRestClient {
             api_key: config.auth.api_key.clone(),
             secret_key: config.auth.secret_key.clone(),
             client,
+            max_rate_limit_retries: config.connection.max_rate_limit_retries,
         })
     }
     
+    /// Replace both signing keys together
+    fn set_credentials(&mut self, api_key: &str, secret_key: &str) {
+        self.api_key = api_key.to_string();
+        self.secret_key = secret_key.to_string();
+    }
+    
     async fn get_orderbook_snapshot(&self, symbol: &str) -> Result<BinanceOrderBookSnapshot> {
         let url = format!("{}/api/v3/depth?symbol={}&limit=100", self.base_url, symbol);
         
-        let response = self.client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| ArbitrageError::Connection(format!("HTTP request failed: {}", e)))?;
+        let response = get_with_backoff(&self.client, &url, self.max_rate_limit_retries).await?;
         
         if !response.status().is_success() {
             return Err(ArbitrageError::Connection(
@@ -380,16 +728,154 @@ impl BinanceRestClient {
         Ok(snapshot)
     }
     
+    async fn get_exchange_info(&self) -> Result<BinanceExchangeInfo> {
+        let url = format!("{}/api/v3/exchangeInfo", self.base_url);
+        
+        let response = get_with_backoff(&self.client, &url, self.max_rate_limit_retries).await?;
+        
+        if !response.status().is_success() {
+            return Err(ArbitrageError::Connection(
+                format!("HTTP request failed with status: {}", response.status())
+            ).into());
+        }
+        
+        response
+            .json()
+            .await
+            .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse exchange info: {}", e)).into())
+    }
+    
+    fn sign(&self, query: &str) -> Result<String> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secret_key.as_bytes())
+            .map_err(|e| ArbitrageError::Config(format!("Invalid secret key: {}", e)))?;
+        mac.update(query.as_bytes());
+        Ok(hex::encode(mac.finalize().into_bytes()))
+    }
+    
+    /// URL with the query, timestamp and signature appended
+    fn signed_url(&self, path: &str, params: &[(&str, String)]) -> Result<String> {
+        let mut query: Vec<String> = params.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        query.push(format!("timestamp={}", chrono::Utc::now().timestamp_millis()));
+        let query = query.join("&");
+        let signature = self.sign(&query)?;
+        Ok(format!("{}{}?{}&signature={}", self.base_url, path, query, signature))
+    }
+    
+    /// Send a signed GET request with the timestamp and signature appended to the query
+    async fn signed_get(&self, path: &str, params: &[(&str, String)]) -> Result<reqwest::Response> {
+        self.send_signed(reqwest::Method::GET, path, params).await
+    }
+    
+    /// Send a signed POST request with the timestamp and signature appended to the query
+    async fn signed_post(&self, path: &str, params: &[(&str, String)]) -> Result<reqwest::Response> {
+        self.send_signed(reqwest::Method::POST, path, params).await
+    }
+    
+    /// Sign and send a request, backing off on rate limits; each retry is signed afresh
+    async fn send_signed(&self, method: reqwest::Method, path: &str, params: &[(&str, String)]) -> Result<reqwest::Response> {
+        let response = send_with_backoff(
+            || Ok(self.client.request(method.clone(), self.signed_url(path, params)?).header("X-MBX-APIKEY", &self.api_key)),
+            self.max_rate_limit_retries,
+        ).await?;
+        
+        let status = response.status();
+        if !status.is_success() {
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            let code = body.get("code").and_then(|c| c.as_i64());
+            let msg = body.get("msg").and_then(|m| m.as_str()).unwrap_or_default();
+            if matches!(code, Some(FILTER_FAILURE | TOO_MUCH_PRECISION)) {
+                return Err(ArbitrageError::FilterViolation(msg.to_string()).into());
+            }
+            return Err(ArbitrageError::Connection(
+                format!("HTTP request failed with status: {} {}", status, msg)
+            ).into());
+        }
+        Ok(response)
+    }
+    
+    async fn get_open_orders(&self, symbol: Option<&str>) -> Result<Vec<BinanceOpenOrder>> {
+        let params: Vec<(&str, String)> = symbol.map(|s| ("symbol", s.to_string())).into_iter().collect();
+        let response = self.signed_get("/api/v3/openOrders", &params).await?;
+        
+        response
+            .json()
+            .await
+            .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse open orders: {}", e)).into())
+    }
+    
     async fn get_account_info(&self) -> Result<BinanceAccountInfo> {
         // This would implement signed request to get account info
         // For now, return an error since we don't have real API keys in tests
         Err(ArbitrageError::Connection("Account info requires valid API credentials".to_string()).into())
     }
     
-    async fn place_order(&self, _order: &LimitOrder) -> Result<BinanceOrderResponse> {
-        // This would implement signed request to place an order
-        // For now, return an error since we don't have real API keys in tests
-        Err(ArbitrageError::Connection("Order placement requires valid API credentials".to_string()).into())
+    async fn place_oco(&self, order: &OcoOrder) -> Result<BinanceOcoResponse> {
+        let mut params = vec![
+            ("symbol", order.symbol.clone()),
+            ("side", order.side.to_string()),
+            ("quantity", order.quantity.to_string()),
+            ("price", order.take_profit_price.to_string()),
+            ("stopPrice", order.stop_price.to_string()),
+        ];
+        if let Some(stop_limit_price) = order.stop_limit_price {
+            params.push(("stopLimitPrice", stop_limit_price.to_string()));
+            params.push(("stopLimitTimeInForce", "GTC".to_string()));
+        }
+        if let Some(client_order_id) = &order.client_order_id {
+            params.push(("listClientOrderId", client_order_id.clone()));
+        }
+        
+        self.signed_post("/api/v3/order/oco", &params)
+            .await?
+            .json()
+            .await
+            .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse OCO response: {}", e)).into())
+    }
+    
+    async fn place_market_order(&self, order: &MarketOrder) -> Result<BinanceOrderResponse> {
+        let mut params = vec![
+            ("symbol", order.symbol.clone()),
+            ("side", order.side.to_string()),
+            ("type", "MARKET".to_string()),
+            ("quantity", order.quantity.to_string()),
+            ("newOrderRespType", "RESULT".to_string()),
+        ];
+        if let Some(client_order_id) = &order.client_order_id {
+            params.push(("newClientOrderId", client_order_id.clone()));
+        }
+        
+        self.signed_post("/api/v3/order", &params)
+            .await?
+            .json()
+            .await
+            .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse order response: {}", e)).into())
+    }
+    
+    async fn place_order(&self, order: &LimitOrder) -> Result<BinanceOrderResponse> {
+        let mut params = vec![
+            ("symbol", order.symbol.clone()),
+            ("side", order.side.to_string()),
+            ("quantity", order.quantity.to_string()),
+            ("price", order.price.to_string()),
+            ("newOrderRespType", "RESULT".to_string()),
+        ];
+        // Spot has no GTX time in force; post-only is its own order type
+        match order.time_in_force {
+            TimeInForce::GTX => params.push(("type", "LIMIT_MAKER".to_string())),
+            time_in_force => {
+                params.push(("type", "LIMIT".to_string()));
+                params.push(("timeInForce", time_in_force.to_string()));
+            }
+        }
+        if let Some(client_order_id) = &order.client_order_id {
+            params.push(("newClientOrderId", client_order_id.clone()));
+        }
+        
+        self.signed_post("/api/v3/order", &params)
+            .await?
+            .json()
+            .await
+            .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse order response: {}", e)).into())
     }
     
     async fn cancel_order(&self, _symbol: &str, _order_id: &str) -> Result<BinanceOrderResponse> {
@@ -405,6 +891,38 @@ impl BinanceRestClient {
     }
 }
 
+#[async_trait]
+impl DepthSnapshotSource for BinanceRestClient {
+    async fn depth_snapshot(&self, symbol: &str) -> Result<DepthSnapshot> {
+        debug!("Getting orderbook snapshot for symbol: {}", symbol);
+        
+        let snapshot = self.get_orderbook_snapshot(symbol).await?;
+        
+        let mut orderbook = OrderBook::new(symbol.to_string(), crate::connectors::Exchange::Binance);
+        
+        // Update bids
+        for bid in snapshot.bids {
+            let (price, quantity) = parse_level(&bid[0], &bid[1])?;
+            orderbook.update_bid(price, quantity);
+        }
+        
+        // Update asks
+        for ask in snapshot.asks {
+            let (price, quantity) = parse_level(&ask[0], &ask[1])?;
+            orderbook.update_ask(price, quantity);
+        }
+        
+        orderbook.set_timestamp(now_ns());
+        
+        // The REST depth response carries no exchange time
+        Ok(DepthSnapshot {
+            last_update_id: snapshot.last_update_id,
+            book: orderbook,
+            exchange_timestamp: None,
+        })
+    }
+}
+
 // Binance API response types
 #[derive(Debug, Deserialize)]
 struct BinanceDepthMessage {
@@ -414,6 +932,12 @@ struct BinanceDepthMessage {
 
 #[derive(Debug, Deserialize)]
 struct BinanceDepthData {
+    #[serde(rename = "U", default)]
+    first_update_id: Option<u64>,
+    #[serde(rename = "u", default)]
+    final_update_id: Option<u64>,
+    #[serde(rename = "E", default)]
+    event_time: Option<i64>,
     #[serde(rename = "b")]
     bids: Vec<[String; 2]>,
     #[serde(rename = "a")]
@@ -440,10 +964,50 @@ struct BinanceTradeData {
 
 #[derive(Debug, Deserialize)]
 struct BinanceOrderBookSnapshot {
+    #[serde(rename = "lastUpdateId")]
+    last_update_id: u64,
     bids: Vec<[String; 2]>,
     asks: Vec<[String; 2]>,
 }
 
+#[derive(Debug, Deserialize)]
+struct BinanceExchangeInfo {
+    symbols: Vec<BinanceSymbolInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceSymbolInfo {
+    symbol: String,
+    filters: Vec<BinanceSymbolFilter>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "filterType")]
+enum BinanceSymbolFilter {
+    #[serde(rename = "PRICE_FILTER")]
+    Price {
+        #[serde(rename = "tickSize")]
+        tick_size: String,
+    },
+    #[serde(rename = "LOT_SIZE")]
+    LotSize {
+        #[serde(rename = "stepSize")]
+        step_size: String,
+        #[serde(rename = "minQty")]
+        min_qty: String,
+    },
+    #[serde(rename = "NOTIONAL", alias = "MIN_NOTIONAL")]
+    Notional {
+        #[serde(rename = "minNotional")]
+        min_notional: String,
+        /// `applyMinToMarket` on NOTIONAL, `applyToMarket` on the legacy MIN_NOTIONAL
+        #[serde(rename = "applyMinToMarket", alias = "applyToMarket", default)]
+        apply_min_to_market: bool,
+    },
+    #[serde(other)]
+    Other,
+}
+
 #[derive(Debug, Deserialize)]
 struct BinanceAccountInfo {
     balances: Vec<BinanceBalance>,
@@ -452,7 +1016,9 @@ struct BinanceAccountInfo {
 #[derive(Debug, Deserialize)]
 struct BinanceBalance {
     asset: String,
+    #[serde(deserialize_with = "f64_from_str_or_num")]
     free: f64,
+    #[serde(deserialize_with = "f64_from_str_or_num")]
     locked: f64,
 }
 
@@ -463,31 +1029,165 @@ struct BinanceOrderResponse {
     #[serde(rename = "clientOrderId")]
     client_order_id: String,
     symbol: String,
-    side: OrderSide,
-    #[serde(rename = "origQty")]
+    side: String,
+    #[serde(rename = "origQty", deserialize_with = "f64_from_str_or_num")]
     orig_qty: f64,
+    #[serde(deserialize_with = "f64_from_str_or_num")]
     price: f64,
+    #[serde(deserialize_with = "binance_order_status")]
     status: OrderStatus,
-    #[serde(rename = "executedQty")]
+    #[serde(rename = "executedQty", deserialize_with = "f64_from_str_or_num")]
     executed_qty: f64,
-    #[serde(rename = "cummulativeQuoteQty")]
+    #[serde(rename = "cummulativeQuoteQty", deserialize_with = "f64_from_str_or_num")]
     cummulative_quote_qty: f64,
     #[serde(rename = "transactTime")]
     transact_time: i64,
 }
 
+/// Order status strings as Binance sends them
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum BinanceOrderStatus {
+    New,
+    PartiallyFilled,
+    Filled,
+    Canceled,
+    PendingCancel,
+    Rejected,
+    Expired,
+    ExpiredInMatch,
+}
+
+impl From<BinanceOrderStatus> for OrderStatus {
+    fn from(status: BinanceOrderStatus) -> Self {
+        match status {
+            BinanceOrderStatus::New => OrderStatus::New,
+            BinanceOrderStatus::PartiallyFilled => OrderStatus::PartiallyFilled,
+            BinanceOrderStatus::Filled => OrderStatus::Filled,
+            BinanceOrderStatus::Canceled | BinanceOrderStatus::PendingCancel => OrderStatus::Canceled,
+            BinanceOrderStatus::Rejected => OrderStatus::Rejected,
+            // Self-trade prevention expires the order in the matching engine
+            BinanceOrderStatus::Expired | BinanceOrderStatus::ExpiredInMatch => OrderStatus::Expired,
+        }
+    }
+}
+
+/// Map a Binance status string to the canonical `OrderStatus`
+fn binance_order_status<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<OrderStatus, D::Error> {
+    BinanceOrderStatus::deserialize(deserializer).map(OrderStatus::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BinanceOcoResponse {
+    order_list_id: i64,
+    orders: Vec<BinanceOcoOrderRef>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BinanceOcoOrderRef {
+    order_id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BinanceOpenOrder {
+    symbol: String,
+    order_id: u64,
+    client_order_id: String,
+    #[serde(deserialize_with = "f64_from_str_or_num")]
+    price: f64,
+    #[serde(deserialize_with = "f64_from_str_or_num")]
+    orig_qty: f64,
+    #[serde(deserialize_with = "f64_from_str_or_num")]
+    executed_qty: f64,
+    #[serde(deserialize_with = "f64_from_str_or_num")]
+    cummulative_quote_qty: f64,
+    #[serde(deserialize_with = "binance_order_status")]
+    status: OrderStatus,
+    side: String,
+    time: i64,
+}
+
+impl BinanceOpenOrder {
+    fn into_order_response(self) -> Result<OrderResponse> {
+        let filled_quantity = self.executed_qty;
+        
+        Ok(OrderResponse {
+            order_id: self.order_id.to_string(),
+            client_order_id: Some(self.client_order_id),
+            side: self.side.parse()?,
+            quantity: self.orig_qty,
+            price: self.price,
+            status: self.status,
+            filled_quantity,
+            average_price: if filled_quantity > 0.0 {
+                Some(self.cummulative_quote_qty / filled_quantity)
+            } else {
+                None
+            },
+            timestamp: ms_to_ns(self.time),
+            symbol: self.symbol,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_parse_depth_message() {
-        let message = r#"{"stream":"btcusdt@depth","data":{"b":[["50000.00","1.00000000"]],"a":[["50100.00","1.00000000"]]}}"#;
+    fn test_order_response_accepts_string_and_number_fields() {
+        let string_form = r#"{"orderId":1,"clientOrderId":"maker_1","symbol":"BTCUSDT","side":"Sell","origQty":"0.25","price":"50100.10","status":"FILLED","executedQty":"0.25","cummulativeQuoteQty":"12525.025","transactTime":1700000000000}"#;
+        let number_form = r#"{"orderId":1,"clientOrderId":"maker_1","symbol":"BTCUSDT","side":"Sell","origQty":0.25,"price":50100.10,"status":"FILLED","executedQty":0.25,"cummulativeQuoteQty":12525.025,"transactTime":1700000000000}"#;
+
+        for payload in [string_form, number_form] {
+            let response: BinanceOrderResponse = serde_json::from_str(payload).unwrap();
+            assert_eq!(response.orig_qty, 0.25);
+            assert_eq!(response.price, 50100.10);
+            assert_eq!(response.executed_qty, 0.25);
+            assert_eq!(response.cummulative_quote_qty, 12525.025);
+        }
+    }
+
+    #[test]
+    fn test_binance_status_strings_map_to_canonical_status() {
+        let cases = [
+            ("NEW", OrderStatus::New),
+            ("PARTIALLY_FILLED", OrderStatus::PartiallyFilled),
+            ("FILLED", OrderStatus::Filled),
+            ("CANCELED", OrderStatus::Canceled),
+            ("PENDING_CANCEL", OrderStatus::Canceled),
+            ("REJECTED", OrderStatus::Rejected),
+            ("EXPIRED", OrderStatus::Expired),
+            ("EXPIRED_IN_MATCH", OrderStatus::Expired),
+        ];
+        for (wire, expected) in cases {
+            let payload = format!(r#"{{"orderId":1,"clientOrderId":"c","symbol":"BTCUSDT","side":"Buy","origQty":"1","price":"50000","status":"{}","executedQty":"0","cummulativeQuoteQty":"0","transactTime":1700000000000}}"#, wire);
+            let response: BinanceOrderResponse = serde_json::from_str(&payload).unwrap();
+            assert_eq!(response.status, expected, "{}", wire);
+        }
+        // Bybit spelling is not a Binance status
+        let payload = r#"{"orderId":1,"clientOrderId":"c","symbol":"BTCUSDT","side":"Buy","origQty":"1","price":"50000","status":"PartiallyFilled","executedQty":"0","cummulativeQuoteQty":"0","transactTime":1700000000000}"#;
+        assert!(serde_json::from_str::<BinanceOrderResponse>(payload).is_err());
+    }
+
+    #[test]
+    fn test_parse_depth_delta() {
+        let message = r#"{"stream":"btcusdt@depth","data":{"U":5,"u":7,"b":[["50000.00","1.00000000"]],"a":[["50100.00","1.00000000"]]}}"#;
         
-        let orderbook = BinanceConnector::parse_depth_message(message).unwrap();
-        assert_eq!(orderbook.symbol, "BTCUSDT");
-        assert_eq!(orderbook.best_bid(), Some(50000.0));
-        assert_eq!(orderbook.best_ask(), Some(50100.0));
+        let delta = BinanceConnector::parse_depth_delta(message).unwrap();
+        assert_eq!((delta.first_update_id, delta.final_update_id), (5, 7));
+        assert_eq!(delta.bids, vec![(50000.0, 1.0)]);
+        assert_eq!(delta.asks, vec![(50100.0, 1.0)]);
+        assert!(BinanceConnector::parse_market_data(message).unwrap().is_none());
+
+        let negative = r#"{"stream":"btcusdt@depth","data":{"U":1,"u":2,"b":[["-50000.00","1.00000000"]],"a":[["50100.00","1.00000000"]]}}"#;
+        let err = BinanceConnector::parse_depth_delta(negative).unwrap_err();
+        assert!(err.to_string().contains("Non-positive price"), "{}", err);
+        let zero_qty_delta = r#"{"stream":"btcusdt@depth","data":{"U":1,"u":2,"b":[["50000.00","0"]],"a":[]}}"#;
+        assert_eq!(BinanceConnector::parse_depth_delta(zero_qty_delta).unwrap().bids, vec![(50000.0, 0.0)]);
     }
 
     #[test]
@@ -499,7 +1199,204 @@ mod tests {
         assert_eq!(price, 50050.0);
         assert_eq!(quantity, 0.1);
         assert_eq!(side, OrderSide::Buy);
-        assert_eq!(timestamp, 1234567890);
+        assert_eq!(timestamp, 1_234_567_890_000_000);
+    }
+
+    /// Depth feed resyncing from a mock `/api/v3/depth` serving `lastUpdateId`
+    /// and a 50500/50510 touch, expected to be hit `snapshots` times
+    async fn mock_depth_feed(server: &wiremock::MockServer, last_update_id: u64, snapshots: u64) -> DepthFeed {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, ResponseTemplate};
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/depth"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "lastUpdateId": last_update_id,
+                "bids": [["50500.00", "2.0"]],
+                "asks": [["50510.00", "2.0"]]
+            })))
+            .expect(snapshots)
+            .mount(server)
+            .await;
+        let config = crate::connectors::test_exchange_config(&server.uri());
+        let source = Arc::new(BinanceRestClient::new(&config).unwrap());
+        DepthFeed::new(crate::connectors::Exchange::Binance, &config.market_data, source)
+    }
+
+    fn depth_frame(first: u64, last: u64, bid: &str) -> Message {
+        Message::Text(format!(
+            r#"{{"stream":"btcusdt@depth@100ms","data":{{"e":"depthUpdate","s":"BTCUSDT","U":{},"u":{},"b":[["{}","1.5"]],"a":[]}}}}"#,
+            first, last, bid
+        ))
+    }
+
+    fn best_bid(update: MarketDataUpdate) -> Option<f64> {
+        match update {
+            MarketDataUpdate::OrderBook { exchange, symbol, orderbook, .. } => {
+                assert_eq!((exchange.as_str(), symbol.as_str()), ("binance", "BTCUSDT"));
+                orderbook.best_bid()
+            }
+            other => panic!("expected an order book update, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_combined_stream_frames_forwarded_to_channel() {
+        let server = wiremock::MockServer::start().await;
+        let feed = mock_depth_feed(&server, 1, 1).await;
+        let (tx, mut rx) = mpsc::channel(8);
+        let frames = [
+            Message::Ping(vec![1]),
+            Message::Text(r#"{"result":null,"id":1}"#.to_string()),
+            depth_frame(1, 2, "50000.00"),
+            Message::Pong(Vec::new()),
+        ];
+        for frame in frames {
+            forward_frame(frame, WsCompression::None, &tx, &PendingAcks::default(), &feed).await.unwrap();
+        }
+        drop(tx);
+
+        // The diff is applied on top of the snapshot rather than replacing the book
+        match rx.recv().await.unwrap() {
+            MarketDataUpdate::OrderBook { exchange, symbol, orderbook, .. } => {
+                assert_eq!((exchange.as_str(), symbol.as_str()), ("binance", "BTCUSDT"));
+                assert_eq!(orderbook.best_bid(), Some(50500.0));
+                assert_eq!(orderbook.bids.len(), 2);
+                assert_eq!(orderbook.best_ask(), Some(50510.0));
+            }
+            other => panic!("expected an order book update, got {:?}", other),
+        }
+        assert!(rx.recv().await.is_none());
+
+        assert_eq!(combined_stream_url("wss://stream.binance.com:9443/ws").unwrap(), "wss://stream.binance.com:9443/stream");
+    }
+
+    #[tokio::test]
+    async fn test_depth_update_carries_exchange_and_receive_times() {
+        let server = wiremock::MockServer::start().await;
+        let feed = mock_depth_feed(&server, 1, 1).await;
+        let (tx, mut rx) = mpsc::channel(8);
+        let frame = Message::Text(
+            r#"{"stream":"btcusdt@depth@100ms","data":{"e":"depthUpdate","E":1700000000000,"s":"BTCUSDT","U":1,"u":2,"b":[["50000.00","1.5"]],"a":[]}}"#.to_string()
+        );
+        forward_frame(frame, WsCompression::None, &tx, &PendingAcks::default(), &feed).await.unwrap();
+
+        let update = rx.recv().await.unwrap();
+        match &update {
+            MarketDataUpdate::OrderBook { orderbook, exchange_timestamp, local_recv_timestamp, .. } => {
+                assert_eq!(*exchange_timestamp, Some(1_700_000_000_000_000_000));
+                assert!(*local_recv_timestamp > 1_700_000_000_000_000_000);
+                // Staleness reads the book's timestamp, which is the local receipt
+                assert_eq!(orderbook.timestamp, *local_recv_timestamp);
+                assert_eq!(update.feed_latency_ns(), Some(local_recv_timestamp - 1_700_000_000_000_000_000));
+            }
+            other => panic!("expected an order book update, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_depth_gap_in_stream_resyncs_from_rest() {
+        let server = wiremock::MockServer::start().await;
+        let feed = mock_depth_feed(&server, 22, 1).await;
+        let (tx, mut rx) = mpsc::channel(8);
+        feed.bootstrap("BTCUSDT", DepthSnapshot {
+            last_update_id: 10,
+            book: OrderBook::new("BTCUSDT".to_string(), crate::connectors::Exchange::Binance),
+            exchange_timestamp: None,
+        }).unwrap();
+
+        for frame in [depth_frame(11, 12, "50000.00"), depth_frame(11, 12, "49000.00")] {
+            forward_frame(frame, WsCompression::None, &tx, &PendingAcks::default(), &feed).await.unwrap();
+        }
+        assert_eq!(best_bid(rx.recv().await.unwrap()), Some(50000.0));
+        // The resent frame leaves the book as it was
+        assert_eq!(best_bid(rx.recv().await.unwrap()), Some(50000.0));
+
+        // Updates 13..=19 are missing, so the book is rebuilt from a snapshot at
+        // 22 and the part of the gapped frame after it is applied on top
+        forward_frame(depth_frame(20, 25, "40000.00"), WsCompression::None, &tx, &PendingAcks::default(), &feed).await.unwrap();
+        let rebuilt = match rx.recv().await.unwrap() {
+            MarketDataUpdate::OrderBook { orderbook, .. } => orderbook,
+            other => panic!("expected an order book update, got {:?}", other),
+        };
+        assert_eq!(rebuilt.bids, vec![(50500.0, 2.0), (40000.0, 1.5)]);
+        forward_frame(depth_frame(26, 27, "50600.00"), WsCompression::None, &tx, &PendingAcks::default(), &feed).await.unwrap();
+        assert_eq!(best_bid(rx.recv().await.unwrap()), Some(50600.0));
+
+        let counts = feed.with_synchronizer("BTCUSDT", |sync| (sync.resync_count(), sync.stale_count(), sync.last_update_id()));
+        assert_eq!(counts, Some((1, 1, Some(27))));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_orderbook_bootstraps_from_snapshot() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+            while let Some(Ok(Message::Text(text))) = ws.next().await {
+                let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                let ack = serde_json::json!({"result": null, "id": request["id"]});
+                ws.send(Message::Text(ack.to_string())).await.unwrap();
+            }
+        });
+        let server = wiremock::MockServer::start().await;
+        mock_depth_feed(&server, 10, 1).await;
+
+        let mut config = crate::connectors::test_exchange_config(&server.uri());
+        config.connection.websocket_url = format!("ws://{}/ws", address);
+        let mut connector = BinanceConnector::new(config).await.unwrap();
+        connector.connect().await.unwrap();
+        connector.subscribe_orderbook("BTCUSDT").await.unwrap();
+
+        let seeded = connector.depth_feed.with_synchronizer("BTCUSDT", |sync| {
+            (sync.awaiting_bootstrap(), sync.last_update_id(), sync.orderbook().and_then(|book| book.best_bid()))
+        });
+        assert_eq!(seeded, Some((false, Some(10), Some(50500.0))));
+    }
+
+    #[tokio::test]
+    async fn test_subscriptions_paced_and_rejections_surfaced() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let server_received = received.clone();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+            while let Some(Ok(Message::Text(text))) = ws.next().await {
+                let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                let params: Vec<String> = serde_json::from_value(request["params"].clone()).unwrap();
+                let response = if params.iter().any(|stream| stream.starts_with("bad")) {
+                    serde_json::json!({"error": {"code": 2, "msg": "Invalid request: unknown stream"}, "id": request["id"]})
+                } else {
+                    serde_json::json!({"result": null, "id": request["id"]})
+                };
+                server_received.lock().unwrap().push((std::time::Instant::now(), params));
+                ws.send(Message::Text(response.to_string())).await.unwrap();
+            }
+        });
+
+        let mut config = crate::connectors::test_exchange_config("http://127.0.0.1:1");
+        config.connection.websocket_url = format!("ws://{}/ws", address);
+        config.connection.ws_subscriptions_per_sec = 20.0;
+        config.connection.ws_subscription_batch_size = 2;
+        let mut connector = BinanceConnector::new(config).await.unwrap();
+        connector.connect().await.unwrap();
+
+        let streams: Vec<String> = ["btcusdt", "ethusdt", "solusdt"].iter()
+            .flat_map(|symbol| [format!("{}@depth@100ms", symbol), format!("{}@trade", symbol)])
+            .collect();
+        connector.subscribe_streams(&streams).await.unwrap();
+
+        let requests = received.lock().unwrap().clone();
+        assert_eq!(requests.iter().map(|(_, params)| params.len()).collect::<Vec<_>>(), vec![2, 2, 2]);
+        for pair in requests.windows(2) {
+            assert!(pair[1].0 - pair[0].0 >= std::time::Duration::from_millis(45), "{:?}", pair[1].0 - pair[0].0);
+        }
+
+        let err = connector.subscribe_streams(&["badusdt@depth".to_string()]).await.unwrap_err();
+        assert!(err.to_string().contains("unknown stream"), "{}", err);
     }
 
     #[tokio::test]
@@ -514,6 +1411,11 @@ mod tests {
                 connection_timeout_secs: 10,
                 max_reconnect_attempts: 5,
                 reconnect_delay_secs: 5,
+                max_rate_limit_retries: 3,
+                ws_compression: WsCompression::None,
+                ws_subscriptions_per_sec: 5.0,
+                ws_subscription_batch_size: 10,
+                ws_subscription_ack_timeout_ms: 5000,
             },
             auth: AuthConfig {
                 api_key: "test_key".to_string(),
@@ -525,6 +1427,7 @@ mod tests {
             trading: TradingConfig {
                 default_order_type: "LIMIT".to_string(),
                 default_time_in_force: "GTC".to_string(),
+                order_types: Vec::new(),
                 additional: HashMap::new(),
             },
             fees: FeeConfig {
@@ -543,6 +1446,8 @@ mod tests {
                 streams: vec![],
                 topics: vec![],
                 depth_levels: 20,
+                resync_on_gap: true,
+                snapshot_bootstrap: true,
                 additional: HashMap::new(),
             },
             monitoring: MonitoringConfig {
@@ -551,6 +1456,11 @@ mod tests {
                 enable_trade_logging: true,
                 log_rotation_size_mb: 100,
                 health_check_interval_secs: 30,
+                webhook: WebhookConfig::default(),
+                trade_store: TradeStoreConfig::default(),
+                prometheus: PrometheusConfig::default(),
+                audit_log: AuditLogConfig::default(),
+                book_integrity_interval_ms: 0,
             },
         };
         
@@ -558,4 +1468,182 @@ mod tests {
         assert!(!connector.is_connected());
         assert_eq!(connector.connection_status(), ConnectionStatus::Disconnected);
     }
+
+    #[tokio::test]
+    async fn test_refresh_symbol_rules_from_exchange_info() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+        
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/exchangeInfo"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "timezone": "UTC",
+                "serverTime": 1700000000000i64,
+                "symbols": [{
+                    "symbol": "BTCUSDT",
+                    "status": "TRADING",
+                    "baseAsset": "BTC",
+                    "quoteAsset": "USDT",
+                    "filters": [
+                        {"filterType": "PRICE_FILTER", "minPrice": "0.01000000", "maxPrice": "1000000.00000000", "tickSize": "0.01000000"},
+                        {"filterType": "LOT_SIZE", "minQty": "0.00001000", "maxQty": "9000.00000000", "stepSize": "0.00001000"},
+                        {"filterType": "ICEBERG_PARTS", "limit": 10},
+                        {"filterType": "NOTIONAL", "minNotional": "5.00000000", "applyMinToMarket": true}
+                    ]
+                }]
+            })))
+            .mount(&server)
+            .await;
+        
+        let mut connector = BinanceConnector::new(crate::connectors::test_exchange_config(&server.uri())).await.unwrap();
+        connector.config.limits.tick_sizes.insert("BTCUSDT".to_string(), 0.1);
+        connector.refresh_symbol_rules().await.unwrap();
+        
+        assert_eq!(connector.symbol_rules("BTCUSDT"), Some(SymbolRules {
+            symbol: "BTCUSDT".to_string(),
+            tick_size: 0.01,
+            lot_size: 0.00001,
+            min_quantity: 0.00001,
+            min_notional: 5.0,
+            price_precision: 2,
+            quantity_precision: 5,
+            apply_min_to_market: true,
+        }));
+        assert_eq!(connector.config.limits.tick_sizes["BTCUSDT"], 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_place_oco_sends_both_price_legs() {
+        use wiremock::matchers::{header, method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+        
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/order/oco"))
+            .and(query_param("symbol", "BTCUSDT"))
+            .and(query_param("side", "SELL"))
+            .and(query_param("quantity", "0.1"))
+            .and(query_param("price", "51000"))
+            .and(query_param("stopPrice", "49000"))
+            .and(query_param("stopLimitPrice", "48900"))
+            .and(header("X-MBX-APIKEY", "test_key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "orderListId": 7,
+                "contingencyType": "OCO",
+                "listClientOrderId": "bracket_1",
+                "symbol": "BTCUSDT",
+                "orders": [
+                    {"symbol": "BTCUSDT", "orderId": 101, "clientOrderId": "a"},
+                    {"symbol": "BTCUSDT", "orderId": 102, "clientOrderId": "b"}
+                ]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        
+        let connector = BinanceConnector::new(crate::connectors::test_exchange_config(&server.uri())).await.unwrap();
+        let response = connector.place_oco(&OcoOrder {
+            symbol: "BTCUSDT".to_string(),
+            side: OrderSide::Sell,
+            quantity: 0.1,
+            take_profit_price: 51000.0,
+            stop_price: 49000.0,
+            stop_limit_price: Some(48900.0),
+            client_order_id: Some("bracket_1".to_string()),
+        }).await.unwrap();
+        
+        assert_eq!(response.list_id, "7");
+        assert_eq!(response.order_ids, vec!["101", "102"]);
+    }
+    
+    #[tokio::test]
+    async fn test_rotated_credentials_sign_subsequent_requests() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+        
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/openOrders"))
+            .and(header("X-MBX-APIKEY", "rotated_key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        
+        let mut connector = BinanceConnector::new(crate::connectors::test_exchange_config(&server.uri())).await.unwrap();
+        assert!(connector.get_open_orders(None).await.is_err());
+        assert!(connector.update_credentials("", "secret").await.is_err());
+        
+        connector.update_credentials("rotated_key", "rotated_secret").await.unwrap();
+        assert!(connector.get_open_orders(None).await.unwrap().is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_get_open_orders_parses_response() {
+        use wiremock::matchers::{header, method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+        
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/openOrders"))
+            .and(query_param("symbol", "BTCUSDT"))
+            .and(header("X-MBX-APIKEY", "test_key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                "symbol": "BTCUSDT",
+                "orderId": 28,
+                "orderListId": -1,
+                "clientOrderId": "maker_abc123",
+                "price": "50000.00000000",
+                "origQty": "0.20000000",
+                "executedQty": "0.05000000",
+                "cummulativeQuoteQty": "2500.00000000",
+                "status": "PARTIALLY_FILLED",
+                "timeInForce": "GTC",
+                "type": "LIMIT",
+                "side": "SELL",
+                "time": 1700000000000i64,
+                "updateTime": 1700000001000i64,
+                "isWorking": true
+            }])))
+            .mount(&server)
+            .await;
+        
+        let connector = BinanceConnector::new(crate::connectors::test_exchange_config(&server.uri())).await.unwrap();
+        let orders = connector.get_open_orders(Some("BTCUSDT")).await.unwrap();
+        
+        assert_eq!(orders.len(), 1);
+        let order = &orders[0];
+        assert_eq!(order.order_id, "28");
+        assert_eq!(order.client_order_id.as_deref(), Some("maker_abc123"));
+        assert_eq!(order.side, OrderSide::Sell);
+        assert_eq!(order.status, OrderStatus::PartiallyFilled);
+        assert_eq!(order.timestamp, 1_700_000_000_000_000_000);
+        assert_eq!((order.quantity, order.price, order.filled_quantity), (0.2, 50000.0, 0.05));
+        assert_eq!(order.average_price, Some(50000.0));
+    }
+    
+    #[tokio::test]
+    async fn test_signed_request_retried_after_rate_limit() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+        
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/openOrders"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/openOrders"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        
+        let connector = BinanceConnector::new(crate::connectors::test_exchange_config(&server.uri())).await.unwrap();
+        assert!(connector.get_open_orders(None).await.unwrap().is_empty());
+    }
 }
@@ -1,7 +1,19 @@
 //! Binance Futures connector implementation
 
 use super::futures::*;
-use crate::{connectors::Exchange, data::OrderBook, Result, ArbitrageError};
+use crate::{
+    config::WsCompression,
+    connectors::{
+        compression::decode_ws_frame,
+        http::{get_with_backoff, send_with_backoff, DEFAULT_MAX_RATE_LIMIT_RETRIES},
+        sequence::{DepthDelta, DepthSnapshot},
+        serde_helpers::parse_level,
+        Exchange,
+    },
+    data::OrderBook,
+    utils::time::{ms_to_ns, now_ns},
+    Result, ArbitrageError,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tokio::net::TcpStream;
@@ -9,6 +21,11 @@ use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
 use futures_util::{SinkExt, StreamExt};
 use tokio_tungstenite::tungstenite::Message;
 use tracing::{debug, error, info, warn};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Binance error code returned when the margin type is already set
+const NO_NEED_TO_CHANGE_MARGIN_TYPE: i64 = -4046;
 
 /// Binance Futures connector
 pub struct BinanceFuturesConnector {
@@ -26,6 +43,12 @@ pub struct BinanceFuturesConnector {
     is_connected: bool,
     /// Subscribed symbols
     subscribed_symbols: Vec<String>,
+    /// Compression applied to binary WebSocket frames
+    ws_compression: WsCompression,
+    /// HTTP client for REST requests
+    client: reqwest::Client,
+    /// Retries of a rate-limited REST request before giving up
+    max_rate_limit_retries: u32,
 }
 
 impl BinanceFuturesConnector {
@@ -39,7 +62,169 @@ impl BinanceFuturesConnector {
             ws_connection: None,
             is_connected: false,
             subscribed_symbols: Vec::new(),
+            ws_compression: WsCompression::None,
+            client: reqwest::Client::new(),
+            max_rate_limit_retries: DEFAULT_MAX_RATE_LIMIT_RETRIES,
+        }
+    }
+
+    /// Retry rate-limited requests up to `retries` times before failing with `RateLimited`
+    pub fn with_max_rate_limit_retries(mut self, retries: u32) -> Self {
+        self.max_rate_limit_retries = retries;
+        self
+    }
+
+    /// Override the REST API base URL
+    pub fn with_api_url(mut self, api_url: &str) -> Self {
+        self.api_url = api_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Decompress binary WebSocket frames with the given codec before parsing
+    pub fn with_ws_compression(mut self, compression: WsCompression) -> Self {
+        self.ws_compression = compression;
+        self
+    }
+
+    /// HMAC-SHA256 signature of a query string
+    fn sign(&self, query: &str) -> Result<String> {
+        let secret = self.secret_key.as_ref()
+            .ok_or_else(|| ArbitrageError::Config("Binance futures secret key not configured".to_string()))?;
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+            .map_err(|e| ArbitrageError::Config(format!("Invalid secret key: {}", e)))?;
+        mac.update(query.as_bytes());
+        Ok(hex::encode(mac.finalize().into_bytes()))
+    }
+
+    /// Send a signed POST request; Binance error codes are returned as `Err((code, msg))`
+    async fn signed_post(&self, path: &str, params: &[(&str, String)]) -> Result<std::result::Result<serde_json::Value, (i64, String)>> {
+        self.signed_request(reqwest::Method::POST, path, params).await
+    }
+
+    /// Send a signed GET request; Binance error codes are returned as `Err((code, msg))`
+    async fn signed_get(&self, path: &str, params: &[(&str, String)]) -> Result<std::result::Result<serde_json::Value, (i64, String)>> {
+        self.signed_request(reqwest::Method::GET, path, params).await
+    }
+
+    async fn signed_request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        params: &[(&str, String)],
+    ) -> Result<std::result::Result<serde_json::Value, (i64, String)>> {
+        let api_key = self.api_key.as_ref()
+            .ok_or_else(|| ArbitrageError::Config("Binance futures API key not configured".to_string()))?;
+
+        // Signed per attempt so a retry after a rate limit carries a fresh timestamp
+        let response = send_with_backoff(|| {
+            let mut query: Vec<String> = params.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+            query.push(format!("timestamp={}", chrono::Utc::now().timestamp_millis()));
+            let query = query.join("&");
+            let signature = self.sign(&query)?;
+            let url = format!("{}{}?{}&signature={}", self.api_url, path, query, signature);
+            Ok(self.client.request(method.clone(), &url).header("X-MBX-APIKEY", api_key))
+        }, self.max_rate_limit_retries).await?;
+
+        let status = response.status();
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse Binance futures response: {}", e)))?;
+
+        if status.is_success() {
+            return Ok(Ok(body));
+        }
+        let code = body.get("code").and_then(|c| c.as_i64()).unwrap_or(status.as_u16() as i64);
+        let msg = body.get("msg").and_then(|m| m.as_str()).unwrap_or_default().to_string();
+        Ok(Err((code, msg)))
+    }
+
+    /// Query parameters for `/fapi/v1/order`; TP/SL cannot be attached here and are
+    /// placed as separate trigger orders
+    fn order_params(order: &FuturesOrder) -> Vec<(&'static str, String)> {
+        let order_type = match order.order_type {
+            FuturesOrderType::Market => "MARKET",
+            FuturesOrderType::Limit => "LIMIT",
+            FuturesOrderType::StopMarket => "STOP_MARKET",
+            FuturesOrderType::StopLimit => "STOP",
+            FuturesOrderType::TakeProfitMarket => "TAKE_PROFIT_MARKET",
+            FuturesOrderType::TakeProfitLimit => "TAKE_PROFIT",
+        };
+        let mut params = vec![
+            ("symbol", order.symbol.clone()),
+            ("side", order.side.to_string()),
+            ("type", order_type.to_string()),
+            ("quantity", order.quantity.to_string()),
+        ];
+        if let Some(price) = order.price {
+            params.push(("price", price.to_string()));
+        }
+        if let Some(stop_price) = order.stop_price {
+            params.push(("stopPrice", stop_price.to_string()));
+        }
+        if matches!(order.order_type, FuturesOrderType::Limit | FuturesOrderType::StopLimit | FuturesOrderType::TakeProfitLimit) {
+            params.push(("timeInForce", format!("{:?}", order.time_in_force)));
         }
+        match order.position_side {
+            Some(PositionSide::Long) => params.push(("positionSide", "LONG".to_string())),
+            Some(PositionSide::Short) => params.push(("positionSide", "SHORT".to_string())),
+            // reduceOnly is only accepted in one-way mode
+            _ if order.reduce_only => params.push(("reduceOnly", "true".to_string())),
+            _ => {}
+        }
+        if order.close_position {
+            params.push(("closePosition", "true".to_string()));
+        }
+        if let Some(client_order_id) = &order.client_order_id {
+            params.push(("newClientOrderId", client_order_id.clone()));
+        }
+        // The default ACK response reports executedQty 0 even for filled market orders
+        params.push(("newOrderRespType", "RESULT".to_string()));
+        params
+    }
+
+    /// Send one order to `/fapi/v1/order`
+    async fn submit_order(&self, order: &FuturesOrder) -> Result<FuturesOrderResponse> {
+        match self.signed_post("/fapi/v1/order", &Self::order_params(order)).await? {
+            Ok(body) => Self::parse_order_response(order, &body),
+            Err((code, msg)) => Err(ArbitrageError::Trading(format!(
+                "Binance futures order for {} rejected: {} {}", order.symbol, code, msg
+            )).into()),
+        }
+    }
+
+    /// Parse a `/fapi/v1/order` response
+    fn parse_order_response(order: &FuturesOrder, body: &serde_json::Value) -> Result<FuturesOrderResponse> {
+        let number = |field: &str| body.get(field).and_then(|v| v.as_str()).and_then(|v| v.parse::<f64>().ok());
+        let status = match body.get("status").and_then(|s| s.as_str()).unwrap_or_default() {
+            "NEW" => FuturesOrderStatus::New,
+            "PARTIALLY_FILLED" => FuturesOrderStatus::PartiallyFilled,
+            "FILLED" => FuturesOrderStatus::Filled,
+            "CANCELED" => FuturesOrderStatus::Canceled,
+            "REJECTED" => FuturesOrderStatus::Rejected,
+            "EXPIRED" | "EXPIRED_IN_MATCH" => FuturesOrderStatus::Expired,
+            other => return Err(ArbitrageError::DataParsing(format!("Unknown Binance futures order status: {:?}", other)).into()),
+        };
+        let order_id = body.get("orderId").and_then(|id| id.as_i64())
+            .ok_or_else(|| ArbitrageError::DataParsing("Binance futures order response missing orderId".to_string()))?;
+        let filled_quantity = number("executedQty").unwrap_or(0.0);
+
+        Ok(FuturesOrderResponse {
+            order_id: order_id.to_string(),
+            client_order_id: body.get("clientOrderId").and_then(|id| id.as_str()).map(str::to_string),
+            symbol: order.symbol.clone(),
+            side: order.side,
+            position_side: order.position_side.clone(),
+            order_type: order.order_type.clone(),
+            quantity: number("origQty").unwrap_or(order.quantity),
+            price: order.price,
+            status,
+            filled_quantity,
+            average_price: number("avgPrice").filter(|_| filled_quantity > 0.0),
+            commission: 0.0,
+            commission_asset: String::new(),
+            timestamp: body.get("updateTime").and_then(|t| t.as_i64()).map(ms_to_ns).unwrap_or_else(now_ns),
+        })
     }
 
     /// Get common USDT perpetual symbols
@@ -69,6 +254,73 @@ impl BinanceFuturesConnector {
         ])
     }
 
+    /// Maximum leverage per symbol: the highest initial leverage across its notional brackets
+    pub async fn get_max_leverage(&self) -> Result<HashMap<String, u32>> {
+        match self.signed_get("/fapi/v1/leverageBracket", &[]).await? {
+            Ok(body) => {
+                let symbols: Vec<BinanceLeverageBrackets> = serde_json::from_value(body)
+                    .map_err(|e| ArbitrageError::ParseError(format!("Failed to parse Binance leverage brackets: {}", e)))?;
+                Ok(symbols
+                    .into_iter()
+                    .filter_map(|symbol| {
+                        let max_leverage = symbol.brackets.iter().map(|bracket| bracket.initial_leverage).max()?;
+                        Some((symbol.symbol, max_leverage))
+                    })
+                    .collect())
+            }
+            Err((code, msg)) => Err(ArbitrageError::Connection(format!(
+                "Failed to fetch Binance leverage brackets: {} {}", code, msg
+            )).into()),
+        }
+    }
+
+    /// Fetch a REST depth snapshot together with its `lastUpdateId`
+    pub async fn get_depth_snapshot(&self, symbol: &str) -> Result<DepthSnapshot> {
+        let url = format!("{}/fapi/v1/depth?symbol={}&limit=100", self.api_url, symbol);
+        let response = get_with_backoff(&self.client, &url, self.max_rate_limit_retries).await?;
+
+        if !response.status().is_success() {
+            return Err(ArbitrageError::Connection(
+                format!("HTTP request failed with status: {}", response.status())
+            ).into());
+        }
+
+        let snapshot: BinanceFuturesDepthSnapshot = response
+            .json()
+            .await
+            .map_err(|e| ArbitrageError::ParseError(format!("Failed to parse Binance futures depth snapshot: {}", e)))?;
+
+        let mut book = OrderBook::new(symbol.to_string(), Exchange::Binance);
+        for (price, quantity) in parse_levels(&snapshot.bids)? {
+            book.update_bid(price, quantity);
+        }
+        for (price, quantity) in parse_levels(&snapshot.asks)? {
+            book.update_ask(price, quantity);
+        }
+        book.set_timestamp(now_ns());
+
+        Ok(DepthSnapshot {
+            last_update_id: snapshot.last_update_id,
+            book,
+            exchange_timestamp: None,
+        })
+    }
+
+    /// Parse a depth message into a delta carrying its `U`/`u` update ID range
+    pub fn parse_depth_delta(&self, msg: &str) -> Result<DepthDelta> {
+        let depth_msg: BinanceFuturesDepthMessage = serde_json::from_str(msg)
+            .map_err(|e| ArbitrageError::ParseError(format!("Failed to parse Binance futures depth: {}", e)))?;
+
+        Ok(DepthDelta {
+            first_update_id: depth_msg.data.first_update_id,
+            final_update_id: depth_msg.data.final_update_id,
+            bids: parse_levels(&depth_msg.data.bids)?,
+            asks: parse_levels(&depth_msg.data.asks)?,
+            exchange_timestamp: Some(ms_to_ns(depth_msg.data.event_time)),
+            local_recv_timestamp: now_ns(),
+        })
+    }
+
     /// Parse depth message from WebSocket
     fn parse_depth_message(&self, msg: &str) -> Result<OrderBook> {
         let depth_msg: BinanceFuturesDepthMessage = serde_json::from_str(msg)
@@ -78,23 +330,17 @@ impl BinanceFuturesConnector {
         
         // Update bids
         for bid in depth_msg.data.bids {
-            let price = bid[0].parse::<f64>()
-                .map_err(|e| ArbitrageError::ParseError(format!("Invalid bid price: {}", e)))?;
-            let quantity = bid[1].parse::<f64>()
-                .map_err(|e| ArbitrageError::ParseError(format!("Invalid bid quantity: {}", e)))?;
+            let (price, quantity) = parse_level(&bid[0], &bid[1])?;
             orderbook.update_bid(price, quantity);
         }
 
         // Update asks
         for ask in depth_msg.data.asks {
-            let price = ask[0].parse::<f64>()
-                .map_err(|e| ArbitrageError::ParseError(format!("Invalid ask price: {}", e)))?;
-            let quantity = ask[1].parse::<f64>()
-                .map_err(|e| ArbitrageError::ParseError(format!("Invalid ask quantity: {}", e)))?;
+            let (price, quantity) = parse_level(&ask[0], &ask[1])?;
             orderbook.update_ask(price, quantity);
         }
 
-        orderbook.set_timestamp(depth_msg.data.event_time);
+        orderbook.set_timestamp(ms_to_ns(depth_msg.data.event_time));
         Ok(orderbook)
     }
 
@@ -111,7 +357,7 @@ impl BinanceFuturesConnector {
             last_funding_rate: mark_msg.data.funding_rate.parse().unwrap_or(0.0),
             next_funding_time: mark_msg.data.next_funding_time,
             interest_rate: mark_msg.data.interest_rate.parse().unwrap_or(0.0),
-            timestamp: mark_msg.data.event_time,
+            timestamp: ms_to_ns(mark_msg.data.event_time),
         })
     }
 
@@ -139,6 +385,13 @@ impl FuturesConnector for BinanceFuturesConnector {
         // In a real implementation, this would fetch from Binance API
         // For now, return mock data for common symbols
         let symbols = self.get_common_usdt_perpetuals().await?;
+        let max_leverage = match self.get_max_leverage().await {
+            Ok(max_leverage) => max_leverage,
+            Err(e) => {
+                warn!("Failed to fetch Binance futures leverage brackets, assuming {}x: {}", DEFAULT_MAX_LEVERAGE, e);
+                HashMap::new()
+            }
+        };
         let mut contracts = HashMap::new();
         
         for symbol in symbols {
@@ -154,12 +407,30 @@ impl FuturesConnector for BinanceFuturesConnector {
                 lot_size: 0.001,
                 maker_fee: 0.0002, // 0.02%
                 taker_fee: 0.0004, // 0.04%
+                contract_multiplier: 1.0,
+                max_leverage: max_leverage.get(&symbol).copied().unwrap_or(DEFAULT_MAX_LEVERAGE),
+                quantity_unit: QuantityUnit::Base,
+                percent_price: Some(PercentPriceBand { multiplier_up: 1.05, multiplier_down: 0.95 }),
             });
         }
         
         Ok(contracts)
     }
 
+    async fn set_leverage(&self, symbol: &str, leverage: u32) -> Result<()> {
+        let params = [("symbol", symbol.to_string()), ("leverage", leverage.to_string())];
+
+        match self.signed_post("/fapi/v1/leverage", &params).await? {
+            Ok(_) => {
+                info!("Set Binance futures leverage for {} to {}x", symbol, leverage);
+                Ok(())
+            }
+            Err((code, msg)) => Err(ArbitrageError::Trading(format!(
+                "Failed to set Binance leverage for {}: {} {}", symbol, code, msg
+            )).into()),
+        }
+    }
+
     async fn get_account_info(&self) -> Result<FuturesAccountInfo> {
         // Mock implementation - in production this would call Binance API
         Err(ArbitrageError::NotImplemented("get_account_info requires API keys".to_string()).into())
@@ -170,9 +441,15 @@ impl FuturesConnector for BinanceFuturesConnector {
         Err(ArbitrageError::NotImplemented("get_positions requires API keys".to_string()).into())
     }
 
-    async fn place_order(&self, _order: &FuturesOrder) -> Result<FuturesOrderResponse> {
-        // Mock implementation - in production this would call Binance API
-        Err(ArbitrageError::NotImplemented("place_order requires API keys".to_string()).into())
+    async fn place_order(&self, order: &FuturesOrder) -> Result<FuturesOrderResponse> {
+        let response = self.submit_order(order).await?;
+        for protective in order.protective_orders() {
+            match self.submit_order(&protective).await {
+                Ok(_) => info!("Placed {:?} at {:?} for Binance order {}", protective.order_type, protective.stop_price, response.order_id),
+                Err(e) => error!("Entry {} on {} is unprotected: {}", response.order_id, order.symbol, e),
+            }
+        }
+        Ok(response)
     }
 
     async fn cancel_order(&self, _symbol: &str, _order_id: &str) -> Result<FuturesOrderResponse> {
@@ -195,6 +472,42 @@ impl FuturesConnector for BinanceFuturesConnector {
         Err(ArbitrageError::NotImplemented("get_mark_price not implemented".to_string()).into())
     }
 
+    async fn set_margin_mode(&self, symbol: &str, mode: MarginMode) -> Result<()> {
+        let margin_type = match mode {
+            MarginMode::Cross => "CROSSED",
+            MarginMode::Isolated => "ISOLATED",
+        };
+        let params = [("symbol", symbol.to_string()), ("marginType", margin_type.to_string())];
+
+        match self.signed_post("/fapi/v1/marginType", &params).await? {
+            Ok(_) => {
+                info!("Set Binance futures margin type for {} to {}", symbol, margin_type);
+                Ok(())
+            }
+            Err((NO_NEED_TO_CHANGE_MARGIN_TYPE, _)) => {
+                debug!("Binance futures margin type for {} already {}", symbol, margin_type);
+                Ok(())
+            }
+            Err((code, msg)) => Err(ArbitrageError::Trading(format!(
+                "Failed to set Binance margin type for {}: {} {}", symbol, code, msg
+            )).into()),
+        }
+    }
+
+    async fn get_position_mode(&self, _symbol: &str) -> Result<PositionMode> {
+        // Binance sets the position mode for the whole account
+        match self.signed_get("/fapi/v1/positionSide/dual", &[]).await? {
+            Ok(body) => match body.get("dualSidePosition").and_then(|dual| dual.as_bool()) {
+                Some(true) => Ok(PositionMode::Hedge),
+                Some(false) => Ok(PositionMode::OneWay),
+                None => Err(ArbitrageError::DataParsing("Binance position mode response missing dualSidePosition".to_string()).into()),
+            },
+            Err((code, msg)) => Err(ArbitrageError::Trading(format!(
+                "Failed to query Binance position mode: {} {}", code, msg
+            )).into()),
+        }
+    }
+
     async fn subscribe_orderbook(&mut self, symbol: &str) -> Result<()> {
         info!("Subscribing to Binance futures orderbook for {}", symbol);
         if !self.subscribed_symbols.contains(&symbol.to_string()) {
@@ -261,20 +574,23 @@ impl BinanceFuturesConnector {
         if let Some(mut ws_stream) = ws {
             while let Some(msg) = ws_stream.next().await {
                 match msg {
-                    Ok(Message::Text(text)) => {
-                        if let Err(e) = Self::process_message_static(&text).await {
-                            warn!("Failed to process message: {}", e);
-                        }
-                    }
                     Ok(Message::Close(_)) => {
                         info!("WebSocket connection closed");
                         break;
                     }
+                    Ok(frame) => match decode_ws_frame(frame, self.ws_compression) {
+                        Ok(Some(text)) => {
+                            if let Err(e) = Self::process_message_static(&text).await {
+                                warn!("Failed to process message: {}", e);
+                            }
+                        }
+                        Ok(None) => {}
+                        Err(e) => warn!("Failed to decode message: {}", e),
+                    },
                     Err(e) => {
                         error!("WebSocket error: {}", e);
                         break;
                     }
-                    _ => {}
                 }
             }
             self.ws_connection = Some(ws_stream);
@@ -316,6 +632,31 @@ impl BinanceFuturesConnector {
     }
 }
 
+/// Parse `[price, quantity]` string pairs
+fn parse_levels(levels: &[[String; 2]]) -> Result<Vec<(f64, f64)>> {
+    levels.iter().map(|level| parse_level(&level[0], &level[1])).collect()
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceFuturesDepthSnapshot {
+    #[serde(rename = "lastUpdateId")]
+    last_update_id: u64,
+    bids: Vec<[String; 2]>,
+    asks: Vec<[String; 2]>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceLeverageBrackets {
+    symbol: String,
+    brackets: Vec<BinanceLeverageBracket>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceLeverageBracket {
+    #[serde(rename = "initialLeverage")]
+    initial_leverage: u32,
+}
+
 // WebSocket message structures
 #[derive(Debug, Deserialize)]
 struct BinanceFuturesDepthMessage {
@@ -377,6 +718,14 @@ struct BinanceFuturesMarkPriceData {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_depth_timestamp_normalized_to_nanos() {
+        let connector = BinanceFuturesConnector::new(None, None);
+        let msg = r#"{"stream":"btcusdt@depth","data":{"e":"depthUpdate","E":1700000000123,"T":1700000000120,"s":"BTCUSDT","U":1,"u":2,"pu":0,"b":[["50000.0","1.0"]],"a":[["50010.0","1.0"]]}}"#;
+        let orderbook = connector.parse_depth_message(msg).unwrap();
+        assert_eq!(orderbook.timestamp, 1_700_000_000_123_000_000);
+    }
+
     #[tokio::test]
     async fn test_binance_futures_connector_creation() {
         let connector = BinanceFuturesConnector::new(None, None);
@@ -402,4 +751,159 @@ mod tests {
         assert_eq!(btc_contract.contract_type, ContractType::Perpetual);
         assert_eq!(btc_contract.quote_asset, "USDT");
     }
+
+    #[tokio::test]
+    async fn test_max_leverage_read_from_brackets() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/fapi/v1/leverageBracket"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                "symbol": "ETHUSDT",
+                "brackets": [
+                    {"bracket": 1, "initialLeverage": 75, "notionalCap": 10000, "notionalFloor": 0, "maintMarginRatio": 0.0065, "cum": 0},
+                    {"bracket": 2, "initialLeverage": 50, "notionalCap": 100000, "notionalFloor": 10000, "maintMarginRatio": 0.01, "cum": 35}
+                ]
+            }])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let connector = BinanceFuturesConnector::new(Some("key".to_string()), Some("secret".to_string()))
+            .with_api_url(&server.uri());
+        let contracts = connector.get_exchange_info().await.unwrap();
+        assert_eq!(contracts["ETHUSDT"].max_leverage, 75);
+        assert_eq!(contracts["BTCUSDT"].max_leverage, DEFAULT_MAX_LEVERAGE);
+    }
+
+    #[tokio::test]
+    async fn test_set_margin_mode_sends_margin_type() {
+        use wiremock::matchers::{header_exists, method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/fapi/v1/marginType"))
+            .and(query_param("symbol", "BTCUSDT"))
+            .and(query_param("marginType", "ISOLATED"))
+            .and(header_exists("X-MBX-APIKEY"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"code": 200, "msg": "success"})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/fapi/v1/marginType"))
+            .and(query_param("marginType", "CROSSED"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({"code": -4046, "msg": "No need to change margin type."})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let connector = BinanceFuturesConnector::new(Some("key".to_string()), Some("secret".to_string()))
+            .with_api_url(&server.uri());
+        connector.set_margin_mode("BTCUSDT", MarginMode::Isolated).await.unwrap();
+        connector.set_margin_mode("BTCUSDT", MarginMode::Cross).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_request_surfaces_error_after_retries() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/fapi/v1/leverage"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let connector = BinanceFuturesConnector::new(Some("key".to_string()), Some("secret".to_string()))
+            .with_api_url(&server.uri())
+            .with_max_rate_limit_retries(1);
+        let err = connector.set_leverage("ETHUSDT", 5).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<ArbitrageError>(), Some(ArbitrageError::RateLimited(_))));
+    }
+
+    #[tokio::test]
+    async fn test_set_leverage_sends_configured_value() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/fapi/v1/leverage"))
+            .and(query_param("symbol", "ETHUSDT"))
+            .and(query_param("leverage", "5"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "symbol": "ETHUSDT", "leverage": 5, "maxNotionalValue": "1000000"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let connector = BinanceFuturesConnector::new(Some("key".to_string()), Some("secret".to_string()))
+            .with_api_url(&server.uri());
+        connector.set_leverage("ETHUSDT", 5).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_take_profit_and_stop_loss_placed_as_reduce_only_triggers() {
+        use crate::connectors::OrderSide;
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/fapi/v1/order"))
+            .and(query_param("type", "LIMIT"))
+            .and(query_param("side", "BUY"))
+            .and(query_param("price", "50000"))
+            .and(query_param("timeInForce", "GTX"))
+            .and(query_param("newOrderRespType", "RESULT"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "orderId": 42, "clientOrderId": "maker_1", "status": "NEW", "origQty": "0.01",
+                "executedQty": "0", "avgPrice": "0.00", "updateTime": 1700000000000i64
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        for (order_type, stop_price, client_order_id) in [("TAKE_PROFIT_MARKET", "50500", "tp_maker_1"), ("STOP_MARKET", "49500", "sl_maker_1")] {
+            Mock::given(method("POST"))
+                .and(path("/fapi/v1/order"))
+                .and(query_param("type", order_type))
+                .and(query_param("side", "SELL"))
+                .and(query_param("stopPrice", stop_price))
+                .and(query_param("reduceOnly", "true"))
+                .and(query_param("newClientOrderId", client_order_id))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"orderId": 43, "status": "NEW"})))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let connector = BinanceFuturesConnector::new(Some("key".to_string()), Some("secret".to_string()))
+            .with_api_url(&server.uri());
+        let order = FuturesOrder {
+            symbol: "BTCUSDT".to_string(),
+            side: OrderSide::Buy,
+            position_side: Some(PositionSide::Both),
+            order_type: FuturesOrderType::Limit,
+            quantity: 0.01,
+            price: Some(50000.0),
+            stop_price: None,
+            time_in_force: FuturesTimeInForce::GTX,
+            reduce_only: false,
+            close_position: false,
+            client_order_id: Some("maker_1".to_string()),
+            take_profit: Some(50500.0),
+            stop_loss: Some(49500.0),
+        };
+        let response = connector.place_order(&order).await.unwrap();
+        assert_eq!(response.order_id, "42");
+        assert_eq!(response.status, FuturesOrderStatus::New);
+        assert_eq!(response.timestamp, 1_700_000_000_000_000_000);
+    }
 }
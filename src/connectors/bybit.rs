@@ -3,20 +3,36 @@
 use crate::{
     config::ExchangeConfig,
     connectors::{
+        http::{get_with_backoff, send_with_backoff},
+        sequence::{DepthDelta, DepthFeed, DepthSnapshot, DepthSnapshotSource},
+        serde_helpers::{f64_from_str_or_num, opt_f64_from_str_or_num, parse_level},
         traits::*,
         ConnectionStatus,
     },
     data::OrderBook,
+    utils::time::{ms_to_ns, now_ns},
     ArbitrageError,
     Result,
 };
 use async_trait::async_trait;
-use serde::{Deserialize, Serialize};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Deserializer, Serialize};
+use sha2::Sha256;
 use std::collections::HashMap;
+use std::sync::Arc;
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 use url::Url;
 
+/// Bybit receive window for signed requests in milliseconds
+const RECV_WINDOW_MS: u64 = 5000;
+
+/// Maximum orders per Bybit spot batch request
+const MAX_BATCH_ORDERS: usize = 10;
+
+/// Bybit retCode for a request timestamp outside the receive window
+const RET_CODE_TIMESTAMP_OUT_OF_WINDOW: i64 = 10002;
+
 /// Bybit exchange connector
 pub struct BybitConnector {
     config: ExchangeConfig,
@@ -25,12 +41,20 @@ pub struct BybitConnector {
     order_update_tx: Option<mpsc::Sender<OrderUpdate>>,
     websocket_client: Option<BybitWebSocketClient>,
     rest_client: BybitRestClient,
+    symbol_rules: HashMap<String, SymbolRules>,
+    /// Books built from the order book stream
+    depth_feed: DepthFeed,
 }
 
 impl BybitConnector {
     /// Create a new Bybit connector
     pub async fn new(config: ExchangeConfig) -> Result<Self> {
         let rest_client = BybitRestClient::new(&config)?;
+        let depth_feed = DepthFeed::new(
+            crate::connectors::Exchange::Bybit,
+            &config.market_data,
+            Arc::new(rest_client.clone()),
+        );
         
         Ok(Self {
             config,
@@ -39,38 +63,56 @@ impl BybitConnector {
             order_update_tx: None,
             websocket_client: None,
             rest_client,
+            symbol_rules: HashMap::new(),
+            depth_feed,
         })
     }
     
-    /// Parse a depth message from Bybit WebSocket
-    pub fn parse_depth_message(message: &str) -> Result<OrderBook> {
+    /// Apply an order book stream message to the symbol's book and return the
+    /// book once it is complete.
+    ///
+    /// A `snapshot` message replaces the book; a `delta` message is sequenced
+    /// by its update ID `u`, so a skipped update resyncs the book from REST.
+    /// The update carries the message's `ts`, the matching-engine time, next
+    /// to the time the message was received.
+    pub async fn process_depth_message(&self, message: &str) -> Result<Option<MarketDataUpdate>> {
+        let local_recv_timestamp = now_ns();
         let data: BybitDepthMessage = serde_json::from_str(message)
             .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse depth message: {}", e)))?;
         
         let symbol = data.topic.split('.').nth(2).unwrap_or("").to_string();
-        let mut orderbook = OrderBook::new(symbol, crate::connectors::Exchange::Bybit);
-        
-        // Update bids
-        for bid in data.data.b {
-            let price: f64 = bid[0].parse()
-                .map_err(|e| ArbitrageError::DataParsing(format!("Invalid bid price: {}", e)))?;
-            let quantity: f64 = bid[1].parse()
-                .map_err(|e| ArbitrageError::DataParsing(format!("Invalid bid quantity: {}", e)))?;
-            orderbook.update_bid(price, quantity);
-        }
+        let parse_levels = |levels: &[[String; 2]]| -> Result<Vec<(f64, f64)>> {
+            levels.iter().map(|level| parse_level(&level[0], &level[1])).collect()
+        };
+        let bids = parse_levels(&data.data.b)?;
+        let asks = parse_levels(&data.data.a)?;
+        let exchange_timestamp = Some(ms_to_ns(data.ts));
         
-        // Update asks
-        for ask in data.data.a {
-            let price: f64 = ask[0].parse()
-                .map_err(|e| ArbitrageError::DataParsing(format!("Invalid ask price: {}", e)))?;
-            let quantity: f64 = ask[1].parse()
-                .map_err(|e| ArbitrageError::DataParsing(format!("Invalid ask quantity: {}", e)))?;
-            orderbook.update_ask(price, quantity);
+        match data.msg_type.as_str() {
+            "snapshot" => {
+                let mut book = OrderBook::new(symbol.clone(), crate::connectors::Exchange::Bybit);
+                for (price, quantity) in bids {
+                    book.update_bid(price, quantity);
+                }
+                for (price, quantity) in asks {
+                    book.update_ask(price, quantity);
+                }
+                book.set_timestamp(local_recv_timestamp);
+                self.depth_feed.bootstrap(&symbol, DepthSnapshot { last_update_id: data.data.u, book, exchange_timestamp })
+            }
+            "delta" => {
+                let delta = DepthDelta {
+                    first_update_id: data.data.u,
+                    final_update_id: data.data.u,
+                    bids,
+                    asks,
+                    exchange_timestamp,
+                    local_recv_timestamp,
+                };
+                self.depth_feed.apply(&symbol, delta).await
+            }
+            other => Err(ArbitrageError::DataParsing(format!("Unknown depth message type: {}", other)).into()),
         }
-        
-        orderbook.set_timestamp(data.ts * 1_000_000); // Convert to nanoseconds
-        
-        Ok(orderbook)
     }
     
     /// Parse a trade message from Bybit WebSocket
@@ -82,16 +124,13 @@ impl BybitConnector {
         
         // Bybit sends an array of trades
         if let Some(trade) = data.data.first() {
-            let price: f64 = trade.price.parse()
-                .map_err(|e| ArbitrageError::DataParsing(format!("Invalid trade price: {}", e)))?;
-            let quantity: f64 = trade.size.parse()
-                .map_err(|e| ArbitrageError::DataParsing(format!("Invalid trade quantity: {}", e)))?;
+            let (price, quantity) = parse_level(&trade.price, &trade.size)?;
             let side = match trade.side.as_str() {
                 "Buy" => OrderSide::Buy,
                 "Sell" => OrderSide::Sell,
                 _ => return Err(ArbitrageError::DataParsing(format!("Invalid trade side: {}", trade.side)).into()),
             };
-            let timestamp = trade.timestamp;
+            let timestamp = ms_to_ns(trade.timestamp);
             
             Ok((symbol, price, quantity, side, timestamp))
         } else {
@@ -189,33 +228,7 @@ impl ExchangeConnector for BybitConnector {
     }
     
     async fn get_orderbook(&self, symbol: &str) -> Result<OrderBook> {
-        debug!("Getting orderbook snapshot for symbol: {}", symbol);
-        
-        let snapshot = self.rest_client.get_orderbook_snapshot(symbol).await?;
-        
-        let mut orderbook = OrderBook::new(symbol.to_string(), crate::connectors::Exchange::Bybit);
-        
-        // Update bids
-        for bid in snapshot.result.b {
-            let price: f64 = bid[0].parse()
-                .map_err(|e| ArbitrageError::DataParsing(format!("Invalid bid price: {}", e)))?;
-            let quantity: f64 = bid[1].parse()
-                .map_err(|e| ArbitrageError::DataParsing(format!("Invalid bid quantity: {}", e)))?;
-            orderbook.update_bid(price, quantity);
-        }
-        
-        // Update asks
-        for ask in snapshot.result.a {
-            let price: f64 = ask[0].parse()
-                .map_err(|e| ArbitrageError::DataParsing(format!("Invalid ask price: {}", e)))?;
-            let quantity: f64 = ask[1].parse()
-                .map_err(|e| ArbitrageError::DataParsing(format!("Invalid ask quantity: {}", e)))?;
-            orderbook.update_ask(price, quantity);
-        }
-        
-        orderbook.set_timestamp(snapshot.time * 1_000_000); // Convert to nanoseconds
-        
-        Ok(orderbook)
+        Ok(self.rest_client.depth_snapshot(symbol).await?.book)
     }
     
     async fn get_balances(&self) -> Result<HashMap<String, Balance>> {
@@ -244,52 +257,110 @@ impl ExchangeConnector for BybitConnector {
         
         let response = self.rest_client.place_order(order).await?;
         
+        // The acknowledgement carries no fills; they arrive through order status queries
         Ok(OrderResponse {
             order_id: response.result.order_id,
-            client_order_id: Some(response.result.order_link_id),
-            symbol: response.result.symbol,
-            side: response.result.side,
-            quantity: response.result.qty,
-            price: response.result.price,
-            status: response.result.order_status,
-            filled_quantity: response.result.cum_exec_qty,
-            average_price: if response.result.cum_exec_qty > 0.0 {
-                Some(response.result.cum_exec_value / response.result.cum_exec_qty)
-            } else {
-                None
-            },
-            timestamp: response.result.created_time,
+            client_order_id: Some(response.result.order_link_id).filter(|id| !id.is_empty()),
+            symbol: order.symbol.clone(),
+            side: order.side,
+            quantity: order.quantity,
+            price: order.price,
+            status: OrderStatus::New,
+            filled_quantity: 0.0,
+            average_price: None,
+            timestamp: now_ns(),
         })
     }
     
-    async fn cancel_order(&self, symbol: &str, order_id: &str) -> Result<OrderResponse> {
-        debug!("Cancelling order: {} for symbol: {}", order_id, symbol);
+    async fn place_market_order(&self, order: &MarketOrder) -> Result<OrderResponse> {
+        debug!("Placing market order: {:?}", order);
         
-        let response = self.rest_client.cancel_order(symbol, order_id).await?;
+        let response = self.rest_client.place_market_order(order).await?;
         
+        // As with limit orders, fills arrive through order status queries
         Ok(OrderResponse {
             order_id: response.result.order_id,
-            client_order_id: Some(response.result.order_link_id),
-            symbol: response.result.symbol,
-            side: response.result.side,
-            quantity: response.result.qty,
-            price: response.result.price,
-            status: response.result.order_status,
-            filled_quantity: response.result.cum_exec_qty,
-            average_price: if response.result.cum_exec_qty > 0.0 {
-                Some(response.result.cum_exec_value / response.result.cum_exec_qty)
-            } else {
-                None
-            },
-            timestamp: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
+            client_order_id: Some(response.result.order_link_id).filter(|id| !id.is_empty()),
+            symbol: order.symbol.clone(),
+            side: order.side,
+            quantity: order.quantity,
+            price: 0.0,
+            status: OrderStatus::New,
+            filled_quantity: 0.0,
+            average_price: None,
+            timestamp: now_ns(),
+        })
+    }
+    
+    async fn place_batch(&self, orders: &[LimitOrder]) -> Result<Vec<OrderResponse>> {
+        debug!("Placing batch of {} orders", orders.len());
+        
+        let mut responses = Vec::with_capacity(orders.len());
+        for chunk in orders.chunks(MAX_BATCH_ORDERS) {
+            let batch = self.rest_client.place_batch(chunk).await?;
+            let results = batch.ret_ext_info.map(|info| info.list).unwrap_or_default();
+            for (index, order) in chunk.iter().enumerate() {
+                let placed = batch.result.list.get(index);
+                let accepted = results.get(index).map_or(placed.is_some(), |result| result.code == 0);
+                if !accepted {
+                    let reason = results.get(index).map(|result| result.msg.as_str()).unwrap_or("missing from response");
+                    warn!("Bybit rejected batched order {:?}: {}", order.client_order_id, reason);
+                }
+                responses.push(OrderResponse {
+                    order_id: placed.map(|placed| placed.order_id.clone()).unwrap_or_default(),
+                    client_order_id: order.client_order_id.clone(),
+                    symbol: order.symbol.clone(),
+                    side: order.side,
+                    quantity: order.quantity,
+                    price: order.price,
+                    status: if accepted { OrderStatus::New } else { OrderStatus::Rejected },
+                    filled_quantity: 0.0,
+                    average_price: None,
+                    timestamp: placed
+                        .and_then(|placed| placed.create_at.parse().ok())
+                        .map_or_else(now_ns, ms_to_ns),
+                });
+            }
+        }
+        Ok(responses)
+    }
+    
+    async fn place_oco(&self, order: &OcoOrder) -> Result<OcoResponse> {
+        debug!("Placing OCO order: {:?}", order);
+        
+        let response = self.rest_client.place_oco(order).await?;
+        
+        Ok(OcoResponse {
+            list_id: response.result.order_id.clone(),
+            order_ids: vec![response.result.order_id],
         })
     }
     
+    async fn cancel_order(&self, symbol: &str, order_id: &str) -> Result<OrderResponse> {
+        debug!("Cancelling order: {} for symbol: {}", order_id, symbol);
+        
+        let order = self.rest_client.cancel_order(symbol, order_id).await?;
+        Ok(OrderResponse { timestamp: now_ns(), ..order.into_order_response()? })
+    }
+    
     async fn get_order_status(&self, symbol: &str, order_id: &str) -> Result<OrderStatus> {
         debug!("Getting order status: {} for symbol: {}", order_id, symbol);
         
         let order = self.rest_client.get_order(symbol, order_id).await?;
-        Ok(order.result.order_status)
+        Ok(order.order_status)
+    }
+    
+    async fn get_order(&self, symbol: &str, order_id: &str) -> Result<OrderResponse> {
+        debug!("Getting order: {} for symbol: {}", order_id, symbol);
+        
+        self.rest_client.get_order(symbol, order_id).await?.into_order_response()
+    }
+    
+    async fn get_open_orders(&self, symbol: Option<&str>) -> Result<Vec<OrderResponse>> {
+        debug!("Getting open orders for {:?}", symbol);
+        
+        let orders = self.rest_client.get_open_orders(symbol).await?;
+        orders.result.list.into_iter().map(BybitOpenOrder::into_order_response).collect()
     }
     
     fn get_market_data_receiver(&self) -> Option<mpsc::Receiver<MarketDataUpdate>> {
@@ -298,6 +369,41 @@ impl ExchangeConnector for BybitConnector {
         None
     }
     
+    async fn refresh_symbol_rules(&mut self) -> Result<()> {
+        let instruments = self.rest_client.get_instruments_info().await?;
+        
+        for instrument in instruments.result.list {
+            let rules = SymbolRules::from_decimal_strings(
+                &instrument.symbol,
+                &instrument.price_filter.tick_size,
+                &instrument.lot_size_filter.base_precision,
+                &instrument.lot_size_filter.min_order_qty,
+                &instrument.lot_size_filter.min_order_amt,
+            )?;
+            self.config.limits.tick_sizes.insert(rules.symbol.clone(), rules.tick_size);
+            self.config.limits.min_order_sizes.insert(rules.symbol.clone(), rules.min_quantity);
+            self.symbol_rules.insert(rules.symbol.clone(), rules);
+        }
+        
+        info!("Loaded trading rules for {} Bybit symbols", self.symbol_rules.len());
+        Ok(())
+    }
+    
+    fn symbol_rules(&self, symbol: &str) -> Option<SymbolRules> {
+        self.symbol_rules.get(symbol).cloned()
+    }
+    
+    async fn update_credentials(&mut self, api_key: &str, secret_key: &str) -> Result<()> {
+        validate_credentials(api_key, secret_key)?;
+        
+        // Market data streams are unauthenticated, so only the REST signer changes
+        self.rest_client.set_credentials(api_key, secret_key);
+        self.config.auth.api_key = api_key.to_string();
+        self.config.auth.secret_key = secret_key.to_string();
+        info!("Bybit API credentials rotated");
+        Ok(())
+    }
+    
     fn get_order_update_receiver(&self) -> Option<mpsc::Receiver<OrderUpdate>> {
         // This would return a receiver for order updates
         // Implementation depends on how we structure the message handling
@@ -337,12 +443,52 @@ impl BybitWebSocketClient {
     }
 }
 
+/// Bybit's capitalized side name
+fn bybit_side(side: OrderSide) -> &'static str {
+    match side {
+        OrderSide::Buy => "Buy",
+        OrderSide::Sell => "Sell",
+    }
+}
+
+/// Bybit V5 request body item for a limit order, without the category
+fn bybit_order_item(order: &LimitOrder) -> serde_json::Value {
+    let mut item = serde_json::json!({
+        "symbol": order.symbol,
+        "side": bybit_side(order.side),
+        "orderType": "Limit",
+        "qty": order.quantity.to_string(),
+        "price": order.price.to_string(),
+        "timeInForce": match order.time_in_force {
+            TimeInForce::GTC => "GTC",
+            TimeInForce::IOC => "IOC",
+            TimeInForce::FOK => "FOK",
+            TimeInForce::GTX => "PostOnly",
+        },
+    });
+    if let Some(client_order_id) = &order.client_order_id {
+        item["orderLinkId"] = serde_json::Value::String(client_order_id.clone());
+    }
+    item
+}
+
+/// Bybit V5 signature: hex HMAC-SHA256 of `timestamp + api_key + recv_window + payload`,
+/// where the payload is the JSON body of a POST or the query string of a GET
+fn sign_v5(secret_key: &str, timestamp: &str, api_key: &str, recv_window: u64, payload: &str) -> Result<String> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret_key.as_bytes())
+        .map_err(|e| ArbitrageError::Config(format!("Invalid secret key: {}", e)))?;
+    mac.update(format!("{}{}{}{}", timestamp, api_key, recv_window, payload).as_bytes());
+    Ok(hex::encode(mac.finalize().into_bytes()))
+}
+
 /// Bybit REST client
+#[derive(Clone)]
 struct BybitRestClient {
     base_url: String,
     api_key: String,
     secret_key: String,
     client: reqwest::Client,
+    max_rate_limit_retries: u32,
 }
 
 impl BybitRestClient {
@@ -364,17 +510,20 @@ impl BybitRestClient {
             api_key: config.auth.api_key.clone(),
             secret_key: config.auth.secret_key.clone(),
             client,
+            max_rate_limit_retries: config.connection.max_rate_limit_retries,
         })
     }
     
+    /// Replace both signing keys together
+    fn set_credentials(&mut self, api_key: &str, secret_key: &str) {
+        self.api_key = api_key.to_string();
+        self.secret_key = secret_key.to_string();
+    }
+    
     async fn get_orderbook_snapshot(&self, symbol: &str) -> Result<BybitOrderBookSnapshot> {
         let url = format!("{}/v5/market/orderbook?category=spot&symbol={}&limit=50", self.base_url, symbol);
         
-        let response = self.client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| ArbitrageError::Connection(format!("HTTP request failed: {}", e)))?;
+        let response = get_with_backoff(&self.client, &url, self.max_rate_limit_retries).await?;
         
         if !response.status().is_success() {
             return Err(ArbitrageError::Connection(
@@ -390,28 +539,253 @@ impl BybitRestClient {
         Ok(snapshot)
     }
     
+    async fn get_instruments_info(&self) -> Result<BybitInstrumentsInfo> {
+        let url = format!("{}/v5/market/instruments-info?category=spot", self.base_url);
+        
+        let response = get_with_backoff(&self.client, &url, self.max_rate_limit_retries).await?;
+        
+        if !response.status().is_success() {
+            return Err(ArbitrageError::Connection(
+                format!("HTTP request failed with status: {}", response.status())
+            ).into());
+        }
+        
+        let info: BybitInstrumentsInfo = response
+            .json()
+            .await
+            .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse instruments info: {}", e)))?;
+        
+        if info.ret_code != 0 {
+            return Err(ArbitrageError::Connection(format!("Bybit instruments-info error: {}", info.ret_msg)).into());
+        }
+        
+        Ok(info)
+    }
+    
+    /// Send a signed GET request; the signature covers timestamp, key, receive window and query
+    async fn signed_get(&self, path: &str, query: &str) -> Result<serde_json::Value> {
+        let url = format!("{}{}?{}", self.base_url, path, query);
+        self.send_signed(|| self.client.get(&url), query).await
+    }
+    
+    /// Send a signed JSON POST request; the signature covers timestamp, key, receive window and body
+    async fn signed_post(&self, path: &str, body: &serde_json::Value) -> Result<serde_json::Value> {
+        let body = body.to_string();
+        let url = format!("{}{}", self.base_url, path);
+        let request = || self.client
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .body(body.clone());
+        self.send_signed(request, &body).await
+    }
+    
+    /// Sign and send a request, returning the JSON envelope.
+    ///
+    /// Rate-limited requests are retried after `Retry-After`, each signed with
+    /// a fresh timestamp. A timestamp rejected as outside the receive window,
+    /// usually local clock drift, surfaces as a timeout rather than a generic
+    /// API error.
+    async fn send_signed(&self, request: impl Fn() -> reqwest::RequestBuilder, payload: &str) -> Result<serde_json::Value> {
+        let mut timestamp = String::new();
+        let response = send_with_backoff(|| {
+            timestamp = chrono::Utc::now().timestamp_millis().to_string();
+            let signature = sign_v5(&self.secret_key, &timestamp, &self.api_key, RECV_WINDOW_MS, payload)?;
+            Ok(request()
+                .header("X-BAPI-API-KEY", &self.api_key)
+                .header("X-BAPI-TIMESTAMP", &timestamp)
+                .header("X-BAPI-RECV-WINDOW", RECV_WINDOW_MS.to_string())
+                .header("X-BAPI-SIGN", signature))
+        }, self.max_rate_limit_retries).await?;
+        
+        if !response.status().is_success() {
+            return Err(ArbitrageError::Connection(
+                format!("HTTP request failed with status: {}", response.status())
+            ).into());
+        }
+        
+        let envelope: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse Bybit response: {}", e)))?;
+        if envelope.get("retCode").and_then(|code| code.as_i64()) == Some(RET_CODE_TIMESTAMP_OUT_OF_WINDOW) {
+            return Err(ArbitrageError::Timeout(format!(
+                "Bybit rejected request timestamp {} as outside the {} ms receive window; check the local clock: {}",
+                timestamp, RECV_WINDOW_MS, envelope.get("retMsg").and_then(|msg| msg.as_str()).unwrap_or_default()
+            )).into());
+        }
+        Ok(envelope)
+    }
+    
+    async fn get_open_orders(&self, symbol: Option<&str>) -> Result<BybitOpenOrders> {
+        let mut query = "category=spot".to_string();
+        if let Some(symbol) = symbol {
+            query.push_str(&format!("&symbol={}", symbol));
+        }
+        
+        let orders: BybitOpenOrders = serde_json::from_value(self.signed_get("/v5/order/realtime", &query).await?)
+            .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse open orders: {}", e)))?;
+        
+        if orders.ret_code != 0 {
+            return Err(ArbitrageError::Connection(format!("Bybit open orders error: {}", orders.ret_msg)).into());
+        }
+        
+        Ok(orders)
+    }
+    
     async fn get_wallet_balance(&self) -> Result<BybitWalletBalance> {
-        // This would implement signed request to get wallet balance
-        // For now, return an error since we don't have real API keys in tests
-        Err(ArbitrageError::Connection("Wallet balance requires valid API credentials".to_string()).into())
+        let balance: BybitWalletBalance = serde_json::from_value(self.signed_get("/v5/account/wallet-balance", "accountType=UNIFIED").await?)
+            .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse wallet balance: {}", e)))?;
+        
+        if balance.ret_code != 0 {
+            return Err(ArbitrageError::Connection(format!("Bybit wallet balance error: {}", balance.ret_msg)).into());
+        }
+        
+        Ok(balance)
     }
     
-    async fn place_order(&self, _order: &LimitOrder) -> Result<BybitOrderResponse> {
-        // This would implement signed request to place an order
-        // For now, return an error since we don't have real API keys in tests
-        Err(ArbitrageError::Connection("Order placement requires valid API credentials".to_string()).into())
+    async fn place_batch(&self, orders: &[LimitOrder]) -> Result<BybitBatchResponse> {
+        let request: Vec<serde_json::Value> = orders.iter().map(bybit_order_item).collect();
+        let body = serde_json::json!({ "category": "spot", "request": request });
+        
+        let response: BybitBatchResponse = serde_json::from_value(self.signed_post("/v5/order/create-batch", &body).await?)
+            .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse batch order response: {}", e)))?;
+        
+        if response.ret_code != 0 {
+            return Err(ArbitrageError::Trading(format!("Bybit batch order rejected: {}", response.ret_msg)).into());
+        }
+        
+        Ok(response)
     }
     
-    async fn cancel_order(&self, _symbol: &str, _order_id: &str) -> Result<BybitOrderResponse> {
-        // This would implement signed request to cancel an order
-        // For now, return an error since we don't have real API keys in tests
-        Err(ArbitrageError::Connection("Order cancellation requires valid API credentials".to_string()).into())
+    /// Bybit spot has no native OCO: the take-profit is a limit order carrying the stop as its stop-loss
+    async fn place_oco(&self, order: &OcoOrder) -> Result<BybitCreateOrderResponse> {
+        let mut body = serde_json::json!({
+            "category": "spot",
+            "symbol": order.symbol,
+            "side": bybit_side(order.side),
+            "orderType": "Limit",
+            "qty": order.quantity.to_string(),
+            "price": order.take_profit_price.to_string(),
+            "stopLoss": order.stop_price.to_string(),
+            "slOrderType": if order.stop_limit_price.is_some() { "Limit" } else { "Market" },
+        });
+        if let Some(stop_limit_price) = order.stop_limit_price {
+            body["slLimitPrice"] = serde_json::Value::String(stop_limit_price.to_string());
+        }
+        if let Some(client_order_id) = &order.client_order_id {
+            body["orderLinkId"] = serde_json::Value::String(client_order_id.clone());
+        }
+        
+        let response: BybitCreateOrderResponse = serde_json::from_value(self.signed_post("/v5/order/create", &body).await?)
+            .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse order response: {}", e)))?;
+        
+        if response.ret_code != 0 {
+            return Err(ArbitrageError::Trading(format!("Bybit OCO order rejected: {}", response.ret_msg)).into());
+        }
+        
+        Ok(response)
     }
     
-    async fn get_order(&self, _symbol: &str, _order_id: &str) -> Result<BybitOrderResponse> {
-        // This would implement signed request to get order status
-        // For now, return an error since we don't have real API keys in tests
-        Err(ArbitrageError::Connection("Order status requires valid API credentials".to_string()).into())
+    /// Place a limit order; Bybit acknowledges with the order ID only
+    async fn place_order(&self, order: &LimitOrder) -> Result<BybitCreateOrderResponse> {
+        let mut body = bybit_order_item(order);
+        body["category"] = serde_json::Value::String("spot".to_string());
+        
+        let response: BybitCreateOrderResponse = serde_json::from_value(self.signed_post("/v5/order/create", &body).await?)
+            .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse order response: {}", e)))?;
+        
+        if response.ret_code != 0 {
+            return Err(ArbitrageError::Trading(format!("Bybit order rejected: {}", response.ret_msg)).into());
+        }
+        
+        Ok(response)
+    }
+    
+    /// Spot market orders size buys in the quote coin unless `marketUnit` says otherwise
+    async fn place_market_order(&self, order: &MarketOrder) -> Result<BybitCreateOrderResponse> {
+        let mut body = serde_json::json!({
+            "category": "spot",
+            "symbol": order.symbol,
+            "side": bybit_side(order.side),
+            "orderType": "Market",
+            "qty": order.quantity.to_string(),
+            "marketUnit": "baseCoin",
+        });
+        if let Some(client_order_id) = &order.client_order_id {
+            body["orderLinkId"] = serde_json::Value::String(client_order_id.clone());
+        }
+        
+        let response: BybitCreateOrderResponse = serde_json::from_value(self.signed_post("/v5/order/create", &body).await?)
+            .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse order response: {}", e)))?;
+        
+        if response.ret_code != 0 {
+            return Err(ArbitrageError::Trading(format!("Bybit order rejected: {}", response.ret_msg)).into());
+        }
+        
+        Ok(response)
+    }
+    
+    /// Cancel an order and read back its final state, so fills that landed
+    /// before the cancel are reported
+    async fn cancel_order(&self, symbol: &str, order_id: &str) -> Result<BybitOpenOrder> {
+        let body = serde_json::json!({ "category": "spot", "symbol": symbol, "orderId": order_id });
+        
+        let response: BybitCreateOrderResponse = serde_json::from_value(self.signed_post("/v5/order/cancel", &body).await?)
+            .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse cancel response: {}", e)))?;
+        
+        if response.ret_code != 0 {
+            return Err(ArbitrageError::Trading(format!("Bybit cancel rejected: {}", response.ret_msg)).into());
+        }
+        
+        self.get_order(symbol, order_id).await
+    }
+    
+    /// Look up one order, falling back to the order history once it has left the realtime list
+    async fn get_order(&self, symbol: &str, order_id: &str) -> Result<BybitOpenOrder> {
+        let query = format!("category=spot&symbol={}&orderId={}", symbol, order_id);
+        for path in ["/v5/order/realtime", "/v5/order/history"] {
+            let orders: BybitOpenOrders = serde_json::from_value(self.signed_get(path, &query).await?)
+                .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse order: {}", e)))?;
+            
+            if orders.ret_code != 0 {
+                return Err(ArbitrageError::Connection(format!("Bybit order query error: {}", orders.ret_msg)).into());
+            }
+            if let Some(order) = orders.result.list.into_iter().next() {
+                return Ok(order);
+            }
+        }
+        Err(ArbitrageError::Trading(format!("Bybit order {} for {} not found", order_id, symbol)).into())
+    }
+}
+
+#[async_trait]
+impl DepthSnapshotSource for BybitRestClient {
+    async fn depth_snapshot(&self, symbol: &str) -> Result<DepthSnapshot> {
+        debug!("Getting orderbook snapshot for symbol: {}", symbol);
+        
+        let snapshot = self.get_orderbook_snapshot(symbol).await?;
+        
+        let mut orderbook = OrderBook::new(symbol.to_string(), crate::connectors::Exchange::Bybit);
+        
+        // Update bids
+        for bid in snapshot.result.b {
+            let (price, quantity) = parse_level(&bid[0], &bid[1])?;
+            orderbook.update_bid(price, quantity);
+        }
+        
+        // Update asks
+        for ask in snapshot.result.a {
+            let (price, quantity) = parse_level(&ask[0], &ask[1])?;
+            orderbook.update_ask(price, quantity);
+        }
+        
+        orderbook.set_timestamp(now_ns());
+        
+        Ok(DepthSnapshot {
+            last_update_id: snapshot.result.u,
+            book: orderbook,
+            exchange_timestamp: Some(ms_to_ns(snapshot.time)),
+        })
     }
 }
 
@@ -457,6 +831,42 @@ struct BybitTradeData {
     price: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct BybitInstrumentsInfo {
+    #[serde(rename = "retCode")]
+    ret_code: i32,
+    #[serde(rename = "retMsg")]
+    ret_msg: String,
+    result: BybitInstrumentsResult,
+}
+
+#[derive(Debug, Deserialize)]
+struct BybitInstrumentsResult {
+    list: Vec<BybitInstrument>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BybitInstrument {
+    symbol: String,
+    lot_size_filter: BybitLotSizeFilter,
+    price_filter: BybitPriceFilter,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BybitLotSizeFilter {
+    base_precision: String,
+    min_order_qty: String,
+    min_order_amt: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BybitPriceFilter {
+    tick_size: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct BybitOrderBookSnapshot {
     #[serde(rename = "retCode")]
@@ -500,43 +910,153 @@ struct BybitAccountInfo {
 #[derive(Debug, Deserialize)]
 struct BybitBalance {
     coin: String,
-    #[serde(rename = "walletBalance")]
+    #[serde(rename = "walletBalance", deserialize_with = "f64_from_str_or_num")]
     wallet_balance: f64,
-    #[serde(rename = "transferBalance")]
-    transfer_balance: f64,
-    #[serde(rename = "bonus")]
-    bonus: f64,
-    #[serde(rename = "lockedBalance")]
+    #[serde(rename = "transferBalance", default, deserialize_with = "opt_f64_from_str_or_num")]
+    transfer_balance: Option<f64>,
+    #[serde(rename = "bonus", default, deserialize_with = "opt_f64_from_str_or_num")]
+    bonus: Option<f64>,
+    #[serde(rename = "locked", alias = "lockedBalance", default, deserialize_with = "f64_from_str_or_num")]
     locked_balance: f64,
 }
 
+/// Order status strings as Bybit V5 sends them
+#[derive(Debug, Clone, Copy, Deserialize)]
+enum BybitOrderStatus {
+    New,
+    PartiallyFilled,
+    Untriggered,
+    Triggered,
+    Filled,
+    #[serde(alias = "Canceled")]
+    Cancelled,
+    PartiallyFilledCanceled,
+    Deactivated,
+    Rejected,
+}
+
+impl From<BybitOrderStatus> for OrderStatus {
+    fn from(status: BybitOrderStatus) -> Self {
+        match status {
+            // Conditional orders rest untriggered, then become live orders once triggered
+            BybitOrderStatus::New | BybitOrderStatus::Untriggered | BybitOrderStatus::Triggered => OrderStatus::New,
+            BybitOrderStatus::PartiallyFilled => OrderStatus::PartiallyFilled,
+            BybitOrderStatus::Filled => OrderStatus::Filled,
+            BybitOrderStatus::Cancelled | BybitOrderStatus::PartiallyFilledCanceled | BybitOrderStatus::Deactivated => OrderStatus::Canceled,
+            BybitOrderStatus::Rejected => OrderStatus::Rejected,
+        }
+    }
+}
+
+/// Map a Bybit status string to the canonical `OrderStatus`
+fn bybit_order_status<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<OrderStatus, D::Error> {
+    BybitOrderStatus::deserialize(deserializer).map(OrderStatus::from)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BybitBatchResponse {
+    ret_code: i32,
+    ret_msg: String,
+    result: BybitBatchResult,
+    #[serde(default)]
+    ret_ext_info: Option<BybitBatchExtInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BybitBatchResult {
+    list: Vec<BybitBatchOrder>,
+}
+
 #[derive(Debug, Deserialize)]
-struct BybitOrderResponse {
+#[serde(rename_all = "camelCase")]
+struct BybitBatchOrder {
+    order_id: String,
+    #[serde(default)]
+    create_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BybitBatchExtInfo {
+    list: Vec<BybitBatchItemResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BybitBatchItemResult {
+    code: i32,
+    msg: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BybitCreateOrderResponse {
+    ret_code: i32,
+    ret_msg: String,
+    result: BybitCreateOrderResult,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BybitCreateOrderResult {
+    order_id: String,
+    #[serde(default)]
+    order_link_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BybitOpenOrders {
     #[serde(rename = "retCode")]
     ret_code: i32,
     #[serde(rename = "retMsg")]
     ret_msg: String,
-    result: BybitOrderResult,
+    result: BybitOpenOrdersResult,
+}
+
+#[derive(Debug, Deserialize)]
+struct BybitOpenOrdersResult {
+    list: Vec<BybitOpenOrder>,
 }
 
 #[derive(Debug, Deserialize)]
-struct BybitOrderResult {
-    #[serde(rename = "orderId")]
+#[serde(rename_all = "camelCase")]
+struct BybitOpenOrder {
     order_id: String,
-    #[serde(rename = "orderLinkId")]
     order_link_id: String,
     symbol: String,
-    side: OrderSide,
+    side: String,
+    #[serde(deserialize_with = "f64_from_str_or_num")]
     qty: f64,
+    #[serde(deserialize_with = "f64_from_str_or_num")]
     price: f64,
-    #[serde(rename = "orderStatus")]
+    #[serde(deserialize_with = "bybit_order_status")]
     order_status: OrderStatus,
-    #[serde(rename = "cumExecQty")]
+    #[serde(deserialize_with = "f64_from_str_or_num")]
     cum_exec_qty: f64,
-    #[serde(rename = "cumExecValue")]
-    cum_exec_value: f64,
-    #[serde(rename = "createdTime")]
-    created_time: i64,
+    #[serde(default, deserialize_with = "opt_f64_from_str_or_num")]
+    avg_price: Option<f64>,
+    created_time: String,
+}
+
+impl BybitOpenOrder {
+    fn into_order_response(self) -> Result<OrderResponse> {
+        let filled_quantity = self.cum_exec_qty;
+        // Bybit reports an empty or zero average price until the order fills
+        let average_price = self.avg_price.filter(|_| filled_quantity > 0.0);
+        
+        Ok(OrderResponse {
+            order_id: self.order_id,
+            client_order_id: Some(self.order_link_id).filter(|id| !id.is_empty()),
+            side: self.side.parse()?,
+            quantity: self.qty,
+            price: self.price,
+            status: self.order_status,
+            filled_quantity,
+            average_price,
+            timestamp: ms_to_ns(self.created_time.parse()
+                .map_err(|_| ArbitrageError::DataParsing(format!("Invalid createdTime in open order: {}", self.created_time)))?),
+            symbol: self.symbol,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -544,13 +1064,117 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_parse_depth_message() {
-        let message = r#"{"topic":"orderbook.1.BTCUSDT","type":"snapshot","ts":1234567890,"data":{"s":"BTCUSDT","b":[["50000.00","1.00"]],"a":[["50100.00","1.00"]],"u":123,"seq":456}}"#;
+    fn test_order_result_accepts_string_and_number_fields() {
+        let string_form = r#"{"orderId":"1","orderLinkId":"","symbol":"BTCUSDT","side":"Buy","qty":"0.5","price":"50000.5","orderStatus":"Filled","cumExecQty":"0.5","avgPrice":"50000.25","createdTime":"1700000000000"}"#;
+        let number_form = r#"{"orderId":"1","orderLinkId":"","symbol":"BTCUSDT","side":"Buy","qty":0.5,"price":50000.5,"orderStatus":"Filled","cumExecQty":0.5,"avgPrice":50000.25,"createdTime":"1700000000000"}"#;
+
+        for payload in [string_form, number_form] {
+            let result: BybitOpenOrder = serde_json::from_str(payload).unwrap();
+            assert_eq!(result.qty, 0.5);
+            assert_eq!(result.price, 50000.5);
+            assert_eq!(result.cum_exec_qty, 0.5);
+            assert_eq!(result.avg_price, Some(50000.25));
+        }
+    }
+
+    #[test]
+    fn test_bybit_status_strings_map_to_canonical_status() {
+        let cases = [
+            ("New", OrderStatus::New),
+            ("Untriggered", OrderStatus::New),
+            ("Triggered", OrderStatus::New),
+            ("PartiallyFilled", OrderStatus::PartiallyFilled),
+            ("Filled", OrderStatus::Filled),
+            ("Cancelled", OrderStatus::Canceled),
+            ("PartiallyFilledCanceled", OrderStatus::Canceled),
+            ("Deactivated", OrderStatus::Canceled),
+            ("Rejected", OrderStatus::Rejected),
+        ];
+        for (wire, expected) in cases {
+            let payload = format!(r#"{{"orderId":"1","orderLinkId":"","symbol":"BTCUSDT","side":"Buy","qty":"0.5","price":"50000","orderStatus":"{}","cumExecQty":"0","createdTime":"1700000000000"}}"#, wire);
+            let order: BybitOpenOrder = serde_json::from_str(&payload).unwrap();
+            assert_eq!(order.order_status, expected, "{}", wire);
+        }
+        // Binance spelling is not a Bybit status
+        let payload = r#"{"orderId":"1","orderLinkId":"","symbol":"BTCUSDT","side":"Buy","qty":"0.5","price":"50000","orderStatus":"PARTIALLY_FILLED","cumExecQty":"0","createdTime":"1700000000000"}"#;
+        assert!(serde_json::from_str::<BybitOpenOrder>(payload).is_err());
+    }
+
+    #[test]
+    fn test_open_order_empty_average_price_is_none() {
+        let payload = r#"{"orderId":"1","orderLinkId":"","symbol":"BTCUSDT","side":"Buy","qty":"0.5","price":50000,"orderStatus":"New","cumExecQty":"0","avgPrice":"","createdTime":"1700000000000"}"#;
+
+        let order = serde_json::from_str::<BybitOpenOrder>(payload).unwrap().into_order_response().unwrap();
+        assert_eq!(order.price, 50000.0);
+        assert_eq!(order.average_price, None);
+    }
+
+    fn depth_message(msg_type: &str, u: u64, bid: &str) -> String {
+        format!(
+            r#"{{"topic":"orderbook.50.BTCUSDT","type":"{}","ts":1234567890,"data":{{"s":"BTCUSDT","b":[["{}","1.00"]],"a":[["50100.00","1.00"]],"u":{},"seq":456}}}}"#,
+            msg_type, bid, u
+        )
+    }
+
+    fn book_of(update: Option<MarketDataUpdate>) -> OrderBook {
+        match update {
+            Some(MarketDataUpdate::OrderBook { orderbook, .. }) => orderbook,
+            other => panic!("expected an order book update, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_process_depth_message() {
+        let connector = BybitConnector::new(crate::connectors::test_exchange_config("http://127.0.0.1:1")).await.unwrap();
         
-        let orderbook = BybitConnector::parse_depth_message(message).unwrap();
+        let update = connector.process_depth_message(&depth_message("snapshot", 123, "50000.00")).await.unwrap();
+        match &update {
+            Some(MarketDataUpdate::OrderBook { exchange_timestamp, local_recv_timestamp, orderbook, .. }) => {
+                assert_eq!(*exchange_timestamp, Some(1_234_567_890_000_000));
+                assert_eq!(orderbook.timestamp, *local_recv_timestamp);
+            }
+            other => panic!("expected an order book update, got {:?}", other),
+        }
+        let orderbook = book_of(update);
         assert_eq!(orderbook.symbol, "BTCUSDT");
         assert_eq!(orderbook.best_bid(), Some(50000.0));
         assert_eq!(orderbook.best_ask(), Some(50100.0));
+
+        // A delta adds to the snapshot instead of replacing it
+        let orderbook = book_of(connector.process_depth_message(&depth_message("delta", 124, "49990.00")).await.unwrap());
+        assert_eq!(orderbook.bids.len(), 2);
+        assert_eq!(orderbook.best_bid(), Some(50000.0));
+    }
+
+    #[tokio::test]
+    async fn test_depth_gap_resyncs_from_rest() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v5/market/orderbook"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "retCode": 0,
+                "retMsg": "OK",
+                "result": {"s": "BTCUSDT", "b": [["50500.00", "2.0"]], "a": [["50510.00", "2.0"]], "ts": 1700000000000i64, "u": 103},
+                "time": 1700000000000i64
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let connector = BybitConnector::new(crate::connectors::test_exchange_config(&server.uri())).await.unwrap();
+
+        connector.process_depth_message(&depth_message("snapshot", 100, "50000.00")).await.unwrap();
+        connector.process_depth_message(&depth_message("delta", 101, "50001.00")).await.unwrap();
+        // Update 102 is missing; the snapshot at 103 covers the gapped delta
+        let orderbook = book_of(connector.process_depth_message(&depth_message("delta", 103, "40000.00")).await.unwrap());
+        assert_eq!(orderbook.best_bid(), Some(50500.0));
+        assert_eq!(orderbook.bids.len(), 1);
+
+        let orderbook = book_of(connector.process_depth_message(&depth_message("delta", 104, "50600.00")).await.unwrap());
+        assert_eq!(orderbook.best_bid(), Some(50600.0));
+        assert_eq!(connector.depth_feed.with_synchronizer("BTCUSDT", |sync| sync.resync_count()), Some(1));
     }
 
     #[test]
@@ -562,7 +1186,7 @@ mod tests {
         assert_eq!(price, 50050.0);
         assert_eq!(quantity, 0.1);
         assert_eq!(side, OrderSide::Buy);
-        assert_eq!(timestamp, 1234567890);
+        assert_eq!(timestamp, 1_234_567_890_000_000);
     }
 
     #[tokio::test]
@@ -577,6 +1201,11 @@ mod tests {
                 connection_timeout_secs: 10,
                 max_reconnect_attempts: 5,
                 reconnect_delay_secs: 5,
+                max_rate_limit_retries: 3,
+                ws_compression: WsCompression::None,
+                ws_subscriptions_per_sec: 5.0,
+                ws_subscription_batch_size: 10,
+                ws_subscription_ack_timeout_ms: 5000,
             },
             auth: AuthConfig {
                 api_key: "test_key".to_string(),
@@ -588,6 +1217,7 @@ mod tests {
             trading: TradingConfig {
                 default_order_type: "Limit".to_string(),
                 default_time_in_force: "GTC".to_string(),
+                order_types: Vec::new(),
                 additional: HashMap::new(),
             },
             fees: FeeConfig {
@@ -606,6 +1236,8 @@ mod tests {
                 streams: vec![],
                 topics: vec![],
                 depth_levels: 50,
+                resync_on_gap: true,
+                snapshot_bootstrap: true,
                 additional: HashMap::new(),
             },
             monitoring: MonitoringConfig {
@@ -614,6 +1246,11 @@ mod tests {
                 enable_trade_logging: true,
                 log_rotation_size_mb: 100,
                 health_check_interval_secs: 30,
+                webhook: WebhookConfig::default(),
+                trade_store: TradeStoreConfig::default(),
+                prometheus: PrometheusConfig::default(),
+                audit_log: AuditLogConfig::default(),
+                book_integrity_interval_ms: 0,
             },
         };
         
@@ -621,4 +1258,295 @@ mod tests {
         assert!(!connector.is_connected());
         assert_eq!(connector.connection_status(), ConnectionStatus::Disconnected);
     }
+
+    #[tokio::test]
+    async fn test_refresh_symbol_rules_from_instruments_info() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+        
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v5/market/instruments-info"))
+            .and(query_param("category", "spot"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "retCode": 0,
+                "retMsg": "OK",
+                "result": {
+                    "category": "spot",
+                    "list": [{
+                        "symbol": "ETHUSDT",
+                        "baseCoin": "ETH",
+                        "quoteCoin": "USDT",
+                        "status": "Trading",
+                        "lotSizeFilter": {
+                            "basePrecision": "0.00001",
+                            "quotePrecision": "0.0000001",
+                            "minOrderQty": "0.00062",
+                            "maxOrderQty": "1229.2336343",
+                            "minOrderAmt": "1",
+                            "maxOrderAmt": "2000000"
+                        },
+                        "priceFilter": {"tickSize": "0.01"}
+                    }]
+                },
+                "time": 1700000000000i64
+            })))
+            .mount(&server)
+            .await;
+        
+        let mut connector = BybitConnector::new(crate::connectors::test_exchange_config(&server.uri())).await.unwrap();
+        connector.refresh_symbol_rules().await.unwrap();
+        
+        let rules = connector.symbol_rules("ETHUSDT").unwrap();
+        assert_eq!(rules.tick_size, 0.01);
+        assert_eq!(rules.lot_size, 0.00001);
+        assert_eq!(rules.min_quantity, 0.00062);
+        assert_eq!(rules.min_notional, 1.0);
+        assert_eq!((rules.price_precision, rules.quantity_precision), (2, 5));
+        assert_eq!(connector.config.limits.min_order_sizes["ETHUSDT"], 0.00062);
+    }
+
+    #[tokio::test]
+    async fn test_place_oco_attaches_stop_to_take_profit() {
+        use wiremock::matchers::{body_partial_json, header_exists, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+        
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v5/order/create"))
+            .and(header_exists("X-BAPI-SIGN"))
+            .and(body_partial_json(serde_json::json!({
+                "symbol": "BTCUSDT",
+                "side": "Buy",
+                "price": "49000",
+                "stopLoss": "51000",
+                "slOrderType": "Market"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "retCode": 0,
+                "retMsg": "OK",
+                "result": {"orderId": "1321", "orderLinkId": ""}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        
+        let connector = BybitConnector::new(crate::connectors::test_exchange_config(&server.uri())).await.unwrap();
+        let response = connector.place_oco(&OcoOrder {
+            symbol: "BTCUSDT".to_string(),
+            side: OrderSide::Buy,
+            quantity: 0.1,
+            take_profit_price: 49000.0,
+            stop_price: 51000.0,
+            stop_limit_price: None,
+            client_order_id: None,
+        }).await.unwrap();
+        
+        assert_eq!(response.order_ids, vec!["1321"]);
+    }
+    
+    #[tokio::test]
+    async fn test_place_batch_sends_single_request() {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+        
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v5/order/create-batch"))
+            .and(body_partial_json(serde_json::json!({
+                "category": "spot",
+                "request": [
+                    {"symbol": "BTCUSDT", "side": "Sell", "price": "50100", "timeInForce": "PostOnly", "orderLinkId": "maker_1"},
+                    {"symbol": "BTCUSDT", "side": "Buy", "price": "50000", "timeInForce": "IOC", "orderLinkId": "taker_1"}
+                ]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "retCode": 0,
+                "retMsg": "OK",
+                "result": {"list": [
+                    {"category": "spot", "symbol": "BTCUSDT", "orderId": "11", "orderLinkId": "maker_1", "createAt": "1700000000000"},
+                    {"category": "spot", "symbol": "BTCUSDT", "orderId": "", "orderLinkId": "taker_1", "createAt": ""}
+                ]},
+                "retExtInfo": {"list": [{"code": 0, "msg": "OK"}, {"code": 170131, "msg": "Insufficient balance."}]}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        
+        let connector = BybitConnector::new(crate::connectors::test_exchange_config(&server.uri())).await.unwrap();
+        let maker = LimitOrder {
+            symbol: "BTCUSDT".to_string(),
+            side: OrderSide::Sell,
+            quantity: 0.1,
+            price: 50100.0,
+            time_in_force: TimeInForce::GTX,
+            client_order_id: Some("maker_1".to_string()),
+        };
+        let taker = LimitOrder {
+            side: OrderSide::Buy,
+            price: 50000.0,
+            time_in_force: TimeInForce::IOC,
+            client_order_id: Some("taker_1".to_string()),
+            ..maker.clone()
+        };
+        let responses = connector.place_batch(&[maker, taker]).await.unwrap();
+        
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0].order_id, "11");
+        assert_eq!(responses[0].status, OrderStatus::New);
+        assert_eq!(responses[0].timestamp, 1_700_000_000_000_000_000);
+        assert_eq!(responses[1].status, OrderStatus::Rejected);
+    }
+    
+    #[tokio::test]
+    async fn test_get_open_orders_parses_response() {
+        use wiremock::matchers::{header, header_exists, method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+        
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v5/order/realtime"))
+            .and(query_param("category", "spot"))
+            .and(header("X-BAPI-API-KEY", "test_key"))
+            .and(header_exists("X-BAPI-SIGN"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "retCode": 0,
+                "retMsg": "OK",
+                "result": {
+                    "category": "spot",
+                    "nextPageCursor": "",
+                    "list": [{
+                        "orderId": "1321003749386327552",
+                        "orderLinkId": "",
+                        "symbol": "ETHUSDT",
+                        "price": "2000.5",
+                        "qty": "1.5",
+                        "side": "Buy",
+                        "orderStatus": "New",
+                        "orderType": "Limit",
+                        "timeInForce": "GTC",
+                        "cumExecQty": "0",
+                        "cumExecValue": "0",
+                        "avgPrice": "",
+                        "createdTime": "1700000000000",
+                        "updatedTime": "1700000000000"
+                    }]
+                },
+                "time": 1700000000000i64
+            })))
+            .mount(&server)
+            .await;
+        
+        let connector = BybitConnector::new(crate::connectors::test_exchange_config(&server.uri())).await.unwrap();
+        let orders = connector.get_open_orders(None).await.unwrap();
+        
+        assert_eq!(orders.len(), 1);
+        let order = &orders[0];
+        assert_eq!(order.order_id, "1321003749386327552");
+        assert_eq!(order.client_order_id, None);
+        assert_eq!(order.side, OrderSide::Buy);
+        assert_eq!(order.status, OrderStatus::New);
+        assert_eq!((order.quantity, order.price, order.filled_quantity), (1.5, 2000.5, 0.0));
+        assert_eq!(order.average_price, None);
+        assert_eq!(order.timestamp, 1_700_000_000_000_000_000);
+    }
+    
+    #[test]
+    fn test_sign_v5_covers_timestamp_key_window_and_payload() {
+        // Reference values: HMAC-SHA256 over "1700000000000" + "test_key" + "5000" + payload
+        let get = sign_v5("test_secret", "1700000000000", "test_key", 5000, "category=spot&symbol=BTCUSDT").unwrap();
+        assert_eq!(get, "a04c472e89f841b28f950a4fe5b68224b8bb8468dfe801a00eb308813c03ad50");
+        let post = sign_v5("test_secret", "1700000000000", "test_key", 5000, r#"{"category":"spot"}"#).unwrap();
+        assert_eq!(post, "214275ddec9be9c7c8dc2a7d006820412b11bc1fa89a8d9aa1848719cbd6a32c");
+        
+        assert_ne!(sign_v5("test_secret", "1700000000000", "test_key", 10000, "category=spot&symbol=BTCUSDT").unwrap(), get);
+    }
+    
+    #[tokio::test]
+    async fn test_timestamp_outside_receive_window_is_a_timeout() {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+        
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v5/order/create"))
+            .and(body_partial_json(serde_json::json!({"category": "spot", "symbol": "BTCUSDT", "timeInForce": "PostOnly"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "retCode": 10002,
+                "retMsg": "invalid request, please check your server timestamp or recv_window param",
+                "result": {}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        
+        let connector = BybitConnector::new(crate::connectors::test_exchange_config(&server.uri())).await.unwrap();
+        let err = connector.place_limit_order(&LimitOrder {
+            symbol: "BTCUSDT".to_string(),
+            side: OrderSide::Sell,
+            quantity: 0.1,
+            price: 50100.0,
+            time_in_force: TimeInForce::GTX,
+            client_order_id: None,
+        }).await.unwrap_err();
+        
+        assert!(matches!(err.downcast_ref::<ArbitrageError>(), Some(ArbitrageError::Timeout(_))), "{}", err);
+    }
+    
+    #[tokio::test]
+    async fn test_cancel_reports_fills_from_order_history() {
+        use wiremock::matchers::{body_partial_json, header_exists, method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+        
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v5/order/cancel"))
+            .and(header_exists("X-BAPI-SIGN"))
+            .and(body_partial_json(serde_json::json!({"category": "spot", "symbol": "BTCUSDT", "orderId": "42"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "retCode": 0, "retMsg": "OK", "result": {"orderId": "42", "orderLinkId": "maker_1"}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // Already closed, so only the history still lists it
+        Mock::given(method("GET"))
+            .and(path("/v5/order/realtime"))
+            .and(query_param("orderId", "42"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "retCode": 0, "retMsg": "OK", "result": {"list": []}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v5/order/history"))
+            .and(query_param("orderId", "42"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "retCode": 0,
+                "retMsg": "OK",
+                "result": {"list": [{
+                    "orderId": "42",
+                    "orderLinkId": "maker_1",
+                    "symbol": "BTCUSDT",
+                    "price": "50100",
+                    "qty": "1",
+                    "side": "Sell",
+                    "orderStatus": "PartiallyFilledCanceled",
+                    "cumExecQty": "0.3",
+                    "avgPrice": "50100",
+                    "createdTime": "1700000000000"
+                }]}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        
+        let connector = BybitConnector::new(crate::connectors::test_exchange_config(&server.uri())).await.unwrap();
+        let response = connector.cancel_order("BTCUSDT", "42").await.unwrap();
+        
+        assert_eq!(response.status, OrderStatus::Canceled);
+        assert_eq!(response.client_order_id.as_deref(), Some("maker_1"));
+        assert_eq!((response.filled_quantity, response.average_price), (0.3, Some(50100.0)));
+    }
 }
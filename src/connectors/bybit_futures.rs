@@ -1,7 +1,19 @@
 //! Bybit Futures connector implementation
 
 use super::futures::*;
-use crate::{connectors::Exchange, data::OrderBook, Result, ArbitrageError};
+use crate::{
+    config::WsCompression,
+    connectors::{
+        compression::decode_ws_frame,
+        http::{get_with_backoff, send_with_backoff, DEFAULT_MAX_RATE_LIMIT_RETRIES},
+        serde_helpers::parse_level,
+        Exchange,
+        OrderSide,
+    },
+    data::OrderBook,
+    utils::time::{ms_to_ns, now_ns},
+    Result, ArbitrageError,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tokio::net::TcpStream;
@@ -9,6 +21,17 @@ use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
 use futures_util::{SinkExt, StreamExt};
 use tokio_tungstenite::tungstenite::Message;
 use tracing::{debug, error, info, warn};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Bybit receive window for signed requests in milliseconds
+const RECV_WINDOW_MS: u64 = 5000;
+
+/// Bybit retCode returned when the margin mode is already set
+const MARGIN_MODE_NOT_MODIFIED: i64 = 110026;
+
+/// Bybit retCode returned when the leverage is already set
+const LEVERAGE_NOT_MODIFIED: i64 = 110043;
 
 /// Bybit Futures connector
 pub struct BybitFuturesConnector {
@@ -26,6 +49,12 @@ pub struct BybitFuturesConnector {
     is_connected: bool,
     /// Subscribed symbols
     subscribed_symbols: Vec<String>,
+    /// Compression applied to binary WebSocket frames
+    ws_compression: WsCompression,
+    /// HTTP client for REST requests
+    client: reqwest::Client,
+    /// Retries of a rate-limited REST request before giving up
+    max_rate_limit_retries: u32,
 }
 
 impl BybitFuturesConnector {
@@ -39,9 +68,137 @@ impl BybitFuturesConnector {
             ws_connection: None,
             is_connected: false,
             subscribed_symbols: Vec::new(),
+            ws_compression: WsCompression::None,
+            client: reqwest::Client::new(),
+            max_rate_limit_retries: DEFAULT_MAX_RATE_LIMIT_RETRIES,
         }
     }
 
+    /// Retry rate-limited requests up to `retries` times before failing with `RateLimited`
+    pub fn with_max_rate_limit_retries(mut self, retries: u32) -> Self {
+        self.max_rate_limit_retries = retries;
+        self
+    }
+
+    /// Override the REST API base URL
+    pub fn with_api_url(mut self, api_url: &str) -> Self {
+        self.api_url = api_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Decompress binary WebSocket frames with the given codec before parsing
+    pub fn with_ws_compression(mut self, compression: WsCompression) -> Self {
+        self.ws_compression = compression;
+        self
+    }
+
+    /// Send a signed V5 POST request; returns `(retCode, retMsg, result)`
+    async fn signed_post(&self, path: &str, body: &serde_json::Value) -> Result<(i64, String, serde_json::Value)> {
+        let body = body.to_string();
+        let url = format!("{}{}", self.api_url, path);
+        let request = || self.client
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .body(body.clone());
+        self.send_signed(request, &body).await
+    }
+
+    /// Send a signed V5 GET request; returns `(retCode, retMsg, result)`
+    async fn signed_get(&self, path: &str, query: &str) -> Result<(i64, String, serde_json::Value)> {
+        let url = format!("{}{}?{}", self.api_url, path, query);
+        self.send_signed(|| self.client.get(&url), query).await
+    }
+
+    /// Sign `payload`, the JSON body or query string, and send the request,
+    /// re-signing it for each retry after a rate limit
+    async fn send_signed(&self, request: impl Fn() -> reqwest::RequestBuilder, payload: &str) -> Result<(i64, String, serde_json::Value)> {
+        let (api_key, secret) = match (&self.api_key, &self.secret_key) {
+            (Some(api_key), Some(secret)) => (api_key, secret),
+            _ => return Err(ArbitrageError::Config("Bybit futures API credentials not configured".to_string()).into()),
+        };
+
+        let response = send_with_backoff(|| {
+            let timestamp = chrono::Utc::now().timestamp_millis().to_string();
+            let signed = format!("{}{}{}{}", timestamp, api_key, RECV_WINDOW_MS, payload);
+            let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+                .map_err(|e| ArbitrageError::Config(format!("Invalid secret key: {}", e)))?;
+            mac.update(signed.as_bytes());
+            let signature = hex::encode(mac.finalize().into_bytes());
+            Ok(request()
+                .header("X-BAPI-API-KEY", api_key)
+                .header("X-BAPI-TIMESTAMP", &timestamp)
+                .header("X-BAPI-RECV-WINDOW", RECV_WINDOW_MS.to_string())
+                .header("X-BAPI-SIGN", signature))
+        }, self.max_rate_limit_retries).await?;
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse Bybit futures response: {}", e)))?;
+        let ret_code = body.get("retCode").and_then(|c| c.as_i64()).unwrap_or(-1);
+        let ret_msg = body.get("retMsg").and_then(|m| m.as_str()).unwrap_or_default().to_string();
+        let result = body.get("result").cloned().unwrap_or(serde_json::Value::Null);
+        Ok((ret_code, ret_msg, result))
+    }
+
+    /// JSON body for `/v5/order/create`, with any TP/SL attached to the entry
+    fn order_body(order: &FuturesOrder) -> serde_json::Value {
+        let order_type = match order.order_type {
+            FuturesOrderType::Limit | FuturesOrderType::StopLimit | FuturesOrderType::TakeProfitLimit => "Limit",
+            FuturesOrderType::Market | FuturesOrderType::StopMarket | FuturesOrderType::TakeProfitMarket => "Market",
+        };
+        let time_in_force = match order.time_in_force {
+            FuturesTimeInForce::GTC => "GTC",
+            FuturesTimeInForce::IOC => "IOC",
+            FuturesTimeInForce::FOK => "FOK",
+            FuturesTimeInForce::GTX => "PostOnly",
+        };
+        // 0 is one-way mode; hedge mode addresses the long (1) or short (2) position
+        let position_idx = match order.position_side {
+            Some(PositionSide::Long) => 1,
+            Some(PositionSide::Short) => 2,
+            _ => 0,
+        };
+        let mut body = serde_json::json!({
+            "category": "linear",
+            "symbol": order.symbol,
+            "side": format!("{:?}", order.side),
+            "orderType": order_type,
+            "qty": order.quantity.to_string(),
+            "timeInForce": time_in_force,
+            "reduceOnly": order.reduce_only,
+            "closeOnTrigger": order.close_position,
+            "positionIdx": position_idx,
+        });
+        if let Some(price) = order.price {
+            body["price"] = price.to_string().into();
+        }
+        if let Some(trigger) = order.stop_price {
+            // 1 triggers on a rise, 2 on a fall: stops fire against the order side, take-profits with it
+            let rising = match order.order_type {
+                FuturesOrderType::TakeProfitMarket | FuturesOrderType::TakeProfitLimit => order.side == OrderSide::Sell,
+                _ => order.side == OrderSide::Buy,
+            };
+            body["triggerPrice"] = trigger.to_string().into();
+            body["triggerDirection"] = if rising { 1 } else { 2 }.into();
+        }
+        if order.take_profit.is_some() || order.stop_loss.is_some() {
+            body["tpslMode"] = "Full".into();
+        }
+        if let Some(take_profit) = order.take_profit {
+            body["takeProfit"] = take_profit.to_string().into();
+            body["tpTriggerBy"] = "MarkPrice".into();
+        }
+        if let Some(stop_loss) = order.stop_loss {
+            body["stopLoss"] = stop_loss.to_string().into();
+            body["slTriggerBy"] = "MarkPrice".into();
+        }
+        if let Some(client_order_id) = &order.client_order_id {
+            body["orderLinkId"] = client_order_id.clone().into();
+        }
+        body
+    }
+
     /// Get common USDT perpetual symbols (same as Binance)
     pub async fn get_common_usdt_perpetuals(&self) -> Result<Vec<String>> {
         // Common USDT perpetual contracts available on both Binance and Bybit
@@ -69,6 +226,36 @@ impl BybitFuturesConnector {
         ])
     }
 
+    /// Maximum leverage per linear symbol, from the instruments' `leverageFilter`
+    pub async fn get_max_leverage(&self) -> Result<HashMap<String, u32>> {
+        let url = format!("{}/v5/market/instruments-info?category=linear&limit=1000", self.api_url);
+        let response = get_with_backoff(&self.client, &url, self.max_rate_limit_retries).await?;
+
+        if !response.status().is_success() {
+            return Err(ArbitrageError::Connection(
+                format!("HTTP request failed with status: {}", response.status())
+            ).into());
+        }
+
+        let info: BybitLinearInstrumentsInfo = response
+            .json()
+            .await
+            .map_err(|e| ArbitrageError::DataParsing(format!("Failed to parse linear instruments info: {}", e)))?;
+        if info.ret_code != 0 {
+            return Err(ArbitrageError::Connection(format!("Bybit instruments-info error: {}", info.ret_msg)).into());
+        }
+
+        Ok(info
+            .result
+            .list
+            .into_iter()
+            .filter_map(|instrument| {
+                let max_leverage: f64 = instrument.leverage_filter.max_leverage.parse().ok()?;
+                Some((instrument.symbol, max_leverage.floor() as u32))
+            })
+            .collect())
+    }
+
     /// Parse depth message from WebSocket
     fn parse_depth_message(&self, msg: &str) -> Result<OrderBook> {
         let depth_msg: BybitFuturesMessage = serde_json::from_str(msg)
@@ -82,10 +269,7 @@ impl BybitFuturesConnector {
                 if let Some(bids) = data.bids {
                     for bid in bids {
                         if bid.len() >= 2 {
-                            let price = bid[0].parse::<f64>()
-                                .map_err(|e| ArbitrageError::ParseError(format!("Invalid bid price: {}", e)))?;
-                            let quantity = bid[1].parse::<f64>()
-                                .map_err(|e| ArbitrageError::ParseError(format!("Invalid bid quantity: {}", e)))?;
+                            let (price, quantity) = parse_level(&bid[0], &bid[1])?;
                             orderbook.update_bid(price, quantity);
                         }
                     }
@@ -95,16 +279,14 @@ impl BybitFuturesConnector {
                 if let Some(asks) = data.asks {
                     for ask in asks {
                         if ask.len() >= 2 {
-                            let price = ask[0].parse::<f64>()
-                                .map_err(|e| ArbitrageError::ParseError(format!("Invalid ask price: {}", e)))?;
-                            let quantity = ask[1].parse::<f64>()
-                                .map_err(|e| ArbitrageError::ParseError(format!("Invalid ask quantity: {}", e)))?;
+                            let (price, quantity) = parse_level(&ask[0], &ask[1])?;
                             orderbook.update_ask(price, quantity);
                         }
                     }
                 }
 
-                orderbook.set_timestamp(data.ts.unwrap_or(chrono::Utc::now().timestamp_millis()));
+                // Bybit V5 puts the millisecond `ts` on the envelope, not inside `data`
+                orderbook.set_timestamp(data.ts.or(depth_msg.ts).map_or_else(now_ns, ms_to_ns));
                 return Ok(orderbook);
             }
         }
@@ -127,7 +309,7 @@ impl BybitFuturesConnector {
                     last_funding_rate: data.funding_rate.and_then(|s| s.parse().ok()).unwrap_or(0.0),
                     next_funding_time: data.next_funding_time.unwrap_or(0),
                     interest_rate: 0.0,
-                    timestamp: data.ts.unwrap_or(chrono::Utc::now().timestamp_millis()),
+                    timestamp: data.ts.or(mark_msg.ts).map_or_else(now_ns, ms_to_ns),
                 });
             }
         }
@@ -159,6 +341,13 @@ impl FuturesConnector for BybitFuturesConnector {
         // In a real implementation, this would fetch from Bybit API
         // For now, return mock data for common symbols
         let symbols = self.get_common_usdt_perpetuals().await?;
+        let max_leverage = match self.get_max_leverage().await {
+            Ok(max_leverage) => max_leverage,
+            Err(e) => {
+                warn!("Failed to fetch Bybit futures leverage limits, assuming {}x: {}", DEFAULT_MAX_LEVERAGE, e);
+                HashMap::new()
+            }
+        };
         let mut contracts = HashMap::new();
         
         for symbol in symbols {
@@ -174,6 +363,10 @@ impl FuturesConnector for BybitFuturesConnector {
                 lot_size: 0.001,
                 maker_fee: -0.00025, // -0.025% (rebate)
                 taker_fee: 0.00075,  // 0.075%
+                contract_multiplier: 1.0,
+                max_leverage: max_leverage.get(&symbol).copied().unwrap_or(DEFAULT_MAX_LEVERAGE),
+                quantity_unit: QuantityUnit::Base,
+                percent_price: None,
             });
         }
         
@@ -190,9 +383,31 @@ impl FuturesConnector for BybitFuturesConnector {
         Err(ArbitrageError::NotImplemented("get_positions requires API keys".to_string()).into())
     }
 
-    async fn place_order(&self, _order: &FuturesOrder) -> Result<FuturesOrderResponse> {
-        // Mock implementation - in production this would call Bybit API
-        Err(ArbitrageError::NotImplemented("place_order requires API keys".to_string()).into())
+    async fn place_order(&self, order: &FuturesOrder) -> Result<FuturesOrderResponse> {
+        match self.signed_post("/v5/order/create", &Self::order_body(order)).await? {
+            (0, _, result) => Ok(FuturesOrderResponse {
+                order_id: result.get("orderId").and_then(|id| id.as_str()).unwrap_or_default().to_string(),
+                client_order_id: result.get("orderLinkId").and_then(|id| id.as_str())
+                    .filter(|id| !id.is_empty())
+                    .map(str::to_string),
+                symbol: order.symbol.clone(),
+                side: order.side,
+                position_side: order.position_side.clone(),
+                order_type: order.order_type.clone(),
+                quantity: order.quantity,
+                price: order.price,
+                // V5 acknowledges creation only; fills arrive via order status or the private stream
+                status: FuturesOrderStatus::New,
+                filled_quantity: 0.0,
+                average_price: None,
+                commission: 0.0,
+                commission_asset: String::new(),
+                timestamp: now_ns(),
+            }),
+            (code, msg, _) => Err(ArbitrageError::Trading(format!(
+                "Bybit futures order for {} rejected: {} {}", order.symbol, code, msg
+            )).into()),
+        }
     }
 
     async fn cancel_order(&self, _symbol: &str, _order_id: &str) -> Result<FuturesOrderResponse> {
@@ -210,6 +425,68 @@ impl FuturesConnector for BybitFuturesConnector {
         Err(ArbitrageError::NotImplemented("get_funding_rate not implemented".to_string()).into())
     }
 
+    async fn set_margin_mode(&self, symbol: &str, mode: MarginMode) -> Result<()> {
+        // Bybit V5 unified accounts set margin mode account-wide
+        let margin_mode = match mode {
+            MarginMode::Cross => "REGULAR_MARGIN",
+            MarginMode::Isolated => "ISOLATED_MARGIN",
+        };
+        let body = serde_json::json!({ "setMarginMode": margin_mode });
+
+        match self.signed_post("/v5/account/set-margin-mode", &body).await? {
+            (0, _, _) => {
+                info!("Set Bybit margin mode to {} (requested for {})", margin_mode, symbol);
+                Ok(())
+            }
+            (MARGIN_MODE_NOT_MODIFIED, _, _) => {
+                debug!("Bybit margin mode already {}", margin_mode);
+                Ok(())
+            }
+            (code, msg, _) => Err(ArbitrageError::Trading(format!(
+                "Failed to set Bybit margin mode for {}: {} {}", symbol, code, msg
+            )).into()),
+        }
+    }
+
+    async fn set_leverage(&self, symbol: &str, leverage: u32) -> Result<()> {
+        let body = serde_json::json!({
+            "category": "linear",
+            "symbol": symbol,
+            "buyLeverage": leverage.to_string(),
+            "sellLeverage": leverage.to_string(),
+        });
+
+        match self.signed_post("/v5/position/set-leverage", &body).await? {
+            (0, _, _) => {
+                info!("Set Bybit leverage for {} to {}x", symbol, leverage);
+                Ok(())
+            }
+            (LEVERAGE_NOT_MODIFIED, _, _) => {
+                debug!("Bybit leverage for {} already {}x", symbol, leverage);
+                Ok(())
+            }
+            (code, msg, _) => Err(ArbitrageError::Trading(format!(
+                "Failed to set Bybit leverage for {}: {} {}", symbol, code, msg
+            )).into()),
+        }
+    }
+
+    async fn get_position_mode(&self, symbol: &str) -> Result<PositionMode> {
+        // Hedge mode lists the long (1) and short (2) sides even when flat
+        let query = format!("category=linear&symbol={}", symbol);
+        match self.signed_get("/v5/position/list", &query).await? {
+            (0, _, result) => {
+                let hedged = result.get("list")
+                    .and_then(|list| list.as_array())
+                    .is_some_and(|list| list.iter().any(|p| matches!(p.get("positionIdx").and_then(|i| i.as_i64()), Some(1 | 2))));
+                Ok(if hedged { PositionMode::Hedge } else { PositionMode::OneWay })
+            }
+            (code, msg, _) => Err(ArbitrageError::Trading(format!(
+                "Failed to query Bybit position mode for {}: {} {}", symbol, code, msg
+            )).into()),
+        }
+    }
+
     async fn get_mark_price(&self, _symbol: &str) -> Result<MarkPrice> {
         // Mock implementation - in production this would call Bybit API
         Err(ArbitrageError::NotImplemented("get_mark_price not implemented".to_string()).into())
@@ -289,20 +566,23 @@ impl BybitFuturesConnector {
         if let Some(mut ws_stream) = ws {
             while let Some(msg) = ws_stream.next().await {
                 match msg {
-                    Ok(Message::Text(text)) => {
-                        if let Err(e) = Self::process_message_static(&text).await {
-                            warn!("Failed to process message: {}", e);
-                        }
-                    }
                     Ok(Message::Close(_)) => {
                         info!("WebSocket connection closed");
                         break;
                     }
+                    Ok(frame) => match decode_ws_frame(frame, self.ws_compression) {
+                        Ok(Some(text)) => {
+                            if let Err(e) = Self::process_message_static(&text).await {
+                                warn!("Failed to process message: {}", e);
+                            }
+                        }
+                        Ok(None) => {}
+                        Err(e) => warn!("Failed to decode message: {}", e),
+                    },
                     Err(e) => {
                         error!("WebSocket error: {}", e);
                         break;
                     }
-                    _ => {}
                 }
             }
             self.ws_connection = Some(ws_stream);
@@ -344,6 +624,33 @@ impl BybitFuturesConnector {
     }
 }
 
+#[derive(Debug, Deserialize)]
+struct BybitLinearInstrumentsInfo {
+    #[serde(rename = "retCode")]
+    ret_code: i64,
+    #[serde(rename = "retMsg")]
+    ret_msg: String,
+    result: BybitLinearInstruments,
+}
+
+#[derive(Debug, Deserialize)]
+struct BybitLinearInstruments {
+    list: Vec<BybitLinearInstrument>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BybitLinearInstrument {
+    symbol: String,
+    #[serde(rename = "leverageFilter")]
+    leverage_filter: BybitLeverageFilter,
+}
+
+#[derive(Debug, Deserialize)]
+struct BybitLeverageFilter {
+    #[serde(rename = "maxLeverage")]
+    max_leverage: String,
+}
+
 // WebSocket message structures
 #[derive(Debug, Deserialize)]
 struct BybitFuturesMessage {
@@ -380,6 +687,14 @@ struct BybitFuturesData {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_depth_timestamp_normalized_to_nanos() {
+        let connector = BybitFuturesConnector::new(None, None);
+        let msg = r#"{"topic":"orderbook.50.BTCUSDT","type":"snapshot","ts":1700000000123,"data":{"s":"BTCUSDT","b":[["50000.0","1.0"]],"a":[["50010.0","1.0"]],"u":1}}"#;
+        let orderbook = connector.parse_depth_message(msg).unwrap();
+        assert_eq!(orderbook.timestamp, 1_700_000_000_123_000_000);
+    }
+
     #[tokio::test]
     async fn test_bybit_futures_connector_creation() {
         let connector = BybitFuturesConnector::new(None, None);
@@ -397,13 +712,31 @@ mod tests {
 
     #[tokio::test]
     async fn test_exchange_info() {
-        let connector = BybitFuturesConnector::new(None, None);
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v5/market/instruments-info"))
+            .and(query_param("category", "linear"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "retCode": 0,
+                "retMsg": "OK",
+                "result": {"list": [{"symbol": "BTCUSDT", "leverageFilter": {"minLeverage": "1", "maxLeverage": "100.00", "leverageStep": "0.01"}}]}
+            })))
+            .mount(&server)
+            .await;
+
+        let connector = BybitFuturesConnector::new(None, None).with_api_url(&server.uri());
         let contracts = connector.get_exchange_info().await.unwrap();
         assert!(contracts.contains_key("BTCUSDT"));
         
         let btc_contract = &contracts["BTCUSDT"];
         assert_eq!(btc_contract.contract_type, ContractType::Perpetual);
         assert_eq!(btc_contract.quote_asset, "USDT");
+        assert_eq!(btc_contract.max_leverage, 100);
+        // Unlisted in the instruments response, so the conservative default applies
+        assert_eq!(contracts["ETHUSDT"].max_leverage, DEFAULT_MAX_LEVERAGE);
         // Bybit has maker rebate
         assert!(btc_contract.maker_fee < 0.0);
     }
@@ -418,4 +751,98 @@ mod tests {
         assert!(msg.contains("orderbook.50.BTCUSDT"));
         assert!(msg.contains("tickers.BTCUSDT"));
     }
+
+    #[tokio::test]
+    async fn test_set_margin_mode_sends_set_margin_mode() {
+        use wiremock::matchers::{body_partial_json, header_exists, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v5/account/set-margin-mode"))
+            .and(body_partial_json(serde_json::json!({"setMarginMode": "ISOLATED_MARGIN"})))
+            .and(header_exists("X-BAPI-SIGN"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"retCode": 0, "retMsg": "OK", "result": {}})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let connector = BybitFuturesConnector::new(Some("key".to_string()), Some("secret".to_string()))
+            .with_api_url(&server.uri());
+        connector.set_margin_mode("BTCUSDT", MarginMode::Isolated).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_set_leverage_sends_configured_value() {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v5/position/set-leverage"))
+            .and(body_partial_json(serde_json::json!({
+                "category": "linear",
+                "symbol": "BTCUSDT",
+                "buyLeverage": "3",
+                "sellLeverage": "3"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"retCode": 0, "retMsg": "OK", "result": {}})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let connector = BybitFuturesConnector::new(Some("key".to_string()), Some("secret".to_string()))
+            .with_api_url(&server.uri());
+        connector.set_leverage("BTCUSDT", 3).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_take_profit_and_stop_loss_attached_to_entry() {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v5/order/create"))
+            .and(body_partial_json(serde_json::json!({
+                "category": "linear",
+                "symbol": "BTCUSDT",
+                "side": "Buy",
+                "orderType": "Limit",
+                "price": "50000",
+                "timeInForce": "PostOnly",
+                "positionIdx": 0,
+                "tpslMode": "Full",
+                "takeProfit": "50500",
+                "stopLoss": "49500",
+                "orderLinkId": "maker_1"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "retCode": 0, "retMsg": "OK", "result": {"orderId": "abc", "orderLinkId": "maker_1"}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let connector = BybitFuturesConnector::new(Some("key".to_string()), Some("secret".to_string()))
+            .with_api_url(&server.uri());
+        let order = FuturesOrder {
+            symbol: "BTCUSDT".to_string(),
+            side: OrderSide::Buy,
+            position_side: Some(PositionSide::Both),
+            order_type: FuturesOrderType::Limit,
+            quantity: 0.01,
+            price: Some(50000.0),
+            stop_price: None,
+            time_in_force: FuturesTimeInForce::GTX,
+            reduce_only: false,
+            close_position: false,
+            client_order_id: Some("maker_1".to_string()),
+            take_profit: Some(50500.0),
+            stop_loss: Some(49500.0),
+        };
+        let response = connector.place_order(&order).await.unwrap();
+        assert_eq!(response.order_id, "abc");
+        assert_eq!(response.client_order_id.as_deref(), Some("maker_1"));
+    }
 }
@@ -0,0 +1,84 @@
+//! Decompression of binary WebSocket frames
+//!
+//! Some venues send market data as gzip or deflate compressed binary frames
+//! instead of text. Frames are decoded here, according to the venue's
+//! [`WsCompression`] setting, before any JSON parsing.
+
+use crate::{config::WsCompression, ArbitrageError, Result};
+use flate2::read::{DeflateDecoder, MultiGzDecoder, ZlibDecoder};
+use std::io::Read;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Decode a WebSocket frame into its JSON text.
+///
+/// Text frames pass through untouched; binary frames are decompressed.
+/// Control frames carry no payload and return `None`.
+pub fn decode_ws_frame(message: Message, compression: WsCompression) -> Result<Option<String>> {
+    match message {
+        Message::Text(text) => Ok(Some(text)),
+        Message::Binary(bytes) => decompress(&bytes, compression).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// Decompress a binary payload into UTF-8 text
+pub fn decompress(bytes: &[u8], compression: WsCompression) -> Result<String> {
+    let mut text = String::new();
+    let read = match compression {
+        WsCompression::None => {
+            return String::from_utf8(bytes.to_vec())
+                .map_err(|e| ArbitrageError::DataParsing(format!("Binary frame is not UTF-8: {}", e)).into());
+        }
+        WsCompression::Gzip => MultiGzDecoder::new(bytes).read_to_string(&mut text),
+        WsCompression::Deflate if has_zlib_header(bytes) => ZlibDecoder::new(bytes).read_to_string(&mut text),
+        WsCompression::Deflate => DeflateDecoder::new(bytes).read_to_string(&mut text),
+    };
+    match read {
+        Ok(_) => Ok(text),
+        Err(e) => Err(ArbitrageError::DataParsing(format!("Failed to decompress {:?} frame: {}", compression, e)).into()),
+    }
+}
+
+/// Whether the payload starts with a zlib (RFC 1950) header
+fn has_zlib_header(bytes: &[u8]) -> bool {
+    match bytes {
+        [cmf, flg, ..] => cmf & 0x0f == 8 && (u16::from(*cmf) << 8 | u16::from(*flg)) % 31 == 0,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::{DeflateEncoder, GzEncoder, ZlibEncoder};
+    use flate2::Compression;
+    use std::io::Write;
+
+    const DEPTH: &str = r#"{"e":"depthUpdate","s":"BTCUSDT","b":[["50000.0","1.5"]],"a":[]}"#;
+
+    #[test]
+    fn test_compressed_frames_decode_to_json() {
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+        gzip.write_all(DEPTH.as_bytes()).unwrap();
+        let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+        zlib.write_all(DEPTH.as_bytes()).unwrap();
+        let mut raw = DeflateEncoder::new(Vec::new(), Compression::default());
+        raw.write_all(DEPTH.as_bytes()).unwrap();
+
+        let frames = [
+            (Message::Binary(gzip.finish().unwrap()), WsCompression::Gzip),
+            (Message::Binary(zlib.finish().unwrap()), WsCompression::Deflate),
+            (Message::Binary(raw.finish().unwrap()), WsCompression::Deflate),
+            (Message::Text(DEPTH.to_string()), WsCompression::Gzip),
+        ];
+        for (frame, compression) in frames {
+            let text = decode_ws_frame(frame, compression).unwrap().unwrap();
+            assert_eq!(text, DEPTH);
+            let json: serde_json::Value = serde_json::from_str(&text).unwrap();
+            assert_eq!(json["s"], "BTCUSDT");
+        }
+
+        assert!(decode_ws_frame(Message::Ping(Vec::new()), WsCompression::Gzip).unwrap().is_none());
+        assert!(decode_ws_frame(Message::Binary(DEPTH.as_bytes().to_vec()), WsCompression::Gzip).is_err());
+    }
+}
@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use crate::{connectors::{Exchange, OrderSide}, Result};
+use crate::{connectors::{Exchange, OrderSide}, ArbitrageError, Result};
 
 /// Futures contract specification
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +29,134 @@ pub struct FuturesContract {
     pub maker_fee: f64,
     /// Taker fee rate
     pub taker_fee: f64,
+    /// Units of base asset per contract (quote units per contract for inverse contracts)
+    #[serde(default = "default_contract_multiplier")]
+    pub contract_multiplier: f64,
+    /// Maximum leverage allowed for the contract
+    #[serde(default = "default_max_leverage")]
+    pub max_leverage: u32,
+    /// Unit the venue expects order quantities in
+    #[serde(default)]
+    pub quantity_unit: QuantityUnit,
+    /// Band limit prices must stay within around the mark price, if the venue enforces one
+    #[serde(default)]
+    pub percent_price: Option<PercentPriceBand>,
+}
+
+/// Percent-price filter: limit prices must lie within multiples of a reference price
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PercentPriceBand {
+    /// Highest allowed price as a multiple of the reference price
+    pub multiplier_up: f64,
+    /// Lowest allowed price as a multiple of the reference price
+    pub multiplier_down: f64,
+}
+
+/// Tolerance absorbing float error when dividing a price by the tick size
+const TICK_EPSILON: f64 = 1e-9;
+
+/// Unit of an order quantity on a venue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuantityUnit {
+    /// Quantity is in base asset units
+    #[default]
+    Base,
+    /// Quantity is a number of contracts of `contract_multiplier` base units each
+    Contracts,
+}
+
+fn default_contract_multiplier() -> f64 {
+    1.0
+}
+
+/// Leverage cap assumed for a contract whose venue limit could not be fetched
+pub(crate) const DEFAULT_MAX_LEVERAGE: u32 = 20;
+
+fn default_max_leverage() -> u32 {
+    DEFAULT_MAX_LEVERAGE
+}
+
+impl FuturesContract {
+    /// Base asset quantity represented by a contract quantity
+    pub fn base_quantity(&self, contracts: f64) -> f64 {
+        contracts * self.contract_multiplier
+    }
+
+    /// Contract quantity needed to represent a base asset quantity
+    pub fn contracts_for_base(&self, base_quantity: f64) -> f64 {
+        base_quantity / self.contract_multiplier
+    }
+
+    /// Order quantity in this venue's unit for a canonical base asset quantity
+    pub fn venue_quantity(&self, base_quantity: f64) -> f64 {
+        match self.quantity_unit {
+            QuantityUnit::Base => base_quantity,
+            QuantityUnit::Contracts => self.contracts_for_base(base_quantity),
+        }
+    }
+
+    /// Canonical base asset quantity for an order quantity in this venue's unit
+    pub fn base_from_venue(&self, venue_quantity: f64) -> f64 {
+        match self.quantity_unit {
+            QuantityUnit::Base => venue_quantity,
+            QuantityUnit::Contracts => self.base_quantity(venue_quantity),
+        }
+    }
+
+    /// Asset that notional and PnL are denominated in: quote for linear, base for inverse
+    pub fn settlement_asset(&self) -> &str {
+        if self.contract_type.is_inverse() {
+            &self.base_asset
+        } else {
+            &self.quote_asset
+        }
+    }
+
+    /// Notional of a contract quantity at a price, in the settlement asset
+    pub fn notional(&self, contracts: f64, price: f64) -> f64 {
+        if self.contract_type.is_inverse() {
+            contracts * self.contract_multiplier / price
+        } else {
+            self.base_quantity(contracts) * price
+        }
+    }
+
+    /// Snap a price onto the tick grid, rounding up or down
+    pub fn snap_to_tick(&self, price: f64, round_up: bool) -> f64 {
+        if self.tick_size <= 0.0 {
+            return price;
+        }
+        let ticks = price / self.tick_size;
+        let ticks = if round_up { (ticks - TICK_EPSILON).ceil() } else { (ticks + TICK_EPSILON).floor() };
+        ticks * self.tick_size
+    }
+
+    /// Maker price moved `ticks` ticks more aggressive, kept on the tick grid and
+    /// clamped into the percent-price band around `reference_price`
+    pub fn improve_maker_price(&self, price: f64, side: OrderSide, ticks: u32, reference_price: Option<f64>) -> f64 {
+        let step = self.tick_size * ticks as f64;
+        // Round back toward the passive side so the improvement never exceeds `ticks`
+        let mut improved = match side {
+            OrderSide::Buy => self.snap_to_tick(price + step, false),
+            OrderSide::Sell => self.snap_to_tick(price - step, true),
+        };
+        if let (Some(band), Some(reference)) = (self.percent_price, reference_price.filter(|price| *price > 0.0)) {
+            let high = self.snap_to_tick(reference * band.multiplier_up, false);
+            let low = self.snap_to_tick(reference * band.multiplier_down, true);
+            improved = improved.min(high).max(low);
+        }
+        improved
+    }
+
+    /// PnL of a signed contract quantity (positive long) moved from entry to exit, in the settlement asset
+    pub fn pnl(&self, contracts: f64, entry_price: f64, exit_price: f64) -> f64 {
+        if self.contract_type.is_inverse() {
+            contracts * self.contract_multiplier * (1.0 / entry_price - 1.0 / exit_price)
+        } else {
+            self.base_quantity(contracts) * (exit_price - entry_price)
+        }
+    }
 }
 
 /// Contract type
@@ -40,6 +168,15 @@ pub enum ContractType {
     Quarterly,
     /// Bi-quarterly contract
     BiQuarterly,
+    /// Coin-margined perpetual, quoted in quote units per contract and settled in base
+    InversedPerpetual,
+}
+
+impl ContractType {
+    /// Whether PnL and notional follow the inverse (coin-margined) formulas
+    pub fn is_inverse(&self) -> bool {
+        matches!(self, ContractType::InversedPerpetual)
+    }
 }
 
 /// Futures position
@@ -74,10 +211,46 @@ pub enum PositionSide {
     Long,
     /// Short position
     Short,
-    /// Both (hedge mode)
+    /// Both (one-way mode)
     Both,
 }
 
+/// How an account holds positions in a symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PositionMode {
+    /// A single net position; orders carry `PositionSide::Both`
+    #[default]
+    OneWay,
+    /// Separate long and short positions; orders name the side they open or close
+    Hedge,
+}
+
+impl PositionMode {
+    /// Position side an order must carry in this mode.
+    ///
+    /// In hedge mode buys open longs and sells open shorts; reduce-only orders
+    /// close the opposite side.
+    pub fn position_side(self, side: OrderSide, reduce_only: bool) -> PositionSide {
+        match (self, side, reduce_only) {
+            (PositionMode::OneWay, _, _) => PositionSide::Both,
+            (PositionMode::Hedge, OrderSide::Buy, false) | (PositionMode::Hedge, OrderSide::Sell, true) => PositionSide::Long,
+            (PositionMode::Hedge, OrderSide::Sell, false) | (PositionMode::Hedge, OrderSide::Buy, true) => PositionSide::Short,
+        }
+    }
+}
+
+/// Margin mode for futures positions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarginMode {
+    /// Margin shared across all positions
+    #[default]
+    Cross,
+    /// Margin isolated per position
+    Isolated,
+}
+
 /// Futures order
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FuturesOrder {
@@ -103,6 +276,44 @@ pub struct FuturesOrder {
     pub close_position: bool,
     /// Client order ID
     pub client_order_id: Option<String>,
+    /// Take-profit trigger price attached to the entry
+    #[serde(default)]
+    pub take_profit: Option<f64>,
+    /// Stop-loss trigger price attached to the entry
+    #[serde(default)]
+    pub stop_loss: Option<f64>,
+}
+
+impl FuturesOrder {
+    /// Reduce-only trigger orders that close this entry at its take-profit and stop-loss,
+    /// for venues that cannot attach them to the entry itself
+    pub fn protective_orders(&self) -> Vec<FuturesOrder> {
+        let exit_side = match self.side {
+            OrderSide::Buy => OrderSide::Sell,
+            OrderSide::Sell => OrderSide::Buy,
+        };
+        let triggers = [
+            (self.take_profit, FuturesOrderType::TakeProfitMarket, "tp"),
+            (self.stop_loss, FuturesOrderType::StopMarket, "sl"),
+        ];
+        triggers
+            .into_iter()
+            .filter_map(|(trigger, order_type, tag)| {
+                trigger.map(|trigger| FuturesOrder {
+                    side: exit_side,
+                    order_type,
+                    price: None,
+                    stop_price: Some(trigger),
+                    time_in_force: FuturesTimeInForce::GTC,
+                    reduce_only: true,
+                    client_order_id: self.client_order_id.as_ref().map(|id| format!("{}_{}", tag, id)),
+                    take_profit: None,
+                    stop_loss: None,
+                    ..self.clone()
+                })
+            })
+            .collect()
+    }
 }
 
 /// Futures order type
@@ -164,7 +375,7 @@ pub struct FuturesOrderResponse {
     pub commission: f64,
     /// Commission asset
     pub commission_asset: String,
-    /// Timestamp
+    /// Timestamp in nanoseconds
     pub timestamp: i64,
 }
 
@@ -215,7 +426,7 @@ pub struct MarkPrice {
     pub next_funding_time: i64,
     /// Interest rate
     pub interest_rate: f64,
-    /// Timestamp
+    /// Timestamp in nanoseconds
     pub timestamp: i64,
 }
 
@@ -246,6 +457,17 @@ pub trait FuturesConnector: Send + Sync {
     /// Get mark price
     async fn get_mark_price(&self, symbol: &str) -> Result<MarkPrice>;
     
+    /// Set the margin mode used for a symbol's positions
+    async fn set_margin_mode(&self, symbol: &str, mode: MarginMode) -> Result<()>;
+    
+    /// Set the leverage used for a symbol's positions
+    async fn set_leverage(&self, symbol: &str, leverage: u32) -> Result<()>;
+    
+    /// Position mode the account uses for a symbol
+    async fn get_position_mode(&self, symbol: &str) -> Result<PositionMode> {
+        Err(ArbitrageError::NotImplemented(format!("get_position_mode for {}", symbol)).into())
+    }
+    
     /// Subscribe to order book updates
     async fn subscribe_orderbook(&mut self, symbol: &str) -> Result<()>;
     
@@ -268,6 +490,28 @@ pub trait FuturesConnector: Send + Sync {
     async fn disconnect(&mut self) -> Result<()>;
 }
 
+/// Perpetual symbols listed on both exchanges, named as on `a`.
+///
+/// Contracts are matched on base and quote asset rather than the raw symbol, so
+/// venue-specific spellings of the same market are treated as one.
+pub async fn common_symbols(a: &dyn FuturesConnector, b: &dyn FuturesConnector) -> Result<Vec<String>> {
+    let (a_contracts, b_contracts) = futures_util::future::try_join(a.get_exchange_info(), b.get_exchange_info()).await?;
+    let market = |contract: &FuturesContract| (contract.base_asset.to_uppercase(), contract.quote_asset.to_uppercase());
+
+    let b_markets: std::collections::HashSet<_> = b_contracts
+        .values()
+        .filter(|contract| contract.contract_type == ContractType::Perpetual)
+        .map(market)
+        .collect();
+    let mut symbols: Vec<String> = a_contracts
+        .values()
+        .filter(|contract| contract.contract_type == ContractType::Perpetual && b_markets.contains(&market(contract)))
+        .map(|contract| contract.symbol.clone())
+        .collect();
+    symbols.sort();
+    Ok(symbols)
+}
+
 /// Futures account information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FuturesAccountInfo {
@@ -334,6 +578,24 @@ impl std::fmt::Display for PositionSide {
     }
 }
 
+impl std::fmt::Display for PositionMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PositionMode::OneWay => write!(f, "one-way"),
+            PositionMode::Hedge => write!(f, "hedge"),
+        }
+    }
+}
+
+impl std::fmt::Display for MarginMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MarginMode::Cross => write!(f, "CROSS"),
+            MarginMode::Isolated => write!(f, "ISOLATED"),
+        }
+    }
+}
+
 impl std::fmt::Display for FuturesOrderType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -357,3 +619,170 @@ impl std::fmt::Display for FuturesTimeInForce {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contract_multiplier_scales_notional_and_pnl() {
+        let contract = FuturesContract {
+            symbol: "1000PEPEUSDT".to_string(),
+            base_asset: "PEPE".to_string(),
+            quote_asset: "USDT".to_string(),
+            contract_type: ContractType::Perpetual,
+            min_order_size: 1.0,
+            price_precision: 7,
+            quantity_precision: 0,
+            tick_size: 0.0000001,
+            lot_size: 1.0,
+            maker_fee: 0.0002,
+            taker_fee: 0.0004,
+            contract_multiplier: 1000.0,
+            max_leverage: 20,
+            quantity_unit: QuantityUnit::Contracts,
+            percent_price: None,
+        };
+
+        assert!((contract.notional(5.0, 0.00001) - 0.05).abs() < 1e-12);
+        assert!((contract.pnl(-5.0, 0.00001, 0.000008) - 0.01).abs() < 1e-12);
+        assert_eq!(contract.base_quantity(5.0), 5000.0);
+        assert_eq!(contract.contracts_for_base(5000.0), 5.0);
+    }
+
+    #[test]
+    fn test_inverse_pnl_settles_in_base() {
+        let mut contract = FuturesContract {
+            symbol: "BTCUSD".to_string(),
+            base_asset: "BTC".to_string(),
+            quote_asset: "USD".to_string(),
+            contract_type: ContractType::Perpetual,
+            min_order_size: 1.0,
+            price_precision: 1,
+            quantity_precision: 0,
+            tick_size: 0.5,
+            lot_size: 1.0,
+            maker_fee: 0.0002,
+            taker_fee: 0.0005,
+            contract_multiplier: 1.0,
+            max_leverage: 20,
+            quantity_unit: QuantityUnit::Contracts,
+            percent_price: None,
+        };
+        // Linear: 1000 contracts of 1 BTC, 40000 -> 50000
+        assert!((contract.pnl(1000.0, 40000.0, 50000.0) - 10_000_000.0).abs() < 1e-6);
+        assert_eq!(contract.settlement_asset(), "USD");
+
+        // Inverse: 1000 contracts of 1 USD gain 1000 * (1/40000 - 1/50000) = 0.005 BTC
+        contract.contract_type = ContractType::InversedPerpetual;
+        assert!((contract.pnl(1000.0, 40000.0, 50000.0) - 0.005).abs() < 1e-12);
+        assert!((contract.pnl(-1000.0, 40000.0, 50000.0) + 0.005).abs() < 1e-12);
+        assert!((contract.notional(1000.0, 50000.0) - 0.02).abs() < 1e-12);
+        assert_eq!(contract.settlement_asset(), "BTC");
+    }
+
+    #[test]
+    fn test_base_size_converted_to_venue_contracts() {
+        let mut contract = FuturesContract {
+            symbol: "ETHUSDT".to_string(),
+            base_asset: "ETH".to_string(),
+            quote_asset: "USDT".to_string(),
+            contract_type: ContractType::Perpetual,
+            min_order_size: 1.0,
+            price_precision: 2,
+            quantity_precision: 0,
+            tick_size: 0.01,
+            lot_size: 1.0,
+            maker_fee: 0.0002,
+            taker_fee: 0.0004,
+            contract_multiplier: 0.01,
+            max_leverage: 20,
+            quantity_unit: QuantityUnit::Contracts,
+            percent_price: None,
+        };
+
+        // Each contract is worth 0.01 ETH
+        assert!((contract.venue_quantity(1.5) - 150.0).abs() < 1e-9);
+        assert!((contract.base_from_venue(150.0) - 1.5).abs() < 1e-9);
+
+        contract.quantity_unit = QuantityUnit::Base;
+        assert_eq!(contract.venue_quantity(1.5), 1.5);
+    }
+
+    #[test]
+    fn test_improved_price_clamped_to_percent_price_band() {
+        let mut contract = perpetual("BTCUSDT", "BTC");
+        assert!((contract.improve_maker_price(50000.0, OrderSide::Buy, 3, None) - 50000.3).abs() < 1e-9);
+        assert!((contract.improve_maker_price(50000.0, OrderSide::Sell, 3, None) - 49999.7).abs() < 1e-9);
+
+        // A 5% band around a 47620 mark caps buys at 50001.0
+        contract.percent_price = Some(PercentPriceBand { multiplier_up: 1.05, multiplier_down: 0.95 });
+        let improved = contract.improve_maker_price(50000.0, OrderSide::Buy, 20, Some(47620.0));
+        assert!((improved - 50001.0).abs() < 1e-9, "{}", improved);
+        let improved = contract.improve_maker_price(45240.0, OrderSide::Sell, 20, Some(47620.0));
+        assert!((improved - 45239.0).abs() < 1e-9, "{}", improved);
+    }
+
+    /// Connector stub that only lists contracts
+    struct ListingConnector {
+        contracts: Vec<FuturesContract>,
+    }
+
+    fn perpetual(symbol: &str, base_asset: &str) -> FuturesContract {
+        FuturesContract {
+            symbol: symbol.to_string(),
+            base_asset: base_asset.to_string(),
+            quote_asset: "USDT".to_string(),
+            contract_type: ContractType::Perpetual,
+            min_order_size: 0.001,
+            price_precision: 2,
+            quantity_precision: 3,
+            tick_size: 0.1,
+            lot_size: 0.001,
+            maker_fee: 0.0002,
+            taker_fee: 0.0004,
+            contract_multiplier: 1.0,
+            max_leverage: 20,
+            quantity_unit: QuantityUnit::Base,
+            percent_price: None,
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl FuturesConnector for ListingConnector {
+        async fn get_exchange_info(&self) -> Result<HashMap<String, FuturesContract>> {
+            Ok(self.contracts.iter().map(|contract| (contract.symbol.clone(), contract.clone())).collect())
+        }
+        async fn get_account_info(&self) -> Result<FuturesAccountInfo> { unimplemented!() }
+        async fn get_positions(&self) -> Result<Vec<FuturesPosition>> { unimplemented!() }
+        async fn place_order(&self, _order: &FuturesOrder) -> Result<FuturesOrderResponse> { unimplemented!() }
+        async fn cancel_order(&self, _symbol: &str, _order_id: &str) -> Result<FuturesOrderResponse> { unimplemented!() }
+        async fn get_order_status(&self, _symbol: &str, _order_id: &str) -> Result<FuturesOrderResponse> { unimplemented!() }
+        async fn get_funding_rate(&self, _symbol: &str) -> Result<FundingRate> { unimplemented!() }
+        async fn get_mark_price(&self, _symbol: &str) -> Result<MarkPrice> { unimplemented!() }
+        async fn set_margin_mode(&self, _symbol: &str, _mode: MarginMode) -> Result<()> { unimplemented!() }
+        async fn set_leverage(&self, _symbol: &str, _leverage: u32) -> Result<()> { unimplemented!() }
+        async fn subscribe_orderbook(&mut self, _symbol: &str) -> Result<()> { Ok(()) }
+        async fn subscribe_trades(&mut self, _symbol: &str) -> Result<()> { Ok(()) }
+        async fn subscribe_mark_price(&mut self, _symbol: &str) -> Result<()> { Ok(()) }
+        async fn subscribe_funding_rate(&mut self, _symbol: &str) -> Result<()> { Ok(()) }
+        fn is_connected(&self) -> bool { true }
+        async fn connect(&mut self) -> Result<()> { Ok(()) }
+        async fn disconnect(&mut self) -> Result<()> { Ok(()) }
+    }
+
+    #[tokio::test]
+    async fn test_common_symbols_intersects_listings() {
+        let mut quarterly = perpetual("ETHUSDT_240628", "ETH");
+        quarterly.contract_type = ContractType::Quarterly;
+        let binance = ListingConnector {
+            contracts: vec![perpetual("BTCUSDT", "BTC"), perpetual("ETHUSDT", "ETH"), perpetual("1000PEPEUSDT", "PEPE"), perpetual("DOGEUSDT", "DOGE")],
+        };
+        let bybit = ListingConnector {
+            contracts: vec![perpetual("BTCUSDT", "BTC"), perpetual("PEPEUSDT", "PEPE"), perpetual("SOLUSDT", "SOL"), quarterly],
+        };
+
+        let symbols = common_symbols(&binance, &bybit).await.unwrap();
+        assert_eq!(symbols, vec!["1000PEPEUSDT".to_string(), "BTCUSDT".to_string()]);
+    }
+}
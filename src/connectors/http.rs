@@ -0,0 +1,118 @@
+//! Shared HTTP helpers for exchange REST clients
+
+use crate::{ArbitrageError, Result};
+use reqwest::{header::RETRY_AFTER, RequestBuilder, Response, StatusCode};
+use std::time::Duration;
+use tracing::warn;
+
+/// Back-off used when a rate-limit response carries no usable `Retry-After`
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+/// Rate-limit retries for clients built without an exchange config
+pub(crate) const DEFAULT_MAX_RATE_LIMIT_RETRIES: u32 = 3;
+
+/// Binance's "IP banned after ignoring 429s" status
+const STATUS_IP_BANNED: u16 = 418;
+
+/// Back-off requested by a rate-limit (429/418) response, if it is one
+pub(crate) fn rate_limit_delay(response: &Response) -> Option<Duration> {
+    let status = response.status();
+    if status != StatusCode::TOO_MANY_REQUESTS && status.as_u16() != STATUS_IP_BANNED {
+        return None;
+    }
+
+    let retry_after = response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_RETRY_AFTER);
+    Some(retry_after)
+}
+
+/// Send a GET request, sleeping for `Retry-After` on rate-limit responses
+pub(crate) async fn get_with_backoff(client: &reqwest::Client, url: &str, max_retries: u32) -> Result<Response> {
+    send_with_backoff(|| Ok(client.get(url)), max_retries).await
+}
+
+/// Send the request `build` returns, sleeping for `Retry-After` on rate-limit
+/// responses and failing with `RateLimited` once `max_retries` are used up.
+///
+/// The request is rebuilt for every attempt, so signed requests go out with a
+/// fresh timestamp and signature.
+pub(crate) async fn send_with_backoff(
+    mut build: impl FnMut() -> Result<RequestBuilder>,
+    max_retries: u32,
+) -> Result<Response> {
+    let mut attempt = 0;
+    loop {
+        let response = build()?
+            .send()
+            .await
+            .map_err(|e| ArbitrageError::Connection(format!("HTTP request failed: {}", e)))?;
+
+        let delay = match rate_limit_delay(&response) {
+            Some(delay) => delay,
+            None => return Ok(response),
+        };
+
+        if attempt >= max_retries {
+            return Err(ArbitrageError::RateLimited(format!(
+                "HTTP {} after {} retries, retry after {}s",
+                response.status(), attempt, delay.as_secs()
+            )).into());
+        }
+
+        attempt += 1;
+        warn!("Rate limited with HTTP {}, backing off {}s (retry {}/{})",
+              response.status(), delay.as_secs(), attempt, max_retries);
+        tokio::time::sleep(delay).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_retry_after_is_honored() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/depth"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/depth"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = reqwest::Client::new();
+        let url = format!("{}/api/v3/depth", server.uri());
+        let start = std::time::Instant::now();
+        let response = get_with_backoff(&client, &url, 3).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(start.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_surfaces_error_when_retries_exhausted() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(418).insert_header("Retry-After", "0"))
+            .mount(&server)
+            .await;
+
+        let client = reqwest::Client::new();
+        let err = get_with_backoff(&client, &server.uri(), 2).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<ArbitrageError>(), Some(ArbitrageError::RateLimited(_))));
+    }
+}
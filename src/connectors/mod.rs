@@ -6,6 +6,11 @@ pub mod bybit;
 pub mod futures;
 pub mod binance_futures;
 pub mod bybit_futures;
+pub mod sequence;
+pub mod reconnect;
+pub mod compression;
+pub(crate) mod http;
+pub(crate) mod serde_helpers;
 
 pub use traits::*;
 pub use binance::BinanceConnector;
@@ -94,6 +99,73 @@ impl ConnectorFactory {
     }
 }
 
+/// Exchange configuration pointing REST calls at a local mock server
+#[cfg(test)]
+pub(crate) fn test_exchange_config(rest_api_url: &str) -> crate::config::ExchangeConfig {
+    use crate::config::*;
+    use std::collections::HashMap;
+
+    ExchangeConfig {
+        connection: ConnectionConfig {
+            websocket_url: "wss://stream.example.com/ws".to_string(),
+            rest_api_url: rest_api_url.to_string(),
+            connection_timeout_secs: 5,
+            max_reconnect_attempts: 5,
+            reconnect_delay_secs: 5,
+            max_rate_limit_retries: 3,
+            ws_compression: WsCompression::None,
+            ws_subscriptions_per_sec: 5.0,
+            ws_subscription_batch_size: 10,
+            ws_subscription_ack_timeout_ms: 5000,
+        },
+        auth: AuthConfig {
+            api_key: "test_key".to_string(),
+            secret_key: "test_secret".to_string(),
+            testnet: false,
+            testnet_websocket_url: None,
+            testnet_rest_api_url: None,
+        },
+        trading: TradingConfig {
+            default_order_type: "LIMIT".to_string(),
+            default_time_in_force: "GTC".to_string(),
+            order_types: Vec::new(),
+            additional: HashMap::new(),
+        },
+        fees: FeeConfig {
+            maker_fee: 0.001,
+            taker_fee: 0.001,
+            fee_currency: "USDT".to_string(),
+            additional: HashMap::new(),
+        },
+        limits: LimitsConfig {
+            order_rate_limit: 1200,
+            market_data_rate_limit: 6000,
+            min_order_sizes: HashMap::new(),
+            tick_sizes: HashMap::new(),
+        },
+        market_data: MarketDataConfig {
+            streams: vec![],
+            topics: vec![],
+            depth_levels: 20,
+            resync_on_gap: true,
+            snapshot_bootstrap: true,
+            additional: HashMap::new(),
+        },
+        monitoring: MonitoringConfig {
+            enable_metrics: false,
+            metrics_interval_secs: 60,
+            enable_trade_logging: false,
+            log_rotation_size_mb: 100,
+            health_check_interval_secs: 30,
+            webhook: WebhookConfig::default(),
+            trade_store: TradeStoreConfig::default(),
+            prometheus: PrometheusConfig::default(),
+            audit_log: AuditLogConfig::default(),
+            book_integrity_interval_ms: 0,
+        },
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,120 @@
+//! Reconnect-storm protection
+//!
+//! Repeated reconnect failures in quick succession put the exchange into a
+//! long cool-down instead of retrying in a tight loop.
+
+use crate::config::ReconnectGuardConfig;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Tracks reconnect failures for one exchange and enforces the cool-down
+#[derive(Debug, Clone)]
+pub struct ReconnectGuard {
+    max_failures: usize,
+    window: Duration,
+    cooldown: Duration,
+    /// Recent failure times within the window
+    failures: VecDeque<Instant>,
+    /// End of the active cool-down, if any
+    cooldown_until: Option<Instant>,
+}
+
+impl ReconnectGuard {
+    /// Create a guard from configuration
+    pub fn new(config: &ReconnectGuardConfig) -> Self {
+        Self {
+            max_failures: config.max_failures as usize,
+            window: Duration::from_secs(config.failure_window_secs),
+            cooldown: Duration::from_secs(config.cooldown_secs),
+            failures: VecDeque::new(),
+            cooldown_until: None,
+        }
+    }
+
+    /// Whether a reconnect may be attempted now
+    pub fn can_attempt(&self, now: Instant) -> bool {
+        self.cooldown_remaining(now).is_none()
+    }
+
+    /// Time left in the active cool-down
+    pub fn cooldown_remaining(&self, now: Instant) -> Option<Duration> {
+        self.cooldown_until
+            .filter(|until| *until > now)
+            .map(|until| until - now)
+    }
+
+    /// Record a failed reconnect; returns true if this failure starts the cool-down
+    pub fn record_failure(&mut self, now: Instant) -> bool {
+        self.failures.push_back(now);
+        while let Some(first) = self.failures.front() {
+            if now.duration_since(*first) > self.window {
+                self.failures.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        if self.max_failures > 0 && self.failures.len() >= self.max_failures {
+            self.failures.clear();
+            self.cooldown_until = Some(now + self.cooldown);
+            return true;
+        }
+        false
+    }
+
+    /// Record a successful reconnect
+    pub fn record_success(&mut self) {
+        self.reset();
+    }
+
+    /// Clear failures and any cool-down, allowing an immediate retry
+    pub fn reset(&mut self) {
+        self.failures.clear();
+        self.cooldown_until = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cooldown_engages_after_repeated_failures() {
+        let mut guard = ReconnectGuard::new(&ReconnectGuardConfig {
+            max_failures: 3,
+            failure_window_secs: 60,
+            cooldown_secs: 600,
+        });
+        let start = Instant::now();
+
+        assert!(!guard.record_failure(start));
+        assert!(!guard.record_failure(start + Duration::from_secs(1)));
+        assert!(guard.can_attempt(start + Duration::from_secs(2)));
+
+        assert!(guard.record_failure(start + Duration::from_secs(2)));
+        assert!(!guard.can_attempt(start + Duration::from_secs(3)));
+        assert_eq!(guard.cooldown_remaining(start + Duration::from_secs(302)), Some(Duration::from_secs(300)));
+        assert!(guard.can_attempt(start + Duration::from_secs(603)));
+
+        // Manual retry clears the cool-down immediately
+        guard.record_failure(start + Duration::from_secs(700));
+        guard.record_failure(start + Duration::from_secs(701));
+        guard.record_failure(start + Duration::from_secs(702));
+        guard.reset();
+        assert!(guard.can_attempt(start + Duration::from_secs(703)));
+    }
+
+    #[test]
+    fn test_failures_outside_window_do_not_accumulate() {
+        let mut guard = ReconnectGuard::new(&ReconnectGuardConfig {
+            max_failures: 3,
+            failure_window_secs: 10,
+            cooldown_secs: 600,
+        });
+        let start = Instant::now();
+
+        for i in 0..10 {
+            assert!(!guard.record_failure(start + Duration::from_secs(i * 20)));
+        }
+    }
+}
@@ -0,0 +1,705 @@
+//! Order book sequence tracking and gap-fill resynchronization
+//!
+//! Depth streams deliver incremental deltas tagged with update IDs. When an ID
+//! is skipped the local book can no longer be trusted, so delta application is
+//! paused, a fresh REST snapshot is fetched and the sequence baseline is reset.
+//! Resent or late frames whose updates were all applied already are dropped.
+//!
+//! On startup the book can be bootstrapped the way Binance documents: deltas
+//! are buffered while a REST snapshot is fetched, buffered deltas already
+//! covered by the snapshot's `lastUpdateId` are discarded, and the rest are
+//! applied on top of the snapshot.
+//!
+//! [`DepthFeed`] keeps one synchronizer per symbol of a connector's depth
+//! stream, so the stream's diffs only ever reach consumers as complete books,
+//! and paces the REST snapshots those books need.
+
+use crate::{
+    config::MarketDataConfig,
+    connectors::{Exchange, MarketDataUpdate},
+    data::OrderBook,
+    ArbitrageError, Result,
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// REST order book snapshots used to bootstrap and resync books
+#[async_trait]
+pub trait DepthSnapshotSource: Send + Sync {
+    /// Current book for a symbol together with the last update ID it includes
+    async fn depth_snapshot(&self, symbol: &str) -> Result<DepthSnapshot>;
+}
+
+/// Incremental depth update carrying its update ID range
+#[derive(Debug, Clone)]
+pub struct DepthDelta {
+    /// First update ID contained in this delta
+    pub first_update_id: u64,
+    /// Last update ID contained in this delta
+    pub final_update_id: u64,
+    /// Bid levels (price, quantity); zero quantity removes the level
+    pub bids: Vec<(f64, f64)>,
+    /// Ask levels (price, quantity); zero quantity removes the level
+    pub asks: Vec<(f64, f64)>,
+    /// Exchange event time in nanoseconds, when the frame carries one
+    pub exchange_timestamp: Option<i64>,
+    /// Local time the frame was received in nanoseconds
+    pub local_recv_timestamp: i64,
+}
+
+/// REST order book snapshot tagged with the last update ID it includes
+#[derive(Debug, Clone)]
+pub struct DepthSnapshot {
+    /// `lastUpdateId` reported with the snapshot
+    pub last_update_id: u64,
+    /// Order book as of that update, timestamped with its local receipt
+    pub book: OrderBook,
+    /// Exchange time of the snapshot in nanoseconds, when the response carries one
+    pub exchange_timestamp: Option<i64>,
+}
+
+/// Result of checking a delta against the current sequence baseline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceCheck {
+    /// No baseline yet; the delta establishes one
+    Baseline,
+    /// Delta continues the sequence
+    Contiguous,
+    /// Every update in the delta was already applied (duplicate or late frame)
+    Stale {
+        /// Last update ID applied to the book
+        last_applied: u64,
+        /// Last update ID contained in the delta
+        received: u64,
+    },
+    /// One or more updates were skipped
+    Gap {
+        /// Update ID that was expected next
+        expected: u64,
+        /// First update ID actually received
+        received: u64,
+    },
+}
+
+/// Maintains a locally built order book and resyncs it from REST on sequence gaps
+pub struct DepthSynchronizer {
+    symbol: String,
+    exchange: Exchange,
+    book: Option<OrderBook>,
+    exchange_timestamp: Option<i64>,
+    last_update_id: Option<u64>,
+    resync_on_gap: bool,
+    ignore_stale_frames: bool,
+    snapshot_bootstrap: bool,
+    buffered: Vec<DepthDelta>,
+    resync_count: u64,
+    stale_count: u64,
+}
+
+impl DepthSynchronizer {
+    /// Create a synchronizer for a symbol
+    pub fn new(symbol: &str, exchange: Exchange, resync_on_gap: bool) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            exchange,
+            book: None,
+            exchange_timestamp: None,
+            last_update_id: None,
+            resync_on_gap,
+            ignore_stale_frames: true,
+            snapshot_bootstrap: false,
+            buffered: Vec::new(),
+            resync_count: 0,
+            stale_count: 0,
+        }
+    }
+
+    /// Create a synchronizer using an exchange's market data settings
+    pub fn from_config(symbol: &str, exchange: Exchange, config: &MarketDataConfig) -> Self {
+        Self::new(symbol, exchange, config.resync_on_gap).with_snapshot_bootstrap(config.snapshot_bootstrap)
+    }
+
+    /// Buffer deltas until [`bootstrap`](Self::bootstrap) is called with a REST snapshot
+    pub fn with_snapshot_bootstrap(mut self, enabled: bool) -> Self {
+        self.snapshot_bootstrap = enabled;
+        self
+    }
+
+    /// Whether duplicate/late frames are silently dropped (default) or reported as errors
+    pub fn with_ignore_stale_frames(mut self, ignore: bool) -> Self {
+        self.ignore_stale_frames = ignore;
+        self
+    }
+
+    /// Check a delta's update IDs against the current baseline
+    pub fn check_sequence(&self, delta: &DepthDelta) -> SequenceCheck {
+        match self.last_update_id {
+            None => SequenceCheck::Baseline,
+            Some(last) if delta.final_update_id <= last => SequenceCheck::Stale {
+                last_applied: last,
+                received: delta.final_update_id,
+            },
+            Some(last) if delta.first_update_id <= last + 1 => SequenceCheck::Contiguous,
+            Some(last) => SequenceCheck::Gap {
+                expected: last + 1,
+                received: delta.first_update_id,
+            },
+        }
+    }
+
+    /// Apply a delta, resyncing from a REST snapshot if a gap is detected
+    pub async fn apply_delta(&mut self, source: &dyn DepthSnapshotSource, delta: DepthDelta) -> Result<()> {
+        if let Some(gapped) = self.offer(delta)? {
+            debug!("Fetching REST snapshot to resync {}", self.symbol);
+            let snapshot = source.depth_snapshot(&self.symbol).await?;
+            self.resync(snapshot, &gapped);
+        }
+        Ok(())
+    }
+
+    /// Apply a delta without fetching anything, returning it when a gap means
+    /// the book must first be resynced with [`resync`](Self::resync)
+    pub fn offer(&mut self, delta: DepthDelta) -> Result<Option<DepthDelta>> {
+        if self.awaiting_bootstrap() {
+            self.buffered.push(delta);
+            return Ok(None);
+        }
+        match self.check_sequence(&delta) {
+            SequenceCheck::Baseline | SequenceCheck::Contiguous => {
+                self.apply_levels(&delta);
+                Ok(None)
+            }
+            SequenceCheck::Stale { last_applied, received } => {
+                self.stale_count += 1;
+                if !self.ignore_stale_frames {
+                    return Err(ArbitrageError::DataParsing(format!(
+                        "Stale frame on {}: update {} already applied (last {})", self.symbol, received, last_applied
+                    )).into());
+                }
+                debug!("Dropping stale frame on {}: update {} already applied (last {})", self.symbol, received, last_applied);
+                Ok(None)
+            }
+            SequenceCheck::Gap { expected, received } => {
+                warn!("Sequence gap on {}: expected {}, received {}", self.symbol, expected, received);
+                if !self.resync_on_gap {
+                    return Err(ArbitrageError::DataParsing(format!(
+                        "Sequence gap on {}: expected {}, received {}", self.symbol, expected, received
+                    )).into());
+                }
+                Ok(Some(delta))
+            }
+        }
+    }
+
+    /// Seed the book from a REST snapshot and apply the deltas buffered since subscribing.
+    ///
+    /// Buffered deltas with `u <= lastUpdateId` are already in the snapshot and are
+    /// dropped. If the first remaining delta starts after `lastUpdateId + 1` the
+    /// snapshot is too old; the buffer is kept and an error is returned so the
+    /// caller can fetch a newer snapshot.
+    pub fn bootstrap(&mut self, snapshot: DepthSnapshot) -> Result<()> {
+        let snapshot_id = snapshot.last_update_id;
+        self.buffered.retain(|delta| delta.final_update_id > snapshot_id);
+
+        if let Some(first) = self.buffered.first() {
+            if first.first_update_id > snapshot_id + 1 {
+                return Err(ArbitrageError::DataParsing(format!(
+                    "Snapshot for {} at {} predates buffered update {}", self.symbol, snapshot_id, first.first_update_id
+                )).into());
+            }
+        }
+
+        self.book = Some(snapshot.book);
+        self.exchange_timestamp = snapshot.exchange_timestamp;
+        self.last_update_id = Some(snapshot_id);
+        for delta in std::mem::take(&mut self.buffered) {
+            match self.check_sequence(&delta) {
+                SequenceCheck::Gap { expected, received } => {
+                    self.book = None;
+                    self.last_update_id = None;
+                    return Err(ArbitrageError::DataParsing(format!(
+                        "Sequence gap in buffered deltas on {}: expected {}, received {}", self.symbol, expected, received
+                    )).into());
+                }
+                SequenceCheck::Stale { .. } => self.stale_count += 1,
+                SequenceCheck::Baseline | SequenceCheck::Contiguous => self.apply_levels(&delta),
+            }
+        }
+        info!("Bootstrapped {} order book from snapshot {}, baseline {:?}", self.symbol, snapshot_id, self.last_update_id);
+        Ok(())
+    }
+
+    /// Apply a delta's levels, advance the baseline and take over its
+    /// timestamps; levels with a non-positive price or negative quantity are skipped
+    fn apply_levels(&mut self, delta: &DepthDelta) {
+        let book = self.book.get_or_insert_with(|| OrderBook::new(self.symbol.clone(), self.exchange));
+        for &(price, quantity) in &delta.bids {
+            if is_valid_level(price, quantity) {
+                book.update_bid(price, quantity);
+            } else {
+                warn!("Skipping invalid {} bid level {} @ {}", self.symbol, quantity, price);
+            }
+        }
+        for &(price, quantity) in &delta.asks {
+            if is_valid_level(price, quantity) {
+                book.update_ask(price, quantity);
+            } else {
+                warn!("Skipping invalid {} ask level {} @ {}", self.symbol, quantity, price);
+            }
+        }
+        book.set_timestamp(delta.local_recv_timestamp);
+        self.exchange_timestamp = delta.exchange_timestamp;
+        self.last_update_id = Some(delta.final_update_id);
+    }
+
+    /// Rebuild the book from a REST snapshot and reset the sequence baseline
+    /// to the snapshot's `lastUpdateId`.
+    ///
+    /// The delta that exposed the gap is then checked against the new
+    /// baseline like any other: applied if it continues the snapshot, dropped
+    /// if the snapshot already covers it.
+    pub fn resync(&mut self, snapshot: DepthSnapshot, gapped: &DepthDelta) {
+        self.book = Some(snapshot.book);
+        self.exchange_timestamp = snapshot.exchange_timestamp;
+        self.last_update_id = Some(snapshot.last_update_id);
+        self.resync_count += 1;
+        info!("Resynced {} order book from REST snapshot, new baseline {}", self.symbol, snapshot.last_update_id);
+        match self.check_sequence(gapped) {
+            SequenceCheck::Baseline | SequenceCheck::Contiguous => self.apply_levels(gapped),
+            SequenceCheck::Stale { .. } => self.stale_count += 1,
+            SequenceCheck::Gap { expected, received } => {
+                // The snapshot predates the delta; the next delta resyncs again
+                warn!("Snapshot for {} still behind the stream: expected {}, received {}", self.symbol, expected, received);
+            }
+        }
+    }
+
+    /// Current order book, if one has been built; its timestamp is the local
+    /// receipt of its latest update
+    pub fn orderbook(&self) -> Option<&OrderBook> {
+        self.book.as_ref()
+    }
+
+    /// Exchange time of the book's latest update, when the exchange stamped it
+    pub fn exchange_timestamp(&self) -> Option<i64> {
+        self.exchange_timestamp
+    }
+
+    /// Current order book as a market data update carrying both its exchange
+    /// and local receive times
+    pub fn market_data_update(&self) -> Option<MarketDataUpdate> {
+        self.book.as_ref().map(|book| MarketDataUpdate::OrderBook {
+            exchange: self.exchange.to_string(),
+            symbol: self.symbol.clone(),
+            orderbook: book.clone(),
+            exchange_timestamp: self.exchange_timestamp,
+            local_recv_timestamp: book.timestamp,
+        })
+    }
+
+    /// Whether deltas are being buffered until a bootstrap snapshot arrives
+    pub fn awaiting_bootstrap(&self) -> bool {
+        self.snapshot_bootstrap && self.last_update_id.is_none()
+    }
+
+    /// Last applied update ID
+    pub fn last_update_id(&self) -> Option<u64> {
+        self.last_update_id
+    }
+
+    /// Number of REST resyncs performed
+    pub fn resync_count(&self) -> u64 {
+        self.resync_count
+    }
+
+    /// Number of deltas waiting for a bootstrap snapshot
+    pub fn buffered_count(&self) -> usize {
+        self.buffered.len()
+    }
+
+    /// Number of duplicate/late frames seen
+    pub fn stale_count(&self) -> u64 {
+        self.stale_count
+    }
+}
+
+/// First wait before retrying a failed snapshot for a symbol
+const SNAPSHOT_RETRY_BASE: Duration = Duration::from_millis(500);
+
+/// Longest wait between snapshot retries for a symbol
+const SNAPSHOT_RETRY_MAX: Duration = Duration::from_secs(30);
+
+/// A symbol's synchronizer and the state of its REST snapshot requests
+struct FeedBook {
+    sync: DepthSynchronizer,
+    /// A snapshot request is outstanding; no other is sent until it returns
+    snapshot_in_flight: bool,
+    /// Consecutive failed snapshots, driving the retry backoff
+    snapshot_failures: u32,
+    /// No snapshot is requested before this
+    retry_at: Option<Instant>,
+}
+
+impl FeedBook {
+    /// Whether a snapshot may be requested now
+    fn snapshot_due(&self, now: Instant) -> bool {
+        !self.snapshot_in_flight && self.retry_at.is_none_or(|retry_at| now >= retry_at)
+    }
+
+    /// Record the outcome of a snapshot, backing off exponentially on failure
+    fn snapshot_settled(&mut self, succeeded: bool) {
+        self.snapshot_in_flight = false;
+        if succeeded {
+            self.snapshot_failures = 0;
+            self.retry_at = None;
+        } else {
+            self.snapshot_failures += 1;
+            let backoff = SNAPSHOT_RETRY_BASE
+                .saturating_mul(1 << self.snapshot_failures.min(16).saturating_sub(1))
+                .min(SNAPSHOT_RETRY_MAX);
+            self.retry_at = Some(Instant::now() + backoff);
+        }
+    }
+}
+
+/// Why a snapshot is being fetched for a symbol
+enum SnapshotPurpose {
+    /// Seed a book that has none yet, applying the deltas buffered meanwhile
+    Bootstrap,
+    /// Rebuild the book after the carried delta exposed a gap
+    Resync(DepthDelta),
+}
+
+/// Books built from one exchange's depth stream, one synchronizer per symbol.
+///
+/// Cloning yields another handle to the same books. At most one REST snapshot
+/// is in flight per symbol, failed snapshots are retried with exponential
+/// backoff, and the books are never locked while a snapshot is fetched, so
+/// other symbols keep updating and deltas keep being buffered meanwhile.
+#[derive(Clone)]
+pub struct DepthFeed {
+    exchange: Exchange,
+    config: MarketDataConfig,
+    source: Arc<dyn DepthSnapshotSource>,
+    books: Arc<Mutex<HashMap<String, FeedBook>>>,
+}
+
+impl DepthFeed {
+    /// Create a feed resyncing and bootstrapping books from `source`
+    pub fn new(exchange: Exchange, config: &MarketDataConfig, source: Arc<dyn DepthSnapshotSource>) -> Self {
+        Self {
+            exchange,
+            config: config.clone(),
+            source,
+            books: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, FeedBook>> {
+        self.books.lock().unwrap()
+    }
+
+    fn book_mut<'a>(&self, books: &'a mut HashMap<String, FeedBook>, symbol: &str) -> &'a mut FeedBook {
+        books.entry(symbol.to_string()).or_insert_with(|| FeedBook {
+            sync: DepthSynchronizer::from_config(symbol, self.exchange, &self.config),
+            snapshot_in_flight: false,
+            snapshot_failures: 0,
+            retry_at: None,
+        })
+    }
+
+    /// Seed a symbol's book from a snapshot, applying any deltas buffered before it
+    pub fn bootstrap(&self, symbol: &str, snapshot: DepthSnapshot) -> Result<Option<MarketDataUpdate>> {
+        let mut books = self.lock();
+        let sync = &mut self.book_mut(&mut books, symbol).sync;
+        sync.bootstrap(snapshot)?;
+        Ok(sync.market_data_update())
+    }
+
+    /// Fetch a snapshot for a symbol that is still waiting for its bootstrap,
+    /// unless one is already in flight or backing off
+    pub async fn seed(&self, symbol: &str) -> Result<Option<MarketDataUpdate>> {
+        {
+            let mut books = self.lock();
+            let book = self.book_mut(&mut books, symbol);
+            if !book.sync.awaiting_bootstrap() || !book.snapshot_due(Instant::now()) {
+                return Ok(book.sync.market_data_update());
+            }
+            book.snapshot_in_flight = true;
+        }
+        self.fetch_snapshot(symbol, SnapshotPurpose::Bootstrap).await
+    }
+
+    /// Apply a symbol's delta and return its book once one has been built.
+    ///
+    /// A book still waiting for its bootstrap snapshot, or whose delta exposed
+    /// a gap, fetches a snapshot here when one is due. While a snapshot is in
+    /// flight or backing off, deltas are buffered for a bootstrap and dropped
+    /// after a gap, and no book is returned.
+    pub async fn apply(&self, symbol: &str, delta: DepthDelta) -> Result<Option<MarketDataUpdate>> {
+        let purpose = {
+            let mut books = self.lock();
+            let book = self.book_mut(&mut books, symbol);
+            let purpose = match book.sync.offer(delta)? {
+                Some(gapped) => SnapshotPurpose::Resync(gapped),
+                None if book.sync.awaiting_bootstrap() => SnapshotPurpose::Bootstrap,
+                None => return Ok(book.sync.market_data_update()),
+            };
+            if !book.snapshot_due(Instant::now()) {
+                debug!("Snapshot for {} pending, holding back its book", symbol);
+                return Ok(None);
+            }
+            book.snapshot_in_flight = true;
+            purpose
+        };
+        self.fetch_snapshot(symbol, purpose).await
+    }
+
+    /// Fetch a snapshot without holding the books, then apply it for `purpose`
+    async fn fetch_snapshot(&self, symbol: &str, purpose: SnapshotPurpose) -> Result<Option<MarketDataUpdate>> {
+        debug!("Fetching REST snapshot for {}", symbol);
+        let snapshot = self.source.depth_snapshot(symbol).await;
+
+        let mut books = self.lock();
+        let book = self.book_mut(&mut books, symbol);
+        let applied = snapshot.and_then(|snapshot| match purpose {
+            SnapshotPurpose::Bootstrap => book.sync.bootstrap(snapshot),
+            SnapshotPurpose::Resync(gapped) => {
+                book.sync.resync(snapshot, &gapped);
+                Ok(())
+            }
+        });
+        book.snapshot_settled(applied.is_ok());
+        applied?;
+        Ok(book.sync.market_data_update())
+    }
+
+    /// Read a symbol's synchronizer, once the feed has seen the symbol
+    pub fn with_synchronizer<R>(&self, symbol: &str, read: impl FnOnce(&DepthSynchronizer) -> R) -> Option<R> {
+        self.lock().get(symbol).map(|book| read(&book.sync))
+    }
+}
+
+/// Whether a level can go into a book; zero quantity is a removal
+fn is_valid_level(price: f64, quantity: f64) -> bool {
+    price.is_finite() && price > 0.0 && quantity.is_finite() && quantity >= 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::{test_exchange_config, BinanceConnector};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn delta(first: u64, last: u64, bid: f64) -> DepthDelta {
+        DepthDelta {
+            first_update_id: first,
+            final_update_id: last,
+            bids: vec![(bid, 1.0)],
+            asks: vec![(bid + 10.0, 1.0)],
+            exchange_timestamp: None,
+            local_recv_timestamp: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gap_triggers_rest_resync() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/depth"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "lastUpdateId": 200,
+                "bids": [["50500.00", "2.0"]],
+                "asks": [["50510.00", "2.0"]]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let connector = BinanceConnector::new(test_exchange_config(&server.uri())).await.unwrap();
+        let mut sync = DepthSynchronizer::new("BTCUSDT", Exchange::Binance, true);
+
+        sync.apply_delta(&connector, delta(1, 10, 50000.0)).await.unwrap();
+        sync.apply_delta(&connector, delta(11, 20, 50001.0)).await.unwrap();
+        assert_eq!(sync.resync_count(), 0);
+
+        // Updates 21..=29 are missing
+        sync.apply_delta(&connector, delta(30, 40, 50002.0)).await.unwrap();
+
+        // The baseline is the snapshot's, which already covers the gapped delta
+        assert_eq!(sync.resync_count(), 1);
+        assert_eq!(sync.last_update_id(), Some(200));
+        assert_eq!(sync.orderbook().unwrap().best_bid(), Some(50500.0));
+
+        // Deltas the snapshot covers are dropped instead of overwriting it
+        sync.apply_delta(&connector, delta(41, 45, 50600.0)).await.unwrap();
+        assert_eq!(sync.orderbook().unwrap().best_bid(), Some(50500.0));
+        assert_eq!(sync.stale_count(), 2);
+
+        // Sequence continues from the snapshot without another request
+        sync.apply_delta(&connector, delta(195, 205, 50600.0)).await.unwrap();
+        assert_eq!(sync.resync_count(), 1);
+        assert_eq!(sync.last_update_id(), Some(205));
+        assert_eq!(sync.orderbook().unwrap().best_bid(), Some(50600.0));
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_frame_leaves_book_unchanged() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/depth"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let connector = BinanceConnector::new(test_exchange_config(&server.uri())).await.unwrap();
+        let mut sync = DepthSynchronizer::new("BTCUSDT", Exchange::Binance, true);
+
+        sync.apply_delta(&connector, delta(1, 10, 50000.0)).await.unwrap();
+        sync.apply_delta(&connector, DepthDelta { bids: vec![(50000.0, 0.0), (50100.0, 1.0)], ..delta(11, 20, 50100.0) }).await.unwrap();
+        let before = sync.orderbook().unwrap().clone();
+
+        // A resent frame carrying a better bid must not reach the book
+        let duplicate = delta(11, 20, 50200.0);
+        assert!(matches!(sync.check_sequence(&duplicate), SequenceCheck::Stale { last_applied: 20, received: 20 }));
+        sync.apply_delta(&connector, duplicate).await.unwrap();
+
+        let after = sync.orderbook().unwrap();
+        assert_eq!(after.best_bid(), before.best_bid());
+        assert_eq!(after.best_ask(), before.best_ask());
+        assert_eq!(sync.last_update_id(), Some(20));
+        assert_eq!(sync.stale_count(), 1);
+        assert_eq!(sync.resync_count(), 0);
+
+        let mut strict = DepthSynchronizer::new("BTCUSDT", Exchange::Binance, true).with_ignore_stale_frames(false);
+        strict.apply_delta(&connector, delta(1, 10, 50000.0)).await.unwrap();
+        assert!(strict.apply_delta(&connector, delta(5, 10, 50000.0)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_bootstrap_applies_buffered_deltas() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/depth"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "lastUpdateId": 100,
+                "bids": [["50000.00", "1.0"], ["49990.00", "2.0"]],
+                "asks": [["50010.00", "1.0"], ["50020.00", "2.0"]]
+            })))
+            .mount(&server)
+            .await;
+
+        let connector = BinanceConnector::new(test_exchange_config(&server.uri())).await.unwrap();
+        let mut sync = DepthSynchronizer::from_config(
+            "BTCUSDT",
+            Exchange::Binance,
+            &test_exchange_config(&server.uri()).market_data,
+        );
+
+        // Subscribed: deltas arrive and are buffered before the snapshot
+        let frames = [
+            r#"{"stream":"btcusdt@depth","data":{"U":95,"u":99,"b":[["49000.00","9.0"]],"a":[]}}"#,
+            r#"{"stream":"btcusdt@depth","data":{"U":100,"u":103,"b":[["50000.00","0"],["50005.00","1.5"]],"a":[]}}"#,
+            r#"{"stream":"btcusdt@depth","data":{"U":104,"u":106,"b":[],"a":[["50010.00","0"],["50008.00","0.5"]]}}"#,
+        ];
+        for frame in frames {
+            sync.apply_delta(&connector, BinanceConnector::parse_depth_delta(frame).unwrap()).await.unwrap();
+        }
+        assert_eq!(sync.buffered_count(), 3);
+        assert!(sync.orderbook().is_none());
+
+        sync.bootstrap(connector.get_depth_snapshot("BTCUSDT").await.unwrap()).unwrap();
+
+        // 95..=99 predates the snapshot and is dropped; 100..=103 straddles it
+        let book = sync.orderbook().unwrap();
+        assert_eq!(book.bids, vec![(50005.0, 1.5), (49990.0, 2.0)]);
+        assert_eq!(book.asks, vec![(50008.0, 0.5), (50020.0, 2.0)]);
+        assert_eq!(sync.last_update_id(), Some(106));
+        assert_eq!(sync.buffered_count(), 0);
+
+        // Live deltas now apply directly
+        sync.apply_delta(&connector, delta(107, 108, 50006.0)).await.unwrap();
+        assert_eq!(sync.orderbook().unwrap().best_bid(), Some(50006.0));
+    }
+
+    #[test]
+    fn test_invalid_levels_never_reach_the_book() {
+        let mut sync = DepthSynchronizer::new("BTCUSDT", Exchange::Binance, true);
+        sync.apply_levels(&DepthDelta {
+            first_update_id: 1,
+            final_update_id: 1,
+            bids: vec![(-50000.0, 1.0), (0.0, 1.0), (49990.0, 2.0)],
+            asks: vec![(50010.0, -1.0), (f64::NAN, 1.0), (50020.0, 1.0)],
+            ..delta(1, 1, 0.0)
+        });
+
+        let book = sync.orderbook().unwrap();
+        assert_eq!(book.bids, vec![(49990.0, 2.0)]);
+        assert_eq!(book.asks, vec![(50020.0, 1.0)]);
+    }
+
+    #[test]
+    fn test_bootstrap_rejects_snapshot_older_than_buffer() {
+        let mut sync = DepthSynchronizer::new("BTCUSDT", Exchange::Binance, true).with_snapshot_bootstrap(true);
+        sync.buffered.push(delta(120, 125, 50000.0));
+
+        let snapshot = DepthSnapshot {
+            last_update_id: 100,
+            book: OrderBook::new("BTCUSDT".to_string(), Exchange::Binance),
+            exchange_timestamp: None,
+        };
+        assert!(sync.bootstrap(snapshot).is_err());
+        assert!(sync.orderbook().is_none());
+        assert_eq!(sync.buffered_count(), 1);
+    }
+
+    /// Serves snapshots at a settable `lastUpdateId`, counting requests
+    struct CountingSource {
+        last_update_id: std::sync::atomic::AtomicU64,
+        requests: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl DepthSnapshotSource for CountingSource {
+        async fn depth_snapshot(&self, symbol: &str) -> Result<DepthSnapshot> {
+            use std::sync::atomic::Ordering;
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            let mut book = OrderBook::new(symbol.to_string(), Exchange::Binance);
+            book.update_bid(50500.0, 2.0);
+            Ok(DepthSnapshot { last_update_id: self.last_update_id.load(Ordering::SeqCst), book, exchange_timestamp: None })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_feed_backs_off_failed_bootstrap_snapshots() {
+        use std::sync::atomic::Ordering;
+        let source = Arc::new(CountingSource {
+            last_update_id: std::sync::atomic::AtomicU64::new(5),
+            requests: std::sync::atomic::AtomicUsize::new(0),
+        });
+        let feed = DepthFeed::new(Exchange::Binance, &test_exchange_config("http://127.0.0.1:1").market_data, source.clone());
+
+        // The snapshot predates the buffered delta, so bootstrapping fails
+        assert!(feed.apply("BTCUSDT", delta(100, 101, 50000.0)).await.is_err());
+        assert_eq!(source.requests.load(Ordering::SeqCst), 1);
+
+        // Diffs during the backoff are buffered without another request
+        for first in [102, 104, 106] {
+            assert!(feed.apply("BTCUSDT", delta(first, first + 1, 50000.0)).await.unwrap().is_none());
+        }
+        assert_eq!(source.requests.load(Ordering::SeqCst), 1);
+        assert_eq!(feed.with_synchronizer("BTCUSDT", |sync| sync.buffered_count()), Some(4));
+
+        source.last_update_id.store(103, Ordering::SeqCst);
+        tokio::time::sleep(SNAPSHOT_RETRY_BASE).await;
+        let update = feed.apply("BTCUSDT", delta(108, 109, 50600.0)).await.unwrap();
+        assert_eq!(source.requests.load(Ordering::SeqCst), 2);
+        assert!(matches!(update, Some(MarketDataUpdate::OrderBook { orderbook, .. }) if orderbook.best_bid() == Some(50600.0)));
+        assert_eq!(feed.with_synchronizer("BTCUSDT", |sync| sync.last_update_id()), Some(Some(109)));
+    }
+}
@@ -0,0 +1,54 @@
+//! Parsers for exchange numeric fields sent as either strings or numbers
+
+use crate::ArbitrageError;
+use serde::{de::Error, Deserialize, Deserializer};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrNumber {
+    String(String),
+    Number(f64),
+}
+
+/// Accept `"1.5"` or `1.5`
+pub(crate) fn f64_from_str_or_num<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    match StringOrNumber::deserialize(deserializer)? {
+        StringOrNumber::Number(value) => Ok(value),
+        StringOrNumber::String(value) => value
+            .trim()
+            .parse()
+            .map_err(|_| D::Error::custom(format!("invalid number: {:?}", value))),
+    }
+}
+
+/// Accept `"1.5"`, `1.5`, `""` or `null`; empty strings and nulls become `None`
+pub(crate) fn opt_f64_from_str_or_num<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+    match Option::<StringOrNumber>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(StringOrNumber::Number(value)) => Ok(Some(value)),
+        Some(StringOrNumber::String(value)) if value.trim().is_empty() => Ok(None),
+        Some(StringOrNumber::String(value)) => value
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|_| D::Error::custom(format!("invalid number: {:?}", value))),
+    }
+}
+
+/// Parse a `[price, quantity]` level, rejecting anything that would poison a book.
+///
+/// Prices must be finite and positive. Quantities must be finite and non-negative;
+/// zero is kept because depth deltas use it to remove a level.
+pub(crate) fn parse_level(price: &str, quantity: &str) -> crate::Result<(f64, f64)> {
+    let price: f64 = price.trim().parse()
+        .map_err(|e| ArbitrageError::DataParsing(format!("Invalid price {:?}: {}", price, e)))?;
+    let quantity: f64 = quantity.trim().parse()
+        .map_err(|e| ArbitrageError::DataParsing(format!("Invalid quantity {:?}: {}", quantity, e)))?;
+    if !price.is_finite() || price <= 0.0 {
+        return Err(ArbitrageError::DataParsing(format!("Non-positive price {}", price)).into());
+    }
+    if !quantity.is_finite() || quantity < 0.0 {
+        return Err(ArbitrageError::DataParsing(format!("Negative quantity {} at price {}", quantity, price)).into());
+    }
+    Ok((price, quantity))
+}
@@ -1,6 +1,6 @@
 //! Exchange connector traits and common types
 
-use crate::{data::OrderBook, Result};
+use crate::{data::OrderBook, ArbitrageError, Result};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -39,17 +39,194 @@ pub trait ExchangeConnector {
     /// Place a limit order
     async fn place_limit_order(&self, order: &LimitOrder) -> Result<OrderResponse>;
     
+    /// Place several limit orders, in one request where the exchange supports batching.
+    ///
+    /// The default places them one at a time, stopping at the first error.
+    async fn place_batch(&self, orders: &[LimitOrder]) -> Result<Vec<OrderResponse>>
+    where
+        Self: Sync,
+    {
+        let mut responses = Vec::with_capacity(orders.len());
+        for order in orders {
+            responses.push(self.place_limit_order(order).await?);
+        }
+        Ok(responses)
+    }
+    
+    /// Place a market order for a base quantity
+    async fn place_market_order(&self, _order: &MarketOrder) -> Result<OrderResponse> {
+        Err(ArbitrageError::NotImplemented("Market orders are not supported by this connector".to_string()).into())
+    }
+    
+    /// Place a take-profit/stop pair where filling one cancels the other
+    async fn place_oco(&self, order: &OcoOrder) -> Result<OcoResponse>;
+    
     /// Cancel an order
     async fn cancel_order(&self, symbol: &str, order_id: &str) -> Result<OrderResponse>;
     
     /// Get order status
     async fn get_order_status(&self, symbol: &str, order_id: &str) -> Result<OrderStatus>;
     
+    /// Get an order with its filled quantity and average fill price
+    async fn get_order(&self, _symbol: &str, _order_id: &str) -> Result<OrderResponse> {
+        Err(ArbitrageError::NotImplemented("Order queries are not supported by this connector".to_string()).into())
+    }
+    
+    /// Get orders currently resting on the exchange, optionally for one symbol
+    async fn get_open_orders(&self, symbol: Option<&str>) -> Result<Vec<OrderResponse>>;
+    
     /// Get market data receiver
     fn get_market_data_receiver(&self) -> Option<mpsc::Receiver<MarketDataUpdate>>;
     
     /// Get order update receiver
     fn get_order_update_receiver(&self) -> Option<mpsc::Receiver<OrderUpdate>>;
+    
+    /// Fetch symbol trading rules from the exchange, overriding configured limits
+    async fn refresh_symbol_rules(&mut self) -> Result<()>;
+    
+    /// Trading rules for a symbol, if fetched
+    fn symbol_rules(&self, symbol: &str) -> Option<SymbolRules>;
+    
+    /// Swap the API keys used to sign requests without dropping market-data connections.
+    ///
+    /// Requests signed after this returns use the new keys.
+    async fn update_credentials(&mut self, _api_key: &str, _secret_key: &str) -> Result<()> {
+        Err(ArbitrageError::NotImplemented("Credential rotation is not supported by this connector".to_string()).into())
+    }
+}
+
+/// Reject empty keys before they replace working ones
+pub(crate) fn validate_credentials(api_key: &str, secret_key: &str) -> Result<()> {
+    if api_key.trim().is_empty() || secret_key.trim().is_empty() {
+        return Err(ArbitrageError::Config("API key and secret key must not be empty".to_string()).into());
+    }
+    Ok(())
+}
+
+/// Trading rules for a symbol as published by the exchange
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SymbolRules {
+    /// Symbol
+    pub symbol: String,
+    /// Minimum price increment
+    pub tick_size: f64,
+    /// Minimum quantity increment
+    pub lot_size: f64,
+    /// Minimum order quantity
+    pub min_quantity: f64,
+    /// Minimum order value in quote currency
+    pub min_notional: f64,
+    /// Decimal places allowed in prices
+    pub price_precision: u32,
+    /// Decimal places allowed in quantities
+    pub quantity_precision: u32,
+    /// Whether market orders are held to `min_notional` too
+    #[serde(default = "default_apply_min_to_market")]
+    pub apply_min_to_market: bool,
+}
+
+fn default_apply_min_to_market() -> bool {
+    true
+}
+
+impl SymbolRules {
+    /// Build rules from the exchange's decimal strings, deriving precision from the step sizes
+    pub fn from_decimal_strings(
+        symbol: &str,
+        tick_size: &str,
+        lot_size: &str,
+        min_quantity: &str,
+        min_notional: &str,
+    ) -> Result<Self> {
+        let parse = |field: &str, value: &str| -> Result<f64> {
+            value.parse().map_err(|e| {
+                ArbitrageError::DataParsing(format!("Invalid {} '{}' for {}: {}", field, value, symbol, e)).into()
+            })
+        };
+        
+        Ok(Self {
+            symbol: symbol.to_string(),
+            tick_size: parse("tick size", tick_size)?,
+            lot_size: parse("lot size", lot_size)?,
+            min_quantity: parse("min quantity", min_quantity)?,
+            min_notional: parse("min notional", min_notional)?,
+            price_precision: decimal_places(tick_size),
+            quantity_precision: decimal_places(lot_size),
+            apply_min_to_market: default_apply_min_to_market(),
+        })
+    }
+    
+    /// Order notional as the exchange computes it.
+    ///
+    /// Exchanges multiply the price and quantity strings they receive in exact
+    /// decimal arithmetic, so both are taken at the published precision and
+    /// multiplied as scaled integers rather than as floats.
+    pub fn order_notional(&self, price: f64, quantity: f64) -> f64 {
+        match self.scaled_notional(price, quantity) {
+            Some((units, scale)) => units as f64 / scale as f64,
+            None => price * quantity,
+        }
+    }
+    
+    /// Whether an order clears `min_notional`.
+    ///
+    /// For market orders `price` is the exchange's reference price (Binance uses
+    /// the recent average price), and the check only applies when
+    /// `apply_min_to_market` is set.
+    pub fn meets_min_notional(&self, price: f64, quantity: f64, market: bool) -> bool {
+        if self.min_notional <= 0.0 || (market && !self.apply_min_to_market) {
+            return true;
+        }
+        match self.scaled_notional(price, quantity) {
+            Some((units, scale)) => units >= (self.min_notional * scale as f64).round() as i128,
+            None => price * quantity >= self.min_notional,
+        }
+    }
+    
+    /// Snap an order onto this symbol's tick and lot grid.
+    ///
+    /// Prices move away from the touch (buys down, sells up) and quantities
+    /// round down, so a conformed order is never more aggressive or larger
+    /// than the original.
+    pub fn conform(&self, order: &LimitOrder) -> LimitOrder {
+        let price_round = match order.side {
+            OrderSide::Buy => f64::floor,
+            OrderSide::Sell => f64::ceil,
+        };
+        LimitOrder {
+            price: snap_to_step(order.price, self.tick_size, self.price_precision, price_round),
+            quantity: snap_to_step(order.quantity, self.lot_size, self.quantity_precision, f64::floor),
+            ..order.clone()
+        }
+    }
+    
+    /// Notional in units of 10^-(price_precision + quantity_precision), with that scale
+    fn scaled_notional(&self, price: f64, quantity: f64) -> Option<(i128, i128)> {
+        let price_scale = 10i128.checked_pow(self.price_precision)?;
+        let quantity_scale = 10i128.checked_pow(self.quantity_precision)?;
+        let price_units = (price * price_scale as f64).round() as i128;
+        let quantity_units = (quantity * quantity_scale as f64).round() as i128;
+        Some((price_units.checked_mul(quantity_units)?, price_scale.checked_mul(quantity_scale)?))
+    }
+}
+
+/// Round a value to a whole number of steps, then to the step's decimal places
+fn snap_to_step(value: f64, step: f64, precision: u32, round: fn(f64) -> f64) -> f64 {
+    if step <= 0.0 {
+        return value;
+    }
+    // Values already on the grid can land a hair either side of it after division
+    let steps = round((value / step * 1e6).round() / 1e6);
+    let scale = 10f64.powi(precision as i32);
+    (steps * step * scale).round() / scale
+}
+
+/// Significant decimal places in a step size such as "0.00100000"
+fn decimal_places(step: &str) -> u32 {
+    match step.split_once('.') {
+        Some((_, fraction)) => fraction.trim_end_matches('0').len() as u32,
+        None => 0,
+    }
 }
 
 /// Account balance information
@@ -87,6 +264,47 @@ pub struct LimitOrder {
     pub client_order_id: Option<String>,
 }
 
+/// Market order request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketOrder {
+    /// Trading symbol
+    pub symbol: String,
+    /// Order side (Buy/Sell)
+    pub side: OrderSide,
+    /// Order quantity in the base asset
+    pub quantity: f64,
+    /// Client order ID (optional)
+    pub client_order_id: Option<String>,
+}
+
+/// One-cancels-other bracket: a take-profit limit and a stop that close the same quantity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OcoOrder {
+    /// Trading symbol
+    pub symbol: String,
+    /// Side of both legs (opposite to the position being protected)
+    pub side: OrderSide,
+    /// Quantity closed by whichever leg fills
+    pub quantity: f64,
+    /// Limit price of the take-profit leg
+    pub take_profit_price: f64,
+    /// Trigger price of the stop leg
+    pub stop_price: f64,
+    /// Limit price once the stop triggers; `None` stops out at market
+    pub stop_limit_price: Option<f64>,
+    /// Client ID for the order list (optional)
+    pub client_order_id: Option<String>,
+}
+
+/// Exchange acknowledgement of an OCO bracket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OcoResponse {
+    /// Exchange ID of the order list
+    pub list_id: String,
+    /// Exchange IDs of the orders in the list
+    pub order_ids: Vec<String>,
+}
+
 /// Order side
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OrderSide {
@@ -105,6 +323,18 @@ impl std::fmt::Display for OrderSide {
     }
 }
 
+impl std::str::FromStr for OrderSide {
+    type Err = ArbitrageError;
+    
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "buy" => Ok(OrderSide::Buy),
+            "sell" => Ok(OrderSide::Sell),
+            _ => Err(ArbitrageError::DataParsing(format!("Unknown order side: {}", s))),
+        }
+    }
+}
+
 /// Time in force
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TimeInForce {
@@ -150,7 +380,7 @@ pub struct OrderResponse {
     pub filled_quantity: f64,
     /// Average fill price
     pub average_price: Option<f64>,
-    /// Order timestamp
+    /// Order timestamp in nanoseconds
     pub timestamp: i64,
 }
 
@@ -184,6 +414,23 @@ impl std::fmt::Display for OrderStatus {
     }
 }
 
+impl std::str::FromStr for OrderStatus {
+    type Err = ArbitrageError;
+    
+    /// Accepts both Binance (`PARTIALLY_FILLED`) and Bybit (`PartiallyFilled`) spellings
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.replace('_', "").to_lowercase().as_str() {
+            "new" | "untriggered" | "triggered" => Ok(OrderStatus::New),
+            "partiallyfilled" => Ok(OrderStatus::PartiallyFilled),
+            "filled" => Ok(OrderStatus::Filled),
+            "canceled" | "cancelled" | "pendingcancel" | "partiallyfilledcanceled" | "deactivated" => Ok(OrderStatus::Canceled),
+            "rejected" => Ok(OrderStatus::Rejected),
+            "expired" | "expiredinmatch" => Ok(OrderStatus::Expired),
+            _ => Err(ArbitrageError::DataParsing(format!("Unknown order status: {}", s))),
+        }
+    }
+}
+
 /// Market data update
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MarketDataUpdate {
@@ -195,8 +442,12 @@ pub enum MarketDataUpdate {
         symbol: String,
         /// Order book data
         orderbook: OrderBook,
-        /// Update timestamp
-        timestamp: i64,
+        /// Exchange time of the book's latest update in nanoseconds, when the
+        /// exchange stamps it; feed latency is measured from it
+        exchange_timestamp: Option<i64>,
+        /// Local time the book's latest update was received in nanoseconds;
+        /// staleness is measured from it
+        local_recv_timestamp: i64,
     },
     /// Trade update
     Trade {
@@ -210,7 +461,7 @@ pub enum MarketDataUpdate {
         quantity: f64,
         /// Trade side (from taker perspective)
         side: OrderSide,
-        /// Trade timestamp
+        /// Trade timestamp in nanoseconds
         timestamp: i64,
     },
     /// Ticker update
@@ -227,11 +478,24 @@ pub enum MarketDataUpdate {
         volume_24h: f64,
         /// 24h price change
         price_change_24h: f64,
-        /// Update timestamp
+        /// Update timestamp in nanoseconds
         timestamp: i64,
     },
 }
 
+impl MarketDataUpdate {
+    /// Delay in nanoseconds between the exchange stamping an order book update
+    /// and its local receipt, when the exchange stamps it
+    pub fn feed_latency_ns(&self) -> Option<i64> {
+        match self {
+            MarketDataUpdate::OrderBook { exchange_timestamp, local_recv_timestamp, .. } => {
+                exchange_timestamp.map(|exchange_timestamp| local_recv_timestamp - exchange_timestamp)
+            }
+            _ => None,
+        }
+    }
+}
+
 /// Order update
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderUpdate {
@@ -239,7 +503,7 @@ pub struct OrderUpdate {
     pub exchange: String,
     /// Order response data
     pub order: OrderResponse,
-    /// Update timestamp
+    /// Update timestamp in nanoseconds
     pub timestamp: i64,
 }
 
@@ -260,6 +524,21 @@ pub enum ConnectionEvent {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_borderline_min_notional_uses_exchange_precision() {
+        let mut rules = SymbolRules::from_decimal_strings("SOLUSDT", "0.01", "0.00001", "0.00001", "15").unwrap();
+        // Float noise from sizing: the order goes out as 0.30000 @ 50.00
+        let quantity = 0.7 - 0.4;
+        assert!(50.0 * quantity < 15.0);
+        assert_eq!(rules.order_notional(50.0, quantity), 15.0);
+        assert!(rules.meets_min_notional(50.0, quantity, false));
+        assert!(!rules.meets_min_notional(50.0, 0.29999, false));
+
+        rules.apply_min_to_market = false;
+        assert!(rules.meets_min_notional(50.0, 0.1, true));
+        assert!(!rules.meets_min_notional(50.0, 0.1, false));
+    }
+
     #[test]
     fn test_balance_total() {
         let balance = Balance {
@@ -57,6 +57,18 @@ pub enum ArbitrageError {
     /// Timeout error
     #[error("Timeout error: {0}")]
     Timeout(String),
+    
+    /// Exchange rate limit error
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+    
+    /// Trade history storage error
+    #[error("Storage error: {0}")]
+    Storage(String),
+    
+    /// Order rejected by an exchange price, lot or notional filter
+    #[error("Filter violation: {0}")]
+    FilterViolation(String),
 }
 
 /// Application version
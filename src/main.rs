@@ -1,13 +1,14 @@
 use clap::{Parser, Subcommand};
 use cross_exchange_arbitrage::{
-    config::ArbitrageConfig,
-    strategy::ArbitrageStrategy,
-    trading::{DryRunExecutor, LiveTradingExecutor},
+    config::{ArbitrageConfig, ExchangeConfig, StrategyMode},
+    connectors::{ConnectorFactory, Exchange},
+    strategy::{ArbitrageStrategy, BookIntegrityChecker},
+    trading::{DryRunExecutor, KillSwitch, LiveTradingExecutor},
     utils::logger,
-    Result,
+    ArbitrageError, Result,
 };
 use std::path::PathBuf;
-use tracing::{info, error};
+use tracing::{info, error, warn};
 
 #[derive(Parser)]
 #[command(name = "arbitrage")]
@@ -26,6 +27,10 @@ struct Cli {
     #[arg(long, default_value = "logs/arbitrage.log")]
     log_file: PathBuf,
     
+    /// Strategies to run: spot, futures or both (overrides the config)
+    #[arg(long, global = true)]
+    strategy: Option<StrategyMode>,
+    
     #[command(subcommand)]
     command: Commands,
 }
@@ -45,6 +50,10 @@ enum Commands {
         /// End date for historical data (YYYY-MM-DD)
         #[arg(long)]
         end_date: Option<String>,
+        
+        /// Seed for the simulation RNG, for reproducible runs
+        #[arg(long)]
+        seed: Option<u64>,
     },
     /// Run in live trading mode
     Live {
@@ -56,6 +65,16 @@ enum Commands {
     Validate,
     /// Show system status
     Status,
+    /// List open orders across enabled exchanges
+    Orders {
+        /// Only show orders for this symbol
+        #[arg(long)]
+        symbol: Option<String>,
+        
+        /// Directory holding per-exchange config files (<exchange>.toml)
+        #[arg(long, default_value = "config")]
+        exchange_config_dir: PathBuf,
+    },
 }
 
 #[tokio::main]
@@ -68,12 +87,15 @@ async fn main() -> Result<()> {
     info!("Starting Cross-Exchange Arbitrage System v{}", cross_exchange_arbitrage::VERSION);
     
     // Load configuration
-    let config = ArbitrageConfig::from_file(&cli.config)?;
+    let mut config = ArbitrageConfig::from_file(&cli.config)?;
     info!("Configuration loaded from: {}", cli.config.display());
+    if let Some(mode) = cli.strategy {
+        config.strategy.mode = mode;
+    }
     
     match cli.command {
-        Commands::DryRun { live_data, start_date, end_date } => {
-            run_dry_run(config, live_data, start_date, end_date).await
+        Commands::DryRun { live_data, start_date, end_date, seed } => {
+            run_dry_run(config, live_data, start_date, end_date, seed).await
         }
         Commands::Live { skip_balance_check } => {
             run_live_trading(config, skip_balance_check).await
@@ -84,6 +106,9 @@ async fn main() -> Result<()> {
         Commands::Status => {
             show_status().await
         }
+        Commands::Orders { symbol, exchange_config_dir } => {
+            show_open_orders(config, symbol, exchange_config_dir).await
+        }
     }
 }
 
@@ -92,9 +117,17 @@ async fn run_dry_run(
     live_data: bool,
     start_date: Option<String>,
     end_date: Option<String>,
+    seed: Option<u64>,
 ) -> Result<()> {
     info!("Starting dry-run mode");
     
+    // The simulator only fills spot orders
+    if config.strategy.mode != StrategyMode::Spot {
+        return Err(ArbitrageError::Config(format!(
+            "Dry-run simulates spot orders only; --strategy {} is not supported", config.strategy.mode
+        )).into());
+    }
+    
     if live_data {
         info!("Using live market data for simulation");
     } else {
@@ -104,7 +137,11 @@ async fn run_dry_run(
     }
     
     let mut executor = DryRunExecutor::new(config.clone()).await?;
-    let mut strategy = ArbitrageStrategy::new(config).await?;
+    if let Some(seed) = seed {
+        info!("Using simulation seed {}", seed);
+        executor = executor.with_seed(seed);
+    }
+    let strategy = ArbitrageStrategy::new(config).await?;
     
     // Start the simulation
     strategy.run_with_executor(&mut executor).await?;
@@ -117,14 +154,21 @@ async fn run_dry_run(
 }
 
 async fn run_live_trading(config: ArbitrageConfig, skip_balance_check: bool) -> Result<()> {
-    info!("Starting live trading mode");
+    info!("Starting live trading mode ({} strategy)", config.strategy.mode);
+    
+    // The futures connectors do not stream order books, so a live futures
+    // strategy would never see a book to scan
+    if config.strategy.mode != StrategyMode::Spot {
+        return Err(ArbitrageError::Config(format!(
+            "Live trading runs the spot strategy only; --strategy {} has no futures order book feed", config.strategy.mode
+        )).into());
+    }
     
     if skip_balance_check {
         info!("Skipping initial balance check");
     }
     
     let mut executor = LiveTradingExecutor::new(config.clone()).await?;
-    let mut strategy = ArbitrageStrategy::new(config).await?;
     
     // Perform pre-flight checks
     if !skip_balance_check {
@@ -135,9 +179,54 @@ async fn run_live_trading(config: ArbitrageConfig, skip_balance_check: bool) ->
     executor.check_connectivity().await?;
     info!("Connectivity check passed");
     
-    // Start live trading
+    executor.restore_active_orders().await?;
+    
+    // Start live trading; the kill switch stops it without killing the process
     info!("Starting live trading...");
-    strategy.run_with_executor(&mut executor).await?;
+    let kill_switch = KillSwitch::new(config.risk.kill_switch.clone());
+    let strategy = ArbitrageStrategy::new(config.clone()).await?;
+    let integrity = BookIntegrityChecker::from_config(&config.monitoring).map(|checker| {
+        let books = [Exchange::Binance, Exchange::Bybit]
+            .into_iter()
+            .map(|exchange| (exchange, config.exchanges.exchange_symbol(&config.strategy.symbol, &exchange.to_string())))
+            .collect();
+        tokio::spawn(checker.run(strategy.market_data(), books, executor.connectors()))
+    });
+    let position_ages = (config.risk.max_position_age_secs > 0)
+        .then(|| tokio::spawn(executor.clone().monitor_position_ages()));
+    let controls = config.risk.kill_switch.command_file.is_some().then(|| {
+        let mut executor = executor.clone();
+        let commands = KillSwitch::new(config.risk.kill_switch.clone());
+        tokio::spawn(async move {
+            loop {
+                for command in commands.next_commands().await {
+                    if let Err(e) = executor.apply_control(command).await {
+                        error!("Failed to apply control command {}: {}", command, e);
+                    }
+                }
+            }
+        })
+    });
+    let killed = tokio::select! {
+        result = strategy.run_with_executor(&mut executor) => {
+            result?;
+            false
+        }
+        _ = kill_switch.triggered() => true,
+    };
+    if let Some(integrity) = integrity {
+        integrity.abort();
+    }
+    if let Some(position_ages) = position_ages {
+        position_ages.abort();
+    }
+    if let Some(controls) = controls {
+        controls.abort();
+    }
+    if killed {
+        warn!("Kill switch triggered");
+        executor.emergency_shutdown().await?;
+    }
     
     Ok(())
 }
@@ -170,6 +259,38 @@ async fn show_status() -> Result<()> {
     Ok(())
 }
 
+async fn show_open_orders(config: ArbitrageConfig, symbol: Option<String>, exchange_config_dir: PathBuf) -> Result<()> {
+    info!("Fetching open orders...");
+    
+    for name in &config.exchanges.enabled {
+        let exchange: Exchange = name.parse()?;
+        let exchange_config = ExchangeConfig::from_file_for_exchange(
+            exchange_config_dir.join(format!("{}.toml", exchange)),
+            name,
+        )?;
+        let connector = ConnectorFactory::create_connector(exchange, exchange_config).await?;
+        
+        match connector.get_open_orders(symbol.as_deref()).await {
+            Ok(orders) => {
+                println!("{}: {} open order(s)", exchange, orders.len());
+                for order in orders {
+                    println!(
+                        "  {} {} {} {} @ {} filled {} [{}]",
+                        order.order_id, order.symbol, order.side, order.quantity,
+                        order.price, order.filled_quantity, order.status
+                    );
+                }
+            }
+            Err(e) => {
+                error!("Failed to fetch open orders from {}: {}", exchange, e);
+                println!("{}: error fetching open orders: {}", exchange, e);
+            }
+        }
+    }
+    
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,12 +2,16 @@
 
 use crate::{
     config::ArbitrageConfig,
-    connectors::{Exchange, LimitOrder, OrderSide, TimeInForce},
+    connectors::{Exchange, LimitOrder, MarketDataUpdate, OrderSide, TimeInForce},
     data::{OrderBook, MarketDataManager},
+    strategy::book_levels::BookLevels,
+    strategy::risk_manager::{Market, PortfolioFill, PortfolioRiskManager},
     trading::{DryRunExecutor, LiveTradingExecutor},
+    utils::time::now_ns,
     Result,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
@@ -31,7 +35,7 @@ pub struct ArbitrageOpportunity {
     pub spread_bps: f64,
     /// Expected profit
     pub expected_profit: f64,
-    /// Timestamp when opportunity was detected
+    /// Timestamp when opportunity was detected, in nanoseconds
     pub timestamp: i64,
 }
 
@@ -96,6 +100,10 @@ pub struct ArbitrageStrategy {
     statistics: Arc<RwLock<StrategyStatistics>>,
     /// Current opportunities
     opportunities: Arc<RwLock<Vec<ArbitrageOpportunity>>>,
+    /// Portfolio-wide limits shared with other strategies, if any
+    portfolio: Option<Arc<RwLock<PortfolioRiskManager>>>,
+    /// Latest USD reference price per quote stablecoin
+    stablecoin_prices: Arc<RwLock<HashMap<String, f64>>>,
     /// Start time
     start_time: std::time::Instant,
 }
@@ -111,12 +119,25 @@ impl ArbitrageStrategy {
             state: Arc::new(RwLock::new(StrategyState::Stopped)),
             statistics: Arc::new(RwLock::new(StrategyStatistics::default())),
             opportunities: Arc::new(RwLock::new(Vec::new())),
+            portfolio: None,
+            stablecoin_prices: Arc::new(RwLock::new(HashMap::new())),
             start_time: std::time::Instant::now(),
         })
     }
     
+    /// Admit every order through a portfolio risk manager shared with other strategies
+    pub fn with_portfolio_risk(mut self, portfolio: Arc<RwLock<PortfolioRiskManager>>) -> Self {
+        self.portfolio = Some(portfolio);
+        self
+    }
+    
+    /// Market data cache the strategy detects on
+    pub fn market_data(&self) -> Arc<RwLock<MarketDataManager>> {
+        self.market_data.clone()
+    }
+    
     /// Run the strategy with dry-run executor
-    pub async fn run_with_executor<T>(&mut self, executor: &mut T) -> Result<()>
+    pub async fn run_with_executor<T>(&self, executor: &mut T) -> Result<()>
     where
         T: StrategyExecutor,
     {
@@ -137,19 +158,7 @@ impl ArbitrageStrategy {
             // Update market data (in real implementation, this would be from live feeds)
             self.update_market_data().await?;
             
-            // Detect arbitrage opportunities
-            let opportunities = self.detect_opportunities().await?;
-            
-            // Execute profitable opportunities
-            for opportunity in opportunities {
-                if let Err(e) = self.execute_opportunity(executor, &opportunity).await {
-                    error!("Failed to execute opportunity: {}", e);
-                    self.update_error_statistics().await;
-                }
-            }
-            
-            // Update statistics
-            self.update_statistics().await;
+            self.process_market_data(executor).await?;
             
             // Sleep for a short interval (in real implementation, this would be event-driven)
             tokio::time::sleep(std::time::Duration::from_millis(100)).await;
@@ -170,6 +179,61 @@ impl ArbitrageStrategy {
         Ok(())
     }
     
+    /// Detect opportunities on the current books and execute them
+    pub async fn process_market_data<T>(&self, executor: &mut T) -> Result<()>
+    where
+        T: StrategyExecutor,
+    {
+        let opportunities = self.detect_opportunities().await?;
+        
+        for opportunity in opportunities {
+            if let Err(e) = self.execute_opportunity(executor, &opportunity).await {
+                error!("Failed to execute opportunity: {}", e);
+                self.update_error_statistics().await;
+            }
+        }
+        
+        self.update_statistics().await;
+        Ok(())
+    }
+    
+    /// Apply an orderbook update from a feed or a recorded session
+    pub async fn update_orderbook(&self, exchange: Exchange, orderbook: OrderBook) -> Result<()> {
+        if let (Some(bid), Some(ask)) = (orderbook.best_bid(), orderbook.best_ask()) {
+            let symbol = self.config.exchanges.canonical_symbol(&exchange.to_string(), &orderbook.symbol);
+            if let Some((currency, usd_price)) = self.config.strategy.stablecoin_reference_price(&symbol, (bid + ask) / 2.0) {
+                self.update_stablecoin_price(currency, usd_price).await;
+            }
+        }
+        let update = MarketDataUpdate::OrderBook {
+            exchange: exchange.to_string(),
+            symbol: orderbook.symbol.clone(),
+            orderbook,
+            exchange_timestamp: None,
+            local_recv_timestamp: now_ns(),
+        };
+        self.market_data.write().await.process_update(update).await;
+        Ok(())
+    }
+    
+    /// Record a USD reference price for a quote stablecoin
+    pub async fn update_stablecoin_price(&self, currency: &str, usd_price: f64) {
+        let paused_before = self.is_quote_paused(currency).await;
+        self.stablecoin_prices.write().await.insert(currency.to_string(), usd_price);
+        let paused = self.is_quote_paused(currency).await;
+        if paused && !paused_before {
+            warn!("{} depegged to ${:.4}; pausing spot trading quoted in {}", currency, usd_price, currency);
+        } else if !paused && paused_before {
+            info!("{} back at ${:.4}; resuming spot trading quoted in {}", currency, usd_price, currency);
+        }
+    }
+    
+    /// Whether trading in a quote currency is paused because its reference price has
+    /// drifted from $1 by more than `max_stablecoin_depeg_bps`
+    pub async fn is_quote_paused(&self, currency: &str) -> bool {
+        self.stablecoin_prices.read().await.get(currency).is_some_and(|price| self.config.strategy.is_depegged(*price))
+    }
+    
     /// Update market data with mock data for testing
     async fn update_market_data(&self) -> Result<()> {
         let market_data = self.market_data.write().await;
@@ -181,29 +245,29 @@ impl ArbitrageStrategy {
         let mut binance_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
         binance_book.update_bid(base_price - 10.0, 1.0);
         binance_book.update_ask(base_price + 5.0, 1.0); // Lower ask for buying
-        binance_book.set_timestamp(chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0));
+        binance_book.set_timestamp(now_ns());
         
         // Bybit orderbook (slightly higher bid)
         let mut bybit_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Bybit);
         bybit_book.update_bid(base_price + 15.0, 1.0); // Higher bid for selling
         bybit_book.update_ask(base_price + 25.0, 1.0);
-        bybit_book.set_timestamp(chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0));
+        bybit_book.set_timestamp(now_ns());
         
         // Update market data (simulate the process_update method)
-        use crate::connectors::MarketDataUpdate;
-        
         let binance_update = MarketDataUpdate::OrderBook {
             exchange: "binance".to_string(),
             symbol: "BTCUSDT".to_string(),
             orderbook: binance_book,
-            timestamp: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
+            exchange_timestamp: None,
+            local_recv_timestamp: now_ns(),
         };
         
         let bybit_update = MarketDataUpdate::OrderBook {
             exchange: "bybit".to_string(),
             symbol: "BTCUSDT".to_string(),
             orderbook: bybit_book,
-            timestamp: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
+            exchange_timestamp: None,
+            local_recv_timestamp: now_ns(),
         };
         
         market_data.process_update(binance_update).await;
@@ -218,70 +282,23 @@ impl ArbitrageStrategy {
         let market_data = self.market_data.read().await;
         let mut opportunities = Vec::new();
         
-        // Get orderbooks for the symbol
-        let binance_book = market_data.get_orderbook(Exchange::Binance, &self.config.strategy.symbol).await;
-        let bybit_book = market_data.get_orderbook(Exchange::Bybit, &self.config.strategy.symbol).await;
-        
-        if let (Some(binance_book), Some(bybit_book)) = (binance_book, bybit_book) {
-            // Check for arbitrage opportunities
-            
-            // Opportunity 1: Buy on Binance, Sell on Bybit
-            if let (Some(binance_ask), Some(bybit_bid)) = (binance_book.best_ask(), bybit_book.best_bid()) {
-                if bybit_bid > binance_ask {
-                    let spread_bps = ((bybit_bid - binance_ask) / binance_ask * 10000.0).round();
-                    
-                    if spread_bps >= self.config.strategy.min_spread_bps as f64 {
-                        let quantity = (binance_book.best_ask_quantity().unwrap_or(0.0))
-                            .min(bybit_book.best_bid_quantity().unwrap_or(0.0))
-                            .min(self.config.strategy.max_position_size);
-                        
-                        if quantity > self.config.execution.min_order_size {
-                            let expected_profit = (bybit_bid - binance_ask) * quantity;
-                            
-                            opportunities.push(ArbitrageOpportunity {
-                                symbol: self.config.strategy.symbol.clone(),
-                                buy_exchange: Exchange::Binance,
-                                sell_exchange: Exchange::Bybit,
-                                buy_price: binance_ask,
-                                sell_price: bybit_bid,
-                                quantity,
-                                spread_bps,
-                                expected_profit,
-                                timestamp: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
-                            });
-                        }
-                    }
-                }
-            }
-            
-            // Opportunity 2: Buy on Bybit, Sell on Binance
-            if let (Some(bybit_ask), Some(binance_bid)) = (bybit_book.best_ask(), binance_book.best_bid()) {
-                if binance_bid > bybit_ask {
-                    let spread_bps = ((binance_bid - bybit_ask) / bybit_ask * 10000.0).round();
-                    
-                    if spread_bps >= self.config.strategy.min_spread_bps as f64 {
-                        let quantity = (bybit_book.best_ask_quantity().unwrap_or(0.0))
-                            .min(binance_book.best_bid_quantity().unwrap_or(0.0))
-                            .min(self.config.strategy.max_position_size);
-                        
-                        if quantity > self.config.execution.min_order_size {
-                            let expected_profit = (binance_bid - bybit_ask) * quantity;
-                            
-                            opportunities.push(ArbitrageOpportunity {
-                                symbol: self.config.strategy.symbol.clone(),
-                                buy_exchange: Exchange::Bybit,
-                                sell_exchange: Exchange::Binance,
-                                buy_price: bybit_ask,
-                                sell_price: binance_bid,
-                                quantity,
-                                spread_bps,
-                                expected_profit,
-                                timestamp: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
-                            });
-                        }
-                    }
-                }
-            }
+        // Get orderbooks for the symbol under each exchange's ticker
+        let symbol = &self.config.strategy.symbol;
+        let paused = match self.config.strategy.quote_currency(symbol) {
+            Some(quote) => self.is_quote_paused(quote).await,
+            None => false,
+        };
+        let binance_symbol = self.config.exchanges.exchange_symbol(symbol, &Exchange::Binance.to_string());
+        let bybit_symbol = self.config.exchanges.exchange_symbol(symbol, &Exchange::Bybit.to_string());
+        let binance_book = market_data.get_orderbook(Exchange::Binance, &binance_symbol).await;
+        let bybit_book = market_data.get_orderbook(Exchange::Bybit, &bybit_symbol).await;
+        
+        if paused {
+            debug!("Skipping {}: its quote currency is depegged", symbol);
+        } else if let (Some(binance_book), Some(bybit_book)) = (binance_book, bybit_book) {
+            // Buy on Binance and sell on Bybit, then the reverse
+            opportunities.extend(self.evaluate_direction(Exchange::Binance, &binance_book, Exchange::Bybit, &bybit_book));
+            opportunities.extend(self.evaluate_direction(Exchange::Bybit, &bybit_book, Exchange::Binance, &binance_book));
         }
         
         // Update opportunities count
@@ -308,6 +325,56 @@ impl ArbitrageStrategy {
         Ok(opportunities)
     }
     
+    /// Opportunity buying on `buy_book` and selling on `sell_book`, if its spread clears the threshold.
+    ///
+    /// With `executable_spread_size` set, the spread is the VWAP edge of
+    /// sweeping that size through both books, and each leg is priced at the
+    /// deepest level it reaches; otherwise only the touch is used.
+    fn evaluate_direction(
+        &self,
+        buy_exchange: Exchange,
+        buy_book: &OrderBook,
+        sell_exchange: Exchange,
+        sell_book: &OrderBook,
+    ) -> Option<ArbitrageOpportunity> {
+        let symbol = &self.config.strategy.symbol;
+        let max_quantity = self.config.risk_limits(symbol).max_position_size;
+        let target = self.config.strategy.executable_spread_size;
+        
+        let (buy_price, sell_price, quantity, spread_bps, expected_profit) = if target > 0.0 {
+            let quantity = target.min(max_quantity);
+            let (buy_vwap, buy_limit) = sweep_levels(&buy_book.best_n(OrderSide::Sell, usize::MAX), quantity)?;
+            let (sell_vwap, sell_limit) = sweep_levels(&sell_book.best_n(OrderSide::Buy, usize::MAX), quantity)?;
+            let spread_bps = (sell_vwap - buy_vwap) / buy_vwap * 10000.0;
+            (buy_limit, sell_limit, quantity, spread_bps, (sell_vwap - buy_vwap) * quantity)
+        } else {
+            let (ask, bid) = (buy_book.best_ask()?, sell_book.best_bid()?);
+            let quantity = buy_book.best_ask_quantity().unwrap_or(0.0)
+                .min(sell_book.best_bid_quantity().unwrap_or(0.0))
+                .min(max_quantity);
+            (ask, bid, quantity, (bid - ask) / ask * 10000.0, (bid - ask) * quantity)
+        };
+        
+        if spread_bps <= 0.0
+            || spread_bps < self.config.strategy.min_spread_bps_for(symbol)
+            || quantity <= self.config.execution.min_order_size
+        {
+            return None;
+        }
+        
+        Some(ArbitrageOpportunity {
+            symbol: symbol.clone(),
+            buy_exchange,
+            sell_exchange,
+            buy_price,
+            sell_price,
+            quantity,
+            spread_bps,
+            expected_profit,
+            timestamp: now_ns(),
+        })
+    }
+    
     /// Execute an arbitrage opportunity
     async fn execute_opportunity<T>(&self, executor: &mut T, opportunity: &ArbitrageOpportunity) -> Result<()>
     where
@@ -317,7 +384,7 @@ impl ArbitrageStrategy {
         
         // Create buy order
         let buy_order = LimitOrder {
-            symbol: opportunity.symbol.clone(),
+            symbol: self.config.exchanges.exchange_symbol(&opportunity.symbol, &opportunity.buy_exchange.to_string()),
             side: OrderSide::Buy,
             quantity: opportunity.quantity,
             price: opportunity.buy_price,
@@ -327,7 +394,7 @@ impl ArbitrageStrategy {
         
         // Create sell order
         let sell_order = LimitOrder {
-            symbol: opportunity.symbol.clone(),
+            symbol: self.config.exchanges.exchange_symbol(&opportunity.symbol, &opportunity.sell_exchange.to_string()),
             side: OrderSide::Sell,
             quantity: opportunity.quantity,
             price: opportunity.sell_price,
@@ -335,10 +402,42 @@ impl ArbitrageStrategy {
             client_order_id: Some(format!("arb_sell_{}", uuid::Uuid::new_v4())),
         };
         
+        // Count both legs against portfolio-wide limits before placing either
+        let buy_fill = PortfolioFill {
+            market: Market::Spot,
+            exchange: opportunity.buy_exchange,
+            symbol: opportunity.symbol.clone(),
+            side: OrderSide::Buy,
+            quantity: opportunity.quantity,
+            price: opportunity.buy_price,
+        };
+        let sell_fill = PortfolioFill {
+            exchange: opportunity.sell_exchange,
+            side: OrderSide::Sell,
+            price: opportunity.sell_price,
+            ..buy_fill.clone()
+        };
+        if let Some(portfolio) = &self.portfolio {
+            portfolio.write().await.admit(&[buy_fill.clone(), sell_fill.clone()])?;
+        }
+        
         // Execute orders
         let buy_result = executor.execute_order(opportunity.buy_exchange, buy_order).await;
         let sell_result = executor.execute_order(opportunity.sell_exchange, sell_order).await;
         
+        if let Some(portfolio) = &self.portfolio {
+            let mut portfolio = portfolio.write().await;
+            if buy_result.is_err() {
+                portfolio.revert(std::slice::from_ref(&buy_fill));
+            }
+            if sell_result.is_err() {
+                portfolio.revert(std::slice::from_ref(&sell_fill));
+            }
+            if buy_result.is_ok() && sell_result.is_ok() {
+                portfolio.record_pnl(Market::Spot, opportunity.expected_profit);
+            }
+        }
+        
         match (buy_result, sell_result) {
             (Ok(buy_response), Ok(sell_response)) => {
                 info!("Successfully executed arbitrage: Buy order {} on {}, Sell order {} on {}",
@@ -443,10 +542,10 @@ pub trait StrategyExecutor {
 
 /// Implementation for DryRunExecutor
 impl StrategyExecutor for DryRunExecutor {
-    fn execute_order(&mut self, _exchange: Exchange, order: LimitOrder) -> impl std::future::Future<Output = Result<crate::connectors::OrderResponse>> + '_ {
+    fn execute_order(&mut self, exchange: Exchange, order: LimitOrder) -> impl std::future::Future<Output = Result<crate::connectors::OrderResponse>> + '_ {
         async move {
-            // For dry-run, we don't need to specify exchange as it's simulated
-            self.execute_order(order).await
+            // The exchange selects the simulated fill latency model
+            self.execute_order_on(exchange, order).await
         }
     }
 }
@@ -460,6 +559,34 @@ impl StrategyExecutor for LiveTradingExecutor {
     }
 }
 
+/// Spread in basis points between buying `quantity` through `buy_book`'s asks
+/// and selling it through `sell_book`'s bids, each at its volume-weighted price.
+///
+/// `None` when either side lacks the depth to fill `quantity`.
+pub fn executable_spread_bps(buy_book: &OrderBook, sell_book: &OrderBook, quantity: f64) -> Option<f64> {
+    let (buy_vwap, _) = sweep_levels(&buy_book.best_n(OrderSide::Sell, usize::MAX), quantity)?;
+    let (sell_vwap, _) = sweep_levels(&sell_book.best_n(OrderSide::Buy, usize::MAX), quantity)?;
+    Some((sell_vwap - buy_vwap) / buy_vwap * 10000.0)
+}
+
+/// Volume-weighted price and deepest price touched filling `quantity` from
+/// best-first levels, or `None` if they cannot fill it
+fn sweep_levels(levels: &[(f64, f64)], quantity: f64) -> Option<(f64, f64)> {
+    if quantity <= 0.0 {
+        return None;
+    }
+    let (mut filled, mut cost) = (0.0, 0.0);
+    for &(price, available) in levels {
+        let take = available.min(quantity - filled);
+        filled += take;
+        cost += take * price;
+        if filled >= quantity {
+            return Some((cost / filled, price));
+        }
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -467,7 +594,7 @@ mod tests {
     fn create_test_config() -> ArbitrageConfig {
         let mut config = ArbitrageConfig::default();
         config.strategy.symbol = "BTCUSDT".to_string();
-        config.strategy.min_spread_bps = 5;
+        config.strategy.min_spread_bps = 5.0;
         config.strategy.max_position_size = 1.0;
         config.execution.min_order_size = 0.001;
         config
@@ -505,6 +632,68 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_thin_touch_rejected_by_executable_spread() {
+        // The touch shows a 20 bps edge, but only 0.01 BTC deep on each side
+        let mut binance = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        binance.update_ask(50000.0, 0.01);
+        binance.update_ask(50500.0, 5.0);
+        binance.update_bid(49900.0, 5.0);
+        let mut bybit = OrderBook::new("BTCUSDT".to_string(), Exchange::Bybit);
+        bybit.update_bid(50100.0, 0.01);
+        bybit.update_bid(49600.0, 5.0);
+        bybit.update_ask(50600.0, 5.0);
+
+        assert!(executable_spread_bps(&binance, &bybit, 1.0).unwrap() < 0.0);
+        assert!(executable_spread_bps(&binance, &bybit, 10.0).is_none());
+
+        let mut config = create_test_config();
+        config.execution.min_order_size = 0.0;
+        let touch = ArbitrageStrategy::new(config.clone()).await.unwrap();
+        touch.update_orderbook(Exchange::Binance, binance.clone()).await.unwrap();
+        touch.update_orderbook(Exchange::Bybit, bybit.clone()).await.unwrap();
+        assert_eq!(touch.detect_opportunities().await.unwrap().len(), 1);
+
+        config.strategy.executable_spread_size = 1.0;
+        let sized = ArbitrageStrategy::new(config).await.unwrap();
+        sized.update_orderbook(Exchange::Binance, binance).await.unwrap();
+        sized.update_orderbook(Exchange::Bybit, bybit).await.unwrap();
+        assert!(sized.detect_opportunities().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_depegged_quote_pauses_detection() {
+        let mut binance = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        binance.update_bid(49900.0, 1.0);
+        binance.update_ask(50000.0, 1.0);
+        let mut bybit = OrderBook::new("BTCUSDT".to_string(), Exchange::Bybit);
+        bybit.update_bid(50100.0, 1.0);
+        bybit.update_ask(50200.0, 1.0);
+        let reference = |mid: f64| {
+            let mut book = OrderBook::new("USDCUSDT".to_string(), Exchange::Binance);
+            book.update_bid(mid - 0.0001, 1000.0);
+            book.update_ask(mid + 0.0001, 1000.0);
+            book
+        };
+
+        let mut config = create_test_config();
+        config.strategy.max_stablecoin_depeg_bps = 50.0;
+        config.strategy.stablecoin_reference_symbols.insert("USDT".to_string(), "USDCUSDT".to_string());
+        let strategy = ArbitrageStrategy::new(config).await.unwrap();
+        strategy.update_orderbook(Exchange::Binance, binance).await.unwrap();
+        strategy.update_orderbook(Exchange::Bybit, bybit).await.unwrap();
+        assert_eq!(strategy.detect_opportunities().await.unwrap().len(), 1);
+
+        // One USDC buys 1.03 USDT, so USDT trades near $0.97
+        strategy.update_orderbook(Exchange::Binance, reference(1.03)).await.unwrap();
+        assert!(strategy.is_quote_paused("USDT").await);
+        assert!(strategy.detect_opportunities().await.unwrap().is_empty());
+
+        strategy.update_orderbook(Exchange::Binance, reference(1.001)).await.unwrap();
+        assert!(!strategy.is_quote_paused("USDT").await);
+        assert_eq!(strategy.detect_opportunities().await.unwrap().len(), 1);
+    }
+
     #[tokio::test]
     async fn test_strategy_state_management() {
         let config = create_test_config();
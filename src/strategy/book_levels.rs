@@ -0,0 +1,92 @@
+//! Level-wise access to order book sides
+//!
+//! Depth-aware signals look past the touch: summing the top levels, walking a
+//! taker order through the book or sweeping a target size. [`BookLevels`]
+//! gives them one way to read a side's levels in price priority, and lets
+//! trade-driven simulation take traded liquidity out of the book.
+
+use crate::{connectors::OrderSide, data::OrderBook};
+
+/// Level-wise reads and trade updates of an order book
+pub trait BookLevels {
+    /// Top `n` levels of one side in price priority, as `(price, quantity)`;
+    /// `Buy` reads the bids (highest first) and `Sell` the asks (lowest first).
+    /// Returns every level when the side holds fewer than `n`.
+    fn best_n(&self, side: OrderSide, n: usize) -> Vec<(f64, f64)>;
+
+    /// Remove the liquidity a trade of `quantity` at `price` consumed.
+    ///
+    /// `side` is the aggressor: a buy lifts asks at or below `price`, a sell
+    /// hits bids at or above it, best level first. Exhausted levels are
+    /// removed; any quantity beyond the matching levels is ignored.
+    fn apply_trade(&mut self, price: f64, quantity: f64, side: OrderSide);
+}
+
+impl BookLevels for OrderBook {
+    fn best_n(&self, side: OrderSide, n: usize) -> Vec<(f64, f64)> {
+        let levels = match side {
+            OrderSide::Buy => &self.bids,
+            OrderSide::Sell => &self.asks,
+        };
+        levels.iter().take(n).copied().collect()
+    }
+
+    fn apply_trade(&mut self, price: f64, quantity: f64, side: OrderSide) {
+        let (levels, crosses): (_, fn(f64, f64) -> bool) = match side {
+            OrderSide::Buy => (&mut self.asks, |level, trade| level <= trade),
+            OrderSide::Sell => (&mut self.bids, |level, trade| level >= trade),
+        };
+        let mut remaining = quantity;
+        for level in levels.iter_mut() {
+            if remaining <= 0.0 || !crosses(level.0, price) {
+                break;
+            }
+            let take = level.1.min(remaining);
+            level.1 -= take;
+            remaining -= take;
+        }
+        levels.retain(|&(_, quantity)| quantity > 0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::Exchange;
+
+    #[test]
+    fn test_best_n_in_price_priority_and_capped_by_depth() {
+        let mut book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        for (price, quantity) in [(49990.0, 2.0), (50000.0, 1.0), (49995.0, 0.5)] {
+            book.update_bid(price, quantity);
+        }
+        for (price, quantity) in [(50020.0, 3.0), (50010.0, 1.5)] {
+            book.update_ask(price, quantity);
+        }
+
+        assert_eq!(book.best_n(OrderSide::Buy, 2), vec![(50000.0, 1.0), (49995.0, 0.5)]);
+        assert_eq!(book.best_n(OrderSide::Sell, 5), vec![(50010.0, 1.5), (50020.0, 3.0)]);
+        assert!(book.best_n(OrderSide::Buy, 0).is_empty());
+    }
+
+    #[test]
+    fn test_trade_through_levels_reduces_and_removes_them() {
+        let mut book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        for (price, quantity) in [(50010.0, 1.0), (50020.0, 2.0), (50030.0, 4.0)] {
+            book.update_ask(price, quantity);
+        }
+        book.update_bid(50000.0, 1.0);
+
+        // A buy at 50020 exhausts 50010 and eats into 50020 but never past it
+        book.apply_trade(50020.0, 1.5, OrderSide::Buy);
+        assert_eq!(book.asks, vec![(50020.0, 1.5), (50030.0, 4.0)]);
+        book.apply_trade(50020.0, 5.0, OrderSide::Buy);
+        assert_eq!(book.asks, vec![(50030.0, 4.0)]);
+
+        // A sell above the best bid touches nothing
+        book.apply_trade(50005.0, 1.0, OrderSide::Sell);
+        assert_eq!(book.bids, vec![(50000.0, 1.0)]);
+        book.apply_trade(50000.0, 0.25, OrderSide::Sell);
+        assert_eq!(book.bids, vec![(50000.0, 0.75)]);
+    }
+}
@@ -0,0 +1,634 @@
+//! Spot and futures strategies run side by side
+//!
+//! One runner owns both strategies, the connectors they share and a single
+//! [`PortfolioRiskManager`]. Each enabled strategy runs on its own market data
+//! concurrently with the other, while every opportunity either of them
+//! executes is admitted against the same portfolio-wide limits.
+
+use crate::{
+    config::{ArbitrageConfig, StrategyMode},
+    connectors::{futures::{common_symbols, FuturesConnector}, Exchange},
+    data::OrderBook,
+    strategy::{
+        arbitrage::{ArbitrageStrategy, StrategyExecutor, StrategyStatistics},
+        futures_arbitrage::{FuturesArbitrageStats, FuturesArbitrageStrategy, FuturesStrategyState},
+        risk_manager::{Market, PortfolioRiskManager},
+    },
+    trading::{control::ControlCommand, kill_switch::KillSwitch, sweep::RecordedBook},
+    utils::{metrics::WatchdogStatus, HealthEventType, WebhookNotifier},
+    Result,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+/// Interval between futures scans while running live, in milliseconds
+const FUTURES_SCAN_INTERVAL_MS: u64 = 100;
+
+/// Interval between futures opportunity watchdog checks while running live, in milliseconds
+const WATCHDOG_CHECK_INTERVAL_MS: u64 = 10_000;
+
+/// Futures connectors shared by the runner, by exchange
+pub type FuturesConnectors = HashMap<Exchange, Arc<dyn FuturesConnector>>;
+
+/// Statistics of both strategies and their combined totals
+#[derive(Debug, Clone, Serialize)]
+pub struct DualModeStatistics {
+    /// Strategies that ran
+    pub mode: StrategyMode,
+    /// Spot strategy statistics
+    pub spot: StrategyStatistics,
+    /// Futures strategy statistics
+    pub futures: FuturesArbitrageStats,
+    /// Opportunities executed by both strategies
+    pub opportunities_executed: u64,
+    /// Realized PnL of both strategies, in the reporting currency
+    pub total_pnl: f64,
+    /// Combined notional of every open position
+    pub total_notional: f64,
+    /// Opportunities refused by the portfolio-wide limits
+    pub risk_rejections: u64,
+}
+
+/// Runs the spot and futures strategies under one portfolio risk view
+pub struct DualModeRunner {
+    config: ArbitrageConfig,
+    mode: StrategyMode,
+    spot: ArbitrageStrategy,
+    futures: FuturesArbitrageStrategy,
+    connectors: FuturesConnectors,
+    portfolio: Arc<RwLock<PortfolioRiskManager>>,
+    notifier: WebhookNotifier,
+    metrics_addr: RwLock<Option<SocketAddr>>,
+    kill_switch: Option<KillSwitch>,
+}
+
+impl DualModeRunner {
+    /// Create both strategies around one portfolio risk manager.
+    ///
+    /// `connectors` places futures orders; strategies not enabled by `mode`
+    /// are created but never run. When the futures strategy runs with both
+    /// venues connected, an empty `futures_symbols` monitors every perpetual
+    /// listed on both, and the symbols are checked against the venues'
+    /// listings before any scan.
+    pub async fn new(
+        config: ArbitrageConfig,
+        mode: StrategyMode,
+        futures_symbols: Vec<String>,
+        connectors: FuturesConnectors,
+    ) -> Result<Self> {
+        let venues = match (connectors.get(&Exchange::Bybit), connectors.get(&Exchange::Binance)) {
+            (Some(bybit), Some(binance)) if mode.runs_futures() => Some((bybit.clone(), binance.clone())),
+            _ => None,
+        };
+        let futures_symbols = match &venues {
+            Some((bybit, binance)) if futures_symbols.is_empty() => {
+                let symbols: Vec<String> = common_symbols(binance.as_ref(), bybit.as_ref())
+                    .await?
+                    .into_iter()
+                    .map(|symbol| config.exchanges.canonical_symbol(&Exchange::Binance.to_string(), &symbol))
+                    .collect();
+                info!("Monitoring {} futures symbols listed on both exchanges", symbols.len());
+                symbols
+            }
+            _ => futures_symbols,
+        };
+
+        let portfolio = Arc::new(RwLock::new(PortfolioRiskManager::new(&config.risk)));
+        let spot = ArbitrageStrategy::new(config.clone()).await?.with_portfolio_risk(portfolio.clone());
+        let mut futures = FuturesArbitrageStrategy::new(config.clone(), futures_symbols).await?.with_portfolio_risk(portfolio.clone());
+        if let Some((bybit, binance)) = &venues {
+            futures.validate_symbols(bybit.as_ref(), binance.as_ref()).await?;
+        }
+        let notifier = WebhookNotifier::new(config.monitoring.webhook.clone());
+        Ok(Self { config, mode, spot, futures, connectors, portfolio, notifier, metrics_addr: RwLock::new(None), kill_switch: None })
+    }
+
+    /// Apply operator commands from the kill switch's command file to the futures strategy while running
+    pub fn with_kill_switch(mut self, kill_switch: KillSwitch) -> Self {
+        self.kill_switch = Some(kill_switch);
+        self
+    }
+
+    /// Strategies this runner runs
+    pub fn mode(&self) -> StrategyMode {
+        self.mode
+    }
+
+    /// Portfolio risk manager shared by both strategies
+    pub fn portfolio(&self) -> Arc<RwLock<PortfolioRiskManager>> {
+        self.portfolio.clone()
+    }
+
+    /// Address the Prometheus endpoint is served on, once a run has started it
+    pub async fn metrics_addr(&self) -> Option<SocketAddr> {
+        *self.metrics_addr.read().await
+    }
+
+    /// Deliver a book to the strategy trading its market.
+    ///
+    /// A book for a configured stablecoin reference pair also updates the
+    /// depeg pause of the other strategy.
+    pub async fn update_orderbook(&self, market: Market, exchange: Exchange, orderbook: OrderBook) -> Result<()> {
+        if let (Some(bid), Some(ask)) = (orderbook.best_bid(), orderbook.best_ask()) {
+            let symbol = self.config.exchanges.canonical_symbol(&exchange.to_string(), &orderbook.symbol);
+            if let Some((currency, usd_price)) = self.config.strategy.stablecoin_reference_price(&symbol, (bid + ask) / 2.0) {
+                match market {
+                    Market::Spot => self.futures.update_stablecoin_price(currency, usd_price).await,
+                    Market::Futures => self.spot.update_stablecoin_price(currency, usd_price).await,
+                }
+            }
+        }
+        match market {
+            Market::Spot => self.spot.update_orderbook(exchange, orderbook).await,
+            Market::Futures => self.futures.update_orderbook(exchange, orderbook).await,
+        }
+    }
+
+    /// Run the enabled strategies concurrently until the spot loop ends, or in
+    /// futures-only mode until [`DualModeRunner::stop`] is called.
+    ///
+    /// The futures strategy scans whatever books were delivered through
+    /// [`DualModeRunner::update_orderbook`]. With `prometheus.enabled`, the
+    /// first run also starts serving the futures strategy's metrics.
+    pub async fn run<T>(&self, spot_executor: &mut T) -> Result<DualModeStatistics>
+    where
+        T: StrategyExecutor,
+    {
+        info!("Starting {} strategy run", self.mode);
+        let mode = self.mode;
+        let spot = &self.spot;
+        let futures = &self.futures;
+        let connectors = &self.connectors;
+        if mode.runs_futures() {
+            if let (Some(bybit), Some(binance)) = (connectors.get(&Exchange::Bybit), connectors.get(&Exchange::Binance)) {
+                futures.configure_leverage(bybit.as_ref(), binance.as_ref()).await?;
+            }
+            if let Some(exporter) = futures.metrics_exporter() {
+                let mut metrics_addr = self.metrics_addr.write().await;
+                if metrics_addr.is_none() {
+                    *metrics_addr = Some(exporter.start_server().await?);
+                }
+            }
+            futures.start().await?;
+        }
+
+        let spot_leg = async {
+            if mode.runs_spot() {
+                let result = spot.run_with_executor(spot_executor).await;
+                // The futures leg runs for as long as the spot loop does
+                futures.stop().await?;
+                result?;
+            }
+            Ok::<(), anyhow::Error>(())
+        };
+        let futures_leg = async {
+            if mode.runs_futures() {
+                let mut scan = tokio::time::interval(std::time::Duration::from_millis(FUTURES_SCAN_INTERVAL_MS));
+                let mut watchdog = tokio::time::interval(std::time::Duration::from_millis(WATCHDOG_CHECK_INTERVAL_MS));
+                while futures.get_state().await == FuturesStrategyState::Running {
+                    tokio::select! {
+                        _ = scan.tick() => execute_futures_opportunities(futures, connectors).await?,
+                        _ = watchdog.tick() => {
+                            self.check_opportunity_watchdog().await;
+                        }
+                        commands = next_control_commands(self.kill_switch.as_ref()) => {
+                            for command in commands {
+                                futures.apply_control(command).await;
+                            }
+                        }
+                    }
+                }
+            }
+            Ok::<(), anyhow::Error>(())
+        };
+        let (spot_result, futures_result) = tokio::join!(spot_leg, futures_leg);
+        spot_result?;
+        futures_result?;
+
+        let statistics = self.get_statistics().await;
+        info!("{} strategy run completed: {} opportunities executed, PnL {:.2}",
+              self.mode, statistics.opportunities_executed, statistics.total_pnl);
+        Ok(statistics)
+    }
+
+    /// Check that futures opportunities are still appearing, alerting the
+    /// webhook when market data or opportunities have gone quiet
+    pub async fn check_opportunity_watchdog(&self) -> WatchdogStatus {
+        let status = self.futures.check_opportunity_watchdog().await;
+        let (event_type, reason) = match status {
+            WatchdogStatus::NoMarketData { idle_ms } => {
+                (HealthEventType::NoMarketData, format!("no futures market data for {} s", idle_ms / 1000))
+            }
+            WatchdogStatus::NoOpportunities { idle_ms } => {
+                (HealthEventType::NoOpportunities, format!("no futures opportunities detected for {} s", idle_ms / 1000))
+            }
+            WatchdogStatus::WarmingUp | WatchdogStatus::Healthy => return status,
+        };
+        if let Err(e) = self.notifier.notify(event_type, None, &reason).await {
+            warn!("Failed to send opportunity watchdog notification: {}", e);
+        }
+        status
+    }
+
+    /// Replay recorded spot and futures sessions concurrently, each in its own
+    /// order, and return the combined statistics
+    pub async fn replay<T>(
+        &self,
+        spot_session: &[RecordedBook],
+        futures_session: &[RecordedBook],
+        spot_executor: &mut T,
+    ) -> Result<DualModeStatistics>
+    where
+        T: StrategyExecutor,
+    {
+        let spot_leg = async {
+            if self.mode.runs_spot() {
+                for recorded in spot_session {
+                    self.spot.update_orderbook(recorded.exchange, recorded.orderbook.clone()).await?;
+                    self.spot.process_market_data(spot_executor).await?;
+                }
+            }
+            Ok::<(), anyhow::Error>(())
+        };
+        let futures_leg = async {
+            if self.mode.runs_futures() {
+                for recorded in futures_session {
+                    self.futures.update_orderbook(recorded.exchange, recorded.orderbook.clone()).await?;
+                    execute_futures_opportunities(&self.futures, &self.connectors).await?;
+                }
+            }
+            Ok::<(), anyhow::Error>(())
+        };
+        let (spot_result, futures_result) = tokio::join!(spot_leg, futures_leg);
+        spot_result?;
+        futures_result?;
+        Ok(self.get_statistics().await)
+    }
+
+    /// Stop both strategies
+    pub async fn stop(&self) -> Result<()> {
+        self.spot.stop().await;
+        self.futures.stop().await
+    }
+
+    /// Statistics of both strategies and their combined totals
+    pub async fn get_statistics(&self) -> DualModeStatistics {
+        let spot = self.spot.get_statistics().await;
+        let futures = self.futures.get_statistics().await;
+        let portfolio = self.portfolio.read().await;
+        DualModeStatistics {
+            mode: self.mode,
+            opportunities_executed: spot.opportunities_executed + futures.opportunities_executed,
+            total_pnl: portfolio.total_realized_pnl(),
+            total_notional: portfolio.total_notional(),
+            risk_rejections: portfolio.rejections(),
+            spot,
+            futures,
+        }
+    }
+}
+
+/// Operator commands from the next command file, or never without a kill switch
+async fn next_control_commands(kill_switch: Option<&KillSwitch>) -> Vec<ControlCommand> {
+    match kill_switch {
+        Some(kill_switch) => kill_switch.next_commands().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Scan the current books for futures opportunities, closing any elapsed
+/// coalescing windows, and execute each on its venues' connectors, then run
+/// any net position checks that are due
+async fn execute_futures_opportunities(strategy: &FuturesArbitrageStrategy, connectors: &FuturesConnectors) -> Result<()> {
+    for opportunity in strategy.scan_opportunities().await? {
+        let (maker, taker) = match (connectors.get(&opportunity.maker_exchange), connectors.get(&opportunity.taker_exchange)) {
+            (Some(maker), Some(taker)) => (maker, taker),
+            _ => {
+                warn!("No futures connector for {} or {}, skipping {}",
+                      opportunity.maker_exchange, opportunity.taker_exchange, opportunity.correlation_id);
+                continue;
+            }
+        };
+        if let Err(e) = strategy.execute_opportunity(&opportunity, maker.as_ref(), taker.as_ref()).await {
+            warn!("Failed to execute futures opportunity {}: {}", opportunity.correlation_id, e);
+        }
+    }
+    for (symbol, maker_exchange, taker_exchange) in strategy.due_neutrality_checks().await {
+        if let (Some(maker), Some(taker)) = (connectors.get(&maker_exchange), connectors.get(&taker_exchange)) {
+            if let Err(e) = strategy.enforce_net_neutrality(&symbol, maker.as_ref(), taker.as_ref()).await {
+                warn!("Net position check failed for {}: {}", symbol, e);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::futures::{
+        ContractType, FundingRate, FuturesAccountInfo, FuturesContract, FuturesOrder, FuturesOrderResponse,
+        FuturesOrderStatus, FuturesPosition, MarginMode, MarkPrice, QuantityUnit,
+    };
+    use crate::trading::DryRunExecutor;
+    use crate::ArbitrageError;
+
+    /// Futures connector listing `listings` that fills every order in full
+    struct FillingConnector {
+        listings: Vec<FuturesContract>,
+        placed: std::sync::Mutex<Vec<FuturesOrder>>,
+    }
+
+    /// Both venues listing a BTCUSDT perpetual allowing up to `max_leverage`
+    fn filling_connectors(max_leverage: u32) -> FuturesConnectors {
+        let contract = FuturesContract {
+            symbol: "BTCUSDT".to_string(),
+            base_asset: "BTC".to_string(),
+            quote_asset: "USDT".to_string(),
+            contract_type: ContractType::Perpetual,
+            min_order_size: 0.001,
+            price_precision: 2,
+            quantity_precision: 3,
+            tick_size: 0.01,
+            lot_size: 0.001,
+            maker_fee: 0.0002,
+            taker_fee: 0.0004,
+            contract_multiplier: 1.0,
+            max_leverage,
+            quantity_unit: QuantityUnit::Base,
+            percent_price: None,
+        };
+        [Exchange::Binance, Exchange::Bybit]
+            .into_iter()
+            .map(|exchange| {
+                let connector = FillingConnector { listings: vec![contract.clone()], placed: std::sync::Mutex::new(Vec::new()) };
+                (exchange, Arc::new(connector) as Arc<dyn FuturesConnector>)
+            })
+            .collect()
+    }
+
+    #[async_trait::async_trait]
+    impl FuturesConnector for FillingConnector {
+        async fn get_exchange_info(&self) -> Result<HashMap<String, FuturesContract>> {
+            Ok(self.listings.iter().map(|contract| (contract.symbol.clone(), contract.clone())).collect())
+        }
+
+        async fn get_account_info(&self) -> Result<FuturesAccountInfo> {
+            Ok(FuturesAccountInfo {
+                total_wallet_balance: 1_000_000.0,
+                total_unrealized_pnl: 0.0,
+                total_margin_balance: 1_000_000.0,
+                total_position_initial_margin: 0.0,
+                total_order_initial_margin: 0.0,
+                available_balance: 1_000_000.0,
+                max_withdraw_amount: 1_000_000.0,
+                margin_ratio: None,
+                update_time: 0,
+                balances: Vec::new(),
+                positions: Vec::new(),
+            })
+        }
+
+        async fn get_positions(&self) -> Result<Vec<FuturesPosition>> {
+            Ok(Vec::new())
+        }
+
+        async fn place_order(&self, order: &FuturesOrder) -> Result<FuturesOrderResponse> {
+            self.placed.lock().unwrap().push(order.clone());
+            Ok(FuturesOrderResponse {
+                order_id: format!("order_{}", self.placed.lock().unwrap().len()),
+                client_order_id: order.client_order_id.clone(),
+                symbol: order.symbol.clone(),
+                side: order.side,
+                position_side: order.position_side.clone(),
+                order_type: order.order_type.clone(),
+                quantity: order.quantity,
+                price: order.price,
+                status: FuturesOrderStatus::Filled,
+                filled_quantity: order.quantity,
+                average_price: None,
+                commission: 0.0,
+                commission_asset: "USDT".to_string(),
+                timestamp: 0,
+            })
+        }
+
+        async fn cancel_order(&self, _symbol: &str, _order_id: &str) -> Result<FuturesOrderResponse> {
+            Err(ArbitrageError::NotImplemented("cancel_order".to_string()).into())
+        }
+
+        async fn get_order_status(&self, _symbol: &str, _order_id: &str) -> Result<FuturesOrderResponse> {
+            Err(ArbitrageError::NotImplemented("get_order_status".to_string()).into())
+        }
+
+        async fn get_funding_rate(&self, _symbol: &str) -> Result<FundingRate> {
+            Err(ArbitrageError::NotImplemented("get_funding_rate".to_string()).into())
+        }
+
+        async fn get_mark_price(&self, _symbol: &str) -> Result<MarkPrice> {
+            Err(ArbitrageError::NotImplemented("get_mark_price".to_string()).into())
+        }
+
+        async fn set_margin_mode(&self, _symbol: &str, _mode: MarginMode) -> Result<()> {
+            Ok(())
+        }
+
+        async fn set_leverage(&self, _symbol: &str, _leverage: u32) -> Result<()> {
+            Ok(())
+        }
+
+        async fn subscribe_orderbook(&mut self, _symbol: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn subscribe_trades(&mut self, _symbol: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn subscribe_mark_price(&mut self, _symbol: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn subscribe_funding_rate(&mut self, _symbol: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn is_connected(&self) -> bool {
+            true
+        }
+
+        async fn connect(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn disconnect(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// BTCUSDT crossed by 20 bps: Bybit bids above the Binance ask
+    fn crossed_session() -> Vec<RecordedBook> {
+        let mut session = Vec::new();
+        for _ in 0..3 {
+            for (exchange, bid, ask) in [(Exchange::Binance, 49990.0, 50000.0), (Exchange::Bybit, 50100.0, 50110.0)] {
+                let mut orderbook = OrderBook::new("BTCUSDT".to_string(), exchange);
+                orderbook.update_bid(bid, 1.0);
+                orderbook.update_ask(ask, 1.0);
+                session.push(RecordedBook { exchange, orderbook });
+            }
+        }
+        session
+    }
+
+    async fn replay(config: ArbitrageConfig) -> DualModeStatistics {
+        let runner = DualModeRunner::new(config.clone(), StrategyMode::Both, vec!["BTCUSDT".to_string()], filling_connectors(20))
+            .await
+            .unwrap();
+        let mut executor = DryRunExecutor::new(config).await.unwrap().with_seed(7);
+        runner.replay(&crossed_session(), &crossed_session(), &mut executor).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_quiet_feed_alerts_webhook() {
+        use crate::utils::time::ManualClock;
+        use wiremock::matchers::{body_partial_json, method};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(serde_json::json!({"event_type": "no_market_data"})))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut config = ArbitrageConfig::default();
+        config.strategy.zero_opportunity_alert_secs = 60;
+        config.monitoring.webhook.url = Some(server.uri());
+        config.monitoring.webhook.max_retries = 0;
+        let mut runner = DualModeRunner::new(config, StrategyMode::Futures, vec!["BTCUSDT".to_string()], FuturesConnectors::new())
+            .await
+            .unwrap();
+        let clock = Arc::new(ManualClock::new(0));
+        runner.futures = runner.futures.with_clock(clock.clone());
+
+        let mut orderbook = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        orderbook.update_bid(49990.0, 1.0);
+        orderbook.update_ask(50000.0, 1.0);
+        runner.update_orderbook(Market::Futures, Exchange::Binance, orderbook).await.unwrap();
+        assert_eq!(runner.check_opportunity_watchdog().await, WatchdogStatus::WarmingUp);
+
+        // The feed goes quiet past the window; the webhook's rate limit holds repeats back
+        clock.advance(std::time::Duration::from_secs(61));
+        assert!(matches!(runner.check_opportunity_watchdog().await, WatchdogStatus::NoMarketData { .. }));
+        assert!(matches!(runner.check_opportunity_watchdog().await, WatchdogStatus::NoMarketData { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_run_configures_leverage_before_scanning() {
+        let mut config = ArbitrageConfig::default();
+        config.execution.leverage = 10;
+        let runner = DualModeRunner::new(config.clone(), StrategyMode::Futures, vec!["BTCUSDT".to_string()], filling_connectors(5))
+            .await
+            .unwrap();
+        let mut executor = DryRunExecutor::new(config).await.unwrap();
+
+        // Both venues cap BTCUSDT at 5x, so leverage cannot be set and the run never starts
+        let err = runner.run(&mut executor).await.unwrap_err();
+        assert!(err.to_string().contains("exceeds"), "{}", err);
+        assert_ne!(runner.futures.get_state().await, FuturesStrategyState::Running);
+    }
+
+    #[tokio::test]
+    async fn test_run_serves_prometheus_metrics() {
+        let mut config = ArbitrageConfig::default();
+        config.monitoring.prometheus.enabled = true;
+        config.monitoring.prometheus.listen_addr = "127.0.0.1:0".to_string();
+        let runner = DualModeRunner::new(config.clone(), StrategyMode::Futures, vec!["BTCUSDT".to_string()], filling_connectors(20))
+            .await
+            .unwrap();
+        let mut executor = DryRunExecutor::new(config).await.unwrap();
+        assert!(runner.metrics_addr().await.is_none());
+
+        let scrape = async {
+            while runner.futures.get_state().await != FuturesStrategyState::Running {
+                tokio::task::yield_now().await;
+            }
+            let addr = runner.metrics_addr().await.unwrap();
+            let body = reqwest::get(format!("http://{}/metrics", addr)).await.unwrap().text().await.unwrap();
+            runner.stop().await.unwrap();
+            body
+        };
+        let (result, body) = tokio::join!(runner.run(&mut executor), scrape);
+        result.unwrap();
+        assert!(body.contains("# TYPE arbitrage_spread_capture_bps histogram"), "{}", body);
+    }
+
+    #[tokio::test]
+    async fn test_command_file_disables_futures_venue_while_running() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("commands");
+        std::fs::write(&path, "disable bybit\n").unwrap();
+        let mut config = ArbitrageConfig::default();
+        config.risk.kill_switch.command_file = Some(path.to_string_lossy().into_owned());
+        config.risk.kill_switch.poll_interval_ms = 5;
+        let runner = DualModeRunner::new(config.clone(), StrategyMode::Futures, vec!["BTCUSDT".to_string()], filling_connectors(20))
+            .await
+            .unwrap()
+            .with_kill_switch(KillSwitch::new(config.risk.kill_switch.clone()));
+        let mut executor = DryRunExecutor::new(config).await.unwrap();
+
+        let operator = async {
+            while !runner.futures.is_exchange_disabled(Exchange::Bybit).await {
+                tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            }
+            runner.stop().await.unwrap();
+        };
+        let (result, ()) = tokio::join!(runner.run(&mut executor), operator);
+        result.unwrap();
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_futures_symbols_checked_against_listings() {
+        let config = ArbitrageConfig::default();
+
+        // No symbols given: every perpetual listed on both venues is monitored
+        let runner = DualModeRunner::new(config.clone(), StrategyMode::Futures, Vec::new(), filling_connectors(20))
+            .await
+            .unwrap();
+        assert_eq!(runner.futures.active_symbols(), ["BTCUSDT".to_string()]);
+
+        // Symbols missing from a venue are dropped before any scan
+        let runner = DualModeRunner::new(config, StrategyMode::Futures, vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()], filling_connectors(20))
+            .await
+            .unwrap();
+        assert_eq!(runner.futures.active_symbols(), ["BTCUSDT".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_replayed_spot_and_futures_share_one_notional_limit() {
+        let mut config = ArbitrageConfig::default();
+        config.strategy.min_spread_bps = 5.0;
+        config.strategy.max_position_size = 1.0;
+        config.execution.min_order_size = 0.001;
+
+        let unlimited = replay(config.clone()).await;
+        assert!(unlimited.spot.opportunities_executed > 0 && unlimited.futures.opportunities_executed > 0);
+        assert_eq!(unlimited.opportunities_executed,
+                   unlimited.spot.opportunities_executed + unlimited.futures.opportunities_executed);
+        assert!((unlimited.total_pnl - (unlimited.spot.total_pnl + unlimited.futures.total_pnl)).abs() < 1e-6);
+        assert_eq!(unlimited.risk_rejections, 0);
+
+        // Room for one opportunity from either market, but not one from each
+        config.risk.max_total_notional_usd = 150_000.0;
+        let capped = replay(config).await;
+        assert_eq!(capped.opportunities_executed, 1);
+        assert!(capped.total_notional <= 150_000.0);
+        assert!(capped.risk_rejections > 0);
+    }
+}
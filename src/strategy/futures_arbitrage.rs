@@ -2,19 +2,27 @@
 //! Strategy: Bybit Maker + Binance Taker for cross-exchange arbitrage
 
 use crate::{
-    config::ArbitrageConfig,
+    config::{ArbitrageConfig, ExecutionEngine, RoundingMode},
     connectors::{
         Exchange, OrderSide,
-        futures::{FuturesConnector, FuturesOrder, FuturesOrderType, FuturesTimeInForce, PositionSide, MarkPrice}
+        futures::{FuturesConnector, FuturesContract, FuturesOrder, FuturesOrderResponse, FuturesOrderStatus, FuturesOrderType, FuturesTimeInForce, PositionMode, PositionSide, MarkPrice}
     },
     data::OrderBook,
+    strategy::{book_levels::BookLevels, grouping::GroupedLevels, ladder::MakerLadder, scheduler::SymbolScheduler, sizing::{ConfidenceSizer, SizingInputs}},
+    strategy::risk_manager::{Market, PortfolioFill, PortfolioRiskManager},
+    utils::currency::{CurrencyConverter, CurrencyReport},
+    utils::metrics::{LogSampler, OpportunityWatchdog, ProfitRateMonitor, ProfitRateStatus, WatchdogStatus},
+    utils::time::{age_ms, now_ns, Clock, SystemClock},
+    trading::control::ControlCommand,
+    utils::prometheus::MetricsExporter,
+    utils::trade_store::TradeWriter,
     Result, ArbitrageError,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, info_span, warn, Instrument};
 
 /// Futures arbitrage opportunity
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,8 +53,48 @@ pub struct FuturesArbitrageOpportunity {
     pub taker_fee: f64,
     /// Risk score (0-100)
     pub risk_score: f64,
-    /// Timestamp when opportunity was detected
+    /// Timestamp when opportunity was detected, in nanoseconds
     pub timestamp: i64,
+    /// ID shared by every log line and client order ID of this opportunity
+    pub correlation_id: String,
+    /// Strategy monotonic time in milliseconds after which the opportunity is stale and skipped
+    pub deadline_ms: Option<i64>,
+}
+
+/// The concrete orders an opportunity would send, with projected fills
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionPlan {
+    /// ID of the previewed opportunity
+    pub correlation_id: String,
+    /// Exchange receiving the maker leg
+    pub maker_exchange: Exchange,
+    /// Post-only maker order, in the venue's symbol and quantity unit
+    pub maker_order: FuturesOrder,
+    /// Exchange receiving the taker leg
+    pub taker_exchange: Exchange,
+    /// Taker hedge order, in the venue's symbol and quantity unit
+    pub taker_order: FuturesOrder,
+    /// Child order size if the hedge would be sliced, in the venue's quantity unit
+    pub hedge_slice: Option<f64>,
+    /// Base quantity of the taker leg fillable from the current book
+    pub projected_taker_quantity: f64,
+    /// Volume-weighted taker fill price from walking the current book
+    pub projected_taker_price: f64,
+    /// How far the projected taker price is worse than detected, in bps
+    pub projected_slippage_bps: f64,
+    /// Profit if the maker fills at its limit and the taker at the projected price
+    pub projected_profit: f64,
+}
+
+/// Fair value taken from a reference exchange's book
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReferencePrice {
+    /// Exchange the price was taken from
+    pub exchange: Exchange,
+    /// Mid price of that exchange's book
+    pub price: f64,
+    /// Age of the book in milliseconds
+    pub age_ms: i64,
 }
 
 /// Strategy state for futures arbitrage
@@ -69,7 +117,7 @@ pub struct FuturesArbitrageStats {
     pub opportunities_detected: u64,
     /// Total opportunities executed
     pub opportunities_executed: u64,
-    /// Total profit/loss
+    /// Total profit/loss in the reporting currency
     pub total_pnl: f64,
     /// Total maker rebates earned
     pub total_maker_rebates: f64,
@@ -85,6 +133,38 @@ pub struct FuturesArbitrageStats {
     pub uptime_seconds: u64,
     /// Last execution timestamp
     pub last_execution: Option<i64>,
+    /// Executions that left net exposure beyond tolerance
+    pub residual_exposure_alarms: u64,
+    /// Opportunities dropped because their deadline passed before execution
+    pub expired_opportunities: u64,
+    /// Executions aborted because the book moved against the detected prices
+    #[serde(default)]
+    pub last_look_aborts: u64,
+    /// Executions aborted because a leg priced too far from its venue's mark
+    #[serde(default)]
+    pub mark_deviation_aborts: u64,
+    /// Mark prices ignored by a guard because they were older than `max_mark_price_age_ms`
+    #[serde(default)]
+    pub stale_mark_prices: u64,
+    /// Executions whose legs both reported an average fill price
+    #[serde(default)]
+    pub reconciled_executions: u64,
+    /// Cumulative realized minus expected spread capture in USD (negative = fills worse than assumed)
+    #[serde(default)]
+    pub fill_slippage_usd: f64,
+    /// Realized minus expected spread of the most recent reconciled execution in bps
+    #[serde(default)]
+    pub last_fill_slippage_bps: f64,
+    /// Expected PnL per quote currency, before conversion
+    #[serde(default)]
+    pub pnl_by_currency: HashMap<String, f64>,
+    /// Executions left out of the converted PnL, fee and volume totals because
+    /// their quote currency had no rate; their PnL stays in `pnl_by_currency`
+    #[serde(default)]
+    pub unconverted_executions: u64,
+    /// Detected opportunities left out of the log by `opportunity_log_limit`
+    #[serde(default)]
+    pub opportunity_logs_suppressed: u64,
 }
 
 impl Default for FuturesArbitrageStats {
@@ -100,10 +180,90 @@ impl Default for FuturesArbitrageStats {
             total_volume: 0.0,
             uptime_seconds: 0,
             last_execution: None,
+            residual_exposure_alarms: 0,
+            expired_opportunities: 0,
+            last_look_aborts: 0,
+            mark_deviation_aborts: 0,
+            stale_mark_prices: 0,
+            reconciled_executions: 0,
+            fill_slippage_usd: 0.0,
+            last_fill_slippage_bps: 0.0,
+            pnl_by_currency: HashMap::new(),
+            unconverted_executions: 0,
+            opportunity_logs_suppressed: 0,
+        }
+    }
+}
+
+/// Settlement asset of the USDT-margined perpetuals traded by this strategy
+const MARGIN_ASSET: &str = "USDT";
+
+/// Bybit maker fee rate; negative is a rebate (-0.025%)
+const MAKER_FEE: f64 = -0.00025;
+
+/// Binance taker fee rate (0.04%)
+const TAKER_FEE: f64 = 0.0004;
+
+/// A book with only bids or only asks (common right after connect) is not usable yet
+fn is_two_sided(book: &OrderBook) -> bool {
+    book.best_bid().is_some() && book.best_ask().is_some()
+}
+
+/// Total quantity on the top `levels` levels of a book side; `Buy` sums bids, `Sell` sums asks
+fn depth_top_n(book: &OrderBook, side: OrderSide, levels: usize) -> f64 {
+    book.best_n(side, levels).iter().map(|(_, quantity)| quantity).sum()
+}
+
+/// Fill `quantity` against the book side a taker order of `side` consumes.
+///
+/// Returns the quantity filled and its volume-weighted price.
+fn walk_book(book: &OrderBook, side: OrderSide, quantity: f64) -> (f64, Option<f64>) {
+    let levels = match side {
+        OrderSide::Buy => book.best_n(OrderSide::Sell, usize::MAX),
+        OrderSide::Sell => book.best_n(OrderSide::Buy, usize::MAX),
+    };
+    let (mut filled, mut cost) = (0.0, 0.0);
+    for (price, available) in levels {
+        let take = available.min(quantity - filled);
+        if take <= 0.0 {
+            break;
         }
+        filled += take;
+        cost += take * price;
     }
+    (filled, (filled > 0.0).then(|| cost / filled))
+}
+
+/// Bid/ask spread of a book in basis points of the mid price
+fn spread_bps(book: &OrderBook) -> Option<f64> {
+    let (bid, ask) = (book.best_bid()?, book.best_ask()?);
+    Some((ask - bid) / ((ask + bid) / 2.0) * 10000.0)
+}
+
+/// Last mid price and smoothed absolute mid change for one book
+#[derive(Debug, Clone, Copy)]
+struct MidVolatility {
+    last_mid: f64,
+    average_bps: f64,
+}
+
+/// Net position check waiting for an execution's maker orders to resolve
+#[derive(Debug, Clone)]
+struct PendingNeutrality {
+    maker_exchange: Exchange,
+    taker_exchange: Exchange,
+    /// Net base-asset position across both venues before the execution
+    baseline: f64,
+    /// Net base-asset change the executions meant to leave, from the hedge ratio
+    intended_delta: f64,
+    /// Venue symbol and order ID of each maker order
+    maker_orders: Vec<(String, String)>,
+    checked_at: Option<std::time::Instant>,
 }
 
+/// Minimum time between polls of a pending net position check
+const NEUTRALITY_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
 /// Futures arbitrage strategy
 pub struct FuturesArbitrageStrategy {
     /// Configuration
@@ -120,6 +280,54 @@ pub struct FuturesArbitrageStrategy {
     mark_prices: Arc<RwLock<HashMap<Exchange, HashMap<String, MarkPrice>>>>,
     /// Active symbols for monitoring
     active_symbols: Vec<String>,
+    /// Activity-weighted scan scheduler for active symbols
+    scheduler: Arc<RwLock<SymbolScheduler>>,
+    /// Rolling realized profit rate
+    profit_rate: Arc<RwLock<ProfitRateMonitor>>,
+    /// Alerts when detection goes quiet while market data keeps flowing
+    opportunity_watchdog: Arc<RwLock<OpportunityWatchdog>>,
+    /// Caps per-opportunity log lines
+    opportunity_log_sampler: Arc<RwLock<LogSampler>>,
+    /// Margin reserved by in-flight executions per exchange
+    reserved_margin: Arc<RwLock<HashMap<Exchange, f64>>>,
+    /// Smoothed round-trip latency per exchange in milliseconds
+    exchange_latency_ms: Arc<RwLock<HashMap<Exchange, f64>>>,
+    /// Symbols whose margin mode has already been applied per exchange
+    margin_mode_applied: Arc<RwLock<HashSet<(Exchange, String)>>>,
+    /// Position mode detected per exchange and venue symbol
+    position_modes: Arc<RwLock<HashMap<(Exchange, String), PositionMode>>>,
+    /// Start of the most recent execution, for the global execution throttle
+    last_execution_at: Arc<RwLock<Option<std::time::Instant>>>,
+    /// Open coalescing windows per symbol, keyed by the time of the first buffered update
+    coalesce_windows: Arc<RwLock<HashMap<String, std::time::Instant>>>,
+    /// Contract specs per exchange and venue symbol, used to convert order quantities
+    contract_specs: Arc<RwLock<HashMap<(Exchange, String), FuturesContract>>>,
+    /// Time of the last successful fill per symbol, for the post-fill cooldown
+    last_fill_at: Arc<RwLock<HashMap<String, std::time::Instant>>>,
+    /// Time of the last confirmed order per venue, for canary probing
+    last_venue_trade_at: Arc<RwLock<HashMap<Exchange, std::time::Instant>>>,
+    /// Venues dropped from pairing by an operator
+    disabled_exchanges: Arc<RwLock<HashSet<Exchange>>>,
+    /// Net position checks per symbol waiting on resting maker orders
+    pending_neutrality: Arc<RwLock<HashMap<String, PendingNeutrality>>>,
+    /// Symbols still waiting for a two-sided book on both exchanges
+    not_ready_symbols: Arc<RwLock<HashSet<String>>>,
+    /// Trade history sink, when enabled
+    trade_store: Option<Arc<TradeWriter>>,
+    /// Prometheus metrics registry, when enabled
+    metrics_exporter: Option<Arc<MetricsExporter>>,
+    /// Latest USD reference price per quote stablecoin
+    stablecoin_prices: Arc<RwLock<HashMap<String, f64>>>,
+    /// Converts quote-currency amounts into the reporting currency
+    converter: Arc<RwLock<CurrencyConverter>>,
+    /// Scales capped opportunity sizes by confidence
+    sizer: ConfidenceSizer,
+    /// Smoothed mid-price changes per exchange and symbol
+    mid_volatility: Arc<RwLock<HashMap<(Exchange, String), MidVolatility>>>,
+    /// Portfolio-wide limits shared with other strategies, if any
+    portfolio: Option<Arc<RwLock<PortfolioRiskManager>>>,
+    /// Monotonic time for deadlines and rates; wall time only for book ages
+    clock: Arc<dyn Clock>,
     /// Start time
     start_time: std::time::Instant,
 }
@@ -128,6 +336,20 @@ impl FuturesArbitrageStrategy {
     /// Create a new futures arbitrage strategy
     pub async fn new(config: ArbitrageConfig, symbols: Vec<String>) -> Result<Self> {
         info!("Creating futures arbitrage strategy for {} symbols", symbols.len());
+        let scheduler = SymbolScheduler::new(symbols.clone(), config.strategy.scans_per_tick);
+        let profit_rate = ProfitRateMonitor::new(
+            config.strategy.profit_rate_window_secs,
+            config.strategy.min_profit_per_hour_usd,
+        );
+        let opportunity_watchdog = OpportunityWatchdog::new(config.strategy.zero_opportunity_alert_secs);
+        let opportunity_log_sampler = LogSampler::new(
+            config.strategy.opportunity_log_limit,
+            config.strategy.opportunity_log_interval_ms,
+        );
+        let trade_store = TradeWriter::from_config(&config.monitoring.trade_store)?.map(Arc::new);
+        let metrics_exporter = MetricsExporter::from_config(&config.monitoring.prometheus).map(Arc::new);
+        let sizer = ConfidenceSizer::new(config.strategy.confidence_sizing.clone());
+        let converter = CurrencyConverter::from_config(&config.strategy);
         
         Ok(Self {
             config,
@@ -137,44 +359,365 @@ impl FuturesArbitrageStrategy {
             market_data: Arc::new(RwLock::new(HashMap::new())),
             mark_prices: Arc::new(RwLock::new(HashMap::new())),
             active_symbols: symbols,
+            scheduler: Arc::new(RwLock::new(scheduler)),
+            profit_rate: Arc::new(RwLock::new(profit_rate)),
+            opportunity_watchdog: Arc::new(RwLock::new(opportunity_watchdog)),
+            opportunity_log_sampler: Arc::new(RwLock::new(opportunity_log_sampler)),
+            reserved_margin: Arc::new(RwLock::new(HashMap::new())),
+            exchange_latency_ms: Arc::new(RwLock::new(HashMap::new())),
+            margin_mode_applied: Arc::new(RwLock::new(HashSet::new())),
+            position_modes: Arc::new(RwLock::new(HashMap::new())),
+            coalesce_windows: Arc::new(RwLock::new(HashMap::new())),
+            last_execution_at: Arc::new(RwLock::new(None)),
+            contract_specs: Arc::new(RwLock::new(HashMap::new())),
+            last_fill_at: Arc::new(RwLock::new(HashMap::new())),
+            last_venue_trade_at: Arc::new(RwLock::new(HashMap::new())),
+            pending_neutrality: Arc::new(RwLock::new(HashMap::new())),
+            disabled_exchanges: Arc::new(RwLock::new(HashSet::new())),
+            not_ready_symbols: Arc::new(RwLock::new(HashSet::new())),
+            trade_store,
+            metrics_exporter,
+            stablecoin_prices: Arc::new(RwLock::new(HashMap::new())),
+            converter: Arc::new(RwLock::new(converter)),
+            sizer,
+            mid_volatility: Arc::new(RwLock::new(HashMap::new())),
+            portfolio: None,
+            clock: Arc::new(SystemClock::new()),
             start_time: std::time::Instant::now(),
         })
     }
 
+    /// Admit every opportunity through a portfolio risk manager shared with other strategies
+    pub fn with_portfolio_risk(mut self, portfolio: Arc<RwLock<PortfolioRiskManager>>) -> Self {
+        self.portfolio = Some(portfolio);
+        self
+    }
+
+    /// Symbols currently monitored, by canonical name
+    pub fn active_symbols(&self) -> &[String] {
+        &self.active_symbols
+    }
+
+    /// Replace the system clock, e.g. with a `ManualClock` in tests
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Age of a book timestamp against the strategy's wall clock, clamped at zero
+    fn book_age_ms(&self, timestamp_ns: i64) -> i64 {
+        age_ms(self.clock.wall_ns(), timestamp_ns, self.config.strategy.clock_skew_tolerance_ms)
+    }
+
     /// Start the strategy
     pub async fn start(&self) -> Result<()> {
         info!("Starting futures arbitrage strategy");
+        for symbol in &self.active_symbols {
+            info!("{} break-even spread: {:.2} bps (maker sell) / {:.2} bps (maker buy), min_spread_bps: {}",
+                  symbol,
+                  self.break_even_spread_bps(symbol, OrderSide::Sell),
+                  self.break_even_spread_bps(symbol, OrderSide::Buy),
+                  self.config.strategy.min_spread_bps_for(symbol));
+        }
         let mut state = self.state.write().await;
         *state = FuturesStrategyState::Running;
         Ok(())
     }
 
+    /// Minimum spread in bps at which an opportunity in the given maker direction breaks even.
+    ///
+    /// Combines the maker fee (or rebate), the taker fee and the configured slippage
+    /// tolerance charged on the taker leg. The spread is measured against the same
+    /// reference price detection uses: the taker ask when the maker sells, and the
+    /// maker ask when the maker buys. Fees are currently the same for every symbol.
+    pub fn break_even_spread_bps(&self, _symbol: &str, maker_side: OrderSide) -> f64 {
+        let taker_cost = TAKER_FEE + self.config.execution.slippage_tolerance;
+        let break_even = match maker_side {
+            OrderSide::Sell => (taker_cost + MAKER_FEE) / (1.0 - MAKER_FEE),
+            OrderSide::Buy => (taker_cost + MAKER_FEE) / (1.0 - taker_cost),
+        };
+        break_even * 10000.0
+    }
+
+    /// Check every active symbol is listed on both exchanges and return the ones that are not.
+    ///
+    /// Call once at startup. Unlisted symbols are logged with the venues missing
+    /// them and dropped from monitoring, or fail startup when
+    /// `exclude_unlisted_symbols` is off.
+    pub async fn validate_symbols(
+        &mut self,
+        bybit_connector: &dyn FuturesConnector,
+        binance_connector: &dyn FuturesConnector,
+    ) -> Result<Vec<String>> {
+        let mut missing: HashMap<String, Vec<Exchange>> = HashMap::new();
+        for (exchange, connector) in [(Exchange::Bybit, bybit_connector), (Exchange::Binance, binance_connector)] {
+            let contracts = self.load_contract_specs(exchange, connector).await?;
+            for canonical in &self.active_symbols {
+                let symbol = self.config.exchanges.exchange_symbol(canonical, &exchange.to_string());
+                if !contracts.contains_key(&symbol) {
+                    missing.entry(canonical.clone()).or_default().push(exchange);
+                }
+            }
+        }
+        
+        let mut excluded: Vec<String> = missing.keys().cloned().collect();
+        excluded.sort();
+        for symbol in &excluded {
+            let venues: Vec<String> = missing[symbol].iter().map(|exchange| exchange.to_string()).collect();
+            warn!("Symbol {} is not listed on {}, excluding it from monitoring", symbol, venues.join(", "));
+        }
+        if excluded.is_empty() {
+            return Ok(excluded);
+        }
+        if !self.config.strategy.exclude_unlisted_symbols {
+            return Err(ArbitrageError::Config(format!(
+                "Symbols not listed on every exchange: {}", excluded.join(", ")
+            )).into());
+        }
+        
+        self.active_symbols.retain(|symbol| !missing.contains_key(symbol));
+        *self.scheduler.write().await = SymbolScheduler::new(self.active_symbols.clone(), self.config.strategy.scans_per_tick);
+        info!("Excluded {} unlisted symbols, monitoring {}: {}",
+              excluded.len(), self.active_symbols.len(), excluded.join(", "));
+        Ok(excluded)
+    }
+
+    /// Set the configured leverage on every active symbol on both exchanges.
+    ///
+    /// Call once before starting live trading; fails if the leverage exceeds a
+    /// contract's allowed maximum.
+    pub async fn configure_leverage(
+        &self,
+        bybit_connector: &dyn FuturesConnector,
+        binance_connector: &dyn FuturesConnector,
+    ) -> Result<()> {
+        let leverage = self.config.execution.leverage;
+        for (exchange, connector) in [(Exchange::Bybit, bybit_connector), (Exchange::Binance, binance_connector)] {
+            let contracts = self.load_contract_specs(exchange, connector).await?;
+            for canonical in &self.active_symbols {
+                let symbol = self.config.exchanges.exchange_symbol(canonical, &exchange.to_string());
+                let max_leverage = match contracts.get(&symbol) {
+                    Some(contract) => contract.max_leverage,
+                    None => {
+                        return Err(ArbitrageError::Config(format!(
+                            "No contract info for {} on {}", symbol, exchange
+                        )).into());
+                    }
+                };
+
+                if leverage > max_leverage {
+                    return Err(ArbitrageError::Config(format!(
+                        "Leverage {}x exceeds {} maximum of {}x on {}",
+                        leverage, symbol, max_leverage, exchange
+                    )).into());
+                }
+
+                connector.set_leverage(&symbol, leverage).await?;
+                info!("Leverage for {} on {} set to {}x", symbol, exchange, leverage);
+            }
+        }
+        Ok(())
+    }
+
+    /// Fetch and cache an exchange's contract specs so order quantities can be
+    /// translated to the venue's unit
+    pub async fn load_contract_specs(
+        &self,
+        exchange: Exchange,
+        connector: &dyn FuturesConnector,
+    ) -> Result<HashMap<String, FuturesContract>> {
+        let contracts = connector.get_exchange_info().await?;
+        let mut specs = self.contract_specs.write().await;
+        for (symbol, contract) in &contracts {
+            specs.insert((exchange, symbol.clone()), contract.clone());
+        }
+        Ok(contracts)
+    }
+
+    /// Maker price improved by `maker_improvement_ticks` within the venue's tick and
+    /// percent-price filters; unchanged when disabled or no contract spec is cached
+    async fn improved_maker_price(&self, opportunity: &FuturesArbitrageOpportunity, venue_symbol: &str) -> f64 {
+        let ticks = self.config.execution.maker_improvement_ticks;
+        if ticks == 0 {
+            return opportunity.maker_price;
+        }
+        let contract = match self.contract_specs.read().await.get(&(opportunity.maker_exchange, venue_symbol.to_string())) {
+            Some(contract) => contract.clone(),
+            None => return opportunity.maker_price,
+        };
+        let mark_price = self.fresh_mark_price(opportunity.maker_exchange, venue_symbol).await;
+
+        let improved = contract.improve_maker_price(opportunity.maker_price, opportunity.maker_side, ticks, mark_price);
+        let unbanded = contract.improve_maker_price(opportunity.maker_price, opportunity.maker_side, ticks, None);
+        if improved != unbanded {
+            debug!("Clamped {} maker price from {:.8} to {:.8} by the percent-price band",
+                   opportunity.symbol, unbanded, improved);
+        }
+        improved
+    }
+
+    /// Cached mark price for a venue symbol, or `None` when missing or older than
+    /// `max_mark_price_age_ms`; a stale mark is logged so the guard relying on it is visibly skipped
+    async fn fresh_mark_price(&self, exchange: Exchange, venue_symbol: &str) -> Option<f64> {
+        let (mark_price, timestamp) = self.mark_prices.read().await
+            .get(&exchange)
+            .and_then(|prices| prices.get(venue_symbol))
+            .map(|mark| (mark.mark_price, mark.timestamp))?;
+        let max_age_ms = self.config.execution.max_mark_price_age_ms;
+        let age_ms = self.book_age_ms(timestamp);
+        if max_age_ms > 0 && age_ms > max_age_ms as i64 {
+            self.statistics.write().await.stale_mark_prices += 1;
+            warn!("Ignoring {} mark price for {}: {} ms old exceeds max_mark_price_age_ms {}",
+                  exchange, venue_symbol, age_ms, max_age_ms);
+            return None;
+        }
+        Some(mark_price)
+    }
+
+    /// Convert a canonical base-unit quantity to the venue's order unit; sizes
+    /// pass through unchanged when no contract spec is cached
+    async fn venue_quantity(&self, exchange: Exchange, symbol: &str, base_quantity: f64) -> f64 {
+        match self.contract_specs.read().await.get(&(exchange, symbol.to_string())) {
+            Some(contract) => contract.venue_quantity(base_quantity),
+            None => base_quantity,
+        }
+    }
+
     /// Stop the strategy
     pub async fn stop(&self) -> Result<()> {
         info!("Stopping futures arbitrage strategy");
-        let mut state = self.state.write().await;
-        *state = FuturesStrategyState::Stopped;
+        *self.state.write().await = FuturesStrategyState::Stopped;
+        if let Some(store) = &self.trade_store {
+            store.shutdown().await;
+        }
         Ok(())
     }
 
-    /// Update market data
+    /// Apply an operator command.
+    ///
+    /// Disabling a venue discards its cached books so nothing stale is paired
+    /// once it is re-enabled; updates from it are ignored until then.
+    pub async fn apply_control(&self, command: ControlCommand) {
+        match command {
+            ControlCommand::DisableExchange(exchange) => {
+                if self.disabled_exchanges.write().await.insert(exchange) {
+                    self.market_data.write().await.remove(&exchange);
+                    warn!("{} disabled, excluding it from opportunity pairing", exchange);
+                }
+            }
+            ControlCommand::EnableExchange(exchange) => {
+                if self.disabled_exchanges.write().await.remove(&exchange) {
+                    info!("{} re-enabled for opportunity pairing", exchange);
+                }
+            }
+        }
+    }
+
+    /// Whether an operator has disabled a venue
+    pub async fn is_exchange_disabled(&self, exchange: Exchange) -> bool {
+        self.disabled_exchanges.read().await.contains(&exchange)
+    }
+
+    /// Update market data; books are stored under the canonical symbol
     pub async fn update_orderbook(&self, exchange: Exchange, orderbook: OrderBook) -> Result<()> {
-        let symbol = orderbook.symbol.clone();
+        if self.is_exchange_disabled(exchange).await {
+            return Ok(());
+        }
+        let symbol = self.config.exchanges.canonical_symbol(&exchange.to_string(), &orderbook.symbol);
+        if let (Some(bid), Some(ask)) = (orderbook.best_bid(), orderbook.best_ask()) {
+            if let Some((currency, usd_price)) = self.config.strategy.stablecoin_reference_price(&symbol, (bid + ask) / 2.0) {
+                self.update_stablecoin_price(currency, usd_price).await;
+            }
+        }
+        self.record_mid_volatility(exchange, &symbol, &orderbook).await;
         
         {
             let mut market_data = self.market_data.write().await;
             let exchange_data = market_data.entry(exchange).or_insert_with(HashMap::new);
             exchange_data.insert(symbol.clone(), orderbook);
         }
+        self.scheduler.write().await.record_update(&symbol);
+        self.opportunity_watchdog.write().await.record_market_data(self.clock.monotonic_ms());
         
         // Trigger opportunity detection after market data update
         if self.is_running().await {
-            self.detect_opportunities().await?;
+            if self.config.strategy.coalesce_ms == 0 {
+                self.detect_opportunities().await?;
+            } else {
+                self.coalesce_windows.write().await
+                    .entry(symbol)
+                    .or_insert_with(std::time::Instant::now);
+                self.flush_coalesced().await?;
+            }
         }
         
         Ok(())
     }
 
+    /// Fair value for a symbol from the first reference exchange with a fresh book.
+    ///
+    /// Walks the primary exchange and then the fallback reference exchanges in
+    /// order. If every reference is stale, the freshest available book is used.
+    pub async fn reference_price(&self, symbol: &str) -> Option<ReferencePrice> {
+        let max_age_ms = self.config.exchanges.reference_max_age_ms as i64;
+        let market_data = self.market_data.read().await;
+        let candidates: Vec<ReferencePrice> = self.config.exchanges.reference_order()
+            .iter()
+            .filter_map(|name| name.parse::<Exchange>().ok())
+            .filter_map(|exchange| {
+                let book = market_data.get(&exchange)?.get(symbol)?;
+                let (bid, ask) = (book.best_bid()?, book.best_ask()?);
+                Some(ReferencePrice { exchange, price: (bid + ask) / 2.0, age_ms: self.book_age_ms(book.timestamp) })
+            })
+            .collect();
+        
+        if let Some(fresh) = candidates.iter().find(|candidate| candidate.age_ms <= max_age_ms) {
+            if fresh.exchange != candidates[0].exchange {
+                debug!("Reference for {} fell back to {}: {} book is {} ms old",
+                       symbol, fresh.exchange, candidates[0].exchange, candidates[0].age_ms);
+            }
+            return Some(*fresh);
+        }
+        let freshest = candidates.into_iter().min_by_key(|candidate| candidate.age_ms)?;
+        warn!("Every reference book for {} is stale, using {} ({} ms old)", symbol, freshest.exchange, freshest.age_ms);
+        Some(freshest)
+    }
+
+    /// Record a USD reference price for a quote stablecoin (e.g. from a USDC/USD feed)
+    pub async fn update_stablecoin_price(&self, currency: &str, usd_price: f64) {
+        let paused_before = self.is_quote_paused(currency).await;
+        self.stablecoin_prices.write().await.insert(currency.to_string(), usd_price);
+        {
+            let mut converter = self.converter.write().await;
+            if converter.reporting_currency() == "USD" {
+                converter.set_rate(currency, usd_price);
+            }
+        }
+        let paused = self.is_quote_paused(currency).await;
+        if paused && !paused_before {
+            warn!("{} depegged to ${:.4}; pausing trading quoted in {}", currency, usd_price, currency);
+        } else if !paused && paused_before {
+            info!("{} back at ${:.4}; resuming trading quoted in {}", currency, usd_price, currency);
+        }
+    }
+
+    /// Set the reporting-currency value of one unit of a quote currency
+    pub async fn update_conversion_rate(&self, currency: &str, rate: f64) {
+        self.converter.write().await.set_rate(currency, rate);
+    }
+
+    /// Expected PnL per quote currency aggregated into the reporting currency at current rates
+    pub async fn pnl_report(&self) -> CurrencyReport {
+        let pnl_by_currency = self.statistics.read().await.pnl_by_currency.clone();
+        self.converter.read().await.report(&pnl_by_currency)
+    }
+
+    /// Whether trading in a quote currency is paused because its reference price has
+    /// drifted from $1 by more than `max_stablecoin_depeg_bps`
+    pub async fn is_quote_paused(&self, currency: &str) -> bool {
+        self.stablecoin_prices.read().await.get(currency).is_some_and(|price| self.config.strategy.is_depegged(*price))
+    }
+
     /// Update mark price
     pub async fn update_mark_price(&self, exchange: Exchange, mark_price: MarkPrice) -> Result<()> {
         let symbol = mark_price.symbol.clone();
@@ -188,8 +731,54 @@ impl FuturesArbitrageStrategy {
         Ok(())
     }
 
+    /// Run detection for symbols whose coalescing window has elapsed.
+    ///
+    /// Only the latest book per symbol is kept, so detection sees the freshest
+    /// state. Call periodically so windows close even when updates stop.
+    pub async fn flush_coalesced(&self) -> Result<Vec<FuturesArbitrageOpportunity>> {
+        let window = std::time::Duration::from_millis(self.config.strategy.coalesce_ms);
+        let ready: Vec<String> = {
+            let mut windows = self.coalesce_windows.write().await;
+            let ready: Vec<String> = windows
+                .iter()
+                .filter(|(_, opened)| opened.elapsed() >= window)
+                .map(|(symbol, _)| symbol.clone())
+                .collect();
+            for symbol in &ready {
+                windows.remove(symbol);
+            }
+            ready
+        };
+
+        if ready.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.detect_for_symbols(&ready).await
+    }
+
+    /// Opportunities due on a periodic scan: symbols whose coalescing window
+    /// has closed when `coalesce_ms` is set, otherwise the scheduler's next batch
+    pub async fn scan_opportunities(&self) -> Result<Vec<FuturesArbitrageOpportunity>> {
+        if self.config.strategy.coalesce_ms == 0 {
+            self.detect_opportunities().await
+        } else {
+            self.flush_coalesced().await
+        }
+    }
+
     /// Detect arbitrage opportunities
     pub async fn detect_opportunities(&self) -> Result<Vec<FuturesArbitrageOpportunity>> {
+        let symbols = self.scheduler.write().await.next_batch();
+        self.detect_for_symbols(&symbols).await
+    }
+
+    /// Detect arbitrage opportunities for the given symbols
+    async fn detect_for_symbols(&self, symbols: &[String]) -> Result<Vec<FuturesArbitrageOpportunity>> {
+        // Every pairing needs both venues
+        if !self.disabled_exchanges.read().await.is_empty() {
+            self.opportunities.write().await.clear();
+            return Ok(Vec::new());
+        }
         let market_data = self.market_data.read().await;
         let mut opportunities = Vec::new();
 
@@ -197,25 +786,57 @@ impl FuturesArbitrageStrategy {
         let binance_data = market_data.get(&Exchange::Binance);
         let bybit_data = market_data.get(&Exchange::Bybit);
 
-        if let (Some(binance_books), Some(bybit_books)) = (binance_data, bybit_data) {
-            for symbol in &self.active_symbols {
-                if let (Some(binance_book), Some(bybit_book)) = 
-                    (binance_books.get(symbol), bybit_books.get(symbol)) {
-                    
-                    // Strategy: Bybit Maker + Binance Taker
-                    // Look for opportunities where we can place maker orders on Bybit
-                    // and immediately hedge with taker orders on Binance
-                    
-                    self.analyze_maker_taker_opportunity(
-                        symbol,
-                        bybit_book,   // Maker exchange (lower liquidity)
-                        binance_book, // Taker exchange (higher liquidity)
-                        &mut opportunities
-                    ).await?;
+        for symbol in symbols {
+            let books = (
+                binance_data.and_then(|books| books.get(symbol)),
+                bybit_data.and_then(|books| books.get(symbol)),
+            );
+            let (binance_book, bybit_book) = match books {
+                (Some(binance_book), Some(bybit_book)) if is_two_sided(binance_book) && is_two_sided(bybit_book) => {
+                    self.mark_symbol_ready(symbol, true).await;
+                    (binance_book, bybit_book)
                 }
+                _ => {
+                    self.mark_symbol_ready(symbol, false).await;
+                    continue;
+                }
+            };
+
+            if let Some(quote) = self.config.strategy.quote_currency(symbol) {
+                if self.is_quote_paused(quote).await {
+                    debug!("Skipping {}: quote currency {} is depegged", symbol, quote);
+                    continue;
+                }
+            }
+
+            if let Some((exchange, spread)) = self.wide_source_spread(bybit_book, binance_book) {
+                debug!("Skipping {}: {} book spread {:.2} bps exceeds max_source_spread_bps {}",
+                       symbol, exchange, spread, self.config.strategy.max_source_spread_bps);
+                continue;
             }
+
+            // Strategy: Bybit Maker + Binance Taker
+            // Look for opportunities where we can place maker orders on Bybit
+            // and immediately hedge with taker orders on Binance
+
+            self.analyze_maker_taker_opportunity(
+                symbol,
+                bybit_book,   // Maker exchange (lower liquidity)
+                binance_book, // Taker exchange (higher liquidity)
+                &mut opportunities
+            ).await?;
         }
 
+        opportunities.retain(|opp| {
+            let allowed = self.config.strategy.allows_side(&opp.maker_exchange.to_string(), opp.maker_side)
+                && self.config.strategy.allows_side(&opp.taker_exchange.to_string(), opp.taker_side);
+            if !allowed {
+                debug!("Suppressing {} opportunity: {} on {} / {} on {} not in allowed directions",
+                       opp.symbol, opp.maker_side, opp.maker_exchange, opp.taker_side, opp.taker_exchange);
+            }
+            allowed
+        });
+
         // Update statistics
         {
             let mut stats = self.statistics.write().await;
@@ -229,18 +850,111 @@ impl FuturesArbitrageStrategy {
         }
 
         if !opportunities.is_empty() {
-            info!("Detected {} futures arbitrage opportunities", opportunities.len());
-            for opp in &opportunities {
-                info!("Opportunity {}: {} on {} @ {:.2} -> {} on {} @ {:.2}, Spread: {:.2} bps, Profit: ${:.2}",
-                      opp.symbol, opp.maker_side, opp.maker_exchange, opp.maker_price,
-                      opp.taker_side, opp.taker_exchange, opp.taker_price,
-                      opp.spread_bps, opp.expected_profit);
-            }
+            self.log_opportunities(&opportunities).await;
         }
 
         Ok(opportunities)
     }
 
+    /// Log detected opportunities, most profitable first, within the configured log limit
+    async fn log_opportunities(&self, opportunities: &[FuturesArbitrageOpportunity]) {
+        let decision = self.opportunity_log_sampler.write().await.admit(self.clock.monotonic_ms(), opportunities.len());
+        if decision.closed_suppressed > 0 {
+            info!("Suppressed {} opportunity log lines in the last {} ms",
+                  decision.closed_suppressed, self.config.strategy.opportunity_log_interval_ms);
+        }
+        let suppressed = (opportunities.len() - decision.log) as u64;
+        if suppressed > 0 {
+            self.statistics.write().await.opportunity_logs_suppressed += suppressed;
+        }
+        if decision.log == 0 {
+            debug!("Detected {} futures arbitrage opportunities", opportunities.len());
+            return;
+        }
+
+        info!("Detected {} futures arbitrage opportunities", opportunities.len());
+        let mut sampled: Vec<&FuturesArbitrageOpportunity> = opportunities.iter().collect();
+        if decision.log < sampled.len() {
+            sampled.sort_by(|a, b| b.expected_profit.partial_cmp(&a.expected_profit).unwrap_or(std::cmp::Ordering::Equal));
+            sampled.truncate(decision.log);
+        }
+        for opp in sampled {
+            let _span = info_span!("opportunity", correlation_id = %opp.correlation_id).entered();
+            info!("Opportunity {}: {} on {} @ {:.2} -> {} on {} @ {:.2}, Spread: {:.2} bps, Profit: ${:.2}",
+                  opp.symbol, opp.maker_side, opp.maker_exchange, opp.maker_price,
+                  opp.taker_side, opp.taker_exchange, opp.taker_price,
+                  opp.spread_bps, opp.expected_profit);
+        }
+    }
+
+    /// First leg whose own bid/ask spread exceeds `max_source_spread_bps`, if any
+    fn wide_source_spread(&self, maker_book: &OrderBook, taker_book: &OrderBook) -> Option<(Exchange, f64)> {
+        let max_spread = self.config.strategy.max_source_spread_bps;
+        if max_spread <= 0.0 {
+            return None;
+        }
+        [(Exchange::Bybit, maker_book), (Exchange::Binance, taker_book)]
+            .into_iter()
+            .filter_map(|(exchange, book)| spread_bps(book).map(|spread| (exchange, spread)))
+            .find(|(_, spread)| *spread > max_spread)
+    }
+
+    /// Whether both legs have `min_depth_quantity` across the top `min_depth_top_n` levels
+    /// of the side they trade against; `maker_side` is the maker's own side.
+    ///
+    /// With `min_depth_band` set, levels are the book's price bands rather than raw ticks.
+    fn has_min_depth(&self, symbol: &str, maker_book: &OrderBook, taker_book: &OrderBook, maker_side: OrderSide) -> bool {
+        let levels = self.config.strategy.min_depth_top_n;
+        if levels == 0 {
+            return true;
+        }
+        let band = self.config.strategy.min_depth_band;
+        let (maker_grouped, taker_grouped);
+        let (maker_book, taker_book) = if band > 0.0 {
+            maker_grouped = maker_book.grouped(band);
+            taker_grouped = taker_book.grouped(band);
+            (&maker_grouped, &taker_grouped)
+        } else {
+            (maker_book, taker_book)
+        };
+        let required = self.config.strategy.min_depth_quantity;
+        // A selling maker prices off the maker bids and the taker buys the taker asks
+        let (maker_depth, taker_depth) = match maker_side {
+            OrderSide::Sell => (depth_top_n(maker_book, OrderSide::Buy, levels), depth_top_n(taker_book, OrderSide::Sell, levels)),
+            OrderSide::Buy => (depth_top_n(maker_book, OrderSide::Sell, levels), depth_top_n(taker_book, OrderSide::Buy, levels)),
+        };
+        let sufficient = maker_depth > required && taker_depth > required;
+        if !sufficient {
+            debug!("Skipping {} maker {}: top {} levels hold {:.4} / {:.4}, need more than {}",
+                   symbol, maker_side, levels, maker_depth, taker_depth, required);
+        }
+        sufficient
+    }
+
+    /// Track whether a symbol has two-sided books on both exchanges, logging transitions
+    async fn mark_symbol_ready(&self, symbol: &str, ready: bool) {
+        let mut not_ready = self.not_ready_symbols.write().await;
+        if ready {
+            if not_ready.remove(symbol) {
+                info!("Order books for {} are two-sided on both exchanges, symbol is ready", symbol);
+            }
+        } else if not_ready.insert(symbol.to_string()) {
+            info!("Waiting for two-sided order books for {} before detecting opportunities", symbol);
+        }
+    }
+
+    /// Whether a symbol has two-sided books on both exchanges
+    pub async fn is_symbol_ready(&self, symbol: &str) -> bool {
+        !self.not_ready_symbols.read().await.contains(symbol)
+    }
+
+    /// Symbols seen with a missing or one-sided book, sorted by name
+    pub async fn get_not_ready_symbols(&self) -> Vec<String> {
+        let mut symbols: Vec<String> = self.not_ready_symbols.read().await.iter().cloned().collect();
+        symbols.sort();
+        symbols
+    }
+
     /// Analyze maker-taker arbitrage opportunity
     async fn analyze_maker_taker_opportunity(
         &self,
@@ -255,16 +969,21 @@ impl FuturesArbitrageStrategy {
         if let (Some(bybit_bid), Some(binance_ask)) = (maker_book.best_bid(), taker_book.best_ask()) {
             if bybit_bid > binance_ask {
                 let spread = bybit_bid - binance_ask;
-                let spread_bps = (spread / binance_ask * 10000.0).round();
+                let spread_bps = spread / binance_ask * 10000.0;
                 
-                if spread_bps >= self.config.strategy.min_spread_bps as f64 {
-                    let quantity = maker_book.best_bid_quantity().unwrap_or(0.0)
+                if spread_bps >= self.config.strategy.min_spread_bps_for(symbol)
+                    && self.has_min_depth(symbol, maker_book, taker_book, OrderSide::Sell)
+                {
+                    let capped_quantity = maker_book.best_bid_quantity().unwrap_or(0.0)
                         .min(taker_book.best_ask_quantity().unwrap_or(0.0))
-                        .min(self.config.strategy.max_position_size);
+                        .min(self.position_capped_quantity(symbol, bybit_bid))
+                        .min(self.capital_capped_quantity(bybit_bid));
+                    let risk_score = self.calculate_risk_score(spread_bps, capped_quantity, Exchange::Binance).await;
+                    let quantity = self.confidence_sized(symbol, capped_quantity, risk_score, maker_book, taker_book).await;
                     
                     if quantity > self.config.execution.min_order_size {
-                        let maker_fee: f64 = -0.00025; // Bybit maker rebate (-0.025%)
-                        let taker_fee: f64 = 0.0004;   // Binance taker fee (0.04%)
+                        let maker_fee = MAKER_FEE;
+                        let taker_fee = TAKER_FEE;
                         
                         let maker_rebate = bybit_bid * quantity * maker_fee.abs();
                         let taker_cost = binance_ask * quantity * taker_fee;
@@ -284,8 +1003,10 @@ impl FuturesArbitrageStrategy {
                                 expected_profit,
                                 maker_fee,
                                 taker_fee,
-                                risk_score: self.calculate_risk_score(spread_bps, quantity).await,
-                                timestamp: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
+                                risk_score,
+                                timestamp: now_ns(),
+                                correlation_id: new_correlation_id(),
+                                deadline_ms: self.opportunity_deadline_ms(),
                             });
                         }
                     }
@@ -298,16 +1019,21 @@ impl FuturesArbitrageStrategy {
         if let (Some(bybit_ask), Some(binance_bid)) = (maker_book.best_ask(), taker_book.best_bid()) {
             if binance_bid > bybit_ask {
                 let spread = binance_bid - bybit_ask;
-                let spread_bps = (spread / bybit_ask * 10000.0).round();
+                let spread_bps = spread / bybit_ask * 10000.0;
                 
-                if spread_bps >= self.config.strategy.min_spread_bps as f64 {
-                    let quantity = maker_book.best_ask_quantity().unwrap_or(0.0)
+                if spread_bps >= self.config.strategy.min_spread_bps_for(symbol)
+                    && self.has_min_depth(symbol, maker_book, taker_book, OrderSide::Buy)
+                {
+                    let capped_quantity = maker_book.best_ask_quantity().unwrap_or(0.0)
                         .min(taker_book.best_bid_quantity().unwrap_or(0.0))
-                        .min(self.config.strategy.max_position_size);
+                        .min(self.position_capped_quantity(symbol, binance_bid))
+                        .min(self.capital_capped_quantity(binance_bid));
+                    let risk_score = self.calculate_risk_score(spread_bps, capped_quantity, Exchange::Binance).await;
+                    let quantity = self.confidence_sized(symbol, capped_quantity, risk_score, maker_book, taker_book).await;
                     
                     if quantity > self.config.execution.min_order_size {
-                        let maker_fee: f64 = -0.00025; // Bybit maker rebate (-0.025%)
-                        let taker_fee: f64 = 0.0004;   // Binance taker fee (0.04%)
+                        let maker_fee = MAKER_FEE;
+                        let taker_fee = TAKER_FEE;
                         
                         let maker_rebate = bybit_ask * quantity * maker_fee.abs();
                         let taker_cost = binance_bid * quantity * taker_fee;
@@ -327,8 +1053,10 @@ impl FuturesArbitrageStrategy {
                                 expected_profit,
                                 maker_fee,
                                 taker_fee,
-                                risk_score: self.calculate_risk_score(spread_bps, quantity).await,
-                                timestamp: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
+                                risk_score,
+                                timestamp: now_ns(),
+                                correlation_id: new_correlation_id(),
+                                deadline_ms: self.opportunity_deadline_ms(),
                             });
                         }
                     }
@@ -339,8 +1067,112 @@ impl FuturesArbitrageStrategy {
         Ok(())
     }
 
-    /// Calculate risk score for an opportunity
-    async fn calculate_risk_score(&self, spread_bps: f64, quantity: f64) -> f64 {
+    /// Fold a book's mid price into the smoothed mid-change estimate for its exchange and symbol
+    async fn record_mid_volatility(&self, exchange: Exchange, symbol: &str, orderbook: &OrderBook) {
+        const SMOOTHING: f64 = 0.1;
+        let mid = match (orderbook.best_bid(), orderbook.best_ask()) {
+            (Some(bid), Some(ask)) => (bid + ask) / 2.0,
+            _ => return,
+        };
+        let mut volatility = self.mid_volatility.write().await;
+        let tracked = volatility
+            .entry((exchange, symbol.to_string()))
+            .or_insert(MidVolatility { last_mid: mid, average_bps: 0.0 });
+        let change_bps = ((mid - tracked.last_mid) / tracked.last_mid * 10000.0).abs();
+        tracked.average_bps += SMOOTHING * (change_bps - tracked.average_bps);
+        tracked.last_mid = mid;
+    }
+
+    /// Smoothed absolute mid change in bps for a symbol, the larger of the two exchanges
+    pub async fn get_volatility_bps(&self, symbol: &str) -> f64 {
+        self.mid_volatility.read().await
+            .iter()
+            .filter(|((_, tracked), _)| tracked == symbol)
+            .map(|(_, tracked)| tracked.average_bps)
+            .fold(0.0, f64::max)
+    }
+
+    /// Scale a quantity already capped by the hard limits by the opportunity's confidence
+    async fn confidence_sized(
+        &self,
+        symbol: &str,
+        capped_quantity: f64,
+        risk_score: f64,
+        maker_book: &OrderBook,
+        taker_book: &OrderBook,
+    ) -> f64 {
+        // Books without a timestamp are treated as fresh
+        let oldest = [maker_book.timestamp, taker_book.timestamp]
+            .into_iter()
+            .filter(|timestamp| *timestamp > 0)
+            .min();
+        let inputs = SizingInputs {
+            book_age_ms: oldest.map_or(0.0, |timestamp| self.book_age_ms(timestamp) as f64),
+            volatility_bps: self.get_volatility_bps(symbol).await,
+            risk_score,
+        };
+        let quantity = self.sizer.size(capped_quantity, &inputs);
+        if quantity < capped_quantity {
+            debug!("Sized {} down from {:.6} to {:.6} ({:?})", symbol, capped_quantity, quantity, inputs);
+        }
+        quantity
+    }
+
+    /// Largest quantity allowed by the symbol's position size and value limits at `price`
+    fn position_capped_quantity(&self, symbol: &str, price: f64) -> f64 {
+        let limits = self.config.risk_limits(symbol);
+        match limits.max_position_value_usd {
+            cap if cap > 0.0 => limits.max_position_size.min(cap / price),
+            _ => limits.max_position_size,
+        }
+    }
+
+    /// Largest quantity whose notional at `price` fits `max_capital_per_opportunity_usd`
+    fn capital_capped_quantity(&self, price: f64) -> f64 {
+        match self.config.strategy.max_capital_per_opportunity_usd {
+            cap if cap > 0.0 => cap / price,
+            _ => f64::INFINITY,
+        }
+    }
+
+    /// Record a measured round-trip latency for an exchange
+    pub async fn record_latency(&self, exchange: Exchange, latency: std::time::Duration) {
+        const SMOOTHING: f64 = 0.2;
+        let sample_ms = latency.as_secs_f64() * 1000.0;
+        let mut latencies = self.exchange_latency_ms.write().await;
+        latencies
+            .entry(exchange)
+            .and_modify(|avg| *avg += SMOOTHING * (sample_ms - *avg))
+            .or_insert(sample_ms);
+    }
+
+    /// Smoothed round-trip latency for an exchange, if measured
+    pub async fn get_latency_ms(&self, exchange: Exchange) -> Option<f64> {
+        self.exchange_latency_ms.read().await.get(&exchange).copied()
+    }
+
+    /// Risk penalty for the critical leg being on a slower venue than the other leg
+    async fn latency_penalty(&self, critical_exchange: Exchange) -> f64 {
+        let latencies = self.exchange_latency_ms.read().await;
+        let critical = match latencies.get(&critical_exchange) {
+            Some(latency) => *latency,
+            None => return 0.0,
+        };
+        let fastest_other = latencies
+            .iter()
+            .filter(|(exchange, _)| **exchange != critical_exchange)
+            .map(|(_, latency)| *latency)
+            .fold(f64::INFINITY, f64::min);
+        
+        if fastest_other.is_finite() && critical > fastest_other {
+            (critical - fastest_other) * self.config.strategy.latency_penalty_per_ms
+        } else {
+            0.0
+        }
+    }
+
+    /// Calculate risk score for an opportunity; `critical_exchange` is the venue of the leg that must fill fast
+    async fn calculate_risk_score(&self, spread_bps: f64, quantity: f64, critical_exchange: Exchange) -> f64 {
         let mut risk_score: f64 = 0.0;
         
         // Lower spread = higher risk
@@ -360,101 +1192,1237 @@ impl FuturesArbitrageStrategy {
         // Market volatility (simplified)
         risk_score += 10.0;
         
+        // Slower critical leg = higher risk
+        risk_score += self.latency_penalty(critical_exchange).await;
+        
         risk_score.min(100.0)
     }
 
-    /// Execute an arbitrage opportunity
+    /// Execute an arbitrage opportunity.
+    ///
+    /// The atomic engine, with two-phase commit enabled, reserves margin for both
+    /// legs first and places nothing unless both reservations succeed. If the
+    /// hedge leg then fails, the maker order is cancelled. Reservations are
+    /// released once both legs are confirmed or rolled back. The concurrent
+    /// engine skips the reservations and sends both legs at once.
+    ///
+    /// Every log line is emitted inside an `opportunity` span carrying the
+    /// opportunity's correlation ID.
     pub async fn execute_opportunity(
         &self,
         opportunity: &FuturesArbitrageOpportunity,
         bybit_connector: &dyn FuturesConnector,
         binance_connector: &dyn FuturesConnector,
     ) -> Result<()> {
+        let span = info_span!("opportunity", correlation_id = %opportunity.correlation_id);
+        self.execute_opportunity_inner(opportunity, bybit_connector, binance_connector)
+            .instrument(span)
+            .await
+    }
+
+    async fn execute_opportunity_inner(
+        &self,
+        opportunity: &FuturesArbitrageOpportunity,
+        bybit_connector: &dyn FuturesConnector,
+        binance_connector: &dyn FuturesConnector,
+    ) -> Result<()> {
+        self.check_deadline(opportunity).await?;
+        if self.config.execution.preview_only {
+            let plan = self.preview_opportunity(opportunity).await;
+            info!("Preview only, not executing {}: {:?}", opportunity.symbol, plan);
+            return Ok(());
+        }
+        self.check_post_fill_cooldown(&opportunity.symbol).await?;
+        self.claim_execution_slot(&opportunity.symbol).await?;
         info!("Executing futures arbitrage opportunity for {}", opportunity.symbol);
 
-        // Step 1: Place maker order on Bybit
-        let maker_order = FuturesOrder {
-            symbol: opportunity.symbol.clone(),
-            side: opportunity.maker_side,
-            position_side: Some(PositionSide::Both),
-            order_type: FuturesOrderType::Limit,
-            quantity: opportunity.quantity,
-            price: Some(opportunity.maker_price),
-            stop_price: None,
-            time_in_force: FuturesTimeInForce::GTX, // Post-only to ensure maker
-            reduce_only: false,
-            close_position: false,
-            client_order_id: Some(format!("maker_{}_{}", opportunity.symbol, chrono::Utc::now().timestamp_millis())),
-        };
+        info!("Sized legs for {}: maker {} {:.6} @ {:.2}, taker {} {:.6}",
+              opportunity.symbol, opportunity.maker_side, opportunity.quantity, opportunity.maker_price,
+              opportunity.taker_side, self.hedge_quantity(opportunity.quantity));
+        let maker_margin = opportunity.quantity * opportunity.maker_price;
+        let taker_margin = self.hedge_quantity(opportunity.quantity) * opportunity.taker_price;
+        let (mut maker_order, mut taker_order, hedge_slice) = self.prepare_legs(opportunity).await;
 
-        match bybit_connector.place_order(&maker_order).await {
-            Ok(maker_response) => {
-                info!("Maker order placed on Bybit: {}", maker_response.order_id);
+        self.ensure_margin_mode(opportunity.maker_exchange, &maker_order.symbol, bybit_connector).await?;
+        self.ensure_margin_mode(opportunity.taker_exchange, &taker_order.symbol, binance_connector).await?;
+        self.apply_position_mode(opportunity.maker_exchange, &mut maker_order, bybit_connector).await;
+        self.apply_position_mode(opportunity.taker_exchange, &mut taker_order, binance_connector).await;
 
-                // Step 2: Immediately place taker order on Binance to hedge
-                let taker_order = FuturesOrder {
-                    symbol: opportunity.symbol.clone(),
-                    side: opportunity.taker_side,
-                    position_side: Some(PositionSide::Both),
-                    order_type: FuturesOrderType::Market,
-                    quantity: opportunity.quantity,
-                    price: None,
-                    stop_price: None,
-                    time_in_force: FuturesTimeInForce::IOC,
-                    reduce_only: false,
-                    close_position: false,
-                    client_order_id: Some(format!("taker_{}_{}", opportunity.symbol, chrono::Utc::now().timestamp_millis())),
-                };
+        let engine = self.config.execution.execution_engine;
+        let reserve = engine == ExecutionEngine::Atomic && self.config.execution.two_phase_commit;
 
-                match binance_connector.place_order(&taker_order).await {
-                    Ok(taker_response) => {
-                        info!("Taker order placed on Binance: {}", taker_response.order_id);
-                        self.update_execution_statistics(opportunity).await;
-                        Ok(())
-                    }
-                    Err(e) => {
-                        error!("Failed to place taker order on Binance: {}", e);
-                        // TODO: Cancel the maker order on Bybit
-                        warn!("Need to cancel maker order {} on Bybit", maker_response.order_id);
-                        Err(e)
-                    }
-                }
+        // Phase 1: Reserve margin on both legs
+        if reserve {
+            self.reserve_margin(opportunity.maker_exchange, bybit_connector, maker_margin).await?;
+            if let Err(e) = self.reserve_margin(opportunity.taker_exchange, binance_connector, taker_margin).await {
+                self.release_margin(opportunity.maker_exchange, maker_margin).await;
+                warn!("Aborting {} before placing orders: {}", opportunity.symbol, e);
+                return Err(e);
             }
-            Err(e) => {
-                error!("Failed to place maker order on Bybit: {}", e);
-                Err(e)
+        } else {
+            self.check_margin(opportunity.maker_exchange, bybit_connector, maker_margin).await?;
+            self.check_margin(opportunity.taker_exchange, binance_connector, taker_margin).await?;
+        }
+
+        let guards = match self.check_last_look(opportunity).await {
+            Ok(()) => self.check_mark_deviation(opportunity).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = guards {
+            if reserve {
+                self.release_margin(opportunity.maker_exchange, maker_margin).await;
+                self.release_margin(opportunity.taker_exchange, taker_margin).await;
             }
+            return Err(e);
         }
-    }
 
-    /// Update execution statistics
-    async fn update_execution_statistics(&self, opportunity: &FuturesArbitrageOpportunity) {
-        let mut stats = self.statistics.write().await;
-        stats.opportunities_executed += 1;
-        stats.total_pnl += opportunity.expected_profit;
-        stats.total_maker_rebates += opportunity.maker_price * opportunity.quantity * opportunity.maker_fee.abs();
-        stats.total_taker_fees += opportunity.taker_price * opportunity.quantity * opportunity.taker_fee;
-        stats.total_volume += opportunity.quantity * opportunity.maker_price;
-        stats.last_execution = Some(chrono::Utc::now().timestamp());
+        // Count both legs against portfolio-wide limits before placing either
+        let fills = [
+            PortfolioFill {
+                market: Market::Futures,
+                exchange: opportunity.maker_exchange,
+                symbol: opportunity.symbol.clone(),
+                side: opportunity.maker_side,
+                quantity: opportunity.quantity,
+                price: opportunity.maker_price,
+            },
+            PortfolioFill {
+                market: Market::Futures,
+                exchange: opportunity.taker_exchange,
+                symbol: opportunity.symbol.clone(),
+                side: opportunity.taker_side,
+                quantity: self.hedge_quantity(opportunity.quantity),
+                price: opportunity.taker_price,
+            },
+        ];
+        if let Some(portfolio) = &self.portfolio {
+            if let Err(e) = portfolio.write().await.admit(&fills) {
+                if reserve {
+                    self.release_margin(opportunity.maker_exchange, maker_margin).await;
+                    self.release_margin(opportunity.taker_exchange, taker_margin).await;
+                }
+                return Err(e);
+            }
+        }
 
-        // Update average spread
-        if stats.opportunities_executed > 1 {
-            stats.avg_spread_bps = (stats.avg_spread_bps * (stats.opportunities_executed - 1) as f64 + opportunity.spread_bps) / stats.opportunities_executed as f64;
-        } else {
-            stats.avg_spread_bps = opportunity.spread_bps;
+        // Probe idle venues only once the execution has cleared every guard
+        let canaries = match self.send_canary(opportunity.maker_exchange, &opportunity.symbol, &maker_order, bybit_connector).await {
+            Ok(()) => self.send_canary(opportunity.taker_exchange, &opportunity.symbol, &taker_order, binance_connector).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = canaries {
+            if reserve {
+                self.release_margin(opportunity.maker_exchange, maker_margin).await;
+                self.release_margin(opportunity.taker_exchange, taker_margin).await;
+            }
+            if let Some(portfolio) = &self.portfolio {
+                portfolio.write().await.revert(&fills);
+            }
+            return Err(e);
         }
 
-        // Update success rate
-        stats.success_rate = (stats.opportunities_executed as f64 / stats.opportunities_detected as f64) * 100.0;
-    }
+        let baseline = match self.net_exposures(&opportunity.symbol, opportunity.maker_exchange, bybit_connector, opportunity.taker_exchange, binance_connector).await {
+            Ok(exposures) => Some(exposures.iter().map(|(_, _, _, net)| net).sum::<f64>()),
+            Err(e) => {
+                warn!("Failed to read positions for {} before execution, skipping its net position check: {}", opportunity.symbol, e);
+                None
+            }
+        };
 
-    /// Check if strategy is running
-    async fn is_running(&self) -> bool {
-        let state = self.state.read().await;
-        matches!(*state, FuturesStrategyState::Running)
-    }
+        // Phase 2: Fire both legs
+        let result = match engine {
+            ExecutionEngine::Atomic => {
+                self.place_both_legs(opportunity, &maker_order, &taker_order, hedge_slice, bybit_connector, binance_connector).await
+            }
+            ExecutionEngine::Concurrent => {
+                self.place_legs_concurrently(&maker_order, &taker_order, bybit_connector, binance_connector).await
+            }
+        };
 
-    /// Get strategy statistics
+        // Phase 3: Confirm (or roll back) and release reservations
+        if reserve {
+            self.release_margin(opportunity.maker_exchange, maker_margin).await;
+            self.release_margin(opportunity.taker_exchange, taker_margin).await;
+        }
+
+        let mut realized_pnl = None;
+        if let Ok((maker_responses, taker_average)) = &result {
+            let maker_average = maker_responses.iter().find_map(|response| response.average_price);
+            if let (Some(maker_average), Some(taker_average)) = (maker_average, taker_average) {
+                realized_pnl = Some(self.reconcile_fills(opportunity, maker_average, *taker_average).await);
+            }
+        }
+        // A failed execution was rolled back and meant no change, but a
+        // rollback can land after part of a leg filled, so it is checked too
+        if let Some(baseline) = baseline {
+            match &result {
+                Ok((maker_responses, _)) => self.track_neutrality(opportunity, baseline, maker_responses).await,
+                Err(_) => self.track_neutrality(opportunity, baseline, &[]).await,
+            }
+        }
+        let result = result.map(|_| ());
+        if let (Some(portfolio), Err(_)) = (&self.portfolio, &result) {
+            portfolio.write().await.revert(&fills);
+        }
+
+        if result.is_ok() {
+            self.update_execution_statistics(opportunity, realized_pnl).await;
+            let now = std::time::Instant::now();
+            self.last_fill_at.write().await.insert(opportunity.symbol.clone(), now);
+            {
+                let mut last_venue_trade_at = self.last_venue_trade_at.write().await;
+                last_venue_trade_at.insert(opportunity.maker_exchange, now);
+                last_venue_trade_at.insert(opportunity.taker_exchange, now);
+            }
+            if let Some(store) = &self.trade_store {
+                if let Err(e) = store.record_opportunity(opportunity) {
+                    warn!("Failed to persist opportunity {}: {}", opportunity.correlation_id, e);
+                }
+            }
+        }
+
+        if baseline.is_some() {
+            if let Err(e) = self.enforce_net_neutrality(&opportunity.symbol, bybit_connector, binance_connector).await {
+                warn!("Net position check failed for {}: {}", opportunity.symbol, e);
+            }
+        }
+        result
+    }
+
+    /// Register an execution's expected net position change for checking once its maker orders resolve.
+    ///
+    /// `maker_responses` is empty for a failed execution, which intends no change.
+    async fn track_neutrality(&self, opportunity: &FuturesArbitrageOpportunity, baseline: f64, maker_responses: &[FuturesOrderResponse]) {
+        let maker_sign = match opportunity.maker_side {
+            OrderSide::Buy => 1.0,
+            OrderSide::Sell => -1.0,
+        };
+        let intended_delta = if maker_responses.is_empty() {
+            0.0
+        } else {
+            maker_sign * opportunity.quantity * (1.0 - self.config.strategy.hedge_ratio)
+        };
+        let maker_orders = maker_responses.iter().map(|response| (response.symbol.clone(), response.order_id.clone()));
+
+        let mut pending = self.pending_neutrality.write().await;
+        let check = pending.entry(opportunity.symbol.clone()).or_insert_with(|| PendingNeutrality {
+            maker_exchange: opportunity.maker_exchange,
+            taker_exchange: opportunity.taker_exchange,
+            baseline,
+            intended_delta: 0.0,
+            maker_orders: Vec::new(),
+            checked_at: None,
+        });
+        check.intended_delta += intended_delta;
+        check.maker_orders.extend(maker_orders);
+    }
+
+    /// Symbols with a net position check due, with their maker and taker exchanges.
+    ///
+    /// A check is due at most once per second, so a caller may poll this on
+    /// every book update and pass each to [`Self::enforce_net_neutrality`].
+    pub async fn due_neutrality_checks(&self) -> Vec<(String, Exchange, Exchange)> {
+        self.pending_neutrality.read().await
+            .iter()
+            .filter(|(_, check)| check.checked_at.is_none_or(|at| at.elapsed() >= NEUTRALITY_POLL_INTERVAL))
+            .map(|(symbol, check)| (symbol.clone(), check.maker_exchange, check.taker_exchange))
+            .collect()
+    }
+
+    /// Signed net position in base-asset units of a symbol on each venue
+    async fn net_exposures<'a>(
+        &self,
+        symbol: &str,
+        maker_exchange: Exchange,
+        maker_connector: &'a dyn FuturesConnector,
+        taker_exchange: Exchange,
+        taker_connector: &'a dyn FuturesConnector,
+    ) -> Result<Vec<(Exchange, &'a dyn FuturesConnector, String, f64)>> {
+        let mut exposures = Vec::new();
+        for (exchange, connector) in [(maker_exchange, maker_connector), (taker_exchange, taker_connector)] {
+            let exchange_symbol = self.config.exchanges.exchange_symbol(symbol, &exchange.to_string());
+            let contract = self.contract_specs.read().await.get(&(exchange, exchange_symbol.clone())).cloned();
+            let net: f64 = connector
+                .get_positions()
+                .await?
+                .iter()
+                .filter(|position| position.symbol == exchange_symbol)
+                .map(|position| match position.side {
+                    PositionSide::Short => -position.size.abs(),
+                    PositionSide::Long => position.size.abs(),
+                    PositionSide::Both => position.size,
+                })
+                .map(|size| contract.as_ref().map_or(size, |contract| contract.base_from_venue(size)))
+                .sum();
+            exposures.push((exchange, connector, exchange_symbol, net));
+        }
+        Ok(exposures)
+    }
+
+    /// Deadline for an opportunity detected now, per `max_decision_latency_ms`
+    fn opportunity_deadline_ms(&self) -> Option<i64> {
+        match self.config.strategy.max_decision_latency_ms {
+            0 => None,
+            latency_ms => Some(self.clock.monotonic_ms() + latency_ms as i64),
+        }
+    }
+
+    /// Drop an opportunity whose deadline passed while it sat in the queue
+    async fn check_deadline(&self, opportunity: &FuturesArbitrageOpportunity) -> Result<()> {
+        let deadline_ms = match opportunity.deadline_ms {
+            Some(deadline_ms) => deadline_ms,
+            None => return Ok(()),
+        };
+
+        let overdue_ms = self.clock.monotonic_ms() - deadline_ms;
+        if overdue_ms > 0 {
+            self.statistics.write().await.expired_opportunities += 1;
+            warn!("Dropping {} opportunity {} ms past its deadline", opportunity.symbol, overdue_ms);
+            return Err(ArbitrageError::Trading(format!(
+                "Opportunity for {} expired {} ms before execution", opportunity.symbol, overdue_ms
+            )).into());
+        }
+        Ok(())
+    }
+
+    /// Re-check both legs against the latest books just before the maker order goes out.
+    ///
+    /// A maker sell is stale once the maker bid has risen past its price, a maker
+    /// buy once the maker ask has fallen below it; the taker leg is stale once
+    /// the price it crosses has moved away. Legs without a cached book pass.
+    async fn check_last_look(&self, opportunity: &FuturesArbitrageOpportunity) -> Result<()> {
+        let tolerance_bps = self.config.execution.last_look_tolerance_bps;
+        if tolerance_bps <= 0.0 {
+            return Ok(());
+        }
+
+        let market_data = self.market_data.read().await;
+        let book = |exchange: Exchange| market_data.get(&exchange).and_then(|books| books.get(&opportunity.symbol));
+        // Positive values are moves against us, in bps of the detected price
+        let adverse_bps = |detected: f64, latest: Option<f64>, against_when_higher: bool| {
+            latest.map_or(0.0, |latest| {
+                let moved = (latest - detected) / detected * 10000.0;
+                if against_when_higher { moved } else { -moved }
+            })
+        };
+        let maker_book = book(opportunity.maker_exchange);
+        let taker_book = book(opportunity.taker_exchange);
+        let (maker_move, taker_move) = match opportunity.maker_side {
+            OrderSide::Sell => (
+                adverse_bps(opportunity.maker_price, maker_book.and_then(|book| book.best_bid()), true),
+                adverse_bps(opportunity.taker_price, taker_book.and_then(|book| book.best_ask()), true),
+            ),
+            OrderSide::Buy => (
+                adverse_bps(opportunity.maker_price, maker_book.and_then(|book| book.best_ask()), false),
+                adverse_bps(opportunity.taker_price, taker_book.and_then(|book| book.best_bid()), false),
+            ),
+        };
+        drop(market_data);
+
+        if maker_move > tolerance_bps || taker_move > tolerance_bps {
+            self.statistics.write().await.last_look_aborts += 1;
+            warn!("Last look aborted {} {}: maker moved {:.2} bps, taker moved {:.2} bps against us (tolerance {} bps)",
+                  opportunity.symbol, opportunity.correlation_id, maker_move, taker_move, tolerance_bps);
+            return Err(ArbitrageError::Trading(format!(
+                "Market for {} moved beyond the last-look tolerance before the maker order", opportunity.symbol
+            )).into());
+        }
+        Ok(())
+    }
+
+    /// Abort when either leg is priced more than `max_mark_deviation_bps` from its
+    /// venue's mark price. Legs without a fresh mark are not checked.
+    async fn check_mark_deviation(&self, opportunity: &FuturesArbitrageOpportunity) -> Result<()> {
+        let max_deviation_bps = self.config.execution.max_mark_deviation_bps;
+        if max_deviation_bps <= 0.0 {
+            return Ok(());
+        }
+
+        for (exchange, price) in [
+            (opportunity.maker_exchange, opportunity.maker_price),
+            (opportunity.taker_exchange, opportunity.taker_price),
+        ] {
+            let venue_symbol = self.config.exchanges.exchange_symbol(&opportunity.symbol, &exchange.to_string());
+            let mark_price = match self.fresh_mark_price(exchange, &venue_symbol).await {
+                Some(mark_price) if mark_price > 0.0 => mark_price,
+                _ => continue,
+            };
+            let deviation_bps = (price - mark_price).abs() / mark_price * 10000.0;
+            if deviation_bps > max_deviation_bps {
+                self.statistics.write().await.mark_deviation_aborts += 1;
+                warn!("Mark guard aborted {} {}: {} price {:.8} is {:.2} bps from mark {:.8} (max {} bps)",
+                      opportunity.symbol, opportunity.correlation_id, exchange, price, deviation_bps, mark_price, max_deviation_bps);
+                return Err(ArbitrageError::Trading(format!(
+                    "{} price for {} deviates {:.2} bps from the mark price", exchange, opportunity.symbol, deviation_bps
+                )).into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Skip a symbol while its `post_fill_cooldown_ms` window after the last fill is open,
+    /// letting the book settle from our own impact
+    async fn check_post_fill_cooldown(&self, symbol: &str) -> Result<()> {
+        let cooldown = std::time::Duration::from_millis(self.config.strategy.post_fill_cooldown_ms);
+        let last_fill = self.last_fill_at.read().await.get(symbol).copied();
+
+        if let Some(last_fill) = last_fill {
+            let elapsed = last_fill.elapsed();
+            if elapsed < cooldown {
+                let remaining_ms = (cooldown - elapsed).as_millis();
+                debug!("Skipping {} for {} ms: post-fill cooldown", symbol, remaining_ms);
+                return Err(ArbitrageError::Trading(format!(
+                    "Execution on {} skipped for {} ms by post-fill cooldown", symbol, remaining_ms
+                )).into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Enforce `min_execution_interval_ms` across all symbols, deferring the
+    /// opportunity if the previous execution started too recently
+    async fn claim_execution_slot(&self, symbol: &str) -> Result<()> {
+        let interval = std::time::Duration::from_millis(self.config.strategy.min_execution_interval_ms);
+        let mut last_execution_at = self.last_execution_at.write().await;
+
+        if let Some(last) = *last_execution_at {
+            let elapsed = last.elapsed();
+            if elapsed < interval {
+                let remaining_ms = (interval - elapsed).as_millis();
+                info!("Deferring {} for {} ms: minimum execution interval not elapsed", symbol, remaining_ms);
+                return Err(ArbitrageError::Trading(format!(
+                    "Execution deferred for {} ms by minimum execution interval", remaining_ms
+                )).into());
+            }
+        }
+
+        *last_execution_at = Some(std::time::Instant::now());
+        Ok(())
+    }
+
+    /// Check that the executions on a symbol left the net position where they meant to.
+    ///
+    /// Runs once every maker order of the symbol's pending check has resolved;
+    /// until then it returns `Ok(None)`. The net base-asset position across both
+    /// venues is compared with its value before the executions plus the change
+    /// their hedge ratio intended, so positions held beforehand are ignored.
+    /// Returns the residual when it exceeds `net_position_tolerance`. With
+    /// `flatten_residual` enabled, a reduce-only market order closes the
+    /// residual on the exchange holding the excess.
+    ///
+    /// `maker_connector` and `taker_connector` trade the check's maker and
+    /// taker exchanges, as returned by [`Self::due_neutrality_checks`].
+    pub async fn enforce_net_neutrality(
+        &self,
+        symbol: &str,
+        maker_connector: &dyn FuturesConnector,
+        taker_connector: &dyn FuturesConnector,
+    ) -> Result<Option<f64>> {
+        let Some(check) = self.pending_neutrality.read().await.get(symbol).cloned() else {
+            return Ok(None);
+        };
+        if let Some(pending) = self.pending_neutrality.write().await.get_mut(symbol) {
+            pending.checked_at = Some(std::time::Instant::now());
+        }
+        for (exchange_symbol, order_id) in &check.maker_orders {
+            let status = maker_connector.get_order_status(exchange_symbol, order_id).await?.status;
+            if !matches!(status, FuturesOrderStatus::Filled | FuturesOrderStatus::Canceled | FuturesOrderStatus::Rejected | FuturesOrderStatus::Expired) {
+                debug!("Maker order {} for {} still {:?}, deferring the net position check", order_id, symbol, status);
+                return Ok(None);
+            }
+        }
+        self.pending_neutrality.write().await.remove(symbol);
+
+        let exposures = self.net_exposures(symbol, check.maker_exchange, maker_connector, check.taker_exchange, taker_connector).await?;
+        let net: f64 = exposures.iter().map(|(_, _, _, net)| net).sum();
+        let residual = net - check.baseline - check.intended_delta;
+        if residual.abs() <= self.config.risk.net_position_tolerance {
+            return Ok(None);
+        }
+
+        error!("Residual net exposure of {:.6} on {} exceeds tolerance {:.6}",
+               residual, symbol, self.config.risk.net_position_tolerance);
+        {
+            let mut stats = self.statistics.write().await;
+            stats.residual_exposure_alarms += 1;
+        }
+
+        if self.config.risk.flatten_residual {
+            // Flatten where the excess sits: the largest position on the residual's side
+            let excess = exposures
+                .iter()
+                .filter(|(_, _, _, net)| net.signum() == residual.signum())
+                .max_by(|a, b| a.3.abs().partial_cmp(&b.3.abs()).unwrap_or(std::cmp::Ordering::Equal));
+            if let Some((exchange, connector, exchange_symbol, _)) = excess {
+                let side = if residual > 0.0 { OrderSide::Sell } else { OrderSide::Buy };
+                let mode = self.position_mode(*exchange, exchange_symbol, *connector).await;
+                let order = FuturesOrder {
+                    symbol: exchange_symbol.clone(),
+                    side,
+                    position_side: Some(mode.position_side(side, true)),
+                    order_type: FuturesOrderType::Market,
+                    quantity: self.venue_quantity(*exchange, exchange_symbol, residual.abs()).await,
+                    price: None,
+                    stop_price: None,
+                    time_in_force: FuturesTimeInForce::IOC,
+                    reduce_only: true,
+                    close_position: false,
+                    client_order_id: Some(format!("flatten_{}_{}", symbol, chrono::Utc::now().timestamp_millis())),
+                    take_profit: None,
+                    stop_loss: None,
+                };
+                connector.place_order(&order).await?;
+                warn!("Flattened residual {:.6} {} on {}", residual, symbol, exchange);
+            }
+        }
+
+        Ok(Some(residual))
+    }
+
+    /// Place the maker leg on Bybit then hedge on Binance, cancelling the maker if the hedge fails.
+    ///
+    /// With `ladder_levels` above 1 the maker leg is laddered across successive
+    /// ticks. Returns the maker order responses and the hedge's exchange-reported
+    /// average fill price, where known.
+    async fn place_both_legs(
+        &self,
+        opportunity: &FuturesArbitrageOpportunity,
+        maker_order: &FuturesOrder,
+        taker_order: &FuturesOrder,
+        hedge_slice: Option<f64>,
+        bybit_connector: &dyn FuturesConnector,
+        binance_connector: &dyn FuturesConnector,
+    ) -> Result<(Vec<FuturesOrderResponse>, Option<f64>)> {
+        let maker_rungs = self.maker_rungs(opportunity, maker_order).await;
+        let mut maker_responses = Vec::with_capacity(maker_rungs.len());
+        for rung in &maker_rungs {
+            match self.timed_place_order(Exchange::Bybit, rung, bybit_connector).await {
+                Ok(response) => {
+                    info!("Maker order placed on Bybit: {}", response.order_id);
+                    maker_responses.push(response);
+                }
+                Err(e) => {
+                    error!("Failed to place maker order on Bybit: {}", e);
+                    for (rung, response) in maker_rungs.iter().zip(&maker_responses) {
+                        self.roll_back_maker(rung, response, bybit_connector).await;
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        let hedge_result = match hedge_slice {
+            Some(slice) if slice < taker_order.quantity => self
+                .place_sliced_hedge(opportunity, taker_order, slice, binance_connector)
+                .await
+                .map(|filled| {
+                    info!("Sliced taker hedge filled {:.6} on Binance", filled);
+                    None
+                }),
+            _ => self
+                .timed_place_order(Exchange::Binance, taker_order, binance_connector)
+                .await
+                .map(|taker_response| {
+                    info!("Taker order placed on Binance: {} (filled {:.6})",
+                          taker_response.order_id, taker_response.filled_quantity);
+                    taker_response.average_price
+                }),
+        };
+
+        match hedge_result {
+            Ok(taker_average) => Ok((maker_responses, taker_average)),
+            Err(e) => {
+                error!("Failed to place taker order on Binance: {}", e);
+                for (rung, response) in maker_rungs.iter().zip(&maker_responses) {
+                    self.roll_back_maker(rung, response, bybit_connector).await;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Maker orders to rest for an opportunity: the ladder's rungs when
+    /// `ladder_levels` is above 1 and the venue's contract spec is cached,
+    /// otherwise the single maker order
+    async fn maker_rungs(&self, opportunity: &FuturesArbitrageOpportunity, maker_order: &FuturesOrder) -> Vec<FuturesOrder> {
+        if self.config.execution.ladder_levels <= 1 {
+            return vec![maker_order.clone()];
+        }
+        let contract = match self.contract_specs.read().await.get(&(opportunity.maker_exchange, maker_order.symbol.clone())) {
+            Some(contract) => contract.clone(),
+            None => {
+                warn!("No contract spec for {} on {}, placing a single maker order instead of a ladder",
+                      maker_order.symbol, opportunity.maker_exchange);
+                return vec![maker_order.clone()];
+            }
+        };
+        match self.build_maker_ladder(maker_order, contract.tick_size, contract.lot_size) {
+            Ok(ladder) => ladder
+                .orders
+                .into_iter()
+                .map(|rung| FuturesOrder {
+                    quantity: rung.quantity,
+                    price: Some(rung.price),
+                    client_order_id: rung.client_order_id,
+                    ..maker_order.clone()
+                })
+                .collect(),
+            Err(e) => {
+                warn!("Failed to build maker ladder for {}, placing a single maker order: {}", maker_order.symbol, e);
+                vec![maker_order.clone()]
+            }
+        }
+    }
+
+    /// Send the maker leg on Bybit and the hedge on Binance at the same time.
+    ///
+    /// A failed hedge cancels the maker; a failed maker unwinds whatever the
+    /// hedge already did. The hedge is never sliced here.
+    async fn place_legs_concurrently(
+        &self,
+        maker_order: &FuturesOrder,
+        taker_order: &FuturesOrder,
+        bybit_connector: &dyn FuturesConnector,
+        binance_connector: &dyn FuturesConnector,
+    ) -> Result<(Vec<FuturesOrderResponse>, Option<f64>)> {
+        let (maker_result, taker_result) = tokio::join!(
+            self.timed_place_order(Exchange::Bybit, maker_order, bybit_connector),
+            self.timed_place_order(Exchange::Binance, taker_order, binance_connector),
+        );
+        match (maker_result, taker_result) {
+            (Ok(maker_response), Ok(taker_response)) => {
+                info!("Maker order placed on Bybit: {}, taker order placed on Binance: {} (filled {:.6})",
+                      maker_response.order_id, taker_response.order_id, taker_response.filled_quantity);
+                Ok((vec![maker_response], taker_response.average_price))
+            }
+            (Ok(maker_response), Err(e)) => {
+                error!("Failed to place taker order on Binance: {}", e);
+                self.roll_back_maker(maker_order, &maker_response, bybit_connector).await;
+                Err(e)
+            }
+            (Err(e), Ok(taker_response)) => {
+                error!("Failed to place maker order on Bybit: {}", e);
+                self.unwind_hedge(taker_order, &taker_response, binance_connector).await;
+                Err(e)
+            }
+            (Err(e), Err(taker_err)) => {
+                error!("Failed to place both legs: maker {}, taker {}", e, taker_err);
+                Err(e)
+            }
+        }
+    }
+
+    /// Place an order and record its round trip as the venue's latency when it is acknowledged
+    async fn timed_place_order(&self, exchange: Exchange, order: &FuturesOrder, connector: &dyn FuturesConnector) -> Result<FuturesOrderResponse> {
+        let sent_at = std::time::Instant::now();
+        let response = connector.place_order(order).await?;
+        self.record_latency(exchange, sent_at.elapsed()).await;
+        Ok(response)
+    }
+
+    /// Cancel a maker order whose hedge failed
+    async fn roll_back_maker(&self, maker_order: &FuturesOrder, maker_response: &FuturesOrderResponse, connector: &dyn FuturesConnector) {
+        match connector.cancel_order(&maker_order.symbol, &maker_response.order_id).await {
+            // The cancel can land after part of the maker traded; that fill is unhedged
+            Ok(cancelled) if cancelled.filled_quantity > 0.0 => {
+                warn!("Maker order {} filled {:.6} {} before the rollback cancel ({:?}); leaving residual for the net position check",
+                      cancelled.order_id, cancelled.filled_quantity, maker_order.symbol, cancelled.status);
+            }
+            Ok(_) => {}
+            Err(cancel_err) => {
+                error!("Failed to roll back maker order {} on Bybit: {}", maker_response.order_id, cancel_err);
+            }
+        }
+    }
+
+    /// Undo a hedge whose maker leg failed: cancel any unfilled remainder and
+    /// close the filled part with a reduce-only market order
+    async fn unwind_hedge(&self, taker_order: &FuturesOrder, taker_response: &FuturesOrderResponse, connector: &dyn FuturesConnector) {
+        if taker_order.quantity - taker_response.filled_quantity > LOT_EPSILON {
+            if let Err(e) = connector.cancel_order(&taker_order.symbol, &taker_response.order_id).await {
+                warn!("Failed to cancel unhedged taker order {} on Binance: {}", taker_response.order_id, e);
+            }
+        }
+        self.close_filled(taker_order, taker_response.filled_quantity, connector).await;
+    }
+
+    /// Close `filled` of an order with a reduce-only market order
+    async fn close_filled(&self, order: &FuturesOrder, filled: f64, connector: &dyn FuturesConnector) {
+        if filled <= 0.0 {
+            return;
+        }
+        let unwind = FuturesOrder {
+            side: match order.side {
+                OrderSide::Buy => OrderSide::Sell,
+                OrderSide::Sell => OrderSide::Buy,
+            },
+            order_type: FuturesOrderType::Market,
+            quantity: filled,
+            price: None,
+            time_in_force: FuturesTimeInForce::IOC,
+            reduce_only: true,
+            client_order_id: order.client_order_id.as_ref().map(|id| format!("unwind_{}", id)),
+            take_profit: None,
+            stop_loss: None,
+            ..order.clone()
+        };
+        match connector.place_order(&unwind).await {
+            Ok(_) => warn!("Unwound {:.6} {} filled by {:?}", unwind.quantity, order.symbol, order.client_order_id),
+            Err(e) => error!("Failed to unwind {:.6} {} filled by {:?}: {}", unwind.quantity, order.symbol, order.client_order_id, e),
+        }
+    }
+
+    /// Probe a venue with a canary-sized copy of `order` unless it traded within `canary_idle_secs`.
+    ///
+    /// The canary is a post-only limit rounded up to the venue's lot step and
+    /// priced on the passive side of `symbol`'s book (the order's own price for
+    /// the maker leg). It is cancelled straight away, and anything it filled is
+    /// closed with a reduce-only market order.
+    async fn send_canary(&self, exchange: Exchange, symbol: &str, order: &FuturesOrder, connector: &dyn FuturesConnector) -> Result<()> {
+        let canary_size = self.config.execution.canary_size;
+        if canary_size <= 0.0 {
+            return Ok(());
+        }
+        let idle = std::time::Duration::from_secs(self.config.execution.canary_idle_secs);
+        if let Some(last_trade) = self.last_venue_trade_at.read().await.get(&exchange) {
+            if last_trade.elapsed() < idle {
+                return Ok(());
+            }
+        }
+
+        let passive_price = {
+            let market_data = self.market_data.read().await;
+            market_data.get(&exchange).and_then(|books| books.get(symbol)).and_then(|book| match order.side {
+                OrderSide::Buy => book.best_bid(),
+                OrderSide::Sell => book.best_ask(),
+            })
+        };
+        let price = order.price.or(passive_price).ok_or_else(|| {
+            ArbitrageError::Trading(format!("No {} book to price a canary for {}, aborting full-size execution", exchange, symbol))
+        })?;
+        let lot_size = self.contract_specs.read().await
+            .get(&(exchange, order.symbol.clone()))
+            .map_or(0.0, |contract| contract.lot_size);
+        let quantity = self.venue_quantity(exchange, &order.symbol, canary_size).await;
+        let canary = FuturesOrder {
+            order_type: FuturesOrderType::Limit,
+            quantity: round_to_lot(quantity, lot_size, round_up).max(lot_size),
+            price: Some(price),
+            time_in_force: FuturesTimeInForce::GTX,
+            client_order_id: order.client_order_id.as_ref().map(|id| format!("canary_{}", id)),
+            take_profit: None,
+            stop_loss: None,
+            ..order.clone()
+        };
+        let response = connector.place_order(&canary).await.map_err(|e| {
+            ArbitrageError::Trading(format!("Canary order on {} failed, aborting full-size execution: {}", exchange, e))
+        })?;
+        let filled = match connector.cancel_order(&canary.symbol, &response.order_id).await {
+            Ok(cancelled) => cancelled.filled_quantity.max(response.filled_quantity),
+            Err(e) => {
+                warn!("Failed to cancel canary order {} on {}: {}", response.order_id, exchange, e);
+                response.filled_quantity
+            }
+        };
+        self.close_filled(&canary, filled, connector).await;
+
+        info!("Canary order {} confirmed on {}", response.order_id, exchange);
+        self.last_venue_trade_at.write().await.insert(exchange, std::time::Instant::now());
+        Ok(())
+    }
+
+    /// Apply the configured margin mode to a symbol once per exchange
+    async fn ensure_margin_mode(&self, exchange: Exchange, symbol: &str, connector: &dyn FuturesConnector) -> Result<()> {
+        let key = (exchange, symbol.to_string());
+        if self.margin_mode_applied.read().await.contains(&key) {
+            return Ok(());
+        }
+
+        let mode = self.config.execution.margin_mode;
+        connector.set_margin_mode(symbol, mode).await?;
+        debug!("Margin mode for {} on {} set to {}", symbol, exchange, mode);
+        self.margin_mode_applied.write().await.insert(key);
+        Ok(())
+    }
+
+    /// Position mode of a symbol's account on an exchange.
+    ///
+    /// A mode configured for the exchange wins; otherwise the account is
+    /// queried once per symbol. Venues whose mode cannot be detected are
+    /// treated as one-way and queried again next time.
+    async fn position_mode(&self, exchange: Exchange, symbol: &str, connector: &dyn FuturesConnector) -> PositionMode {
+        if let Some(mode) = self.config.execution.position_modes.get(&exchange.to_string()) {
+            return *mode;
+        }
+        let key = (exchange, symbol.to_string());
+        if let Some(mode) = self.position_modes.read().await.get(&key) {
+            return *mode;
+        }
+
+        match connector.get_position_mode(symbol).await {
+            Ok(mode) => {
+                debug!("Detected {} position mode for {} on {}", mode, symbol, exchange);
+                self.position_modes.write().await.insert(key, mode);
+                mode
+            }
+            Err(e) => {
+                warn!("Could not detect position mode for {} on {}, assuming one-way: {}", symbol, exchange, e);
+                PositionMode::OneWay
+            }
+        }
+    }
+
+    /// Set an order's position side for the mode of its exchange
+    async fn apply_position_mode(&self, exchange: Exchange, order: &mut FuturesOrder, connector: &dyn FuturesConnector) {
+        let mode = self.position_mode(exchange, &order.symbol, connector).await;
+        order.position_side = Some(mode.position_side(order.side, order.reduce_only));
+    }
+
+    /// Child order size for slicing the hedge, or `None` to send it as a single order
+    async fn hedge_slice_size(&self, opportunity: &FuturesArbitrageOpportunity) -> Option<f64> {
+        let fraction = self.config.execution.hedge_slice_depth_fraction;
+        if fraction <= 0.0 {
+            return None;
+        }
+
+        let market_data = self.market_data.read().await;
+        let book = market_data.get(&opportunity.taker_exchange)?.get(&opportunity.symbol)?;
+        let visible = match opportunity.taker_side {
+            OrderSide::Buy => book.best_ask_quantity(),
+            OrderSide::Sell => book.best_bid_quantity(),
+        }?;
+        Some(visible * fraction).filter(|slice| *slice > 0.0)
+    }
+
+    /// Hedge child size in the taker venue's unit, rounded down to its lot step
+    /// (at least one lot), or `None` to send the hedge as a single order
+    async fn venue_hedge_slice(&self, opportunity: &FuturesArbitrageOpportunity, venue_symbol: &str) -> Option<f64> {
+        let slice = self.hedge_slice_size(opportunity).await?;
+        let slice = self.venue_quantity(opportunity.taker_exchange, venue_symbol, slice).await;
+        let lot_size = self.contract_specs.read().await
+            .get(&(opportunity.taker_exchange, venue_symbol.to_string()))
+            .map_or(0.0, |contract| contract.lot_size);
+        Some(round_to_lot(slice, lot_size, round_down).max(lot_size))
+    }
+
+    /// Execute the hedge as a sequence of child orders, pausing between them and
+    /// re-sizing each against the current taker book. Returns the cumulative filled quantity.
+    ///
+    /// A child whose response does not show it complete has its fill confirmed
+    /// with the exchange. If a child fills nothing, the remainder is sent as one
+    /// order; if that cannot finish the hedge, the fills so far are unwound.
+    async fn place_sliced_hedge(
+        &self,
+        opportunity: &FuturesArbitrageOpportunity,
+        order: &FuturesOrder,
+        first_slice: f64,
+        connector: &dyn FuturesConnector,
+    ) -> Result<f64> {
+        let pause = std::time::Duration::from_millis(self.config.execution.hedge_slice_pause_ms);
+        let mut filled = 0.0;
+        let mut slice = first_slice;
+        let mut child_index = 0;
+
+        while order.quantity - filled > LOT_EPSILON {
+            if child_index > 0 {
+                tokio::time::sleep(pause).await;
+                if let Some(resized) = self.venue_hedge_slice(opportunity, &order.symbol).await {
+                    slice = resized;
+                }
+            }
+
+            let remaining = order.quantity - filled;
+            let child = FuturesOrder {
+                quantity: slice.min(remaining),
+                client_order_id: order.client_order_id.as_ref().map(|id| format!("{}_{}", id, child_index)),
+                ..order.clone()
+            };
+            let child_filled = match self.confirmed_fill(&child, connector).await {
+                Ok(child_filled) => child_filled,
+                Err(e) => {
+                    self.close_filled(order, filled, connector).await;
+                    return Err(e);
+                }
+            };
+            debug!("Hedge child {} for {}: {:.6} of {:.6} filled",
+                   child_index, order.symbol, child_filled, child.quantity);
+            filled += child_filled;
+            child_index += 1;
+
+            if child_filled <= 0.0 {
+                warn!("Hedge child for {} filled nothing, sending the remaining {:.6} as one order", order.symbol, remaining);
+                let rest = FuturesOrder {
+                    quantity: remaining,
+                    client_order_id: order.client_order_id.as_ref().map(|id| format!("{}_rest", id)),
+                    ..order.clone()
+                };
+                filled += self.confirmed_fill(&rest, connector).await.unwrap_or_else(|e| {
+                    warn!("Failed to finish hedge for {}: {}", order.symbol, e);
+                    0.0
+                });
+                if order.quantity - filled > LOT_EPSILON {
+                    self.close_filled(order, filled, connector).await;
+                    return Err(ArbitrageError::Trading(format!(
+                        "Hedge for {} stalled after filling {:.6} of {:.6}; unwound", order.symbol, filled, order.quantity
+                    )).into());
+                }
+            }
+        }
+
+        Ok(filled)
+    }
+
+    /// Place an order and return its filled quantity, asking the exchange when
+    /// the placement response leaves the fill open
+    async fn confirmed_fill(&self, order: &FuturesOrder, connector: &dyn FuturesConnector) -> Result<f64> {
+        let response = connector.place_order(order).await?;
+        let settled = order.quantity - response.filled_quantity <= LOT_EPSILON || matches!(
+            response.status,
+            FuturesOrderStatus::Filled | FuturesOrderStatus::Canceled | FuturesOrderStatus::Rejected | FuturesOrderStatus::Expired
+        );
+        if settled {
+            return Ok(response.filled_quantity);
+        }
+        Ok(connector.get_order_status(&order.symbol, &response.order_id).await?.filled_quantity)
+    }
+
+    /// Reserve margin on an exchange, failing if available balance minus the
+    /// configured minimum reserve and existing reservations is short
+    async fn reserve_margin(&self, exchange: Exchange, connector: &dyn FuturesConnector, amount: f64) -> Result<()> {
+        let account = connector.get_account_info().await?;
+        let mut reserved = self.reserved_margin.write().await;
+        let already_reserved = reserved.get(&exchange).copied().unwrap_or(0.0);
+        self.config.risk.check_reserve(&exchange.to_string(), MARGIN_ASSET, account.available_balance - already_reserved, amount)?;
+
+        *reserved.entry(exchange).or_insert(0.0) += amount;
+        debug!("Reserved {:.2} margin on {}", amount, exchange);
+        Ok(())
+    }
+
+    /// Check an exchange's available margin covers an order without dipping into
+    /// its minimum reserve; venues without a reserve are not queried
+    async fn check_margin(&self, exchange: Exchange, connector: &dyn FuturesConnector, amount: f64) -> Result<()> {
+        if self.config.risk.min_reserve(&exchange.to_string(), MARGIN_ASSET) <= 0.0 {
+            return Ok(());
+        }
+        let account = connector.get_account_info().await?;
+        self.config.risk.check_reserve(&exchange.to_string(), MARGIN_ASSET, account.available_balance, amount)
+    }
+
+    /// Release a previously reserved margin amount
+    async fn release_margin(&self, exchange: Exchange, amount: f64) {
+        let mut reserved = self.reserved_margin.write().await;
+        if let Some(current) = reserved.get_mut(&exchange) {
+            *current = (*current - amount).max(0.0);
+        }
+    }
+
+    /// Orders an opportunity sends: sized, converted to each venue's quantity
+    /// unit, and with the maker price improved; plus the hedge slice size.
+    ///
+    /// With both contract specs cached, the legs are rounded to their lots by
+    /// [`size_legs`](Self::size_legs) so the configured `RoundingMode` applies.
+    async fn prepare_legs(&self, opportunity: &FuturesArbitrageOpportunity) -> (FuturesOrder, FuturesOrder, Option<f64>) {
+        let mut maker_order = self.build_maker_order(opportunity);
+        let mut taker_order = self.build_taker_order(opportunity);
+
+        maker_order.price = Some(self.improved_maker_price(opportunity, &maker_order.symbol).await);
+
+        let contracts = {
+            let specs = self.contract_specs.read().await;
+            specs.get(&(opportunity.maker_exchange, maker_order.symbol.clone())).cloned()
+                .zip(specs.get(&(opportunity.taker_exchange, taker_order.symbol.clone())).cloned())
+        };
+        match contracts {
+            Some((maker, hedge)) => {
+                let (maker_contracts, hedge_contracts) = self.size_legs(&maker, &hedge, maker.contracts_for_base(opportunity.quantity));
+                maker_order.quantity = maker.venue_quantity(maker.base_quantity(maker_contracts));
+                taker_order.quantity = hedge.venue_quantity(hedge.base_quantity(hedge_contracts));
+            }
+            None => {
+                // Translate canonical base-unit sizes to each venue's order unit
+                maker_order.quantity = self.venue_quantity(opportunity.maker_exchange, &maker_order.symbol, maker_order.quantity).await;
+                taker_order.quantity = self.venue_quantity(opportunity.taker_exchange, &taker_order.symbol, taker_order.quantity).await;
+            }
+        }
+        let hedge_slice = self.venue_hedge_slice(opportunity, &taker_order.symbol).await;
+        (maker_order, taker_order, hedge_slice)
+    }
+
+    /// Orders an opportunity would send and their projected fills, without placing anything.
+    ///
+    /// The maker is assumed to fill in full at its limit price; the taker is
+    /// projected by walking the current taker book.
+    pub async fn preview_opportunity(&self, opportunity: &FuturesArbitrageOpportunity) -> ExecutionPlan {
+        let (maker_order, taker_order, hedge_slice) = self.prepare_legs(opportunity).await;
+        let maker_price = maker_order.price.unwrap_or(opportunity.maker_price);
+        let hedge_quantity = self.hedge_quantity(opportunity.quantity);
+
+        let (projected_taker_quantity, projected_taker_price) = {
+            let market_data = self.market_data.read().await;
+            match market_data.get(&opportunity.taker_exchange).and_then(|books| books.get(&opportunity.symbol)) {
+                Some(book) => walk_book(book, opportunity.taker_side, hedge_quantity),
+                None => (0.0, None),
+            }
+        };
+        let projected_taker_price = projected_taker_price.unwrap_or(opportunity.taker_price);
+        let adverse = match opportunity.taker_side {
+            OrderSide::Buy => projected_taker_price - opportunity.taker_price,
+            OrderSide::Sell => opportunity.taker_price - projected_taker_price,
+        };
+
+        let gross = match opportunity.maker_side {
+            OrderSide::Sell => maker_price * opportunity.quantity - projected_taker_price * projected_taker_quantity,
+            OrderSide::Buy => projected_taker_price * projected_taker_quantity - maker_price * opportunity.quantity,
+        };
+        let maker_rebate = maker_price * opportunity.quantity * opportunity.maker_fee.abs();
+        let taker_cost = projected_taker_price * projected_taker_quantity * opportunity.taker_fee;
+
+        ExecutionPlan {
+            correlation_id: opportunity.correlation_id.clone(),
+            maker_exchange: opportunity.maker_exchange,
+            maker_order,
+            taker_exchange: opportunity.taker_exchange,
+            taker_order,
+            hedge_slice,
+            projected_taker_quantity,
+            projected_taker_price,
+            projected_slippage_bps: adverse / opportunity.taker_price * 10000.0,
+            projected_profit: gross + maker_rebate - taker_cost,
+        }
+    }
+
+    /// Build the post-only maker order for an opportunity
+    pub fn build_maker_order(&self, opportunity: &FuturesArbitrageOpportunity) -> FuturesOrder {
+        let execution = &self.config.execution;
+        // A long entry profits above its price, a short entry below
+        let direction = match opportunity.maker_side {
+            OrderSide::Buy => 1.0,
+            OrderSide::Sell => -1.0,
+        };
+        let trigger = |bps: f64, sign: f64| (bps > 0.0).then(|| opportunity.maker_price * (1.0 + sign * direction * bps / 10000.0));
+        FuturesOrder {
+            symbol: self.config.exchanges.exchange_symbol(&opportunity.symbol, &opportunity.maker_exchange.to_string()),
+            side: opportunity.maker_side,
+            position_side: Some(PositionSide::Both),
+            order_type: FuturesOrderType::Limit,
+            quantity: opportunity.quantity,
+            price: Some(opportunity.maker_price),
+            stop_price: None,
+            time_in_force: FuturesTimeInForce::GTX, // Post-only to ensure maker
+            reduce_only: false,
+            close_position: false,
+            client_order_id: Some(format!("maker_{}", opportunity.correlation_id)),
+            take_profit: trigger(execution.maker_take_profit_bps, 1.0),
+            stop_loss: trigger(execution.maker_stop_loss_bps, -1.0),
+        }
+    }
+
+    /// Build the taker hedge order, scaled by the configured hedge ratio
+    pub fn build_taker_order(&self, opportunity: &FuturesArbitrageOpportunity) -> FuturesOrder {
+        FuturesOrder {
+            symbol: self.config.exchanges.exchange_symbol(&opportunity.symbol, &opportunity.taker_exchange.to_string()),
+            side: opportunity.taker_side,
+            position_side: Some(PositionSide::Both),
+            order_type: FuturesOrderType::Market,
+            quantity: self.hedge_quantity(opportunity.quantity),
+            price: None,
+            stop_price: None,
+            time_in_force: FuturesTimeInForce::IOC,
+            reduce_only: false,
+            close_position: false,
+            client_order_id: Some(format!("taker_{}", opportunity.correlation_id)),
+            take_profit: None,
+            stop_loss: None,
+        }
+    }
+
+    /// Hedge leg size for a given maker leg size
+    pub fn hedge_quantity(&self, maker_quantity: f64) -> f64 {
+        maker_quantity * self.config.strategy.hedge_ratio
+    }
+
+    /// Hedge leg contract quantity for a maker fill when the two contracts have different multipliers
+    pub fn hedge_contract_quantity(&self, maker: &FuturesContract, hedge: &FuturesContract, maker_contracts: f64) -> f64 {
+        hedge.contracts_for_base(self.hedge_quantity(maker.base_quantity(maker_contracts)))
+    }
+
+    /// Lot-valid maker and hedge contract quantities for a desired maker size,
+    /// rounded per the configured `RoundingMode`
+    pub fn size_legs(&self, maker: &FuturesContract, hedge: &FuturesContract, maker_contracts: f64) -> (f64, f64) {
+        let (maker_rounding, hedge_rounding): (LotRounding, LotRounding) = match self.config.execution.rounding_mode {
+            RoundingMode::Down => (round_down, round_down),
+            RoundingMode::Nearest => (f64::round, f64::round),
+            RoundingMode::TowardHedge => (round_down, round_up),
+        };
+
+        let maker_quantity = round_to_lot(maker_contracts, maker.lot_size, maker_rounding);
+        let hedge_target = self.hedge_contract_quantity(maker, hedge, maker_quantity);
+        let hedge_quantity = round_to_lot(hedge_target, hedge.lot_size, hedge_rounding);
+        (maker_quantity, hedge_quantity)
+    }
+
+    /// Validate that the maker and hedge instruments share the same base asset
+    pub fn validate_hedge_pair(maker: &FuturesContract, hedge: &FuturesContract) -> Result<()> {
+        if maker.base_asset != hedge.base_asset {
+            return Err(ArbitrageError::Config(format!(
+                "Hedge instrument {} (base {}) does not share base asset with {} (base {})",
+                hedge.symbol, hedge.base_asset, maker.symbol, maker.base_asset
+            )).into());
+        }
+        Ok(())
+    }
+
+    /// Ladder a maker order's size across ticks using the configured level count
+    /// and distribution, with rungs rounded down to `lot_size`
+    pub fn build_maker_ladder(&self, maker_order: &FuturesOrder, tick_size: f64, lot_size: f64) -> Result<MakerLadder> {
+        let execution = &self.config.execution;
+        let touch_price = maker_order.price
+            .ok_or_else(|| ArbitrageError::Trading(format!("Maker order for {} has no price", maker_order.symbol)))?;
+        MakerLadder::build(
+            &maker_order.symbol,
+            maker_order.side,
+            touch_price,
+            tick_size,
+            maker_order.quantity,
+            lot_size,
+            execution.ladder_levels,
+            execution.ladder_distribution,
+            execution.ladder_geometric_ratio,
+        )
+    }
+
+    /// Update execution statistics.
+    ///
+    /// `realized_pnl` is the quote-currency PnL of the confirmed fills; an
+    /// execution whose fills could not be reconciled adds no PnL anywhere.
+    async fn update_execution_statistics(&self, opportunity: &FuturesArbitrageOpportunity, realized_pnl: Option<f64>) {
+        let strategy = &self.config.strategy;
+        let quote = strategy.quote_currency(&opportunity.symbol)
+            .or_else(|| strategy.quote_currencies.first().map(String::as_str))
+            .unwrap_or(strategy.reporting_currency.as_str())
+            .to_string();
+        let pnl = realized_pnl.unwrap_or(0.0);
+        let rate = self.converter.read().await.rate(&quote);
+        match (rate, realized_pnl) {
+            (Some(rate), Some(realized_pnl)) => {
+                let profit = realized_pnl * rate;
+                self.record_realized_pnl(self.clock.monotonic_ms(), profit).await;
+                if let Some(portfolio) = &self.portfolio {
+                    portfolio.write().await.record_pnl(Market::Futures, profit);
+                }
+                info!("Executed {}: realized PnL {:.8} {} ({:.2} {}), expected {:.8}",
+                      opportunity.symbol, realized_pnl, quote, profit, strategy.reporting_currency, opportunity.expected_profit);
+            }
+            (Some(_), None) => warn!("Fills for {} could not be reconciled; its expected PnL of {:.8} {} is not recorded as realized",
+                                     opportunity.symbol, opportunity.expected_profit, quote),
+            (None, _) => warn!("No {} rate for {}; {} PnL of {:.8} {} is kept in pnl_by_currency only, outside the converted PnL, fee and volume totals",
+                               strategy.reporting_currency, quote, opportunity.symbol, pnl, quote),
+        }
+        
+        let mut stats = self.statistics.write().await;
+        stats.opportunities_executed += 1;
+        *stats.pnl_by_currency.entry(quote).or_insert(0.0) += pnl;
+        match rate {
+            Some(rate) => {
+                stats.total_pnl += pnl * rate;
+                stats.total_maker_rebates += opportunity.maker_price * opportunity.quantity * opportunity.maker_fee.abs() * rate;
+                stats.total_taker_fees += opportunity.taker_price * opportunity.quantity * opportunity.taker_fee * rate;
+                stats.total_volume += opportunity.quantity * opportunity.maker_price * rate;
+            }
+            None => stats.unconverted_executions += 1,
+        }
+        stats.last_execution = Some(chrono::Utc::now().timestamp());
+
+        // Update average spread
+        if stats.opportunities_executed > 1 {
+            stats.avg_spread_bps = (stats.avg_spread_bps * (stats.opportunities_executed - 1) as f64 + opportunity.spread_bps) / stats.opportunities_executed as f64;
+        } else {
+            stats.avg_spread_bps = opportunity.spread_bps;
+        }
+
+        // Update success rate
+        stats.success_rate = (stats.opportunities_executed as f64 / stats.opportunities_detected as f64) * 100.0;
+    }
+
+    /// Compare the spread realized by the legs' average fill prices with the spread the
+    /// opportunity assumed, record the difference, and return the quote-currency
+    /// PnL of the fills after the maker rebate and taker fee
+    async fn reconcile_fills(&self, opportunity: &FuturesArbitrageOpportunity, maker_average: f64, taker_average: f64) -> f64 {
+        // Spread per unit captured by buying low on one leg and selling high on the other
+        let (expected, realized, buy_price) = match opportunity.maker_side {
+            OrderSide::Sell => (opportunity.maker_price - opportunity.taker_price, maker_average - taker_average, opportunity.taker_price),
+            OrderSide::Buy => (opportunity.taker_price - opportunity.maker_price, taker_average - maker_average, opportunity.maker_price),
+        };
+        let slippage_usd = (realized - expected) * opportunity.quantity;
+        let slippage_bps = (realized - expected) / buy_price * 10000.0;
+        if let Some(exporter) = &self.metrics_exporter {
+            exporter.observe_spread_capture(realized / buy_price * 10000.0).await;
+        }
+        if slippage_usd < 0.0 {
+            warn!("Fills for {} captured {:.2} bps less than expected (${:.2})", opportunity.symbol, -slippage_bps, -slippage_usd);
+        } else {
+            debug!("Fills for {} captured {:.2} bps more than expected (${:.2})", opportunity.symbol, slippage_bps, slippage_usd);
+        }
+
+        let mut stats = self.statistics.write().await;
+        stats.reconciled_executions += 1;
+        stats.fill_slippage_usd += slippage_usd;
+        stats.last_fill_slippage_bps = slippage_bps;
+
+        let maker_rebate = maker_average * opportunity.quantity * opportunity.maker_fee.abs();
+        let taker_cost = taker_average * opportunity.quantity * opportunity.taker_fee;
+        realized * opportunity.quantity + maker_rebate - taker_cost
+    }
+
+    /// Record realized PnL at a strategy monotonic time and check the profit rate against the configured floor
+    pub async fn record_realized_pnl(&self, timestamp_ms: i64, pnl: f64) -> ProfitRateStatus {
+        let status = {
+            let mut monitor = self.profit_rate.write().await;
+            monitor.record(timestamp_ms, pnl);
+            monitor.check(timestamp_ms)
+        };
+        
+        if let ProfitRateStatus::BelowFloor { rate_per_hour } = status {
+            warn!("Realized profit rate ${:.2}/hour is below the ${:.2}/hour floor; review strategy parameters",
+                  rate_per_hour, self.config.strategy.min_profit_per_hour_usd);
+            if self.config.strategy.pause_on_low_profit_rate && self.is_running().await {
+                warn!("Pausing futures arbitrage strategy due to low profit rate");
+                *self.state.write().await = FuturesStrategyState::Paused;
+            }
+        }
+        status
+    }
+
+    /// Check whether opportunities have stopped appearing; call periodically
+    pub async fn check_opportunity_watchdog(&self) -> WatchdogStatus {
+        let detected = self.statistics.read().await.opportunities_detected;
+        let status = self.opportunity_watchdog.write().await.check(detected, self.clock.monotonic_ms());
+        match status {
+            WatchdogStatus::NoMarketData { idle_ms } => {
+                warn!("No market data received for {} s; check the exchange feeds", idle_ms / 1000);
+            }
+            WatchdogStatus::NoOpportunities { idle_ms } => {
+                warn!("Market data is flowing but no opportunities detected for {} s; check min_spread_bps ({}) and fee settings",
+                      idle_ms / 1000, self.config.strategy.min_spread_bps);
+            }
+            WatchdogStatus::WarmingUp | WatchdogStatus::Healthy => {}
+        }
+        status
+    }
+
+    /// Realized profit per hour over the rolling window
+    pub async fn get_profit_rate_per_hour(&self) -> f64 {
+        self.profit_rate.write().await.rate_per_hour(self.clock.monotonic_ms())
+    }
+
+    /// Check if strategy is running
+    async fn is_running(&self) -> bool {
+        let state = self.state.read().await;
+        matches!(*state, FuturesStrategyState::Running)
+    }
+
+    /// Get strategy statistics
     pub async fn get_statistics(&self) -> FuturesArbitrageStats {
         let stats = self.statistics.read().await;
         let mut stats_clone = stats.clone();
@@ -462,71 +2430,1728 @@ impl FuturesArbitrageStrategy {
         stats_clone
     }
 
-    /// Get current opportunities
-    pub async fn get_current_opportunities(&self) -> Vec<FuturesArbitrageOpportunity> {
-        self.opportunities.read().await.clone()
+    /// Get current opportunities
+    pub async fn get_current_opportunities(&self) -> Vec<FuturesArbitrageOpportunity> {
+        self.opportunities.read().await.clone()
+    }
+
+    /// Get strategy state
+    pub async fn get_state(&self) -> FuturesStrategyState {
+        *self.state.read().await
+    }
+
+    /// Prometheus metrics registry, for serving the `/metrics` endpoint
+    pub fn metrics_exporter(&self) -> Option<Arc<MetricsExporter>> {
+        self.metrics_exporter.clone()
+    }
+
+    /// Get active symbols
+    pub fn get_active_symbols(&self) -> &[String] {
+        &self.active_symbols
+    }
+}
+
+/// Short random ID linking an opportunity's log lines and orders
+fn new_correlation_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()[..12].to_string()
+}
+
+/// Tolerance absorbing float error when dividing a quantity by the lot size
+const LOT_EPSILON: f64 = 1e-9;
+
+/// Rounds a fractional lot count to a whole number of lots
+type LotRounding = fn(f64) -> f64;
+
+fn round_down(lots: f64) -> f64 {
+    (lots + LOT_EPSILON).floor()
+}
+
+fn round_up(lots: f64) -> f64 {
+    (lots - LOT_EPSILON).ceil()
+}
+
+/// Round a quantity to a whole number of lots
+fn round_to_lot(quantity: f64, lot_size: f64, rounding: LotRounding) -> f64 {
+    if lot_size <= 0.0 {
+        return quantity;
+    }
+    rounding(quantity / lot_size) * lot_size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::futures::{
+        FundingRate, FuturesAccountInfo, FuturesPosition, MarginMode, QuantityUnit,
+    };
+
+    fn create_test_config() -> ArbitrageConfig {
+        let mut config = ArbitrageConfig::default();
+        config.strategy.min_spread_bps = 5.0;
+        config.strategy.max_position_size = 1.0;
+        config.execution.min_order_size = 0.001;
+        config
+    }
+
+    fn create_test_opportunity() -> FuturesArbitrageOpportunity {
+        FuturesArbitrageOpportunity {
+            symbol: "BTCUSDT".to_string(),
+            maker_exchange: Exchange::Bybit,
+            taker_exchange: Exchange::Binance,
+            maker_side: OrderSide::Sell,
+            taker_side: OrderSide::Buy,
+            maker_price: 50050.0,
+            taker_price: 50000.0,
+            quantity: 0.4,
+            spread_bps: 10.0,
+            expected_profit: 2.0,
+            maker_fee: -0.00025,
+            taker_fee: 0.0004,
+            risk_score: 20.0,
+            timestamp: now_ns(),
+            correlation_id: "test-corr".to_string(),
+            deadline_ms: None,
+        }
+    }
+
+    /// Futures connector stub with a fixed available balance that records placed orders
+    struct MockFuturesConnector {
+        available_balance: f64,
+        placed_orders: std::sync::Mutex<Vec<FuturesOrder>>,
+        leverage_calls: std::sync::Mutex<Vec<(String, u32)>>,
+        positions: std::sync::Mutex<Vec<FuturesPosition>>,
+        reject_orders: bool,
+        fill_price: Option<f64>,
+        extra_listings: Vec<FuturesContract>,
+        /// Position mode reported by the account; `None` fails detection
+        position_mode: Option<PositionMode>,
+        /// Name used in `journal` entries
+        label: &'static str,
+        /// Placement and cancel calls, in order; may be shared between connectors
+        journal: Arc<std::sync::Mutex<Vec<String>>>,
+        /// Market orders report no fill on placement, only through `get_order_status`
+        unreported_fills: bool,
+        /// Market orders placed after this many stop filling
+        market_fills: Option<usize>,
+        /// Status `get_order_status` reports for every order, overriding `unreported_fills`
+        order_status: std::sync::Mutex<Option<FuturesOrderStatus>>,
+        /// Quantity a successful cancel reports as filled first, opening a
+        /// position of that size; `None` fails cancels
+        cancel_fill: Option<f64>,
+    }
+
+    impl MockFuturesConnector {
+        fn new(available_balance: f64) -> Self {
+            Self {
+                available_balance,
+                placed_orders: std::sync::Mutex::new(Vec::new()),
+                leverage_calls: std::sync::Mutex::new(Vec::new()),
+                positions: std::sync::Mutex::new(Vec::new()),
+                reject_orders: false,
+                fill_price: None,
+                extra_listings: Vec::new(),
+                position_mode: None,
+                label: "mock",
+                journal: Arc::new(std::sync::Mutex::new(Vec::new())),
+                unreported_fills: false,
+                market_fills: None,
+                order_status: std::sync::Mutex::new(None),
+                cancel_fill: None,
+            }
+        }
+
+        fn placed_count(&self) -> usize {
+            self.placed_orders.lock().unwrap().len()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl FuturesConnector for MockFuturesConnector {
+        async fn get_exchange_info(&self) -> Result<HashMap<String, FuturesContract>> {
+            Ok(std::iter::once(create_test_contract("BTCUSDT", "BTC"))
+                .chain(self.extra_listings.iter().cloned())
+                .map(|contract| (contract.symbol.clone(), contract))
+                .collect())
+        }
+
+        async fn get_account_info(&self) -> Result<FuturesAccountInfo> {
+            Ok(FuturesAccountInfo {
+                total_wallet_balance: self.available_balance,
+                total_unrealized_pnl: 0.0,
+                total_margin_balance: self.available_balance,
+                total_position_initial_margin: 0.0,
+                total_order_initial_margin: 0.0,
+                available_balance: self.available_balance,
+                max_withdraw_amount: self.available_balance,
+                margin_ratio: None,
+                update_time: 0,
+                balances: Vec::new(),
+                positions: Vec::new(),
+            })
+        }
+
+        async fn get_positions(&self) -> Result<Vec<FuturesPosition>> {
+            Ok(self.positions.lock().unwrap().clone())
+        }
+
+        async fn place_order(&self, order: &FuturesOrder) -> Result<FuturesOrderResponse> {
+            if self.reject_orders {
+                self.journal.lock().unwrap().push(format!("{} reject", self.label));
+                return Err(ArbitrageError::Trading("Invalid API key".to_string()).into());
+            }
+            let reduce_only = if order.reduce_only { " reduce_only" } else { "" };
+            self.journal.lock().unwrap().push(format!("{} place {}{}", self.label, order.side, reduce_only));
+            self.placed_orders.lock().unwrap().push(order.clone());
+            let market_count = self.placed_orders.lock().unwrap().iter()
+                .filter(|placed| placed.order_type == FuturesOrderType::Market)
+                .count();
+            let fills = order.order_type == FuturesOrderType::Market
+                && !self.unreported_fills
+                && self.market_fills.is_none_or(|limit| market_count <= limit);
+            Ok(FuturesOrderResponse {
+                order_id: format!("order_{}", self.placed_count()),
+                client_order_id: order.client_order_id.clone(),
+                symbol: order.symbol.clone(),
+                side: order.side,
+                position_side: order.position_side.clone(),
+                order_type: order.order_type.clone(),
+                quantity: order.quantity,
+                price: order.price,
+                // A market order the venue did not fill expires
+                status: if order.order_type == FuturesOrderType::Market && !fills && !self.unreported_fills {
+                    FuturesOrderStatus::Expired
+                } else {
+                    FuturesOrderStatus::New
+                },
+                // Market orders fill immediately; resting orders stay open
+                filled_quantity: if fills { order.quantity } else { 0.0 },
+                average_price: self.fill_price,
+                commission: 0.0,
+                commission_asset: "USDT".to_string(),
+                timestamp: 0,
+            })
+        }
+
+        async fn cancel_order(&self, _symbol: &str, order_id: &str) -> Result<FuturesOrderResponse> {
+            self.journal.lock().unwrap().push(format!("{} cancel {}", self.label, order_id));
+            let Some(filled_quantity) = self.cancel_fill else {
+                return Err(ArbitrageError::NotImplemented("cancel_order".to_string()).into());
+            };
+            let index: usize = order_id.trim_start_matches("order_").parse().unwrap();
+            let order = self.placed_orders.lock().unwrap()[index - 1].clone();
+            if filled_quantity > 0.0 {
+                self.positions.lock().unwrap().push(FuturesPosition {
+                    symbol: order.symbol.clone(),
+                    side: match order.side {
+                        OrderSide::Buy => PositionSide::Long,
+                        OrderSide::Sell => PositionSide::Short,
+                    },
+                    size: filled_quantity,
+                    entry_price: order.price.unwrap_or_default(),
+                    mark_price: order.price.unwrap_or_default(),
+                    unrealized_pnl: 0.0,
+                    realized_pnl: 0.0,
+                    margin: 0.0,
+                    leverage: 10.0,
+                    update_time: 0,
+                });
+            }
+            Ok(FuturesOrderResponse {
+                order_id: order_id.to_string(),
+                client_order_id: order.client_order_id,
+                symbol: order.symbol,
+                side: order.side,
+                position_side: order.position_side,
+                order_type: order.order_type,
+                quantity: order.quantity,
+                price: order.price,
+                status: FuturesOrderStatus::Canceled,
+                filled_quantity,
+                average_price: self.fill_price,
+                commission: 0.0,
+                commission_asset: "USDT".to_string(),
+                timestamp: 0,
+            })
+        }
+
+        async fn get_order_status(&self, symbol: &str, order_id: &str) -> Result<FuturesOrderResponse> {
+            let status = match self.order_status.lock().unwrap().clone() {
+                Some(status) => status,
+                None if self.unreported_fills => FuturesOrderStatus::Filled,
+                None => return Err(ArbitrageError::NotImplemented("get_order_status".to_string()).into()),
+            };
+            let index: usize = order_id.trim_start_matches("order_").parse().unwrap();
+            let order = self.placed_orders.lock().unwrap()[index - 1].clone();
+            Ok(FuturesOrderResponse {
+                order_id: order_id.to_string(),
+                client_order_id: order.client_order_id,
+                symbol: symbol.to_string(),
+                side: order.side,
+                position_side: order.position_side,
+                order_type: order.order_type,
+                quantity: order.quantity,
+                price: order.price,
+                filled_quantity: if status == FuturesOrderStatus::Filled { order.quantity } else { 0.0 },
+                status,
+                average_price: self.fill_price,
+                commission: 0.0,
+                commission_asset: "USDT".to_string(),
+                timestamp: 0,
+            })
+        }
+
+        async fn get_funding_rate(&self, _symbol: &str) -> Result<FundingRate> {
+            Err(ArbitrageError::NotImplemented("get_funding_rate".to_string()).into())
+        }
+
+        async fn get_mark_price(&self, _symbol: &str) -> Result<MarkPrice> {
+            Err(ArbitrageError::NotImplemented("get_mark_price".to_string()).into())
+        }
+
+        async fn set_margin_mode(&self, _symbol: &str, _mode: MarginMode) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_position_mode(&self, _symbol: &str) -> Result<PositionMode> {
+            self.position_mode.ok_or_else(|| ArbitrageError::NotImplemented("get_position_mode".to_string()).into())
+        }
+
+        async fn set_leverage(&self, symbol: &str, leverage: u32) -> Result<()> {
+            self.leverage_calls.lock().unwrap().push((symbol.to_string(), leverage));
+            Ok(())
+        }
+
+        async fn subscribe_orderbook(&mut self, _symbol: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn subscribe_trades(&mut self, _symbol: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn subscribe_mark_price(&mut self, _symbol: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn subscribe_funding_rate(&mut self, _symbol: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn is_connected(&self) -> bool {
+            true
+        }
+
+        async fn connect(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn disconnect(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn create_test_contract(symbol: &str, base_asset: &str) -> FuturesContract {
+        FuturesContract {
+            symbol: symbol.to_string(),
+            base_asset: base_asset.to_string(),
+            quote_asset: "USDT".to_string(),
+            contract_type: crate::connectors::futures::ContractType::Perpetual,
+            min_order_size: 0.001,
+            price_precision: 2,
+            quantity_precision: 3,
+            tick_size: 0.1,
+            lot_size: 0.001,
+            maker_fee: 0.0002,
+            taker_fee: 0.0004,
+            contract_multiplier: 1.0,
+            max_leverage: 20,
+            quantity_unit: QuantityUnit::Base,
+            percent_price: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_configure_leverage_validates_against_contract_max() {
+        let mut config = create_test_config();
+        config.execution.leverage = 5;
+        let strategy = FuturesArbitrageStrategy::new(config.clone(), vec!["BTCUSDT".to_string()]).await.unwrap();
+        let bybit = MockFuturesConnector::new(0.0);
+        let binance = MockFuturesConnector::new(0.0);
+
+        strategy.configure_leverage(&bybit, &binance).await.unwrap();
+        assert_eq!(*bybit.leverage_calls.lock().unwrap(), vec![("BTCUSDT".to_string(), 5)]);
+        assert_eq!(*binance.leverage_calls.lock().unwrap(), vec![("BTCUSDT".to_string(), 5)]);
+
+        config.execution.leverage = 50;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+        let bybit = MockFuturesConnector::new(0.0);
+        assert!(strategy.configure_leverage(&bybit, &binance).await.is_err());
+        assert!(bybit.leverage_calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_symbol_missing_on_one_venue_is_excluded() {
+        let symbols = vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()];
+        let mut strategy = FuturesArbitrageStrategy::new(create_test_config(), symbols.clone()).await.unwrap();
+        let bybit = MockFuturesConnector::new(0.0);
+        let binance = MockFuturesConnector {
+            extra_listings: vec![create_test_contract("ETHUSDT", "ETH")],
+            ..MockFuturesConnector::new(0.0)
+        };
+
+        let excluded = strategy.validate_symbols(&bybit, &binance).await.unwrap();
+        assert_eq!(excluded, vec!["ETHUSDT".to_string()]);
+        assert_eq!(strategy.get_active_symbols(), &["BTCUSDT".to_string()]);
+
+        let mut config = create_test_config();
+        config.strategy.exclude_unlisted_symbols = false;
+        let mut strict = FuturesArbitrageStrategy::new(config, symbols).await.unwrap();
+        assert!(strict.validate_symbols(&bybit, &binance).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_futures_strategy_creation() {
+        let config = create_test_config();
+        let symbols = vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()];
+        let strategy = FuturesArbitrageStrategy::new(config, symbols.clone()).await.unwrap();
+        
+        assert_eq!(strategy.get_state().await, FuturesStrategyState::Stopped);
+        assert_eq!(strategy.get_active_symbols(), &symbols);
+    }
+
+    #[tokio::test]
+    async fn test_strategy_state_management() {
+        let config = create_test_config();
+        let symbols = vec!["BTCUSDT".to_string()];
+        let strategy = FuturesArbitrageStrategy::new(config, symbols).await.unwrap();
+        
+        assert_eq!(strategy.get_state().await, FuturesStrategyState::Stopped);
+        
+        strategy.start().await.unwrap();
+        assert_eq!(strategy.get_state().await, FuturesStrategyState::Running);
+        
+        strategy.stop().await.unwrap();
+        assert_eq!(strategy.get_state().await, FuturesStrategyState::Stopped);
+    }
+
+    #[tokio::test]
+    async fn test_risk_score_calculation() {
+        let config = create_test_config();
+        let symbols = vec!["BTCUSDT".to_string()];
+        let strategy = FuturesArbitrageStrategy::new(config, symbols).await.unwrap();
+        
+        // Low spread, high quantity = high risk
+        let high_risk = strategy.calculate_risk_score(5.0, 2.0, Exchange::Binance).await;
+        assert!(high_risk > 50.0);
+        
+        // High spread, low quantity = low risk
+        let low_risk = strategy.calculate_risk_score(25.0, 0.1, Exchange::Binance).await;
+        assert!(low_risk < 30.0);
+    }
+
+    #[tokio::test]
+    async fn test_hedge_leg_scaled_by_ratio() {
+        let mut config = create_test_config();
+        config.strategy.hedge_ratio = 0.75;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+        let opportunity = create_test_opportunity();
+
+        let maker_order = strategy.build_maker_order(&opportunity);
+        let taker_order = strategy.build_taker_order(&opportunity);
+
+        assert!((maker_order.quantity - 0.4).abs() < 1e-9);
+        assert!((taker_order.quantity - 0.3).abs() < 1e-9);
+        assert_eq!(taker_order.side, OrderSide::Buy);
+    }
+
+    #[test]
+    fn test_hedge_pair_requires_same_base_asset() {
+        let perp = create_test_contract("BTCUSDT", "BTC");
+        let quarterly = create_test_contract("BTCUSDT_241227", "BTC");
+        let other = create_test_contract("ETHUSDT", "ETH");
+
+        assert!(FuturesArbitrageStrategy::validate_hedge_pair(&perp, &quarterly).is_ok());
+        assert!(FuturesArbitrageStrategy::validate_hedge_pair(&perp, &other).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_hedge_contracts_respect_multipliers() {
+        let strategy = FuturesArbitrageStrategy::new(create_test_config(), vec!["BTCUSDT".to_string()]).await.unwrap();
+        let maker = create_test_contract("BTCUSDT", "BTC");
+        let mut hedge = create_test_contract("BTCUSD_PERP", "BTC");
+        hedge.contract_multiplier = 0.01;
+
+        assert!((strategy.hedge_contract_quantity(&maker, &hedge, 0.5) - 50.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_order_round_trips_recorded_as_latency() {
+        let strategy = FuturesArbitrageStrategy::new(create_test_config(), vec!["BTCUSDT".to_string()]).await.unwrap();
+        let bybit = MockFuturesConnector::new(100_000.0);
+        let binance = MockFuturesConnector::new(100_000.0);
+        assert!(strategy.get_latency_ms(Exchange::Bybit).await.is_none());
+
+        strategy.execute_opportunity(&create_test_opportunity(), &bybit, &binance).await.unwrap();
+
+        assert!(strategy.get_latency_ms(Exchange::Bybit).await.is_some());
+        assert!(strategy.get_latency_ms(Exchange::Binance).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_slower_critical_leg_increases_risk() {
+        let config = create_test_config();
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+        
+        strategy.record_latency(Exchange::Binance, std::time::Duration::from_millis(20)).await;
+        strategy.record_latency(Exchange::Bybit, std::time::Duration::from_millis(20)).await;
+        let baseline = strategy.calculate_risk_score(25.0, 0.1, Exchange::Binance).await;
+        
+        strategy.record_latency(Exchange::Binance, std::time::Duration::from_millis(520)).await;
+        assert!((strategy.get_latency_ms(Exchange::Binance).await.unwrap() - 120.0).abs() < 1e-9);
+        
+        let slowed = strategy.calculate_risk_score(25.0, 0.1, Exchange::Binance).await;
+        assert!(slowed > baseline);
+        
+        // Opportunities whose critical leg is on the faster venue are unaffected
+        let other_leg = strategy.calculate_risk_score(25.0, 0.1, Exchange::Bybit).await;
+        assert_eq!(other_leg, baseline);
+    }
+
+    #[tokio::test]
+    async fn test_failed_reservation_places_no_orders() {
+        let strategy = FuturesArbitrageStrategy::new(create_test_config(), vec!["BTCUSDT".to_string()]).await.unwrap();
+        let opportunity = create_test_opportunity();
+        let bybit = MockFuturesConnector::new(100_000.0);
+        let binance = MockFuturesConnector::new(100.0); // Cannot cover ~20k notional
+        
+        let result = strategy.execute_opportunity(&opportunity, &bybit, &binance).await;
+        
+        assert!(result.is_err());
+        assert_eq!(bybit.placed_count(), 0);
+        assert_eq!(binance.placed_count(), 0);
+        assert!(strategy.reserved_margin.read().await.values().all(|amount| *amount == 0.0));
+        
+        let funded_binance = MockFuturesConnector::new(100_000.0);
+        strategy.execute_opportunity(&opportunity, &bybit, &funded_binance).await.unwrap();
+        assert_eq!(bybit.placed_count(), 1);
+        assert_eq!(funded_binance.placed_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_maker_leg_laddered_in_lot_steps() {
+        let mut config = create_test_config();
+        config.execution.ladder_levels = 3;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+        let mut bybit = MockFuturesConnector::new(100_000.0);
+        bybit.extra_listings = vec![FuturesContract { lot_size: 0.01, ..create_test_contract("BTCUSDT", "BTC") }];
+        strategy.load_contract_specs(Exchange::Bybit, &bybit).await.unwrap();
+        let binance = MockFuturesConnector::new(100_000.0);
+
+        strategy.execute_opportunity(&create_test_opportunity(), &bybit, &binance).await.unwrap();
+
+        // 0.4 over three rungs a tick apart, the lot remainder in the last
+        let rungs = bybit.placed_orders.lock().unwrap().clone();
+        let expected = [(50050.0, 0.13), (50050.1, 0.13), (50050.2, 0.14)];
+        assert_eq!(rungs.len(), expected.len());
+        for (rung, (price, quantity)) in rungs.iter().zip(expected) {
+            assert!((rung.price.unwrap() - price).abs() < 1e-6, "{:?}", rung.price);
+            assert!((rung.quantity - quantity).abs() < 1e-9, "{}", rung.quantity);
+            assert_eq!(rung.time_in_force, FuturesTimeInForce::GTX);
+        }
+        assert_eq!(binance.placed_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_aliased_symbols_are_paired() {
+        let mut config = create_test_config();
+        let mut aliases = HashMap::new();
+        aliases.insert("binance".to_string(), "POLUSDT".to_string());
+        aliases.insert("bybit".to_string(), "MATICUSDT".to_string());
+        config.exchanges.symbol_aliases.insert("POLUSDT".to_string(), aliases);
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["POLUSDT".to_string()]).await.unwrap();
+        
+        let mut binance_book = OrderBook::new("POLUSDT".to_string(), Exchange::Binance);
+        binance_book.update_bid(0.999, 1.0);
+        binance_book.update_ask(1.000, 1.0);
+        let mut bybit_book = OrderBook::new("MATICUSDT".to_string(), Exchange::Bybit);
+        bybit_book.update_bid(1.010, 1.0);
+        bybit_book.update_ask(1.011, 1.0);
+        strategy.update_orderbook(Exchange::Binance, binance_book).await.unwrap();
+        strategy.update_orderbook(Exchange::Bybit, bybit_book).await.unwrap();
+        
+        let opportunities = strategy.detect_opportunities().await.unwrap();
+        assert_eq!(opportunities.len(), 1);
+        assert_eq!(opportunities[0].symbol, "POLUSDT");
+        assert_eq!(strategy.build_maker_order(&opportunities[0]).symbol, "MATICUSDT");
+        assert_eq!(strategy.build_taker_order(&opportunities[0]).symbol, "POLUSDT");
+    }
+
+    #[tokio::test]
+    async fn test_low_profit_rate_pauses_strategy() {
+        let mut config = create_test_config();
+        config.strategy.min_profit_per_hour_usd = 50.0;
+        config.strategy.profit_rate_window_secs = 600;
+        config.strategy.pause_on_low_profit_rate = true;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+        strategy.start().await.unwrap();
+        
+        // $2 per minute = $120/hour
+        for minute in 0..=10 {
+            let status = strategy.record_realized_pnl(minute * 60_000, 2.0).await;
+            assert!(!matches!(status, ProfitRateStatus::BelowFloor { .. }));
+        }
+        assert_eq!(strategy.get_state().await, FuturesStrategyState::Running);
+        
+        // A single $1 trade ten minutes later: $6/hour
+        let status = strategy.record_realized_pnl(21 * 60_000, 1.0).await;
+        assert!(matches!(status, ProfitRateStatus::BelowFloor { .. }));
+        assert_eq!(strategy.get_state().await, FuturesStrategyState::Paused);
+    }
+
+    #[tokio::test]
+    async fn test_residual_exposure_alarm_and_flatten() {
+        let mut config = create_test_config();
+        config.risk.flatten_residual = true;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+        let position = |side, size| FuturesPosition {
+            symbol: "BTCUSDT".to_string(),
+            side,
+            size,
+            entry_price: 50000.0,
+            mark_price: 50000.0,
+            unrealized_pnl: 0.0,
+            realized_pnl: 0.0,
+            margin: 2000.0,
+            leverage: 10.0,
+            update_time: 0,
+        };
+
+        // Bybit sizes in 0.01 BTC contracts; Binance already held 1 BTC long
+        let bybit = MockFuturesConnector::new(100_000.0);
+        *bybit.order_status.lock().unwrap() = Some(FuturesOrderStatus::New);
+        let contract = FuturesContract {
+            contract_multiplier: 0.01,
+            quantity_unit: QuantityUnit::Contracts,
+            lot_size: 1.0,
+            ..create_test_contract("BTCUSDT", "BTC")
+        };
+        strategy.contract_specs.write().await.insert((Exchange::Bybit, "BTCUSDT".to_string()), contract);
+        let binance = MockFuturesConnector::new(100_000.0);
+        binance.positions.lock().unwrap().push(position(PositionSide::Long, 1.0));
+
+        // The hedge filled while the maker still rests: nothing is checked yet
+        strategy.execute_opportunity(&create_test_opportunity(), &bybit, &binance).await.unwrap();
+        *binance.positions.lock().unwrap() = vec![position(PositionSide::Long, 1.4)];
+        assert_eq!(strategy.enforce_net_neutrality("BTCUSDT", &bybit, &binance).await.unwrap(), None);
+        assert_eq!(strategy.get_statistics().await.residual_exposure_alarms, 0);
+
+        // Maker filled 40 contracts: the execution is flat in BTC, the prior long is not counted
+        *bybit.order_status.lock().unwrap() = Some(FuturesOrderStatus::Filled);
+        bybit.positions.lock().unwrap().push(position(PositionSide::Short, 40.0));
+        assert_eq!(strategy.enforce_net_neutrality("BTCUSDT", &bybit, &binance).await.unwrap(), None);
+        assert_eq!(strategy.get_statistics().await.residual_exposure_alarms, 0);
+        assert!(strategy.due_neutrality_checks().await.is_empty());
+
+        // A second maker is cancelled unfilled, leaving its 0.4 BTC hedge exposed
+        *bybit.order_status.lock().unwrap() = Some(FuturesOrderStatus::New);
+        strategy.last_execution_at.write().await.take();
+        strategy.last_fill_at.write().await.clear();
+        strategy.execute_opportunity(&create_test_opportunity(), &bybit, &binance).await.unwrap();
+        *binance.positions.lock().unwrap() = vec![position(PositionSide::Long, 1.8)];
+        *bybit.order_status.lock().unwrap() = Some(FuturesOrderStatus::Canceled);
+        let residual = strategy.enforce_net_neutrality("BTCUSDT", &bybit, &binance).await.unwrap();
+        assert!((residual.unwrap() - 0.4).abs() < 1e-9, "{:?}", residual);
+        assert_eq!(strategy.get_statistics().await.residual_exposure_alarms, 1);
+
+        let flatten = binance.placed_orders.lock().unwrap().last().unwrap().clone();
+        assert_eq!(flatten.side, OrderSide::Sell);
+        assert_eq!(flatten.order_type, FuturesOrderType::Market);
+        assert!(flatten.reduce_only);
+        assert!((flatten.quantity - 0.4).abs() < 1e-9);
+        assert_eq!(bybit.placed_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_partial_fill_before_rollback_is_checked_and_flattened() {
+        let mut config = create_test_config();
+        config.risk.flatten_residual = true;
+        config.risk.net_position_tolerance = 0.01;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+
+        // The hedge is rejected, and 0.1 of the maker fills before its cancel lands
+        let bybit = MockFuturesConnector { cancel_fill: Some(0.1), ..MockFuturesConnector::new(100_000.0) };
+        let binance = MockFuturesConnector { reject_orders: true, ..MockFuturesConnector::new(100_000.0) };
+        assert!(strategy.execute_opportunity(&create_test_opportunity(), &bybit, &binance).await.is_err());
+
+        assert_eq!(strategy.get_statistics().await.residual_exposure_alarms, 1);
+        let flatten = bybit.placed_orders.lock().unwrap().last().unwrap().clone();
+        assert_eq!(flatten.side, OrderSide::Buy);
+        assert!(flatten.reduce_only);
+        assert!((flatten.quantity - 0.1).abs() < 1e-9);
+        assert!(strategy.due_neutrality_checks().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_coalesced_updates_detect_once_on_latest_book() {
+        let mut config = create_test_config();
+        config.strategy.coalesce_ms = 50;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+        strategy.start().await.unwrap();
+
+        let mut binance_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        binance_book.update_bid(49990.0, 1.0);
+        binance_book.update_ask(50000.0, 1.0);
+        strategy.update_orderbook(Exchange::Binance, binance_book).await.unwrap();
+
+        // Rapid Bybit updates within the window; only the last one crosses
+        for bid in [49980.0, 49995.0, 50100.0] {
+            let mut bybit_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Bybit);
+            bybit_book.update_bid(bid, 1.0);
+            bybit_book.update_ask(bid + 10.0, 1.0);
+            strategy.update_orderbook(Exchange::Bybit, bybit_book).await.unwrap();
+        }
+        assert_eq!(strategy.get_statistics().await.opportunities_detected, 0);
+
+        // The periodic scan closes the window even though no further update arrives
+        tokio::time::sleep(std::time::Duration::from_millis(60)).await;
+        let opportunities = strategy.scan_opportunities().await.unwrap();
+        assert_eq!(opportunities.len(), 1);
+        assert_eq!(opportunities[0].maker_price, 50100.0);
+        assert!(strategy.flush_coalesced().await.unwrap().is_empty());
+        assert_eq!(strategy.get_statistics().await.opportunities_detected, 1);
+    }
+
+    #[tokio::test]
+    async fn test_break_even_spread_for_known_fees() {
+        let mut config = create_test_config();
+        config.execution.slippage_tolerance = 0.001;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+
+        // 4 bps taker + 10 bps slippage - 2.5 bps rebate = 11.5 bps before price scaling
+        let sell = strategy.break_even_spread_bps("BTCUSDT", OrderSide::Sell);
+        let buy = strategy.break_even_spread_bps("BTCUSDT", OrderSide::Buy);
+        assert!((sell - 11.5 / 1.00025).abs() < 1e-9, "{}", sell);
+        assert!((buy - 11.5 / 0.9986).abs() < 1e-9, "{}", buy);
+
+        // At exactly the break-even spread, a maker sell nets zero after costs
+        let ask = 50_000.0;
+        let bid = ask * (1.0 + sell / 10000.0);
+        let pnl = (bid - ask) - bid * MAKER_FEE - ask * (TAKER_FEE + 0.001);
+        assert!(pnl.abs() < 1e-9, "{}", pnl);
+    }
+
+    #[tokio::test]
+    async fn test_buy_only_venue_suppresses_sell_side_maker() {
+        let mut config = create_test_config();
+        config.strategy.allowed_directions.insert("bybit".to_string(), crate::config::TradeDirection::BuyOnly);
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+
+        let mut binance_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        binance_book.update_bid(49990.0, 1.0);
+        binance_book.update_ask(50000.0, 1.0);
+        strategy.update_orderbook(Exchange::Binance, binance_book).await.unwrap();
+
+        // Bybit bid above the Binance ask: a maker sell on Bybit
+        let mut bybit_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Bybit);
+        bybit_book.update_bid(50100.0, 1.0);
+        bybit_book.update_ask(50110.0, 1.0);
+        strategy.update_orderbook(Exchange::Bybit, bybit_book).await.unwrap();
+        assert!(strategy.detect_opportunities().await.unwrap().is_empty());
+
+        // Bybit ask below the Binance bid: a maker buy on Bybit is still allowed
+        let mut bybit_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Bybit);
+        bybit_book.update_bid(49870.0, 1.0);
+        bybit_book.update_ask(49880.0, 1.0);
+        strategy.update_orderbook(Exchange::Bybit, bybit_book).await.unwrap();
+        let opportunities = strategy.detect_opportunities().await.unwrap();
+        assert_eq!(opportunities.len(), 1);
+        assert_eq!(opportunities[0].maker_side, OrderSide::Buy);
+    }
+
+    #[tokio::test]
+    async fn test_wide_source_spread_suppresses_opportunity() {
+        let mut config = create_test_config();
+        config.strategy.max_source_spread_bps = 20.0;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+
+        let mut binance_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        binance_book.update_bid(49990.0, 1.0);
+        binance_book.update_ask(50000.0, 1.0);
+        strategy.update_orderbook(Exchange::Binance, binance_book).await.unwrap();
+
+        // Bybit bid crosses the Binance ask, but Bybit's own spread is ~40 bps
+        let mut bybit_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Bybit);
+        bybit_book.update_bid(50100.0, 1.0);
+        bybit_book.update_ask(50300.0, 1.0);
+        strategy.update_orderbook(Exchange::Bybit, bybit_book).await.unwrap();
+        assert!(strategy.detect_opportunities().await.unwrap().is_empty());
+
+        // The same cross with a tight Bybit book is detected
+        let mut bybit_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Bybit);
+        bybit_book.update_bid(50100.0, 1.0);
+        bybit_book.update_ask(50110.0, 1.0);
+        strategy.update_orderbook(Exchange::Bybit, bybit_book).await.unwrap();
+        assert_eq!(strategy.detect_opportunities().await.unwrap().len(), 1);
     }
 
-    /// Get strategy state
-    pub async fn get_state(&self) -> FuturesStrategyState {
-        *self.state.read().await
+    #[tokio::test]
+    async fn test_fractional_spread_is_not_rounded() {
+        let detect = |min_spread_bps: f64| async move {
+            let mut config = create_test_config();
+            config.strategy.min_spread_bps = min_spread_bps;
+            let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+            let mut binance_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+            binance_book.update_bid(49990.0, 1.0);
+            binance_book.update_ask(50000.0, 1.0);
+            strategy.update_orderbook(Exchange::Binance, binance_book).await.unwrap();
+
+            // Bybit bid is 4.6 bps through the Binance ask
+            let mut bybit_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Bybit);
+            bybit_book.update_bid(50023.0, 1.0);
+            bybit_book.update_ask(50033.0, 1.0);
+            strategy.update_orderbook(Exchange::Bybit, bybit_book).await.unwrap();
+            strategy.detect_opportunities().await.unwrap()
+        };
+
+        let opportunities = detect(4.0).await;
+        assert_eq!(opportunities.len(), 1);
+        assert!((opportunities[0].spread_bps - 4.6).abs() < 1e-9, "{}", opportunities[0].spread_bps);
+        assert!(detect(4.7).await.is_empty());
     }
 
-    /// Get active symbols
-    pub fn get_active_symbols(&self) -> &[String] {
-        &self.active_symbols
+    #[tokio::test]
+    async fn test_symbol_min_spread_override_filters_only_that_symbol() {
+        let mut config = create_test_config();
+        config.strategy.min_spread_bps_overrides.insert("ETHUSDT".to_string(), 15.0);
+        let symbols = vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()];
+        let strategy = FuturesArbitrageStrategy::new(config, symbols).await.unwrap();
+
+        // Both symbols cross by 10 bps
+        for (symbol, binance_ask, bybit_bid) in [("BTCUSDT", 50000.0, 50050.0), ("ETHUSDT", 3000.0, 3003.0)] {
+            let mut binance_book = OrderBook::new(symbol.to_string(), Exchange::Binance);
+            binance_book.update_bid(binance_ask - 1.0, 1.0);
+            binance_book.update_ask(binance_ask, 1.0);
+            strategy.update_orderbook(Exchange::Binance, binance_book).await.unwrap();
+            let mut bybit_book = OrderBook::new(symbol.to_string(), Exchange::Bybit);
+            bybit_book.update_bid(bybit_bid, 1.0);
+            bybit_book.update_ask(bybit_bid + 1.0, 1.0);
+            strategy.update_orderbook(Exchange::Bybit, bybit_book).await.unwrap();
+        }
+
+        let opportunities = strategy.detect_opportunities().await.unwrap();
+        assert_eq!(opportunities.len(), 1);
+        assert_eq!(opportunities[0].symbol, "BTCUSDT");
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[tokio::test]
+    async fn test_opportunity_sized_down_to_capital_cap() {
+        let mut config = create_test_config();
+        config.strategy.max_position_size = 10.0;
+        config.strategy.max_capital_per_opportunity_usd = 10_000.0;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
 
-    fn create_test_config() -> ArbitrageConfig {
-        let mut config = ArbitrageConfig::default();
-        config.strategy.min_spread_bps = 5;
-        config.strategy.max_position_size = 1.0;
-        config.execution.min_order_size = 0.001;
-        config
+        // 5 BTC on both sides and a 20 bps cross
+        let mut binance_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        binance_book.update_bid(49990.0, 5.0);
+        binance_book.update_ask(50000.0, 5.0);
+        strategy.update_orderbook(Exchange::Binance, binance_book).await.unwrap();
+        let mut bybit_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Bybit);
+        bybit_book.update_bid(50100.0, 5.0);
+        bybit_book.update_ask(50110.0, 5.0);
+        strategy.update_orderbook(Exchange::Bybit, bybit_book).await.unwrap();
+
+        let opportunities = strategy.detect_opportunities().await.unwrap();
+        assert_eq!(opportunities.len(), 1);
+        let notional = opportunities[0].quantity * opportunities[0].maker_price;
+        assert!((notional - 10_000.0).abs() < 1e-6, "{}", notional);
     }
 
     #[tokio::test]
-    async fn test_futures_strategy_creation() {
-        let config = create_test_config();
-        let symbols = vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()];
-        let strategy = FuturesArbitrageStrategy::new(config, symbols.clone()).await.unwrap();
-        
-        assert_eq!(strategy.get_state().await, FuturesStrategyState::Stopped);
-        assert_eq!(strategy.get_active_symbols(), &symbols);
+    async fn test_low_confidence_opportunity_sized_down() {
+        let mut config = create_test_config();
+        config.strategy.confidence_sizing.enabled = true;
+        config.strategy.confidence_sizing.max_book_age_ms = 1000;
+
+        // Identical 0.5 BTC books and 20 bps cross; only the book age differs
+        async fn detect(config: ArbitrageConfig, book_age_ms: i64) -> FuturesArbitrageOpportunity {
+            let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+            let timestamp = now_ns() - crate::utils::time::ms_to_ns(book_age_ms);
+            let mut binance_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+            binance_book.update_bid(49990.0, 0.5);
+            binance_book.update_ask(50000.0, 0.5);
+            binance_book.set_timestamp(timestamp);
+            strategy.update_orderbook(Exchange::Binance, binance_book).await.unwrap();
+            let mut bybit_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Bybit);
+            bybit_book.update_bid(50100.0, 0.5);
+            bybit_book.update_ask(50110.0, 0.5);
+            bybit_book.set_timestamp(timestamp);
+            strategy.update_orderbook(Exchange::Bybit, bybit_book).await.unwrap();
+            strategy.detect_opportunities().await.unwrap().remove(0)
+        }
+
+        let confident = detect(config.clone(), 0).await;
+        let doubtful = detect(config, 700).await;
+
+        assert_eq!(confident.risk_score, doubtful.risk_score);
+        assert!(confident.quantity <= 0.5);
+        assert!(doubtful.quantity < confident.quantity * 0.5,
+                "{} vs {}", doubtful.quantity, confident.quantity);
     }
 
     #[tokio::test]
-    async fn test_strategy_state_management() {
-        let config = create_test_config();
-        let symbols = vec!["BTCUSDT".to_string()];
-        let strategy = FuturesArbitrageStrategy::new(config, symbols).await.unwrap();
-        
-        assert_eq!(strategy.get_state().await, FuturesStrategyState::Stopped);
-        
+    async fn test_stale_primary_reference_falls_back() {
+        let mut config = create_test_config();
+        config.exchanges.primary_exchange = "binance".to_string();
+        config.exchanges.reference_exchanges = vec!["bybit".to_string()];
+        config.exchanges.reference_max_age_ms = 1000;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+
+        let book = |exchange: Exchange, mid: f64, age_ms: i64| {
+            let mut book = OrderBook::new("BTCUSDT".to_string(), exchange);
+            book.update_bid(mid - 5.0, 1.0);
+            book.update_ask(mid + 5.0, 1.0);
+            book.set_timestamp(now_ns() - crate::utils::time::ms_to_ns(age_ms));
+            book
+        };
+        strategy.update_orderbook(Exchange::Binance, book(Exchange::Binance, 50000.0, 0)).await.unwrap();
+        strategy.update_orderbook(Exchange::Bybit, book(Exchange::Bybit, 50100.0, 0)).await.unwrap();
+        let reference = strategy.reference_price("BTCUSDT").await.unwrap();
+        assert_eq!((reference.exchange, reference.price), (Exchange::Binance, 50000.0));
+
+        strategy.update_orderbook(Exchange::Binance, book(Exchange::Binance, 50000.0, 5000)).await.unwrap();
+        let reference = strategy.reference_price("BTCUSDT").await.unwrap();
+        assert_eq!((reference.exchange, reference.price), (Exchange::Bybit, 50100.0));
+    }
+
+    #[tokio::test]
+    async fn test_disabled_exchange_produces_no_opportunities() {
+        let strategy = FuturesArbitrageStrategy::new(create_test_config(), vec!["BTCUSDT".to_string()]).await.unwrap();
+        let feed = |exchange: Exchange, bid: f64, ask: f64| {
+            let mut book = OrderBook::new("BTCUSDT".to_string(), exchange);
+            book.update_bid(bid, 1.0);
+            book.update_ask(ask, 1.0);
+            book
+        };
+        strategy.update_orderbook(Exchange::Binance, feed(Exchange::Binance, 49990.0, 50000.0)).await.unwrap();
+        strategy.update_orderbook(Exchange::Bybit, feed(Exchange::Bybit, 50100.0, 50110.0)).await.unwrap();
+        assert_eq!(strategy.detect_opportunities().await.unwrap().len(), 1);
+
+        strategy.apply_control(ControlCommand::DisableExchange(Exchange::Bybit)).await;
+        assert!(strategy.detect_opportunities().await.unwrap().is_empty());
+        strategy.update_orderbook(Exchange::Bybit, feed(Exchange::Bybit, 50100.0, 50110.0)).await.unwrap();
+        assert!(strategy.detect_opportunities().await.unwrap().is_empty());
+
+        strategy.apply_control(ControlCommand::EnableExchange(Exchange::Bybit)).await;
+        strategy.update_orderbook(Exchange::Bybit, feed(Exchange::Bybit, 50100.0, 50110.0)).await.unwrap();
+        let opportunities = strategy.detect_opportunities().await.unwrap();
+        assert_eq!(opportunities.len(), 1);
+        assert_eq!(opportunities[0].maker_exchange, Exchange::Bybit);
+    }
+
+    #[tokio::test]
+    async fn test_thin_levels_below_touch_fail_depth_requirement() {
+        let mut config = create_test_config();
+        config.strategy.min_depth_top_n = 3;
+        config.strategy.min_depth_quantity = 2.0;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+
+        // Fat 1.5 BTC touch on both legs, 0.05 BTC on the next two levels
+        let mut binance_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        binance_book.update_bid(49990.0, 5.0);
+        binance_book.update_ask(50000.0, 1.5);
+        binance_book.update_ask(50001.0, 0.05);
+        binance_book.update_ask(50002.0, 0.05);
+        strategy.update_orderbook(Exchange::Binance, binance_book.clone()).await.unwrap();
+        let mut bybit_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Bybit);
+        bybit_book.update_bid(50100.0, 1.5);
+        bybit_book.update_bid(50099.0, 0.05);
+        bybit_book.update_bid(50098.0, 0.05);
+        bybit_book.update_ask(50110.0, 5.0);
+        strategy.update_orderbook(Exchange::Bybit, bybit_book.clone()).await.unwrap();
+        assert!(strategy.detect_opportunities().await.unwrap().is_empty());
+
+        binance_book.update_ask(50002.0, 1.0);
+        bybit_book.update_bid(50098.0, 1.0);
+        strategy.update_orderbook(Exchange::Binance, binance_book).await.unwrap();
+        strategy.update_orderbook(Exchange::Bybit, bybit_book).await.unwrap();
+        assert_eq!(strategy.detect_opportunities().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_depegged_quote_currency_pauses_trading() {
+        let mut config = create_test_config();
+        config.strategy.max_stablecoin_depeg_bps = 50.0;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+
+        let mut binance_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        binance_book.update_bid(49990.0, 1.0);
+        binance_book.update_ask(50000.0, 1.0);
+        strategy.update_orderbook(Exchange::Binance, binance_book).await.unwrap();
+        let mut bybit_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Bybit);
+        bybit_book.update_bid(50100.0, 1.0);
+        bybit_book.update_ask(50110.0, 1.0);
+        strategy.update_orderbook(Exchange::Bybit, bybit_book).await.unwrap();
+
+        strategy.update_stablecoin_price("USDT", 0.9995).await;
+        assert_eq!(strategy.detect_opportunities().await.unwrap().len(), 1);
+
+        // USDT slips to $0.97: 300 bps off the peg
+        strategy.update_stablecoin_price("USDT", 0.97).await;
+        assert!(strategy.is_quote_paused("USDT").await);
+        assert!(strategy.detect_opportunities().await.unwrap().is_empty());
+
+        strategy.update_stablecoin_price("USDT", 0.999).await;
+        assert_eq!(strategy.detect_opportunities().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_mixed_quote_pnl_aggregates_into_reporting_currency() {
+        let mut config = create_test_config();
+        config.strategy.reporting_currency = "USD".to_string();
+        config.strategy.quote_currencies = vec!["USDT".to_string(), "BTC".to_string()];
+        config.strategy.conversion_rates = HashMap::from([("BTC".to_string(), 60000.0)]);
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string(), "ETHBTC".to_string()]).await.unwrap();
+        // Fetched rate for the stablecoin quote
+        strategy.update_stablecoin_price("USDT", 0.999).await;
+
+        let usdt_leg = create_test_opportunity();
+        let btc_leg = FuturesArbitrageOpportunity {
+            symbol: "ETHBTC".to_string(),
+            maker_price: 0.05,
+            taker_price: 0.04995,
+            quantity: 2.0,
+            expected_profit: 0.0001,
+            ..create_test_opportunity()
+        };
+        strategy.update_execution_statistics(&usdt_leg, Some(usdt_leg.expected_profit)).await;
+        strategy.update_execution_statistics(&btc_leg, Some(btc_leg.expected_profit)).await;
+
+        // 2 USDT * 0.999 + 0.0001 BTC * 60000
+        let stats = strategy.get_statistics().await;
+        assert!((stats.total_pnl - 7.998).abs() < 1e-9, "{}", stats.total_pnl);
+        assert!((stats.total_volume - (0.4 * 50050.0 * 0.999 + 2.0 * 0.05 * 60000.0)).abs() < 1e-6, "{}", stats.total_volume);
+
+        let report = strategy.pnl_report().await;
+        assert_eq!(report.reporting_currency, "USD");
+        assert!((report.total - 7.998).abs() < 1e-9, "{:?}", report);
+        assert_eq!(report.by_currency["BTC"], 0.0001);
+        assert!(report.unconverted.is_empty());
+
+        // A refreshed rate reprices the whole report
+        strategy.update_conversion_rate("BTC", 30000.0).await;
+        assert!((strategy.pnl_report().await.total - 4.998).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_pnl_without_rate_kept_out_of_converted_totals() {
+        let mut config = create_test_config();
+        config.strategy.reporting_currency = "USD".to_string();
+        config.strategy.quote_currencies = vec!["USDT".to_string(), "BTC".to_string()];
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["ETHBTC".to_string()]).await.unwrap();
+        let btc_leg = FuturesArbitrageOpportunity {
+            symbol: "ETHBTC".to_string(),
+            maker_price: 0.05,
+            taker_price: 0.04995,
+            quantity: 2.0,
+            expected_profit: 0.0001,
+            ..create_test_opportunity()
+        };
+        strategy.update_execution_statistics(&btc_leg, Some(btc_leg.expected_profit)).await;
+
+        let stats = strategy.get_statistics().await;
+        assert_eq!(stats.opportunities_executed, 1);
+        assert_eq!(stats.unconverted_executions, 1);
+        assert_eq!((stats.total_pnl, stats.total_volume, stats.total_taker_fees), (0.0, 0.0, 0.0));
+        assert_eq!(stats.pnl_by_currency["BTC"], 0.0001);
+        // Nothing reached the profit-rate monitor, so it never started its window
+        assert_eq!(strategy.profit_rate.write().await.check(i64::MAX / 2), ProfitRateStatus::WarmingUp);
+        assert_eq!(strategy.pnl_report().await.unconverted, vec!["BTC".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_one_sided_book_reports_symbol_not_ready() {
+        let strategy = FuturesArbitrageStrategy::new(create_test_config(), vec!["BTCUSDT".to_string()]).await.unwrap();
         strategy.start().await.unwrap();
-        assert_eq!(strategy.get_state().await, FuturesStrategyState::Running);
-        
-        strategy.stop().await.unwrap();
-        assert_eq!(strategy.get_state().await, FuturesStrategyState::Stopped);
+
+        let mut binance_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        binance_book.update_bid(49990.0, 1.0);
+        binance_book.update_ask(50000.0, 1.0);
+        strategy.update_orderbook(Exchange::Binance, binance_book).await.unwrap();
+
+        // Asks-only Bybit book, priced so the ask side alone would look crossed
+        let mut bybit_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Bybit);
+        bybit_book.update_ask(49000.0, 1.0);
+        strategy.update_orderbook(Exchange::Bybit, bybit_book.clone()).await.unwrap();
+
+        assert!(strategy.detect_opportunities().await.unwrap().is_empty());
+        assert!(!strategy.is_symbol_ready("BTCUSDT").await);
+        assert_eq!(strategy.get_not_ready_symbols().await, vec!["BTCUSDT".to_string()]);
+
+        // Once the bid side arrives the symbol becomes ready
+        bybit_book.update_bid(48990.0, 1.0);
+        strategy.update_orderbook(Exchange::Bybit, bybit_book).await.unwrap();
+        strategy.detect_opportunities().await.unwrap();
+        assert!(strategy.is_symbol_ready("BTCUSDT").await);
     }
 
     #[tokio::test]
-    async fn test_risk_score_calculation() {
-        let config = create_test_config();
-        let symbols = vec!["BTCUSDT".to_string()];
+    async fn test_min_reserve_refuses_order() {
+        let mut config = create_test_config();
+        config.risk.min_reserves.insert(
+            "bybit".to_string(),
+            HashMap::from([("USDT".to_string(), 5_000.0)]),
+        );
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+        let opportunity = create_test_opportunity();
+
+        // 25k available covers the ~20k maker margin, but not once 5k is held in reserve
+        let bybit = MockFuturesConnector::new(25_000.0);
+        let binance = MockFuturesConnector::new(100_000.0);
+        let err = strategy.execute_opportunity(&opportunity, &bybit, &binance).await.unwrap_err();
+
+        assert!(matches!(err.downcast_ref::<ArbitrageError>(), Some(ArbitrageError::RiskManagement(_))));
+        assert_eq!(bybit.placed_count(), 0);
+        assert_eq!(binance.placed_count(), 0);
+
+        // The concurrent engine reserves nothing but still keeps clear of the reserve
+        let mut config = create_test_config();
+        config.execution.execution_engine = ExecutionEngine::Concurrent;
+        config.risk.min_reserves.insert(
+            "bybit".to_string(),
+            HashMap::from([("USDT".to_string(), 5_000.0)]),
+        );
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+        let err = strategy.execute_opportunity(&opportunity, &bybit, &binance).await.unwrap_err();
+
+        assert!(matches!(err.downcast_ref::<ArbitrageError>(), Some(ArbitrageError::RiskManagement(_))));
+        assert_eq!(bybit.placed_count(), 0);
+        assert_eq!(binance.placed_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_leg_rounding_modes() {
+        let mut maker = create_test_contract("BTCUSDT", "BTC");
+        maker.lot_size = 0.001;
+        let mut hedge = create_test_contract("BTCUSDT", "BTC");
+        hedge.lot_size = 0.01;
+
+        let expected = [
+            (RoundingMode::Down, 0.125, 0.12),
+            (RoundingMode::Nearest, 0.126, 0.13),
+            (RoundingMode::TowardHedge, 0.125, 0.13),
+        ];
+        for (mode, expected_maker, expected_hedge) in expected {
+            let mut config = create_test_config();
+            config.execution.rounding_mode = mode;
+            let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+
+            let (maker_quantity, hedge_quantity) = strategy.size_legs(&maker, &hedge, 0.1257);
+            assert!((maker_quantity - expected_maker).abs() < 1e-9, "{:?}: maker {}", mode, maker_quantity);
+            assert!((hedge_quantity - expected_hedge).abs() < 1e-9, "{:?}: hedge {}", mode, hedge_quantity);
+
+            // The orders an opportunity sends are sized the same way
+            strategy.contract_specs.write().await.insert((Exchange::Bybit, "BTCUSDT".to_string()), maker.clone());
+            strategy.contract_specs.write().await.insert((Exchange::Binance, "BTCUSDT".to_string()), hedge.clone());
+            let opportunity = FuturesArbitrageOpportunity { quantity: 0.1257, ..create_test_opportunity() };
+            let plan = strategy.preview_opportunity(&opportunity).await;
+            assert!((plan.maker_order.quantity - expected_maker).abs() < 1e-9, "{:?}: maker order {}", mode, plan.maker_order.quantity);
+            assert!((plan.taker_order.quantity - expected_hedge).abs() < 1e-9, "{:?}: taker order {}", mode, plan.taker_order.quantity);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_large_hedge_is_sliced_against_visible_depth() {
+        let mut config = create_test_config();
+        config.execution.hedge_slice_depth_fraction = 0.5;
+        config.execution.hedge_slice_pause_ms = 1;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+
+        // Only 0.2 BTC shown at the Binance ask against a 0.4 BTC hedge
+        let mut binance_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        binance_book.update_bid(49990.0, 5.0);
+        binance_book.update_ask(50000.0, 0.2);
+        strategy.update_orderbook(Exchange::Binance, binance_book).await.unwrap();
+
+        let opportunity = create_test_opportunity();
+        let slice = strategy.hedge_slice_size(&opportunity).await.unwrap();
+        assert!((slice - 0.1).abs() < 1e-9);
+
+        let binance = MockFuturesConnector::new(100_000.0);
+        let taker_order = strategy.build_taker_order(&opportunity);
+        let filled = strategy.place_sliced_hedge(&opportunity, &taker_order, slice, &binance).await.unwrap();
+
+        let children = binance.placed_orders.lock().unwrap().clone();
+        assert_eq!(children.len(), 4);
+        assert!(children.iter().all(|child| (child.quantity - 0.1).abs() < 1e-9));
+        assert!((filled - taker_order.quantity).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_hedge_children_lot_rounded_resized_and_confirmed() {
+        let mut config = create_test_config();
+        config.execution.hedge_slice_depth_fraction = 0.5;
+        config.execution.hedge_slice_pause_ms = 1;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+
+        // Fills only show up through order status queries
+        let mut binance = MockFuturesConnector::new(100_000.0);
+        binance.unreported_fills = true;
+        binance.extra_listings = vec![FuturesContract { lot_size: 0.01, ..create_test_contract("BTCUSDT", "BTC") }];
+        strategy.load_contract_specs(Exchange::Binance, &binance).await.unwrap();
+
+        // The ask has grown to 0.25 since the first child was sized at 0.1
+        let mut binance_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        binance_book.update_bid(49990.0, 5.0);
+        binance_book.update_ask(50000.0, 0.25);
+        strategy.update_orderbook(Exchange::Binance, binance_book).await.unwrap();
+
+        let opportunity = create_test_opportunity();
+        let taker_order = strategy.build_taker_order(&opportunity);
+        let filled = strategy.place_sliced_hedge(&opportunity, &taker_order, 0.1, &binance).await.unwrap();
+
+        let children: Vec<f64> = binance.placed_orders.lock().unwrap().iter().map(|child| child.quantity).collect();
+        let expected = [0.1, 0.12, 0.12, 0.06];
+        assert_eq!(children.len(), expected.len(), "{:?}", children);
+        assert!(children.iter().zip(expected).all(|(child, expected)| (child - expected).abs() < 1e-9), "{:?}", children);
+        assert!((filled - taker_order.quantity).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_stalled_sliced_hedge_is_unwound() {
+        let mut config = create_test_config();
+        config.execution.hedge_slice_depth_fraction = 0.5;
+        config.execution.hedge_slice_pause_ms = 1;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+
+        let mut binance_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        binance_book.update_bid(49990.0, 5.0);
+        binance_book.update_ask(50000.0, 0.2);
+        strategy.update_orderbook(Exchange::Binance, binance_book).await.unwrap();
+
+        // The venue stops filling after two children
+        let mut binance = MockFuturesConnector::new(100_000.0);
+        binance.market_fills = Some(2);
+        let opportunity = create_test_opportunity();
+        let taker_order = strategy.build_taker_order(&opportunity);
+        assert!(strategy.place_sliced_hedge(&opportunity, &taker_order, 0.1, &binance).await.is_err());
+
+        // Third child, one order for the remaining 0.2, then the 0.2 filled is closed
+        let placed = binance.placed_orders.lock().unwrap().clone();
+        assert_eq!(placed.len(), 5);
+        assert!((placed[3].quantity - 0.2).abs() < 1e-9);
+        let unwind = &placed[4];
+        assert!(unwind.reduce_only);
+        assert_eq!(unwind.side, OrderSide::Sell);
+        assert!((unwind.quantity - 0.2).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_opportunity_logs_share_correlation_id() {
+        use std::io::Write;
+        use std::sync::{Arc as StdArc, Mutex as StdMutex};
+
+        #[derive(Clone)]
+        struct CaptureWriter(StdArc<StdMutex<Vec<u8>>>);
+
+        impl Write for CaptureWriter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let captured = StdArc::new(StdMutex::new(Vec::new()));
+        let writer = CaptureWriter(captured.clone());
+        let subscriber = tracing_subscriber::fmt()
+            .json()
+            .with_current_span(true)
+            .with_max_level(tracing::Level::DEBUG)
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let strategy = FuturesArbitrageStrategy::new(create_test_config(), vec!["BTCUSDT".to_string()]).await.unwrap();
+        let mut binance_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        binance_book.update_bid(49990.0, 1.0);
+        binance_book.update_ask(50000.0, 1.0);
+        let mut bybit_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Bybit);
+        bybit_book.update_bid(50100.0, 1.0);
+        bybit_book.update_ask(50110.0, 1.0);
+        strategy.update_orderbook(Exchange::Binance, binance_book).await.unwrap();
+        strategy.update_orderbook(Exchange::Bybit, bybit_book).await.unwrap();
+
+        let opportunity = strategy.detect_opportunities().await.unwrap().remove(0);
+        let bybit = MockFuturesConnector::new(100_000.0);
+        let binance = MockFuturesConnector::new(100_000.0);
+        strategy.execute_opportunity(&opportunity, &bybit, &binance).await.unwrap();
+
+        let output = String::from_utf8(captured.lock().unwrap().clone()).unwrap();
+        let correlated: Vec<(String, String)> = output
+            .lines()
+            .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+            .filter_map(|event| {
+                let correlation_id = event["span"]["correlation_id"].as_str()?.to_string();
+                let message = event["fields"]["message"].as_str()?.to_string();
+                Some((correlation_id, message))
+            })
+            .collect();
+
+        for expected in ["Opportunity BTCUSDT", "Sized legs", "Maker order placed", "Taker order placed", "expected PnL"] {
+            assert!(correlated.iter().any(|(_, message)| message.contains(expected)), "missing {:?} in {:?}", expected, correlated);
+        }
+        assert!(correlated.iter().all(|(correlation_id, _)| *correlation_id == opportunity.correlation_id));
+
+        let maker_id = bybit.placed_orders.lock().unwrap()[0].client_order_id.clone().unwrap();
+        let taker_id = binance.placed_orders.lock().unwrap()[0].client_order_id.clone().unwrap();
+        assert!(maker_id.ends_with(&opportunity.correlation_id) && taker_id.ends_with(&opportunity.correlation_id));
+    }
+
+    #[tokio::test]
+    async fn test_opportunity_logging_capped_while_all_are_counted() {
+        use std::io::Write;
+        use std::sync::{Arc as StdArc, Mutex as StdMutex};
+
+        #[derive(Clone)]
+        struct CaptureWriter(StdArc<StdMutex<Vec<u8>>>);
+
+        impl Write for CaptureWriter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let captured = StdArc::new(StdMutex::new(Vec::new()));
+        let writer = CaptureWriter(captured.clone());
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mut config = create_test_config();
+        config.strategy.opportunity_log_limit = 1;
+        config.strategy.opportunity_log_interval_ms = 60_000;
+        let symbols = vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()];
         let strategy = FuturesArbitrageStrategy::new(config, symbols).await.unwrap();
-        
-        // Low spread, high quantity = high risk
-        let high_risk = strategy.calculate_risk_score(5.0, 2.0).await;
-        assert!(high_risk > 50.0);
-        
-        // High spread, low quantity = low risk
-        let low_risk = strategy.calculate_risk_score(25.0, 0.1).await;
-        assert!(low_risk < 30.0);
+        // ETH is crossed much wider than BTC, so its opportunity is the more profitable one
+        for (symbol, binance_ask, bybit_bid) in [("BTCUSDT", 50000.0, 50100.0), ("ETHUSDT", 3000.0, 3150.0)] {
+            let mut binance_book = OrderBook::new(symbol.to_string(), Exchange::Binance);
+            binance_book.update_bid(binance_ask - 10.0, 1.0);
+            binance_book.update_ask(binance_ask, 1.0);
+            strategy.update_orderbook(Exchange::Binance, binance_book).await.unwrap();
+            let mut bybit_book = OrderBook::new(symbol.to_string(), Exchange::Bybit);
+            bybit_book.update_bid(bybit_bid, 1.0);
+            bybit_book.update_ask(bybit_bid + 10.0, 1.0);
+            strategy.update_orderbook(Exchange::Bybit, bybit_book).await.unwrap();
+        }
+
+        let mut detected = Vec::new();
+        for _ in 0..10 {
+            detected.extend(strategy.detect_opportunities().await.unwrap());
+        }
+        assert_eq!(detected.len(), 20);
+        let best = detected.iter().max_by(|a, b| a.expected_profit.partial_cmp(&b.expected_profit).unwrap()).unwrap();
+        assert_eq!(best.symbol, "ETHUSDT");
+
+        let output = String::from_utf8(captured.lock().unwrap().clone()).unwrap();
+        let logged: Vec<&str> = output.lines().filter(|line| line.contains("Opportunity ")).collect();
+        assert_eq!(logged.len(), 1, "{:?}", logged);
+        assert!(logged[0].contains("Opportunity ETHUSDT"), "{}", logged[0]);
+
+        let stats = strategy.get_statistics().await;
+        assert_eq!(stats.opportunities_detected, 20);
+        assert_eq!(stats.opportunity_logs_suppressed, 19);
+    }
+
+    #[tokio::test]
+    async fn test_min_execution_interval_defers_second_opportunity() {
+        let mut config = create_test_config();
+        config.strategy.min_execution_interval_ms = 100;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+        let bybit = MockFuturesConnector::new(100_000.0);
+        let binance = MockFuturesConnector::new(100_000.0);
+
+        strategy.execute_opportunity(&create_test_opportunity(), &bybit, &binance).await.unwrap();
+        let err = strategy.execute_opportunity(&create_test_opportunity(), &bybit, &binance).await.unwrap_err();
+        assert!(err.to_string().contains("deferred"), "{}", err);
+        assert_eq!(bybit.placed_count(), 1);
+
+        tokio::time::sleep(std::time::Duration::from_millis(110)).await;
+        strategy.execute_opportunity(&create_test_opportunity(), &bybit, &binance).await.unwrap();
+        assert_eq!(bybit.placed_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_post_fill_cooldown_skips_symbol_then_resumes() {
+        let mut config = create_test_config();
+        config.strategy.post_fill_cooldown_ms = 100;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+        let bybit = MockFuturesConnector::new(100_000.0);
+        let binance = MockFuturesConnector::new(100_000.0);
+
+        strategy.execute_opportunity(&create_test_opportunity(), &bybit, &binance).await.unwrap();
+        let err = strategy.execute_opportunity(&create_test_opportunity(), &bybit, &binance).await.unwrap_err();
+        assert!(err.to_string().contains("post-fill cooldown"), "{}", err);
+        assert_eq!(bybit.placed_count(), 1);
+
+        tokio::time::sleep(std::time::Duration::from_millis(110)).await;
+        strategy.execute_opportunity(&create_test_opportunity(), &bybit, &binance).await.unwrap();
+        assert_eq!(bybit.placed_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_orders_sized_in_venue_contract_units() {
+        let strategy = FuturesArbitrageStrategy::new(create_test_config(), vec!["BTCUSDT".to_string()]).await.unwrap();
+        let bybit = MockFuturesConnector::new(100_000.0);
+        let binance = MockFuturesConnector::new(100_000.0);
+        strategy.load_contract_specs(Exchange::Binance, &binance).await.unwrap();
+
+        // Bybit quotes BTCUSDT in contracts of 0.001 BTC
+        let mut contract = create_test_contract("BTCUSDT", "BTC");
+        contract.contract_multiplier = 0.001;
+        contract.quantity_unit = QuantityUnit::Contracts;
+        strategy.contract_specs.write().await.insert((Exchange::Bybit, "BTCUSDT".to_string()), contract);
+
+        let opportunity = create_test_opportunity();
+        strategy.execute_opportunity(&opportunity, &bybit, &binance).await.unwrap();
+
+        let maker = bybit.placed_orders.lock().unwrap()[0].clone();
+        let taker = binance.placed_orders.lock().unwrap()[0].clone();
+        assert!((maker.quantity - opportunity.quantity * 1000.0).abs() < 1e-9, "{}", maker.quantity);
+        assert!((taker.quantity - opportunity.quantity).abs() < 1e-9, "{}", taker.quantity);
+    }
+
+    #[tokio::test]
+    async fn test_preview_matches_sizing_and_rounding_rules() {
+        let mut config = create_test_config();
+        config.execution.maker_improvement_ticks = 3;
+        config.execution.preview_only = true;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+
+        // Bybit quotes BTCUSDT in contracts of 0.001 BTC on a 0.5 tick
+        let mut contract = create_test_contract("BTCUSDT", "BTC");
+        contract.tick_size = 0.5;
+        contract.contract_multiplier = 0.001;
+        contract.quantity_unit = QuantityUnit::Contracts;
+        strategy.contract_specs.write().await.insert((Exchange::Bybit, "BTCUSDT".to_string()), contract);
+
+        let mut binance_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        binance_book.update_bid(49990.0, 5.0);
+        binance_book.update_ask(50000.0, 0.1);
+        binance_book.update_ask(50010.0, 1.0);
+        strategy.update_orderbook(Exchange::Binance, binance_book).await.unwrap();
+
+        let plan = strategy.preview_opportunity(&create_test_opportunity()).await;
+        assert_eq!(plan.maker_order.price, Some(50048.5));
+        assert!((plan.maker_order.quantity - 400.0).abs() < 1e-9, "{}", plan.maker_order.quantity);
+        assert_eq!(plan.maker_order.time_in_force, FuturesTimeInForce::GTX);
+        assert!((plan.taker_order.quantity - 0.4).abs() < 1e-9, "{}", plan.taker_order.quantity);
+        assert!((plan.projected_taker_quantity - 0.4).abs() < 1e-9);
+        assert!((plan.projected_taker_price - 50007.5).abs() < 1e-9, "{}", plan.projected_taker_price);
+        assert!((plan.projected_slippage_bps - 1.5).abs() < 1e-9, "{}", plan.projected_slippage_bps);
+
+        // With preview_only nothing reaches the exchanges
+        let bybit = MockFuturesConnector::new(100_000.0);
+        let binance = MockFuturesConnector::new(100_000.0);
+        strategy.execute_opportunity(&create_test_opportunity(), &bybit, &binance).await.unwrap();
+        assert_eq!(bybit.placed_count() + binance.placed_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_canary_precedes_full_size_on_idle_venue() {
+        let mut config = create_test_config();
+        config.execution.canary_size = 0.001;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+        let bybit = MockFuturesConnector::new(100_000.0);
+        let binance = MockFuturesConnector::new(100_000.0);
+        let mut binance_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        binance_book.update_bid(49990.0, 5.0);
+        binance_book.update_ask(50000.0, 5.0);
+        strategy.update_orderbook(Exchange::Binance, binance_book).await.unwrap();
+
+        // Canaries rest post-only behind the touch and are cancelled before the full-size legs
+        let opportunity = create_test_opportunity();
+        strategy.execute_opportunity(&opportunity, &bybit, &binance).await.unwrap();
+        for (connector, canary_price) in [(&bybit, 50050.0), (&binance, 49990.0)] {
+            let orders = connector.placed_orders.lock().unwrap().clone();
+            assert_eq!(orders.len(), 2);
+            assert_eq!(orders[0].quantity, 0.001);
+            assert_eq!(orders[0].order_type, FuturesOrderType::Limit);
+            assert_eq!(orders[0].time_in_force, FuturesTimeInForce::GTX);
+            assert_eq!(orders[0].price, Some(canary_price));
+            assert!(orders[0].client_order_id.as_deref().unwrap().starts_with("canary_"));
+            assert_eq!(connector.journal.lock().unwrap()[1], "mock cancel order_1");
+            assert!((orders[1].quantity - opportunity.quantity).abs() < 1e-9);
+        }
+
+        // Both venues just traded, so the next execution goes straight to full size
+        strategy.execute_opportunity(&opportunity, &bybit, &binance).await.unwrap();
+        assert_eq!(bybit.placed_count(), 3);
+        assert_eq!(binance.placed_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_engines_follow_their_rollback_order_on_a_failed_leg() {
+        async fn run(engine: ExecutionEngine, failing: Exchange) -> Vec<String> {
+            let mut config = create_test_config();
+            config.execution.execution_engine = engine;
+            let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+            let journal = Arc::new(std::sync::Mutex::new(Vec::new()));
+            let bybit = MockFuturesConnector {
+                label: "bybit",
+                journal: journal.clone(),
+                reject_orders: failing == Exchange::Bybit,
+                ..MockFuturesConnector::new(100_000.0)
+            };
+            let binance = MockFuturesConnector {
+                label: "binance",
+                journal: journal.clone(),
+                reject_orders: failing == Exchange::Binance,
+                ..MockFuturesConnector::new(100_000.0)
+            };
+            strategy.execute_opportunity(&create_test_opportunity(), &bybit, &binance).await.unwrap_err();
+            assert!(strategy.reserved_margin.read().await.values().all(|amount| *amount == 0.0));
+            let entries = journal.lock().unwrap().clone();
+            entries
+        }
+
+        // Atomic: the hedge only goes out after the maker, and a failed maker sends nothing else
+        assert_eq!(run(ExecutionEngine::Atomic, Exchange::Binance).await, vec!["bybit place SELL", "binance reject", "bybit cancel order_1"]);
+        assert_eq!(run(ExecutionEngine::Atomic, Exchange::Bybit).await, vec!["bybit reject"]);
+
+        // Concurrent: both legs go out, and the surviving leg is undone
+        assert_eq!(run(ExecutionEngine::Concurrent, Exchange::Binance).await, vec!["bybit place SELL", "binance reject", "bybit cancel order_1"]);
+        assert_eq!(run(ExecutionEngine::Concurrent, Exchange::Bybit).await, vec!["bybit reject", "binance place BUY", "binance place SELL reduce_only"]);
+    }
+
+    #[tokio::test]
+    async fn test_canary_failure_aborts_full_execution() {
+        let mut config = create_test_config();
+        config.execution.canary_size = 0.001;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+        let mut bybit = MockFuturesConnector::new(100_000.0);
+        bybit.reject_orders = true;
+        let binance = MockFuturesConnector::new(100_000.0);
+
+        let err = strategy.execute_opportunity(&create_test_opportunity(), &bybit, &binance).await.unwrap_err();
+        assert!(err.to_string().contains("Canary order on bybit failed"), "{}", err);
+        assert_eq!(binance.placed_count(), 0);
+        assert!(strategy.reserved_margin.read().await.values().all(|amount| *amount == 0.0));
+
+        // A venue that fails the margin reservation is never probed
+        let bybit = MockFuturesConnector::new(100_000.0);
+        let underfunded = MockFuturesConnector::new(100.0);
+        strategy.execute_opportunity(&create_test_opportunity(), &bybit, &underfunded).await.unwrap_err();
+        assert_eq!(bybit.placed_count(), 0);
+        assert_eq!(underfunded.placed_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_fill_prices_reconciled_against_expected_spread() {
+        let strategy = FuturesArbitrageStrategy::new(create_test_config(), vec!["BTCUSDT".to_string()]).await.unwrap();
+        let mut bybit = MockFuturesConnector::new(100_000.0);
+        bybit.fill_price = Some(50045.0);
+        let mut binance = MockFuturesConnector::new(100_000.0);
+        binance.fill_price = Some(50010.0);
+
+        // Assumed 50 USD per BTC of spread, realized 35 on 0.4 BTC
+        let opportunity = create_test_opportunity();
+        strategy.execute_opportunity(&opportunity, &bybit, &binance).await.unwrap();
+
+        let stats = strategy.get_statistics().await;
+        assert_eq!(stats.reconciled_executions, 1);
+        assert!((stats.fill_slippage_usd - -6.0).abs() < 1e-9, "{}", stats.fill_slippage_usd);
+        assert!((stats.last_fill_slippage_bps - -3.0).abs() < 1e-9, "{}", stats.last_fill_slippage_bps);
+
+        // The PnL recorded is the one the fills realized, not the opportunity's estimate
+        let realized = 35.0 * 0.4 + 50045.0 * 0.4 * opportunity.maker_fee.abs() - 50010.0 * 0.4 * opportunity.taker_fee;
+        assert!((stats.total_pnl - realized).abs() < 1e-9, "{} vs {}", stats.total_pnl, realized);
+        let window_secs = strategy.config.strategy.profit_rate_window_secs as f64;
+        let rate = strategy.profit_rate.write().await.rate_per_hour(strategy.clock.monotonic_ms());
+        assert!((rate - realized * 3600.0 / window_secs).abs() < 1e-6, "{}", rate);
+    }
+
+    #[tokio::test]
+    async fn test_spread_capture_histogram_scraped() {
+        let mut config = create_test_config();
+        config.monitoring.prometheus.enabled = true;
+        config.monitoring.prometheus.listen_addr = "127.0.0.1:0".to_string();
+        config.monitoring.prometheus.spread_capture_buckets_bps = vec![5.0, 10.0];
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+        let addr = strategy.metrics_exporter().unwrap().start_server().await.unwrap();
+        let mut bybit = MockFuturesConnector::new(100_000.0);
+        bybit.fill_price = Some(50045.0);
+        let mut binance = MockFuturesConnector::new(100_000.0);
+        binance.fill_price = Some(50010.0);
+
+        // Each execution realizes 35 USD on a 50000 buy, 7 bps
+        strategy.execute_opportunity(&create_test_opportunity(), &bybit, &binance).await.unwrap();
+        strategy.execute_opportunity(&create_test_opportunity(), &bybit, &binance).await.unwrap();
+
+        let body = reqwest::get(format!("http://{}/metrics", addr)).await.unwrap().text().await.unwrap();
+        assert!(body.contains("# TYPE arbitrage_spread_capture_bps histogram"), "{}", body);
+        assert!(body.contains("arbitrage_spread_capture_bps_bucket{le=\"5\"} 0\n"), "{}", body);
+        assert!(body.contains("arbitrage_spread_capture_bps_bucket{le=\"10\"} 2\n"), "{}", body);
+        assert!(body.contains("arbitrage_spread_capture_bps_count 2\n"), "{}", body);
+    }
+
+    #[tokio::test]
+    async fn test_adverse_move_aborts_maker_on_last_look() {
+        let mut config = create_test_config();
+        config.execution.last_look_tolerance_bps = 5.0;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+        let bybit = MockFuturesConnector::new(100_000.0);
+        let binance = MockFuturesConnector::new(100_000.0);
+        let feed = |exchange: Exchange, bid: f64, ask: f64| {
+            let mut book = OrderBook::new("BTCUSDT".to_string(), exchange);
+            book.update_bid(bid, 1.0);
+            book.update_ask(ask, 1.0);
+            book
+        };
+
+        // Detected selling 0.4 BTC on Bybit at 50050 and buying on Binance at 50000
+        strategy.update_orderbook(Exchange::Binance, feed(Exchange::Binance, 49990.0, 50000.0)).await.unwrap();
+        strategy.update_orderbook(Exchange::Bybit, feed(Exchange::Bybit, 50050.0, 50060.0)).await.unwrap();
+        strategy.execute_opportunity(&create_test_opportunity(), &bybit, &binance).await.unwrap();
+        assert_eq!(bybit.placed_count(), 1);
+
+        // Bybit rallies 20 bps before the maker order goes out
+        strategy.update_orderbook(Exchange::Bybit, feed(Exchange::Bybit, 50150.0, 50160.0)).await.unwrap();
+        let err = strategy.execute_opportunity(&create_test_opportunity(), &bybit, &binance).await.unwrap_err();
+        assert!(err.to_string().contains("last-look"), "{}", err);
+        assert_eq!(bybit.placed_count(), 1);
+        assert_eq!(binance.placed_count(), 1);
+        assert_eq!(strategy.get_statistics().await.last_look_aborts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_stale_queued_opportunity_is_dropped() {
+        let mut config = create_test_config();
+        config.strategy.max_decision_latency_ms = 50;
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+        let bybit = MockFuturesConnector::new(100_000.0);
+        let binance = MockFuturesConnector::new(100_000.0);
+
+        let queued = FuturesArbitrageOpportunity {
+            deadline_ms: strategy.opportunity_deadline_ms(),
+            ..create_test_opportunity()
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(60)).await;
+
+        let err = strategy.execute_opportunity(&queued, &bybit, &binance).await.unwrap_err();
+        assert!(err.to_string().contains("expired"), "{}", err);
+        assert_eq!(bybit.placed_count(), 0);
+        assert_eq!(strategy.get_statistics().await.expired_opportunities, 1);
+
+        // A fresh opportunity within its deadline still executes
+        let fresh = FuturesArbitrageOpportunity {
+            deadline_ms: strategy.opportunity_deadline_ms(),
+            ..create_test_opportunity()
+        };
+        strategy.execute_opportunity(&fresh, &bybit, &binance).await.unwrap();
+        assert_eq!(bybit.placed_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_stale_mark_bypasses_deviation_guard_with_warning() {
+        use crate::utils::time::{ms_to_ns, ManualClock};
+
+        let mut config = create_test_config();
+        config.execution.max_mark_deviation_bps = 50.0;
+        config.execution.max_mark_price_age_ms = 1000;
+        let clock = Arc::new(ManualClock::new(ms_to_ns(1_700_000_000_000)));
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap()
+            .with_clock(clock.clone());
+        let bybit = MockFuturesConnector::new(100_000.0);
+        let binance = MockFuturesConnector::new(100_000.0);
+        // A mark 2% below the maker price would trip the guard if it were trusted
+        let mark = |timestamp: i64| MarkPrice {
+            symbol: "BTCUSDT".to_string(),
+            mark_price: 49050.0,
+            index_price: 49050.0,
+            estimated_settle_price: None,
+            last_funding_rate: 0.0,
+            next_funding_time: 0,
+            interest_rate: 0.0,
+            timestamp,
+        };
+
+        strategy.update_mark_price(Exchange::Bybit, mark(clock.wall_ns() - ms_to_ns(5000))).await.unwrap();
+        strategy.execute_opportunity(&create_test_opportunity(), &bybit, &binance).await.unwrap();
+        let stats = strategy.get_statistics().await;
+        assert_eq!(stats.stale_mark_prices, 1);
+        assert_eq!(stats.mark_deviation_aborts, 0);
+        assert_eq!(bybit.placed_count(), 1);
+
+        strategy.update_mark_price(Exchange::Bybit, mark(clock.wall_ns())).await.unwrap();
+        let err = strategy.execute_opportunity(&create_test_opportunity(), &bybit, &binance).await.unwrap_err();
+        assert!(err.to_string().contains("deviates"), "{}", err);
+        assert_eq!(strategy.get_statistics().await.mark_deviation_aborts, 1);
+        assert_eq!(bybit.placed_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_backward_wall_clock_jump_keeps_latencies_sane() {
+        use crate::utils::time::{ms_to_ns, s_to_ns, ManualClock};
+
+        let mut config = create_test_config();
+        config.strategy.max_decision_latency_ms = 50;
+        let clock = Arc::new(ManualClock::new(ms_to_ns(1_700_000_000_000)));
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap()
+            .with_clock(clock.clone());
+        let bybit = MockFuturesConnector::new(100_000.0);
+        let binance = MockFuturesConnector::new(100_000.0);
+
+        let mut book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        book.update_bid(49995.0, 1.0);
+        book.update_ask(50005.0, 1.0);
+        book.set_timestamp(clock.wall_ns());
+        strategy.update_orderbook(Exchange::Binance, book).await.unwrap();
+        let queued = FuturesArbitrageOpportunity {
+            deadline_ms: strategy.opportunity_deadline_ms(),
+            ..create_test_opportunity()
+        };
+
+        // NTP steps the wall clock back an hour while 10 ms really pass
+        clock.set_wall_ns(clock.wall_ns() - s_to_ns(3600));
+        clock.advance(std::time::Duration::from_millis(10));
+
+        let reference = strategy.reference_price("BTCUSDT").await.unwrap();
+        assert_eq!(reference.age_ms, 0);
+        strategy.execute_opportunity(&queued, &bybit, &binance).await.unwrap();
+        assert_eq!(strategy.get_statistics().await.expired_opportunities, 0);
+        assert_eq!(strategy.check_opportunity_watchdog().await, WatchdogStatus::WarmingUp);
+    }
+
+    #[tokio::test]
+    async fn test_position_side_follows_each_venue_position_mode() {
+        let mut config = create_test_config();
+        config.execution.position_modes.insert("binance".to_string(), PositionMode::OneWay);
+        let strategy = FuturesArbitrageStrategy::new(config, vec!["BTCUSDT".to_string()]).await.unwrap();
+
+        // Bybit is detected as hedged; Binance's configured mode wins over detection
+        let mut bybit = MockFuturesConnector::new(100_000.0);
+        bybit.position_mode = Some(PositionMode::Hedge);
+        let mut binance = MockFuturesConnector::new(100_000.0);
+        binance.position_mode = Some(PositionMode::Hedge);
+
+        strategy.execute_opportunity(&create_test_opportunity(), &bybit, &binance).await.unwrap();
+        let maker = bybit.placed_orders.lock().unwrap()[0].clone();
+        let taker = binance.placed_orders.lock().unwrap()[0].clone();
+        assert_eq!((maker.side, maker.position_side), (OrderSide::Sell, Some(PositionSide::Short)));
+        assert_eq!((taker.side, taker.position_side), (OrderSide::Buy, Some(PositionSide::Both)));
+
+        assert_eq!(PositionMode::Hedge.position_side(OrderSide::Buy, false), PositionSide::Long);
+        assert_eq!(PositionMode::Hedge.position_side(OrderSide::Buy, true), PositionSide::Short);
+        assert_eq!(PositionMode::OneWay.position_side(OrderSide::Sell, true), PositionSide::Both);
     }
 }
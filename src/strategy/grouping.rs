@@ -0,0 +1,71 @@
+//! Price-band aggregation of order book levels
+//!
+//! Very granular books spread liquidity across many tiny ticks, which makes
+//! top-N depth checks noisy. Grouping buckets levels into fixed-width price
+//! bands and sums their quantities. Bids fall into the band below their price
+//! and asks into the band above, so a grouped book never looks tighter than
+//! the raw one.
+
+use crate::{connectors::OrderSide, data::OrderBook, strategy::book_levels::BookLevels};
+
+/// Tolerance for prices that sit on a band boundary up to float error
+const BAND_EPSILON: f64 = 1e-9;
+
+/// Price-band grouping of book levels
+pub trait GroupedLevels {
+    /// Copy of the book with levels bucketed into `band`-wide price groups and
+    /// their quantities summed; a non-positive band returns an unchanged copy
+    fn grouped(&self, band: f64) -> OrderBook;
+}
+
+impl GroupedLevels for OrderBook {
+    fn grouped(&self, band: f64) -> OrderBook {
+        if !band.is_finite() || band <= 0.0 {
+            return self.clone();
+        }
+        OrderBook {
+            bids: group_levels(&self.best_n(OrderSide::Buy, usize::MAX), band, |units| (units + BAND_EPSILON).floor()),
+            asks: group_levels(&self.best_n(OrderSide::Sell, usize::MAX), band, |units| (units - BAND_EPSILON).ceil()),
+            ..self.clone()
+        }
+    }
+}
+
+/// Merge sorted levels that land in the same band, keeping the input order
+fn group_levels(levels: &[(f64, f64)], band: f64, to_band: impl Fn(f64) -> f64) -> Vec<(f64, f64)> {
+    let mut grouped: Vec<(f64, f64)> = Vec::new();
+    let mut last_band = None;
+    for &(price, quantity) in levels {
+        let index = to_band(price / band);
+        match grouped.last_mut() {
+            Some(level) if last_band == Some(index) => level.1 += quantity,
+            _ => grouped.push((index * band, quantity)),
+        }
+        last_band = Some(index);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::Exchange;
+
+    #[test]
+    fn test_levels_within_a_band_are_merged() {
+        let mut book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        for (price, quantity) in [(50009.9, 1.0), (50005.0, 0.5), (50000.0, 0.25), (49999.9, 2.0)] {
+            book.update_bid(price, quantity);
+        }
+        for (price, quantity) in [(50010.1, 1.0), (50019.0, 0.5), (50020.0, 0.25), (50020.1, 3.0)] {
+            book.update_ask(price, quantity);
+        }
+
+        let grouped = book.grouped(10.0);
+        assert_eq!(grouped.bids, vec![(50000.0, 1.75), (49990.0, 2.0)]);
+        assert_eq!(grouped.asks, vec![(50020.0, 1.75), (50030.0, 3.0)]);
+        assert_eq!(grouped.symbol, "BTCUSDT");
+
+        assert_eq!(book.grouped(0.0).bids, book.bids);
+    }
+}
@@ -0,0 +1,251 @@
+//! Periodic integrity check of cached order books
+//!
+//! Long sessions can leave a cached book inconsistent after a missed delta or
+//! a bad merge. [`check_book`] finds structural defects (unsorted levels, a
+//! crossed touch, non-positive prices or negative quantities) and
+//! [`BookIntegrityChecker`] scans the cached books on an interval, replacing
+//! any that fail with a fresh REST snapshot.
+
+use crate::{
+    config::MonitoringConfig,
+    connectors::{Exchange, ExchangeConnector, MarketDataUpdate, OrderSide},
+    data::{MarketDataManager, OrderBook},
+    strategy::book_levels::BookLevels,
+    utils::time::now_ns,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+/// A structural defect found in a book
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BookDefect {
+    /// Bids are not in strictly descending price order
+    UnsortedBids,
+    /// Asks are not in strictly ascending price order
+    UnsortedAsks,
+    /// Best bid is at or above best ask
+    Crossed,
+    /// A level has a non-positive or non-finite price, or a negative quantity
+    InvalidLevel,
+}
+
+/// Defects in a book, empty when it is consistent
+pub fn check_book(book: &OrderBook) -> Vec<BookDefect> {
+    let bids = book.best_n(OrderSide::Buy, usize::MAX);
+    let asks = book.best_n(OrderSide::Sell, usize::MAX);
+    let mut defects = Vec::new();
+    if bids.windows(2).any(|pair| pair[0].0 <= pair[1].0) {
+        defects.push(BookDefect::UnsortedBids);
+    }
+    if asks.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+        defects.push(BookDefect::UnsortedAsks);
+    }
+    if let (Some(bid), Some(ask)) = (bids.first(), asks.first()) {
+        if bid.0 >= ask.0 {
+            defects.push(BookDefect::Crossed);
+        }
+    }
+    let invalid = |&(price, quantity): &(f64, f64)| !price.is_finite() || price <= 0.0 || quantity.is_nan() || quantity < 0.0;
+    if bids.iter().chain(&asks).any(invalid) {
+        defects.push(BookDefect::InvalidLevel);
+    }
+    defects
+}
+
+/// A cached book that failed the check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlaggedBook {
+    /// Exchange the book is cached for
+    pub exchange: Exchange,
+    /// Symbol the book is cached under
+    pub symbol: String,
+    /// Defects found
+    pub defects: Vec<BookDefect>,
+    /// Whether a REST snapshot replaced the book
+    pub resynced: bool,
+}
+
+/// Scans cached books and re-snapshots those that fail [`check_book`]
+#[derive(Debug, Clone)]
+pub struct BookIntegrityChecker {
+    interval: Duration,
+    /// Books flagged so far
+    flagged: u64,
+}
+
+impl BookIntegrityChecker {
+    /// Create a checker scanning every `interval`
+    pub fn new(interval: Duration) -> Self {
+        Self { interval, flagged: 0 }
+    }
+
+    /// The configured checker, or `None` when `book_integrity_interval_ms` is 0
+    pub fn from_config(config: &MonitoringConfig) -> Option<Self> {
+        (config.book_integrity_interval_ms > 0).then(|| Self::new(Duration::from_millis(config.book_integrity_interval_ms)))
+    }
+
+    /// Books flagged so far
+    pub fn flagged(&self) -> u64 {
+        self.flagged
+    }
+
+    /// Check each `(exchange, symbol)` book once, replacing corrupted ones
+    /// with a snapshot from that exchange's connector
+    pub async fn scan(
+        &mut self,
+        market_data: &MarketDataManager,
+        books: &[(Exchange, String)],
+        connectors: &HashMap<Exchange, Box<dyn ExchangeConnector + Send + Sync>>,
+    ) -> Vec<FlaggedBook> {
+        let mut flagged = Vec::new();
+        for (exchange, symbol) in books {
+            let Some(book) = market_data.get_orderbook(*exchange, symbol).await else {
+                continue;
+            };
+            let defects = check_book(&book);
+            if defects.is_empty() {
+                continue;
+            }
+            warn!("Order book {} on {} failed integrity check: {:?}", symbol, exchange, defects);
+            self.flagged += 1;
+
+            let resynced = match connectors.get(exchange) {
+                Some(connector) => match connector.get_orderbook(symbol).await {
+                    Ok(orderbook) if check_book(&orderbook).is_empty() => {
+                        market_data.process_update(MarketDataUpdate::OrderBook {
+                            exchange: exchange.to_string(),
+                            symbol: symbol.clone(),
+                            orderbook,
+                            exchange_timestamp: None,
+                            local_recv_timestamp: now_ns(),
+                        }).await;
+                        info!("Re-snapshotted order book {} on {}", symbol, exchange);
+                        true
+                    }
+                    Ok(_) => {
+                        warn!("REST snapshot of {} on {} is also inconsistent", symbol, exchange);
+                        false
+                    }
+                    Err(e) => {
+                        warn!("Failed to re-snapshot {} on {}: {}", symbol, exchange, e);
+                        false
+                    }
+                },
+                None => false,
+            };
+            flagged.push(FlaggedBook { exchange: *exchange, symbol: symbol.clone(), defects, resynced });
+        }
+        flagged
+    }
+
+    /// Scan the books every interval until the task is dropped
+    pub async fn run(
+        mut self,
+        market_data: Arc<RwLock<MarketDataManager>>,
+        books: Vec<(Exchange, String)>,
+        connectors: Arc<RwLock<HashMap<Exchange, Box<dyn ExchangeConnector + Send + Sync>>>>,
+    ) {
+        let mut interval = tokio::time::interval(self.interval);
+        loop {
+            interval.tick().await;
+            let market_data = market_data.read().await;
+            let connectors = connectors.read().await;
+            self.scan(&market_data, &books, &connectors).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::{Balance, ConnectionStatus, LimitOrder, OcoOrder, OcoResponse, OrderResponse, OrderStatus};
+    use crate::{ArbitrageError, Result};
+
+    fn book(bids: Vec<(f64, f64)>, asks: Vec<(f64, f64)>) -> OrderBook {
+        OrderBook { bids, asks, ..OrderBook::new("BTCUSDT".to_string(), Exchange::Binance) }
+    }
+
+    /// Serves a consistent snapshot for every symbol
+    struct SnapshotConnector;
+
+    #[async_trait::async_trait]
+    impl ExchangeConnector for SnapshotConnector {
+        async fn connect(&mut self) -> Result<()> { Ok(()) }
+        async fn disconnect(&mut self) -> Result<()> { Ok(()) }
+        fn is_connected(&self) -> bool { true }
+        fn connection_status(&self) -> ConnectionStatus { ConnectionStatus::Connected }
+        async fn subscribe_orderbook(&mut self, _symbol: &str) -> Result<()> { Ok(()) }
+        async fn subscribe_trades(&mut self, _symbol: &str) -> Result<()> { Ok(()) }
+        async fn subscribe_ticker(&mut self, _symbol: &str) -> Result<()> { Ok(()) }
+        async fn get_orderbook(&self, _symbol: &str) -> Result<OrderBook> {
+            Ok(book(vec![(50000.0, 1.0)], vec![(50010.0, 1.0)]))
+        }
+        async fn get_balances(&self) -> Result<HashMap<String, Balance>> { Ok(HashMap::new()) }
+        async fn place_limit_order(&self, _order: &LimitOrder) -> Result<OrderResponse> {
+            Err(ArbitrageError::NotImplemented("place_limit_order".to_string()).into())
+        }
+        async fn place_oco(&self, _order: &OcoOrder) -> Result<OcoResponse> {
+            Err(ArbitrageError::NotImplemented("place_oco".to_string()).into())
+        }
+        async fn cancel_order(&self, _symbol: &str, _order_id: &str) -> Result<OrderResponse> {
+            Err(ArbitrageError::NotImplemented("cancel_order".to_string()).into())
+        }
+        async fn get_order_status(&self, _symbol: &str, _order_id: &str) -> Result<OrderStatus> {
+            Err(ArbitrageError::NotImplemented("get_order_status".to_string()).into())
+        }
+        async fn get_open_orders(&self, _symbol: Option<&str>) -> Result<Vec<OrderResponse>> { Ok(Vec::new()) }
+        fn get_market_data_receiver(&self) -> Option<tokio::sync::mpsc::Receiver<MarketDataUpdate>> { None }
+        fn get_order_update_receiver(&self) -> Option<tokio::sync::mpsc::Receiver<crate::connectors::OrderUpdate>> { None }
+        async fn refresh_symbol_rules(&mut self) -> Result<()> { Ok(()) }
+        fn symbol_rules(&self, _symbol: &str) -> Option<crate::connectors::SymbolRules> { None }
+    }
+
+    #[test]
+    fn test_defects_detected() {
+        assert!(check_book(&book(vec![(50000.0, 1.0), (49990.0, 2.0)], vec![(50010.0, 1.0)])).is_empty());
+        assert_eq!(
+            check_book(&book(vec![(49990.0, 1.0), (50000.0, 2.0)], vec![(50010.0, -1.0), (50005.0, 1.0)])),
+            vec![BookDefect::UnsortedBids, BookDefect::UnsortedAsks, BookDefect::InvalidLevel],
+        );
+        assert_eq!(check_book(&book(vec![(50010.0, 1.0)], vec![(50000.0, 1.0)])), vec![BookDefect::Crossed]);
+    }
+
+    #[tokio::test]
+    async fn test_corrupted_book_flagged_and_resnapshotted() {
+        let market_data = MarketDataManager::new(16);
+        for (exchange, orderbook) in [
+            (Exchange::Binance, book(vec![(50020.0, 1.0)], vec![(50010.0, 1.0)])),
+            (Exchange::Bybit, book(vec![(50000.0, 1.0)], vec![(50010.0, 1.0)])),
+        ] {
+            market_data.process_update(MarketDataUpdate::OrderBook {
+                exchange: exchange.to_string(),
+                symbol: "BTCUSDT".to_string(),
+                orderbook,
+                exchange_timestamp: None,
+                local_recv_timestamp: 0,
+            }).await;
+        }
+        let mut connectors: HashMap<Exchange, Box<dyn ExchangeConnector + Send + Sync>> = HashMap::new();
+        connectors.insert(Exchange::Binance, Box::new(SnapshotConnector));
+
+        let mut checker = BookIntegrityChecker::new(Duration::from_secs(1));
+        let books = [(Exchange::Binance, "BTCUSDT".to_string()), (Exchange::Bybit, "BTCUSDT".to_string())];
+        let flagged = checker.scan(&market_data, &books, &connectors).await;
+        assert_eq!(flagged, vec![FlaggedBook {
+            exchange: Exchange::Binance,
+            symbol: "BTCUSDT".to_string(),
+            defects: vec![BookDefect::Crossed],
+            resynced: true,
+        }]);
+        assert_eq!(checker.flagged(), 1);
+
+        let repaired = market_data.get_orderbook(Exchange::Binance, "BTCUSDT").await.unwrap();
+        assert!(check_book(&repaired).is_empty());
+        assert!(checker.scan(&market_data, &books, &connectors).await.is_empty());
+    }
+}
@@ -0,0 +1,185 @@
+//! Multi-level maker order laddering
+//!
+//! Instead of resting a single maker order at the touch, a ladder spreads the
+//! intended size across several successive ticks. All rungs belong to the same
+//! opportunity and the hedge leg is sized to their cumulative fills.
+
+use crate::{
+    config::LadderDistribution,
+    connectors::{LimitOrder, OrderSide, TimeInForce},
+    ArbitrageError, Result,
+};
+use std::collections::HashMap;
+
+/// A set of maker orders laddered across price levels for one opportunity
+#[derive(Debug, Clone)]
+pub struct MakerLadder {
+    /// Identifier shared by every rung of the ladder
+    pub ladder_id: String,
+    /// Laddered maker orders, best-priced rung first
+    pub orders: Vec<LimitOrder>,
+    /// Filled quantity per client order ID
+    fills: HashMap<String, f64>,
+    /// Quantity already hedged on the taker leg
+    hedged_quantity: f64,
+}
+
+impl MakerLadder {
+    /// Build a ladder of `levels` maker orders starting at `touch_price`.
+    ///
+    /// Buy rungs step down by `tick_size` and sell rungs step up, so every rung
+    /// is at or behind the touch. Rung sizes follow `distribution`, rounded down
+    /// to `lot_size` (0 disables rounding) with the remainder carried into the
+    /// last rung, and always sum to `total_quantity`. Rungs rounded to nothing
+    /// are dropped.
+    #[allow(clippy::too_many_arguments)]
+    pub fn build(
+        symbol: &str,
+        side: OrderSide,
+        touch_price: f64,
+        tick_size: f64,
+        total_quantity: f64,
+        lot_size: f64,
+        levels: u32,
+        distribution: LadderDistribution,
+        geometric_ratio: f64,
+    ) -> Result<Self> {
+        if levels == 0 {
+            return Err(ArbitrageError::Config("Ladder requires at least one level".to_string()).into());
+        }
+        if tick_size <= 0.0 {
+            return Err(ArbitrageError::Config("Ladder tick size must be positive".to_string()).into());
+        }
+
+        let ladder_id = format!("ladder_{}_{}", symbol, chrono::Utc::now().timestamp_millis());
+        let sizes = Self::level_sizes(total_quantity, lot_size, levels, distribution, geometric_ratio);
+
+        let orders = sizes
+            .into_iter()
+            .enumerate()
+            .filter(|(_, quantity)| *quantity > 0.0)
+            .map(|(level, quantity)| {
+                let offset = tick_size * level as f64;
+                let price = match side {
+                    OrderSide::Buy => touch_price - offset,
+                    OrderSide::Sell => touch_price + offset,
+                };
+                LimitOrder {
+                    symbol: symbol.to_string(),
+                    side,
+                    quantity,
+                    price,
+                    time_in_force: TimeInForce::GTX,
+                    client_order_id: Some(format!("{}_{}", ladder_id, level)),
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            ladder_id,
+            orders,
+            fills: HashMap::new(),
+            hedged_quantity: 0.0,
+        })
+    }
+
+    /// Split `total_quantity` across `levels` according to the distribution,
+    /// rounding each rung but the last down to `lot_size`
+    fn level_sizes(total_quantity: f64, lot_size: f64, levels: u32, distribution: LadderDistribution, ratio: f64) -> Vec<f64> {
+        let weights: Vec<f64> = match distribution {
+            LadderDistribution::Flat => vec![1.0; levels as usize],
+            LadderDistribution::Geometric => (0..levels).map(|i| ratio.powi(i as i32)).collect(),
+        };
+        let weight_sum: f64 = weights.iter().sum();
+        let mut sizes: Vec<f64> = weights
+            .iter()
+            .map(|w| total_quantity * w / weight_sum)
+            .map(|size| if lot_size > 0.0 { ((size / lot_size) + 1e-9).floor() * lot_size } else { size })
+            .collect();
+        if sizes.pop().is_some() {
+            let carried = total_quantity - sizes.iter().sum::<f64>();
+            sizes.push(carried);
+        }
+        sizes
+    }
+
+    /// Total quantity across all rungs
+    pub fn total_quantity(&self) -> f64 {
+        self.orders.iter().map(|o| o.quantity).sum()
+    }
+
+    /// Record the cumulative filled quantity reported for a rung
+    pub fn record_fill(&mut self, client_order_id: &str, filled_quantity: f64) {
+        if self.orders.iter().any(|o| o.client_order_id.as_deref() == Some(client_order_id)) {
+            self.fills.insert(client_order_id.to_string(), filled_quantity);
+        }
+    }
+
+    /// Cumulative filled quantity across all rungs
+    pub fn cumulative_filled(&self) -> f64 {
+        self.fills.values().sum()
+    }
+
+    /// Hedge quantity owed for fills not yet hedged; marks it as hedged
+    pub fn take_unhedged_quantity(&mut self) -> f64 {
+        let unhedged = (self.cumulative_filled() - self.hedged_quantity).max(0.0);
+        self.hedged_quantity += unhedged;
+        unhedged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ladder_levels_and_sizes() {
+        let ladder = MakerLadder::build(
+            "BTCUSDT", OrderSide::Sell, 50000.0, 0.5, 1.0, 0.0, 4, LadderDistribution::Geometric, 0.5,
+        ).unwrap();
+
+        assert_eq!(ladder.orders.len(), 4);
+        for (level, order) in ladder.orders.iter().enumerate() {
+            assert!((order.price - (50000.0 + 0.5 * level as f64)).abs() < 1e-9);
+            assert_eq!(order.time_in_force, TimeInForce::GTX);
+        }
+        assert!(ladder.orders[0].quantity > ladder.orders[1].quantity);
+        assert!((ladder.total_quantity() - 1.0).abs() < 1e-9);
+
+        let flat = MakerLadder::build(
+            "BTCUSDT", OrderSide::Buy, 50000.0, 0.5, 0.9, 0.0, 3, LadderDistribution::Flat, 0.5,
+        ).unwrap();
+        assert!((flat.orders[2].price - 49999.0).abs() < 1e-9);
+        assert!(flat.orders.iter().all(|o| (o.quantity - 0.3).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_rungs_rounded_to_lot_with_remainder_in_last() {
+        let ladder = MakerLadder::build(
+            "BTCUSDT", OrderSide::Sell, 50000.0, 0.5, 1.0, 0.01, 3, LadderDistribution::Flat, 0.5,
+        ).unwrap();
+        let sizes: Vec<f64> = ladder.orders.iter().map(|o| o.quantity).collect();
+        assert_eq!(sizes.len(), 3);
+        assert!((sizes[0] - 0.33).abs() < 1e-9 && (sizes[1] - 0.33).abs() < 1e-9, "{:?}", sizes);
+        assert!((sizes[2] - 0.34).abs() < 1e-9, "{:?}", sizes);
+        assert!((ladder.total_quantity() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_hedge_follows_cumulative_fills() {
+        let mut ladder = MakerLadder::build(
+            "BTCUSDT", OrderSide::Buy, 50000.0, 1.0, 1.0, 0.0, 2, LadderDistribution::Flat, 0.5,
+        ).unwrap();
+        let first = ladder.orders[0].client_order_id.clone().unwrap();
+        let second = ladder.orders[1].client_order_id.clone().unwrap();
+
+        ladder.record_fill(&first, 0.2);
+        assert!((ladder.take_unhedged_quantity() - 0.2).abs() < 1e-9);
+
+        ladder.record_fill(&first, 0.5);
+        ladder.record_fill(&second, 0.1);
+        assert!((ladder.cumulative_filled() - 0.6).abs() < 1e-9);
+        assert!((ladder.take_unhedged_quantity() - 0.4).abs() < 1e-9);
+        assert_eq!(ladder.take_unhedged_quantity(), 0.0);
+    }
+}
@@ -1,8 +1,15 @@
 //! Arbitrage strategy implementation
 
 pub mod arbitrage;
+pub mod book_levels;
 pub mod futures_arbitrage;
-// pub mod risk_manager; // Will be implemented later
+pub mod grouping;
+pub mod integrity;
+pub mod dual;
+pub mod ladder;
+pub mod risk_manager;
+pub mod scheduler;
+pub mod sizing;
 // pub mod position_manager; // Will be implemented later
 
 pub use arbitrage::{
@@ -11,5 +18,13 @@ pub use arbitrage::{
 };
 pub use futures_arbitrage::{
     FuturesArbitrageStrategy, FuturesArbitrageOpportunity, 
-    FuturesStrategyState, FuturesArbitrageStats
+    FuturesStrategyState, FuturesArbitrageStats, ReferencePrice, ExecutionPlan
 };
+pub use book_levels::BookLevels;
+pub use grouping::GroupedLevels;
+pub use integrity::{BookDefect, BookIntegrityChecker, FlaggedBook};
+pub use dual::{DualModeRunner, DualModeStatistics};
+pub use ladder::MakerLadder;
+pub use risk_manager::{Market, PortfolioFill, PortfolioRiskManager};
+pub use scheduler::SymbolScheduler;
+pub use sizing::{ConfidenceSizer, SizingInputs};
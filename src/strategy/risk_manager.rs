@@ -0,0 +1,223 @@
+//! Portfolio-wide risk across the spot and futures strategies
+//!
+//! Both strategies admit their legs here before placing them, so limits see
+//! the combined exposure of every market and venue. Admitted legs count
+//! against the limits immediately, which keeps two strategies checking at
+//! the same time from both slipping under a cap; legs whose orders then fail
+//! are reverted.
+
+use crate::{
+    config::RiskConfig,
+    connectors::{Exchange, OrderSide},
+    ArbitrageError, Result,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::warn;
+
+/// Market a position is held in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Market {
+    /// Spot balances
+    Spot,
+    /// Futures positions
+    Futures,
+}
+
+/// One order leg as seen by the portfolio
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioFill {
+    /// Market the leg trades in
+    pub market: Market,
+    /// Exchange the leg is placed on
+    pub exchange: Exchange,
+    /// Canonical symbol
+    pub symbol: String,
+    /// Order side
+    pub side: OrderSide,
+    /// Base quantity
+    pub quantity: f64,
+    /// Limit price
+    pub price: f64,
+}
+
+impl PortfolioFill {
+    /// Signed base quantity: positive for buys, negative for sells
+    fn signed_quantity(&self) -> f64 {
+        match self.side {
+            OrderSide::Buy => self.quantity,
+            OrderSide::Sell => -self.quantity,
+        }
+    }
+
+    fn key(&self) -> (Market, Exchange, String) {
+        (self.market, self.exchange, self.symbol.clone())
+    }
+}
+
+/// Position in one symbol on one venue and market
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct PortfolioPosition {
+    /// Signed base quantity
+    size: f64,
+    /// Price of the last admitted leg, used to value the position
+    price: f64,
+}
+
+/// Combined position and PnL view enforcing portfolio-wide limits
+#[derive(Debug, Clone)]
+pub struct PortfolioRiskManager {
+    /// Cap on the combined notional of every position (0 disables)
+    max_total_notional: f64,
+    /// Loss on combined realized PnL at which new legs are refused (0 disables)
+    loss_limit: f64,
+    positions: HashMap<(Market, Exchange, String), PortfolioPosition>,
+    realized_pnl: HashMap<Market, f64>,
+    rejections: u64,
+}
+
+impl PortfolioRiskManager {
+    /// Create a manager enforcing `max_total_notional_usd` and, over the
+    /// session, `daily_loss_limit`
+    pub fn new(risk: &RiskConfig) -> Self {
+        Self {
+            max_total_notional: risk.max_total_notional_usd,
+            loss_limit: risk.daily_loss_limit,
+            positions: HashMap::new(),
+            realized_pnl: HashMap::new(),
+            rejections: 0,
+        }
+    }
+
+    /// Admit the legs of one opportunity if the portfolio stays within its limits.
+    ///
+    /// Admitted legs are applied to the positions straight away; revert them
+    /// with [`PortfolioRiskManager::revert`] when their orders fail.
+    pub fn admit(&mut self, fills: &[PortfolioFill]) -> Result<()> {
+        if let Err(e) = self.check(fills) {
+            self.rejections += 1;
+            warn!("Portfolio risk rejected {} leg(s): {}", fills.len(), e);
+            return Err(e);
+        }
+        for fill in fills {
+            let position = self.positions.entry(fill.key()).or_default();
+            position.size += fill.signed_quantity();
+            position.price = fill.price;
+        }
+        Ok(())
+    }
+
+    /// Undo admitted legs whose orders were not placed
+    pub fn revert(&mut self, fills: &[PortfolioFill]) {
+        for fill in fills {
+            if let Some(position) = self.positions.get_mut(&fill.key()) {
+                position.size -= fill.signed_quantity();
+            }
+        }
+    }
+
+    /// Record realized PnL from one market
+    pub fn record_pnl(&mut self, market: Market, pnl: f64) {
+        *self.realized_pnl.entry(market).or_insert(0.0) += pnl;
+    }
+
+    /// Combined notional of every position
+    pub fn total_notional(&self) -> f64 {
+        self.positions.values().map(|p| p.size.abs() * p.price).sum()
+    }
+
+    /// Net base position in a symbol across every market and venue
+    pub fn net_position(&self, symbol: &str) -> f64 {
+        self.positions
+            .iter()
+            .filter(|((_, _, s), _)| s == symbol)
+            .map(|(_, p)| p.size)
+            .sum()
+    }
+
+    /// Realized PnL from one market
+    pub fn realized_pnl(&self, market: Market) -> f64 {
+        self.realized_pnl.get(&market).copied().unwrap_or(0.0)
+    }
+
+    /// Realized PnL across both markets
+    pub fn total_realized_pnl(&self) -> f64 {
+        self.realized_pnl.values().sum()
+    }
+
+    /// Legs refused so far
+    pub fn rejections(&self) -> u64 {
+        self.rejections
+    }
+
+    fn check(&self, fills: &[PortfolioFill]) -> Result<()> {
+        let realized = self.total_realized_pnl();
+        if self.loss_limit > 0.0 && realized <= -self.loss_limit {
+            return Err(ArbitrageError::RiskManagement(
+                format!("Combined realized PnL ${:.2} breached loss limit ${:.2}", realized, self.loss_limit)
+            ).into());
+        }
+
+        if self.max_total_notional > 0.0 {
+            let mut positions = self.positions.clone();
+            for fill in fills {
+                let position = positions.entry(fill.key()).or_default();
+                position.size += fill.signed_quantity();
+                position.price = fill.price;
+            }
+            let total_notional: f64 = positions.values().map(|p| p.size.abs() * p.price).sum();
+            if total_notional > self.max_total_notional {
+                return Err(ArbitrageError::RiskManagement(
+                    format!("Combined notional ${:.2} would exceed limit ${:.2}", total_notional, self.max_total_notional)
+                ).into());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ArbitrageConfig;
+
+    fn fill(market: Market, exchange: Exchange, side: OrderSide, quantity: f64) -> PortfolioFill {
+        PortfolioFill { market, exchange, symbol: "BTCUSDT".to_string(), side, quantity, price: 50000.0 }
+    }
+
+    #[test]
+    fn test_notional_limit_spans_spot_and_futures() {
+        let mut config = ArbitrageConfig::default();
+        config.risk.max_total_notional_usd = 75000.0;
+        let mut portfolio = PortfolioRiskManager::new(&config.risk);
+
+        let spot = [fill(Market::Spot, Exchange::Binance, OrderSide::Buy, 0.5), fill(Market::Spot, Exchange::Bybit, OrderSide::Sell, 0.5)];
+        portfolio.admit(&spot).unwrap();
+        assert_eq!(portfolio.total_notional(), 50000.0);
+
+        // Each market alone fits, but together they would hold $100k
+        let futures = [fill(Market::Futures, Exchange::Bybit, OrderSide::Sell, 0.5), fill(Market::Futures, Exchange::Binance, OrderSide::Buy, 0.5)];
+        assert!(portfolio.admit(&futures).is_err());
+        assert_eq!(portfolio.rejections(), 1);
+        assert_eq!(portfolio.total_notional(), 50000.0);
+
+        portfolio.revert(&spot[1..]);
+        assert_eq!(portfolio.net_position("BTCUSDT"), 0.5);
+        portfolio.admit(&futures[..1]).unwrap();
+        assert_eq!(portfolio.net_position("BTCUSDT"), 0.0);
+    }
+
+    #[test]
+    fn test_combined_losses_stop_new_legs() {
+        let mut config = ArbitrageConfig::default();
+        config.risk.daily_loss_limit = 100.0;
+        let mut portfolio = PortfolioRiskManager::new(&config.risk);
+
+        portfolio.record_pnl(Market::Spot, -60.0);
+        portfolio.admit(&[fill(Market::Futures, Exchange::Bybit, OrderSide::Sell, 0.1)]).unwrap();
+        portfolio.record_pnl(Market::Futures, -40.0);
+        assert_eq!(portfolio.total_realized_pnl(), -100.0);
+        assert!(portfolio.admit(&[fill(Market::Spot, Exchange::Binance, OrderSide::Buy, 0.1)]).is_err());
+    }
+}
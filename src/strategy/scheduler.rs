@@ -0,0 +1,134 @@
+//! Adaptive symbol scan scheduling
+//!
+//! Scanning every symbol on every tick wastes work when most symbols are quiet.
+//! The scheduler tracks a smoothed update rate per symbol and hands out a fixed
+//! number of scan slots per tick, weighted towards the most active symbols.
+//! Every symbol keeps a minimum weight so quiet ones are still scanned.
+
+use std::collections::HashMap;
+
+/// Smoothing factor applied to per-tick update counts
+const RATE_SMOOTHING: f64 = 0.3;
+
+/// Minimum weight so quiet symbols are never starved
+const MIN_WEIGHT: f64 = 0.1;
+
+#[derive(Debug, Clone, Default)]
+struct SymbolActivity {
+    /// Updates received since the last tick
+    pending_updates: u64,
+    /// Smoothed updates per tick
+    rate: f64,
+    /// Accumulated scan credit
+    credit: f64,
+}
+
+/// Schedules symbol scans within a per-tick budget based on recent update rate
+#[derive(Debug, Clone)]
+pub struct SymbolScheduler {
+    symbols: Vec<String>,
+    activity: HashMap<String, SymbolActivity>,
+    scans_per_tick: usize,
+}
+
+impl SymbolScheduler {
+    /// Create a scheduler; a budget of 0 scans every symbol on every tick
+    pub fn new(symbols: Vec<String>, scans_per_tick: usize) -> Self {
+        let activity = symbols
+            .iter()
+            .map(|symbol| (symbol.clone(), SymbolActivity::default()))
+            .collect();
+        Self {
+            symbols,
+            activity,
+            scans_per_tick,
+        }
+    }
+
+    /// Record a market data update for a symbol
+    pub fn record_update(&mut self, symbol: &str) {
+        if let Some(activity) = self.activity.get_mut(symbol) {
+            activity.pending_updates += 1;
+        }
+    }
+
+    /// Smoothed update rate for a symbol, in updates per tick
+    pub fn update_rate(&self, symbol: &str) -> Option<f64> {
+        self.activity.get(symbol).map(|activity| activity.rate)
+    }
+
+    /// Advance one tick and return the symbols to scan
+    pub fn next_batch(&mut self) -> Vec<String> {
+        for activity in self.activity.values_mut() {
+            activity.rate += RATE_SMOOTHING * (activity.pending_updates as f64 - activity.rate);
+            activity.pending_updates = 0;
+        }
+
+        if self.scans_per_tick == 0 || self.scans_per_tick >= self.symbols.len() {
+            return self.symbols.clone();
+        }
+
+        // Distribute this tick's budget as credit proportional to activity; a
+        // symbol can be scanned at most once per tick so its share is capped
+        let total_weight: f64 = self.activity.values().map(|a| a.rate + MIN_WEIGHT).sum();
+        for activity in self.activity.values_mut() {
+            let share = self.scans_per_tick as f64 * (activity.rate + MIN_WEIGHT) / total_weight;
+            activity.credit += share.min(1.0);
+        }
+
+        let mut ranked: Vec<&String> = self.symbols.iter().collect();
+        ranked.sort_by(|a, b| {
+            let credit_a = self.activity[*a].credit;
+            let credit_b = self.activity[*b].credit;
+            credit_b.partial_cmp(&credit_a).unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let batch: Vec<String> = ranked
+            .into_iter()
+            .take(self.scans_per_tick)
+            .cloned()
+            .collect();
+        for symbol in &batch {
+            if let Some(activity) = self.activity.get_mut(symbol) {
+                activity.credit -= 1.0;
+            }
+        }
+        batch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_active_symbol_scanned_more_often() {
+        let symbols: Vec<String> = (0..10).map(|i| format!("SYM{}USDT", i)).collect();
+        let mut scheduler = SymbolScheduler::new(symbols.clone(), 2);
+        let mut scans: HashMap<String, u32> = HashMap::new();
+
+        for _ in 0..100 {
+            for _ in 0..20 {
+                scheduler.record_update("SYM0USDT");
+            }
+            scheduler.record_update("SYM1USDT");
+            for symbol in scheduler.next_batch() {
+                *scans.entry(symbol).or_insert(0) += 1;
+            }
+        }
+
+        let busy = scans["SYM0USDT"];
+        for symbol in &symbols[1..] {
+            let count = scans.get(symbol).copied().unwrap_or(0);
+            assert!(count > 0 && busy > count * 3, "{:?}", scans);
+        }
+        assert_eq!(scans.values().sum::<u32>(), 200);
+    }
+
+    #[test]
+    fn test_zero_budget_scans_everything() {
+        let symbols = vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()];
+        let mut scheduler = SymbolScheduler::new(symbols.clone(), 0);
+        assert_eq!(scheduler.next_batch(), symbols);
+    }
+}
@@ -0,0 +1,84 @@
+//! Confidence-scaled position sizing
+//!
+//! Scales an opportunity's quantity, already capped by the hard limits, by how
+//! much we trust it: fresh books, a quiet market and a low risk score size up
+//! towards the cap, while stale books, volatility and risk size down.
+
+use crate::config::ConfidenceSizingConfig;
+
+/// Per-opportunity signals feeding the sizing multiplier
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SizingInputs {
+    /// Age of the older of the two books in milliseconds
+    pub book_age_ms: f64,
+    /// Smoothed absolute mid-price change in basis points
+    pub volatility_bps: f64,
+    /// Opportunity risk score (0-100)
+    pub risk_score: f64,
+}
+
+/// Turns opportunity confidence, volatility and risk into a size multiplier
+#[derive(Debug, Clone)]
+pub struct ConfidenceSizer {
+    config: ConfidenceSizingConfig,
+}
+
+impl ConfidenceSizer {
+    /// Create a sizer; when disabled the multiplier is always 1
+    pub fn new(config: ConfidenceSizingConfig) -> Self {
+        Self { config }
+    }
+
+    /// Confidence in `[0,1]`, falling linearly to 0 at `max_book_age_ms`
+    pub fn confidence(&self, book_age_ms: f64) -> f64 {
+        match self.config.max_book_age_ms {
+            0 => 1.0,
+            max_age => (1.0 - book_age_ms.max(0.0) / max_age as f64).clamp(0.0, 1.0),
+        }
+    }
+
+    /// Size multiplier in `[0,1]`
+    pub fn multiplier(&self, inputs: &SizingInputs) -> f64 {
+        if !self.config.enabled {
+            return 1.0;
+        }
+        let reference = self.config.volatility_reference_bps;
+        let volatility_factor = if reference > 0.0 {
+            reference / (reference + inputs.volatility_bps.max(0.0))
+        } else {
+            1.0
+        };
+        let risk_factor = 1.0 - inputs.risk_score.clamp(0.0, 100.0) / 100.0;
+        (self.confidence(inputs.book_age_ms) * volatility_factor * risk_factor).clamp(0.0, 1.0)
+    }
+
+    /// Scale a quantity already capped by the hard limits
+    pub fn size(&self, capped_quantity: f64, inputs: &SizingInputs) -> f64 {
+        capped_quantity * self.multiplier(inputs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multiplier_combines_signals() {
+        let sizer = ConfidenceSizer::new(ConfidenceSizingConfig {
+            enabled: true,
+            max_book_age_ms: 1000,
+            volatility_reference_bps: 10.0,
+        });
+        let calm = SizingInputs { book_age_ms: 0.0, volatility_bps: 0.0, risk_score: 0.0 };
+        assert_eq!(sizer.multiplier(&calm), 1.0);
+
+        let mixed = SizingInputs { book_age_ms: 500.0, volatility_bps: 10.0, risk_score: 20.0 };
+        assert!((sizer.multiplier(&mixed) - 0.5 * 0.5 * 0.8).abs() < 1e-12);
+
+        let expired = SizingInputs { book_age_ms: 5000.0, ..calm };
+        assert_eq!(sizer.multiplier(&expired), 0.0);
+
+        let disabled = ConfidenceSizer::new(ConfidenceSizingConfig::default());
+        assert_eq!(disabled.multiplier(&mixed), 1.0);
+    }
+}
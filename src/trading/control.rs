@@ -0,0 +1,57 @@
+//! Runtime control commands for operators
+
+use crate::{connectors::Exchange, ArbitrageError};
+use std::fmt;
+use std::str::FromStr;
+
+/// Operator command applied to a running strategy and executor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlCommand {
+    /// Drop a venue from opportunity pairing and stop its feeds
+    DisableExchange(Exchange),
+    /// Return a venue to opportunity pairing and restart its feeds
+    EnableExchange(Exchange),
+}
+
+impl fmt::Display for ControlCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ControlCommand::DisableExchange(exchange) => write!(f, "disable {}", exchange),
+            ControlCommand::EnableExchange(exchange) => write!(f, "enable {}", exchange),
+        }
+    }
+}
+
+impl FromStr for ControlCommand {
+    type Err = ArbitrageError;
+
+    /// Parse the form `Display` writes, e.g. `disable bybit`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        let command = match (words.next(), words.next(), words.next()) {
+            (Some(action), Some(exchange), None) => match action.to_lowercase().as_str() {
+                "disable" => ControlCommand::DisableExchange(exchange.parse()?),
+                "enable" => ControlCommand::EnableExchange(exchange.parse()?),
+                _ => return Err(ArbitrageError::Config(format!("Unknown control command: {}", s))),
+            },
+            _ => return Err(ArbitrageError::Config(format!("Malformed control command: {}", s))),
+        };
+        Ok(command)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_round_trips_through_display() {
+        for command in [ControlCommand::DisableExchange(Exchange::Bybit), ControlCommand::EnableExchange(Exchange::Binance)] {
+            assert_eq!(command.to_string().parse::<ControlCommand>().unwrap(), command);
+        }
+        assert_eq!("  Disable BYBIT ".parse::<ControlCommand>().unwrap(), ControlCommand::DisableExchange(Exchange::Bybit));
+        assert!("pause bybit".parse::<ControlCommand>().is_err());
+        assert!("disable".parse::<ControlCommand>().is_err());
+        assert!("disable kraken".parse::<ControlCommand>().is_err());
+    }
+}
@@ -4,12 +4,14 @@ use crate::{
     config::ArbitrageConfig,
     connectors::{Exchange, LimitOrder, OrderResponse, OrderSide, OrderStatus},
     data::OrderBook,
+    utils::time::now_ns,
     ArbitrageError,
     Result,
 };
-use rand::Rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
@@ -17,7 +19,7 @@ use tracing::{debug, info, warn};
 use uuid::Uuid;
 
 /// Portfolio state for dry-run simulation
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Portfolio {
     /// Asset positions (symbol -> quantity)
     positions: HashMap<String, f64>,
@@ -25,6 +27,13 @@ pub struct Portfolio {
     balances: HashMap<String, f64>,
     /// Initial balances for PnL calculation
     initial_balances: HashMap<String, f64>,
+    /// Currencies counted as cash when calculating PnL
+    #[serde(default = "default_cash_currencies")]
+    cash_currencies: Vec<String>,
+}
+
+fn default_cash_currencies() -> Vec<String> {
+    vec!["USDT".to_string(), "USD".to_string()]
 }
 
 impl Portfolio {
@@ -34,9 +43,20 @@ impl Portfolio {
             positions: HashMap::new(),
             balances: initial_balances.clone(),
             initial_balances,
+            cash_currencies: default_cash_currencies(),
         }
     }
     
+    /// Count these currencies as cash in PnL, in addition to USD
+    pub fn with_cash_currencies(mut self, currencies: &[String]) -> Self {
+        for currency in currencies {
+            if !self.cash_currencies.contains(currency) {
+                self.cash_currencies.push(currency.clone());
+            }
+        }
+        self
+    }
+    
     /// Get position for a symbol
     pub fn get_position(&self, symbol: &str) -> f64 {
         self.positions.get(symbol).copied().unwrap_or(0.0)
@@ -73,7 +93,7 @@ impl Portfolio {
         // Add cash balance changes
         for (currency, balance) in &self.balances {
             let initial = self.initial_balances.get(currency).copied().unwrap_or(0.0);
-            if currency == "USDT" || currency == "USD" {
+            if self.cash_currencies.contains(currency) {
                 total_pnl += balance - initial;
             }
         }
@@ -93,8 +113,14 @@ pub struct PerformanceMetrics {
     pub average_execution_time: Duration,
     /// Success rate (filled orders / total orders)
     pub success_rate: f64,
-    /// Total fees paid
+    /// Net fees: fees paid minus rebates earned
     pub total_fees: f64,
+    /// Gross fees paid on fills charged a positive fee
+    #[serde(default)]
+    pub fees_paid: f64,
+    /// Rebates earned on fills charged a negative fee
+    #[serde(default)]
+    pub rebates_earned: f64,
     /// Sharpe ratio
     pub sharpe_ratio: Option<f64>,
     /// Maximum drawdown
@@ -109,12 +135,122 @@ impl Default for PerformanceMetrics {
             average_execution_time: Duration::from_nanos(0),
             success_rate: 0.0,
             total_fees: 0.0,
+            fees_paid: 0.0,
+            rebates_earned: 0.0,
             sharpe_ratio: None,
             max_drawdown: 0.0,
         }
     }
 }
 
+impl PerformanceMetrics {
+    /// Record a signed fee; negative fees are rebates
+    pub fn record_fee(&mut self, fee: f64) {
+        if fee >= 0.0 {
+            self.fees_paid += fee;
+        } else {
+            self.rebates_earned -= fee;
+        }
+        self.total_fees = self.net_fees();
+    }
+
+    /// Fees paid minus rebates earned
+    pub fn net_fees(&self) -> f64 {
+        self.fees_paid - self.rebates_earned
+    }
+}
+
+/// Point on the dry-run equity curve
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquityPoint {
+    /// Timestamp in milliseconds
+    pub timestamp: i64,
+    /// Total PnL at this point
+    pub pnl: f64,
+}
+
+/// Fee totals split by liquidity role
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FeeTotals {
+    /// Fees paid on maker (post-only) orders
+    pub maker: f64,
+    /// Fees paid on taker orders
+    pub taker: f64,
+    /// Total fees paid
+    pub total: f64,
+}
+
+/// Machine-readable dry-run execution report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DryRunReport {
+    /// Report generation timestamp in milliseconds
+    pub generated_at: i64,
+    /// Total profit/loss
+    pub total_pnl: f64,
+    /// Performance metrics
+    pub metrics: PerformanceMetrics,
+    /// Final portfolio state
+    pub portfolio: Portfolio,
+    /// PnL after each execution
+    pub equity_curve: Vec<EquityPoint>,
+    /// PnL per symbol at current prices
+    pub symbol_pnl: HashMap<String, f64>,
+    /// PnL per quote currency at current prices
+    #[serde(default)]
+    pub quote_pnl: HashMap<String, f64>,
+    /// Fee totals
+    pub fees: FeeTotals,
+}
+
+/// Distribution of exchange-side fill latency
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "distribution", rename_all = "snake_case")]
+pub enum LatencyModel {
+    /// Always the same latency
+    Fixed {
+        /// Latency in milliseconds
+        ms: f64,
+    },
+    /// Uniform between two bounds
+    Uniform {
+        /// Lower bound in milliseconds
+        min_ms: f64,
+        /// Upper bound in milliseconds
+        max_ms: f64,
+    },
+    /// Log-normal with the given mean and standard deviation
+    LogNormal {
+        /// Mean latency in milliseconds
+        mean_ms: f64,
+        /// Standard deviation in milliseconds
+        std_dev_ms: f64,
+    },
+}
+
+impl LatencyModel {
+    /// Draw one latency sample
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Duration {
+        let ms = match *self {
+            LatencyModel::Fixed { ms } => ms,
+            LatencyModel::Uniform { min_ms, max_ms } if max_ms > min_ms => rng.gen_range(min_ms..max_ms),
+            LatencyModel::Uniform { min_ms, .. } => min_ms,
+            // The log-normal is undefined for a non-positive mean
+            LatencyModel::LogNormal { mean_ms, .. } if mean_ms <= 0.0 => 0.0,
+            LatencyModel::LogNormal { mean_ms, std_dev_ms } => {
+                // Parameters of the underlying normal that give the requested mean and std dev
+                let sigma_sq = (1.0 + (std_dev_ms / mean_ms).powi(2)).ln();
+                let mu = mean_ms.ln() - sigma_sq / 2.0;
+                // Box-Muller standard normal
+                let u1: f64 = 1.0 - rng.gen::<f64>();
+                let u2: f64 = rng.gen();
+                let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+                (mu + sigma_sq.sqrt() * z).exp()
+            }
+        };
+        Duration::from_secs_f64(ms.max(0.0) / 1000.0)
+    }
+}
+
 /// Execution configuration for dry-run mode
 #[derive(Debug, Clone)]
 pub struct ExecutionConfig {
@@ -138,6 +274,8 @@ pub struct ExecutionConfig {
     pub rejection_probability: f64,
     /// Minimum fill ratio for partial fills
     pub min_fill_ratio: f64,
+    /// Fill latency per exchange; exchanges without a model use a jittered fraction of the order timeout
+    pub fill_latency: HashMap<Exchange, LatencyModel>,
 }
 
 impl Default for ExecutionConfig {
@@ -153,6 +291,7 @@ impl Default for ExecutionConfig {
             partial_fill_probability: 0.0,
             rejection_probability: 0.0,
             min_fill_ratio: 0.1,
+            fill_latency: HashMap::new(),
         }
     }
 }
@@ -173,22 +312,34 @@ pub struct DryRunExecutor {
     metrics: Arc<RwLock<PerformanceMetrics>>,
     /// Current prices for PnL calculation
     current_prices: Arc<RwLock<HashMap<String, f64>>>,
-    /// Random number generator
-    rng: Arc<RwLock<rand::rngs::ThreadRng>>,
+    /// PnL after each execution
+    equity_curve: Arc<RwLock<Vec<EquityPoint>>>,
+    /// Net cash flow per symbol (sells minus buys, after fees)
+    symbol_cash_flows: Arc<RwLock<HashMap<String, f64>>>,
+    /// Fee totals by liquidity role
+    fee_totals: Arc<RwLock<FeeTotals>>,
+    /// Random number generator; seed it for reproducible runs
+    rng: Arc<RwLock<StdRng>>,
 }
 
 impl DryRunExecutor {
     /// Create a new dry-run executor
     pub async fn new(config: ArbitrageConfig) -> Result<Self> {
-        let exec_config = ExecutionConfig::default();
+        let mut exec_config = ExecutionConfig::default();
+        for (exchange, model) in &config.execution.fill_latency {
+            exec_config.fill_latency.insert(exchange.parse()?, *model);
+        }
         
         // Initialize portfolio with default balances
         let mut initial_balances = HashMap::new();
-        initial_balances.insert("USDT".to_string(), 100000.0); // $100k USDT
+        for quote in &config.strategy.quote_currencies {
+            initial_balances.insert(quote.clone(), 100000.0); // $100k per quote currency
+        }
         initial_balances.insert("BTC".to_string(), 0.0);
         initial_balances.insert("ETH".to_string(), 0.0);
         
-        let portfolio = Portfolio::new(initial_balances);
+        let portfolio = Portfolio::new(initial_balances)
+            .with_cash_currencies(&config.strategy.quote_currencies);
         
         Ok(Self {
             config,
@@ -198,12 +349,36 @@ impl DryRunExecutor {
             execution_history: Arc::new(RwLock::new(Vec::new())),
             metrics: Arc::new(RwLock::new(PerformanceMetrics::default())),
             current_prices: Arc::new(RwLock::new(HashMap::new())),
-            rng: Arc::new(RwLock::new(rand::thread_rng())),
+            equity_curve: Arc::new(RwLock::new(Vec::new())),
+            symbol_cash_flows: Arc::new(RwLock::new(HashMap::new())),
+            fee_totals: Arc::new(RwLock::new(FeeTotals::default())),
+            rng: Arc::new(RwLock::new(StdRng::from_entropy())),
         })
     }
     
+    /// Use a seeded RNG so identical inputs produce identical results
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Arc::new(RwLock::new(StdRng::seed_from_u64(seed)));
+        self
+    }
+    
+    /// Override the simulation settings
+    pub fn with_execution_config(mut self, exec_config: ExecutionConfig) -> Self {
+        self.exec_config = exec_config;
+        self
+    }
+    
     /// Execute a limit order in dry-run mode
     pub async fn execute_order(&mut self, order: LimitOrder) -> Result<OrderResponse> {
+        self.execute(order, None).await
+    }
+    
+    /// Execute a limit order in dry-run mode using the exchange's fill latency model
+    pub async fn execute_order_on(&mut self, exchange: Exchange, order: LimitOrder) -> Result<OrderResponse> {
+        self.execute(order, Some(exchange)).await
+    }
+    
+    async fn execute(&mut self, order: LimitOrder, exchange: Option<Exchange>) -> Result<OrderResponse> {
         let start_time = Instant::now();
         
         debug!("Executing dry-run order: {:?}", order);
@@ -216,7 +391,7 @@ impl DryRunExecutor {
         
         // Simulate execution delay
         if self.exec_config.simulate_delays {
-            let delay = self.calculate_execution_delay().await;
+            let delay = self.calculate_execution_delay(exchange).await;
             tokio::time::sleep(delay).await;
         }
         
@@ -253,7 +428,7 @@ impl DryRunExecutor {
             },
             filled_quantity: fill_quantity,
             average_price: if fill_quantity > 0.0 { Some(execution_price) } else { None },
-            timestamp: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
+            timestamp: now_ns(),
         };
         
         // Update execution history
@@ -264,6 +439,8 @@ impl DryRunExecutor {
         
         // Update metrics
         self.update_metrics(start_time.elapsed(), &order_response, fees).await;
+        self.record_fees(&order, fees).await;
+        self.record_equity_point().await;
         
         info!("Dry-run order executed: {} {} {} @ {}", 
               order_response.side, order_response.filled_quantity, 
@@ -320,12 +497,72 @@ impl DryRunExecutor {
         self.metrics.read().await.clone()
     }
     
+    /// PnL per symbol: net cash flow plus the open position at current prices
+    pub async fn get_symbol_pnl(&self) -> HashMap<String, f64> {
+        let cash_flows = self.symbol_cash_flows.read().await;
+        let portfolio = self.portfolio.read().await;
+        let prices = self.current_prices.read().await;
+        
+        cash_flows
+            .iter()
+            .map(|(symbol, cash_flow)| {
+                let position_value = prices
+                    .get(symbol)
+                    .map(|price| portfolio.get_position(symbol) * price)
+                    .unwrap_or(0.0);
+                (symbol.clone(), cash_flow + position_value)
+            })
+            .collect()
+    }
+    
+    /// PnL per quote currency, summing the PnL of the symbols quoted in it
+    pub async fn get_quote_pnl(&self) -> HashMap<String, f64> {
+        let mut quote_pnl = HashMap::new();
+        for (symbol, pnl) in self.get_symbol_pnl().await {
+            *quote_pnl.entry(self.quote_currency(&symbol).to_string()).or_insert(0.0) += pnl;
+        }
+        quote_pnl
+    }
+    
+    /// Quote currency of a symbol, defaulting to USDT for unconfigured quotes
+    fn quote_currency<'a>(&'a self, symbol: &str) -> &'a str {
+        self.config.strategy.quote_currency(symbol).unwrap_or("USDT")
+    }
+    
+    /// Build the full execution report
+    pub async fn build_report(&self) -> DryRunReport {
+        let results = self.get_results().await;
+        
+        DryRunReport {
+            generated_at: chrono::Utc::now().timestamp_millis(),
+            total_pnl: results.total_pnl,
+            metrics: self.get_performance_metrics().await,
+            portfolio: self.get_portfolio().await,
+            equity_curve: self.equity_curve.read().await.clone(),
+            symbol_pnl: self.get_symbol_pnl().await,
+            quote_pnl: self.get_quote_pnl().await,
+            fees: self.fee_totals.read().await.clone(),
+        }
+    }
+    
+    /// Write the full execution report to a JSON file
+    pub async fn export_report_json<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let report = self.build_report().await;
+        let json = serde_json::to_string_pretty(&report)?;
+        tokio::fs::write(&path, json).await
+            .map_err(|e| ArbitrageError::Trading(format!("Failed to write report: {}", e)))?;
+        
+        info!("Dry-run report written to {}", path.as_ref().display());
+        Ok(())
+    }
+    
     /// Reset executor state
     pub async fn reset(&mut self) -> Result<()> {
         // Reset portfolio to initial state
         {
             let mut portfolio = self.portfolio.write().await;
-            *portfolio = Portfolio::new(portfolio.initial_balances.clone());
+            *portfolio = Portfolio::new(portfolio.initial_balances.clone())
+                .with_cash_currencies(&self.config.strategy.quote_currencies);
         }
         
         // Clear execution history
@@ -352,6 +589,11 @@ impl DryRunExecutor {
             prices.clear();
         }
         
+        // Clear report tracking
+        self.equity_curve.write().await.clear();
+        self.symbol_cash_flows.write().await.clear();
+        *self.fee_totals.write().await = FeeTotals::default();
+        
         info!("Dry-run executor state reset");
         Ok(())
     }
@@ -367,8 +609,16 @@ impl DryRunExecutor {
         rng.gen::<f64>() < self.exec_config.rejection_probability
     }
     
-    async fn calculate_execution_delay(&self) -> Duration {
+    /// Sample a simulated fill latency for an exchange
+    pub async fn sample_fill_latency(&self, exchange: Exchange) -> Duration {
+        self.calculate_execution_delay(Some(exchange)).await
+    }
+    
+    async fn calculate_execution_delay(&self, exchange: Option<Exchange>) -> Duration {
         let mut rng = self.rng.write().await;
+        if let Some(model) = exchange.and_then(|exchange| self.exec_config.fill_latency.get(&exchange)) {
+            return model.sample(&mut *rng);
+        }
         let base_delay = Duration::from_millis(self.config.execution.order_timeout_ms / 10);
         let jitter = rng.gen_range(0.5..1.5);
         Duration::from_nanos((base_delay.as_nanos() as f64 * jitter) as u64)
@@ -449,28 +699,50 @@ impl DryRunExecutor {
         
         let notional_value = fill_quantity * execution_price;
         
-        match order.side {
+        let cash_flow = match order.side {
             OrderSide::Buy => {
                 // Increase position, decrease cash
                 portfolio.update_position(&order.symbol, fill_quantity);
-                portfolio.update_balance("USDT", -(notional_value + fees));
+                -(notional_value + fees)
             }
             OrderSide::Sell => {
                 // Decrease position, increase cash
                 portfolio.update_position(&order.symbol, -fill_quantity);
-                portfolio.update_balance("USDT", notional_value - fees);
+                notional_value - fees
             }
-        }
+        };
+        portfolio.update_balance(self.quote_currency(&order.symbol), cash_flow);
+        
+        let mut cash_flows = self.symbol_cash_flows.write().await;
+        *cash_flows.entry(order.symbol.clone()).or_insert(0.0) += cash_flow;
         
         Ok(())
     }
     
+    async fn record_fees(&self, order: &LimitOrder, fees: f64) {
+        let mut totals = self.fee_totals.write().await;
+        if order.time_in_force == crate::connectors::TimeInForce::GTX {
+            totals.maker += fees;
+        } else {
+            totals.taker += fees;
+        }
+        totals.total += fees;
+    }
+    
+    async fn record_equity_point(&self) {
+        let pnl = self.get_results().await.total_pnl;
+        self.equity_curve.write().await.push(EquityPoint {
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            pnl,
+        });
+    }
+    
     async fn update_metrics(&self, execution_time: Duration, order_response: &OrderResponse, fees: f64) {
         let mut metrics = self.metrics.write().await;
         
         metrics.total_orders += 1;
         metrics.total_volume += order_response.filled_quantity * order_response.price;
-        metrics.total_fees += fees;
+        metrics.record_fee(fees);
         
         // Update average execution time
         let total_time = metrics.average_execution_time * (metrics.total_orders - 1) as u32 + execution_time;
@@ -560,4 +832,161 @@ mod tests {
         assert_eq!(results_after.total_trades, 0);
         assert_eq!(results_after.total_pnl, 0.0);
     }
+
+    #[tokio::test]
+    async fn test_report_splits_pnl_by_quote_currency() {
+        let mut config = create_test_config();
+        config.strategy.quote_currencies = vec!["USDT".to_string(), "USDC".to_string()];
+        config.execution.slippage_tolerance = 0.0;
+        let mut executor = DryRunExecutor::new(config).await.unwrap()
+            .with_execution_config(ExecutionConfig { enable_fees: false, ..ExecutionConfig::default() });
+        
+        let mut btc_book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        btc_book.update_bid(49990.0, 1.0);
+        btc_book.update_ask(50010.0, 1.0);
+        executor.update_market_data(Exchange::Binance, btc_book).await.unwrap();
+        let mut eth_book = OrderBook::new("ETHUSDC".to_string(), Exchange::Binance);
+        eth_book.update_bid(2999.0, 10.0);
+        eth_book.update_ask(3001.0, 10.0);
+        executor.update_market_data(Exchange::Binance, eth_book).await.unwrap();
+        
+        // Buy 0.1 BTC at the ask and sell 2 ETH at the bid; each pays half the spread
+        executor.execute_order(LimitOrder { price: 50010.0, ..create_test_order() }).await.unwrap();
+        executor.execute_order(LimitOrder {
+            symbol: "ETHUSDC".to_string(),
+            side: OrderSide::Sell,
+            quantity: 2.0,
+            price: 2999.0,
+            time_in_force: TimeInForce::GTC,
+            client_order_id: None,
+        }).await.unwrap();
+        
+        let report = executor.build_report().await;
+        assert_eq!(report.quote_pnl.len(), 2);
+        assert!((report.quote_pnl["USDT"] - -1.0).abs() < 1e-6, "{:?}", report.quote_pnl);
+        assert!((report.quote_pnl["USDC"] - -2.0).abs() < 1e-6, "{:?}", report.quote_pnl);
+        assert!((report.portfolio.get_balance("USDT") - (100000.0 - 5001.0)).abs() < 1e-6);
+        assert!((report.portfolio.get_balance("USDC") - (100000.0 + 5998.0)).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_rebates_and_fees_tracked_separately() {
+        let mut config = create_test_config();
+        config.execution.slippage_tolerance = 0.0;
+        let mut executor = DryRunExecutor::new(config).await.unwrap()
+            .with_execution_config(ExecutionConfig { maker_fee: -0.00025, taker_fee: 0.0004, ..ExecutionConfig::default() });
+        let mut book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        book.update_bid(50000.0, 1.0);
+        book.update_ask(50000.0, 1.0);
+        executor.update_market_data(Exchange::Binance, book).await.unwrap();
+        
+        // 0.1 BTC maker fill earns a $1.25 rebate; 0.1 BTC taker fill pays $2.00
+        executor.execute_order(LimitOrder { time_in_force: TimeInForce::GTX, ..create_test_order() }).await.unwrap();
+        executor.execute_order(LimitOrder { side: OrderSide::Sell, ..create_test_order() }).await.unwrap();
+        
+        let metrics = executor.get_performance_metrics().await;
+        assert!((metrics.rebates_earned - 1.25).abs() < 1e-9, "{}", metrics.rebates_earned);
+        assert!((metrics.fees_paid - 2.0).abs() < 1e-9, "{}", metrics.fees_paid);
+        assert!((metrics.net_fees() - 0.75).abs() < 1e-9);
+        assert_eq!(metrics.total_fees, metrics.net_fees());
+    }
+
+    #[tokio::test]
+    async fn test_fill_latency_follows_exchange_model() {
+        let exec_config = ExecutionConfig {
+            fill_latency: HashMap::from([
+                (Exchange::Binance, LatencyModel::LogNormal { mean_ms: 40.0, std_dev_ms: 25.0 }),
+                (Exchange::Bybit, LatencyModel::Uniform { min_ms: 100.0, max_ms: 200.0 }),
+            ]),
+            ..ExecutionConfig::default()
+        };
+        let executor = DryRunExecutor::new(create_test_config()).await.unwrap()
+            .with_execution_config(exec_config)
+            .with_seed(7);
+        
+        const SAMPLES: usize = 20_000;
+        for (exchange, expected_mean_ms) in [(Exchange::Binance, 40.0), (Exchange::Bybit, 150.0)] {
+            let mut total_ms = 0.0;
+            for _ in 0..SAMPLES {
+                total_ms += executor.sample_fill_latency(exchange).await.as_secs_f64() * 1000.0;
+            }
+            let mean_ms = total_ms / SAMPLES as f64;
+            assert!((mean_ms - expected_mean_ms).abs() < expected_mean_ms * 0.03, "{}: {}", exchange, mean_ms);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fill_latency_read_from_config() {
+        let mut config = create_test_config();
+        config.execution.fill_latency.insert("bybit".to_string(), LatencyModel::Fixed { ms: 25.0 });
+        config.execution.fill_latency.insert("binance".to_string(), LatencyModel::LogNormal { mean_ms: 0.0, std_dev_ms: 10.0 });
+        let executor = DryRunExecutor::new(config).await.unwrap();
+        
+        assert_eq!(executor.sample_fill_latency(Exchange::Bybit).await, Duration::from_millis(25));
+        assert_eq!(executor.sample_fill_latency(Exchange::Binance).await, Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_export_report_json() {
+        let config = create_test_config();
+        let mut executor = DryRunExecutor::new(config).await.unwrap();
+        executor.execute_order(create_test_order()).await.unwrap();
+        
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.json");
+        executor.export_report_json(&path).await.unwrap();
+        
+        let content = std::fs::read_to_string(&path).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&content).unwrap();
+        for key in ["metrics", "portfolio", "equity_curve", "symbol_pnl", "fees", "total_pnl"] {
+            assert!(value.get(key).is_some(), "missing key {}", key);
+        }
+        
+        let report: DryRunReport = serde_json::from_str(&content).unwrap();
+        assert_eq!(report.metrics.total_orders, 1);
+        assert_eq!(report.equity_curve.len(), 1);
+        assert!(report.symbol_pnl.contains_key("BTCUSDT"));
+        assert!(report.fees.taker > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_same_seed_reproduces_session() {
+        async fn run_session(seed: u64) -> (Vec<Option<(f64, f64)>>, crate::trading::ExecutionResults) {
+            let exec_config = ExecutionConfig {
+                allow_partial_fills: true,
+                partial_fill_probability: 0.5,
+                rejection_probability: 0.2,
+                ..ExecutionConfig::default()
+            };
+            let mut config = create_test_config();
+            config.execution.slippage_tolerance = 0.001;
+            let mut executor = DryRunExecutor::new(config).await.unwrap()
+                .with_execution_config(exec_config)
+                .with_seed(seed);
+
+            let mut book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+            book.update_bid(49990.0, 5.0);
+            book.update_ask(50010.0, 5.0);
+            executor.update_market_data(Exchange::Binance, book).await.unwrap();
+
+            let mut fills = Vec::new();
+            for i in 0..20 {
+                let mut order = create_test_order();
+                if i % 2 == 1 {
+                    order.side = OrderSide::Sell;
+                    order.price = 49990.0;
+                }
+                let fill = executor.execute_order(order).await.ok()
+                    .map(|response| (response.filled_quantity, response.average_price.unwrap_or(0.0)));
+                fills.push(fill);
+            }
+            (fills, executor.get_results().await)
+        }
+
+        let (fills_a, results_a) = run_session(42).await;
+        let (fills_b, results_b) = run_session(42).await;
+        assert_eq!(fills_a, fills_b);
+        assert_eq!(results_a, results_b);
+        assert!(fills_a.iter().any(|fill| fill.is_none()));
+    }
 }
@@ -0,0 +1,144 @@
+//! Manual kill switch driven by a control file or SIGUSR1, and operator
+//! commands read from a command file
+
+use crate::{config::KillSwitchConfig, trading::control::ControlCommand};
+use std::path::Path;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Watches the configured control mechanisms for a manual stop request or operator commands
+pub struct KillSwitch {
+    config: KillSwitchConfig,
+}
+
+impl KillSwitch {
+    /// Create a kill switch; with nothing configured it never fires
+    pub fn new(config: KillSwitchConfig) -> Self {
+        Self { config }
+    }
+
+    /// Whether the control file currently exists
+    pub fn control_file_present(&self) -> bool {
+        self.config
+            .control_file
+            .as_ref()
+            .is_some_and(|path| Path::new(path).exists())
+    }
+
+    /// Resolve once the control file appears or SIGUSR1 is received
+    pub async fn triggered(&self) {
+        tokio::select! {
+            _ = self.watch_control_file() => {}
+            _ = self.watch_signal() => {}
+        }
+    }
+
+    async fn watch_control_file(&self) {
+        let path = match &self.config.control_file {
+            Some(path) => path,
+            None => return std::future::pending().await,
+        };
+        let mut interval = tokio::time::interval(Duration::from_millis(self.config.poll_interval_ms.max(1)));
+        loop {
+            interval.tick().await;
+            if self.control_file_present() {
+                warn!("Kill switch control file {} found", path);
+                return;
+            }
+        }
+    }
+
+    /// Resolve with the commands of the next command file to appear.
+    ///
+    /// The file is removed once read, so each command is applied once.
+    /// Lines that are blank or start with `#` are skipped, and malformed ones
+    /// are logged and dropped. Never resolves without a command file configured.
+    pub async fn next_commands(&self) -> Vec<ControlCommand> {
+        let path = match &self.config.command_file {
+            Some(path) => path,
+            None => return std::future::pending().await,
+        };
+        let mut interval = tokio::time::interval(Duration::from_millis(self.config.poll_interval_ms.max(1)));
+        loop {
+            interval.tick().await;
+            if !Path::new(path).exists() {
+                continue;
+            }
+            let contents = match std::fs::read_to_string(path) {
+                Ok(contents) => contents,
+                Err(e) => {
+                    warn!("Failed to read command file {}: {}", path, e);
+                    continue;
+                }
+            };
+            if let Err(e) = std::fs::remove_file(path) {
+                warn!("Failed to remove command file {}: {}", path, e);
+            }
+            let commands: Vec<ControlCommand> = contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .filter_map(|line| match line.parse() {
+                    Ok(command) => Some(command),
+                    Err(e) => {
+                        warn!("Ignoring command in {}: {}", path, e);
+                        None
+                    }
+                })
+                .collect();
+            if !commands.is_empty() {
+                info!("Read {} operator commands from {}", commands.len(), path);
+                return commands;
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    async fn watch_signal(&self) {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        if !self.config.sigusr1 {
+            return std::future::pending().await;
+        }
+        match signal(SignalKind::user_defined1()) {
+            Ok(mut stream) => {
+                stream.recv().await;
+                warn!("Kill switch SIGUSR1 received");
+            }
+            Err(e) => {
+                warn!("Failed to install SIGUSR1 handler: {}", e);
+                std::future::pending().await
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    async fn watch_signal(&self) {
+        std::future::pending().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::Exchange;
+
+    #[tokio::test]
+    async fn test_command_file_consumed_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("commands");
+        let kill_switch = KillSwitch::new(KillSwitchConfig {
+            command_file: Some(path.to_string_lossy().into_owned()),
+            poll_interval_ms: 5,
+            ..KillSwitchConfig::default()
+        });
+
+        std::fs::write(&path, "# maintenance\ndisable bybit\nreboot binance\n\nenable binance\n").unwrap();
+        let commands = kill_switch.next_commands().await;
+        assert_eq!(commands, vec![ControlCommand::DisableExchange(Exchange::Bybit), ControlCommand::EnableExchange(Exchange::Binance)]);
+        assert!(!path.exists());
+
+        let again = tokio::time::timeout(Duration::from_millis(50), kill_switch.next_commands()).await;
+        assert!(again.is_err());
+    }
+}
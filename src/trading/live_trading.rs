@@ -3,10 +3,16 @@
 use crate::{
     config::ArbitrageConfig,
     connectors::{
-        Exchange, ExchangeConnector,
-        LimitOrder, OrderResponse, OrderSide, OrderStatus, Balance,
+        reconnect::ReconnectGuard,
+        ConnectionStatus, Exchange, ExchangeConnector,
+        LimitOrder, MarketOrder, OcoOrder, OcoResponse, OrderResponse, OrderSide, OrderStatus, Balance, TimeInForce,
     },
     data::OrderBook,
+    utils::notifier::{HealthEventType, WebhookNotifier},
+    trading::{control::ControlCommand, kill_switch::KillSwitch, order_snapshot::{self, SnapshotOrder}, shadow::{DivergenceReport, ShadowChecker}},
+    utils::time::now_ns,
+    utils::audit_log::{AuditEvent, AuditLog},
+    utils::trade_store::TradeWriter,
     ArbitrageError,
     Result,
 };
@@ -17,6 +23,15 @@ use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
+/// How often a pending exit order is re-queried over REST
+const EXIT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Interval between checks for positions held past `max_position_age_secs`
+const POSITION_AGE_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Balances per exchange, keyed by asset
+type ExchangeBalances = HashMap<Exchange, HashMap<String, Balance>>;
+
 /// Health status for the live trading system
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HealthStatus {
@@ -32,6 +47,9 @@ pub struct HealthStatus {
     pub recent_errors: u64,
     /// System uptime in seconds
     pub uptime_seconds: u64,
+    /// Net position size per base asset summed across exchanges
+    #[serde(default)]
+    pub net_exposure: HashMap<String, f64>,
 }
 
 /// Execution statistics for live trading
@@ -47,8 +65,14 @@ pub struct ExecutionStatistics {
     pub avg_execution_time_ms: f64,
     /// Total volume traded
     pub total_volume: f64,
-    /// Total fees paid
+    /// Net fees: fees paid minus rebates earned
     pub total_fees: f64,
+    /// Gross fees paid on fills charged a positive fee
+    #[serde(default)]
+    pub fees_paid: f64,
+    /// Rebates earned on fills charged a negative fee
+    #[serde(default)]
+    pub rebates_earned: f64,
     /// Success rate percentage
     pub success_rate: f64,
     /// Last execution timestamp
@@ -64,12 +88,31 @@ impl Default for ExecutionStatistics {
             avg_execution_time_ms: 0.0,
             total_volume: 0.0,
             total_fees: 0.0,
+            fees_paid: 0.0,
+            rebates_earned: 0.0,
             success_rate: 0.0,
             last_execution: None,
         }
     }
 }
 
+impl ExecutionStatistics {
+    /// Record a signed fee; negative fees are rebates
+    pub fn record_fee(&mut self, fee: f64) {
+        if fee >= 0.0 {
+            self.fees_paid += fee;
+        } else {
+            self.rebates_earned -= fee;
+        }
+        self.total_fees = self.net_fees();
+    }
+
+    /// Fees paid minus rebates earned
+    pub fn net_fees(&self) -> f64 {
+        self.fees_paid - self.rebates_earned
+    }
+}
+
 /// Position information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Position {
@@ -85,6 +128,30 @@ pub struct Position {
     pub unrealized_pnl: f64,
     /// Last update timestamp
     pub last_update: i64,
+    /// Timestamp the position was opened, in seconds
+    #[serde(default)]
+    pub opened_at: i64,
+}
+
+/// Record of a position flattened by the stop-loss
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StopOut {
+    /// Exchange where the position was held
+    pub exchange: Exchange,
+    /// Symbol
+    pub symbol: String,
+    /// Position size that was flattened
+    pub size: f64,
+    /// Average entry price
+    pub entry_price: f64,
+    /// Average fill price of the flattening order, or the touch it was sent at
+    pub exit_price: f64,
+    /// Loss in basis points of entry notional when the stop fired
+    pub loss_bps: f64,
+    /// Flattening order ID
+    pub order_id: String,
+    /// Stop-out timestamp in nanoseconds
+    pub timestamp: i64,
 }
 
 /// Exchange information
@@ -104,7 +171,8 @@ pub struct ExchangeInfo {
     pub server_time: i64,
 }
 
-/// Live trading executor
+/// Live trading executor; clones share every piece of state
+#[derive(Clone)]
 pub struct LiveTradingExecutor {
     /// Configuration
     config: ArbitrageConfig,
@@ -124,6 +192,32 @@ pub struct LiveTradingExecutor {
     start_time: Instant,
     /// Emergency shutdown flag
     emergency_shutdown: Arc<RwLock<bool>>,
+    /// Stop-outs triggered by the stop-loss
+    stop_outs: Arc<RwLock<Vec<StopOut>>>,
+    /// Stop-loss and stale-position exit orders sent but not yet resolved, by symbol
+    pending_stops: Arc<RwLock<HashMap<String, PendingExit>>>,
+    /// Open time of the position each symbol was last alerted as stale for
+    stale_alerts: Arc<RwLock<HashMap<String, i64>>>,
+    /// Health-transition webhook notifier
+    notifier: Arc<WebhookNotifier>,
+    /// Profit ladder progress per symbol: (levels already hit, initial position size)
+    profit_ladder: Arc<RwLock<HashMap<String, (usize, f64)>>>,
+    /// Reconnect-storm guards per exchange
+    reconnect_guards: Arc<RwLock<HashMap<Exchange, ReconnectGuard>>>,
+    /// Trade history sink, when enabled
+    trade_store: Option<Arc<TradeWriter>>,
+    /// Hash-chained order audit log, when enabled
+    audit_log: Option<Arc<AuditLog>>,
+    /// Venues dropped by an operator; orders to them are refused
+    disabled_exchanges: Arc<RwLock<std::collections::HashSet<Exchange>>>,
+    /// Canonical symbols subscribed per exchange, replayed when a venue is re-enabled
+    subscriptions: Arc<RwLock<HashMap<Exchange, Vec<String>>>>,
+    /// Most recently fetched balances and when they were fetched
+    balance_cache: Arc<RwLock<Option<(Instant, ExchangeBalances)>>>,
+    /// Dry-run simulator shadowing live fills, when enabled
+    shadow: Option<Arc<RwLock<ShadowChecker>>>,
+    /// Held while the active-order snapshot is written, so writes never interleave
+    snapshot_lock: Arc<tokio::sync::Mutex<()>>,
 }
 
 impl LiveTradingExecutor {
@@ -142,6 +236,15 @@ impl LiveTradingExecutor {
             active_orders: 0,
             recent_errors: 0,
             uptime_seconds: 0,
+            net_exposure: HashMap::new(),
+        };
+        
+        let notifier = Arc::new(WebhookNotifier::new(config.monitoring.webhook.clone()));
+        let trade_store = TradeWriter::from_config(&config.monitoring.trade_store)?.map(Arc::new);
+        let audit_log = AuditLog::from_config(&config.monitoring.audit_log)?.map(Arc::new);
+        let shadow = match config.execution.shadow_dry_run {
+            true => Some(Arc::new(RwLock::new(ShadowChecker::new(config.clone()).await?))),
+            false => None,
         };
         
         Ok(Self {
@@ -154,6 +257,19 @@ impl LiveTradingExecutor {
             market_data: Arc::new(RwLock::new(HashMap::new())),
             start_time,
             emergency_shutdown: Arc::new(RwLock::new(false)),
+            stop_outs: Arc::new(RwLock::new(Vec::new())),
+            pending_stops: Arc::new(RwLock::new(HashMap::new())),
+            stale_alerts: Arc::new(RwLock::new(HashMap::new())),
+            notifier,
+            profit_ladder: Arc::new(RwLock::new(HashMap::new())),
+            reconnect_guards: Arc::new(RwLock::new(HashMap::new())),
+            trade_store,
+            audit_log,
+            disabled_exchanges: Arc::new(RwLock::new(std::collections::HashSet::new())),
+            subscriptions: Arc::new(RwLock::new(HashMap::new())),
+            balance_cache: Arc::new(RwLock::new(None)),
+            shadow,
+            snapshot_lock: Arc::new(tokio::sync::Mutex::new(())),
         })
     }
     
@@ -173,6 +289,9 @@ impl LiveTradingExecutor {
                     match connector.connect().await {
                         Ok(()) => {
                             info!("Successfully connected to {}", exchange);
+                            if let Err(e) = connector.refresh_symbol_rules().await {
+                                warn!("Failed to refresh symbol rules for {}, using configured limits: {}", exchange, e);
+                            }
                             health.exchange_connections.insert(exchange, true);
                             connectors.insert(exchange, connector);
                         }
@@ -197,6 +316,11 @@ impl LiveTradingExecutor {
         Ok(())
     }
     
+    /// Exchange connectors, shared with background tasks such as the book integrity checker
+    pub fn connectors(&self) -> Arc<RwLock<HashMap<Exchange, Box<dyn ExchangeConnector + Send + Sync>>>> {
+        self.connectors.clone()
+    }
+    
     /// Check if connected to exchanges
     pub async fn is_connected(&self) -> bool {
         let health = self.health.read().await;
@@ -214,16 +338,35 @@ impl LiveTradingExecutor {
     
     /// Check account balances on all exchanges
     pub async fn check_balances(&self) -> Result<HashMap<Exchange, HashMap<String, Balance>>> {
+        self.refresh_balances(false).await
+    }
+    
+    /// Fetch balances from all exchanges concurrently, reusing results younger than
+    /// `balance_cache_ttl_ms` unless `force` is set
+    pub async fn refresh_balances(&self, force: bool) -> Result<HashMap<Exchange, HashMap<String, Balance>>> {
+        let ttl = Duration::from_millis(self.config.execution.balance_cache_ttl_ms);
+        if !force {
+            if let Some((fetched_at, balances)) = self.balance_cache.read().await.as_ref() {
+                if fetched_at.elapsed() < ttl {
+                    debug!("Using cached balances fetched {:?} ago", fetched_at.elapsed());
+                    return Ok(balances.clone());
+                }
+            }
+        }
+        
         debug!("Checking balances across all exchanges");
         
         let connectors = self.connectors.read().await;
+        let fetches = connectors.iter().map(|(exchange, connector)| async move {
+            (*exchange, connector.get_balances().await)
+        });
         let mut all_balances = HashMap::new();
         
-        for (exchange, connector) in connectors.iter() {
-            match connector.get_balances().await {
+        for (exchange, result) in futures_util::future::join_all(fetches).await {
+            match result {
                 Ok(balances) => {
                     info!("Retrieved {} balances from {}", balances.len(), exchange);
-                    all_balances.insert(*exchange, balances);
+                    all_balances.insert(exchange, balances);
                 }
                 Err(e) => {
                     error!("Failed to get balances from {}: {}", exchange, e);
@@ -232,6 +375,7 @@ impl LiveTradingExecutor {
             }
         }
         
+        *self.balance_cache.write().await = Some((Instant::now(), all_balances.clone()));
         Ok(all_balances)
     }
     
@@ -239,22 +383,37 @@ impl LiveTradingExecutor {
     pub async fn check_connectivity(&self) -> Result<()> {
         debug!("Checking connectivity to all exchanges");
         
-        let connectors = self.connectors.read().await;
-        let mut health = self.health.write().await;
-        
-        for (exchange, connector) in connectors.iter() {
-            let connected = connector.is_connected();
-            health.exchange_connections.insert(*exchange, connected);
+        let disconnected = {
+            let connectors = self.connectors.read().await;
+            let mut health = self.health.write().await;
             
-            if !connected {
-                warn!("Lost connection to {}", exchange);
+            for (exchange, connector) in connectors.iter() {
+                let connected = connector.is_connected();
+                health.exchange_connections.insert(*exchange, connected);
+                
+                if !connected {
+                    warn!("Lost connection to {}", exchange);
+                }
             }
-        }
+            
+            health.is_healthy = health.exchange_connections.values().all(|&connected| connected);
+            health.last_heartbeat = chrono::Utc::now().timestamp();
+            
+            health.exchange_connections
+                .iter()
+                .find(|(_, &connected)| !connected)
+                .map(|(exchange, _)| *exchange)
+        };
         
-        health.is_healthy = health.exchange_connections.values().all(|&connected| connected);
-        health.last_heartbeat = chrono::Utc::now().timestamp();
+        let reason = match disconnected {
+            Some(exchange) => format!("{} disconnected", exchange),
+            None => "all exchanges connected".to_string(),
+        };
+        if let Err(e) = self.notifier.on_health_change(disconnected.is_none(), disconnected, &reason).await {
+            warn!("Failed to send health notification: {}", e);
+        }
         
-        if !health.is_healthy {
+        if disconnected.is_some() {
             return Err(ArbitrageError::Connection("Not all exchanges are connected".to_string()).into());
         }
         
@@ -268,17 +427,19 @@ impl LiveTradingExecutor {
             return Err(ArbitrageError::Trading("System in emergency shutdown".to_string()).into());
         }
         
+        if self.disabled_exchanges.read().await.contains(&exchange) {
+            return Err(ArbitrageError::Trading(format!("{} is disabled", exchange)).into());
+        }
+        
         // Check risk limits
         self.check_risk_limits(&order).await?;
+        self.check_reserve(exchange, &order).await?;
         
         let start_time = Instant::now();
         debug!("Placing order on {}: {:?}", exchange, order);
         
-        let connectors = self.connectors.read().await;
-        let connector = connectors.get(&exchange)
-            .ok_or_else(|| ArbitrageError::Trading(format!("No connector for {}", exchange)))?;
-        
-        match connector.place_limit_order(&order).await {
+        let (order, placed) = self.submit_limit_order(exchange, order).await?;
+        match placed {
             Ok(response) => {
                 info!("Order placed successfully: {} on {}", response.order_id, exchange);
                 
@@ -287,6 +448,15 @@ impl LiveTradingExecutor {
                     let mut active_orders = self.active_orders.write().await;
                     active_orders.insert(response.order_id.clone(), (exchange, response.clone()));
                 }
+                self.persist_active_orders().await;
+                
+                if let Some(store) = &self.trade_store {
+                    if let Err(e) = store.record_order(exchange, &response) {
+                        warn!("Failed to persist order {}: {}", response.order_id, e);
+                    }
+                }
+                self.audit_placement(exchange, &response);
+                self.shadow_fill(exchange, &order, &response).await;
                 
                 // Update statistics
                 self.update_statistics(start_time.elapsed(), true, &response).await;
@@ -301,6 +471,152 @@ impl LiveTradingExecutor {
         }
     }
     
+    /// Send an order, and if the exchange rejects it on a price or lot filter, refresh
+    /// the symbol's rules, re-round the order and retry once.
+    ///
+    /// Returns the order as last sent alongside the exchange's answer to it.
+    async fn submit_limit_order(&self, exchange: Exchange, order: LimitOrder) -> Result<(LimitOrder, Result<OrderResponse>)> {
+        let placed = {
+            let connectors = self.connectors.read().await;
+            let connector = connectors.get(&exchange)
+                .ok_or_else(|| ArbitrageError::Trading(format!("No connector for {}", exchange)))?;
+            self.check_min_notional(connector.as_ref(), &order)?;
+            connector.place_limit_order(&order).await
+        };
+        let reject = match placed {
+            Err(e) if self.config.execution.retry_on_filter_reject
+                && matches!(e.downcast_ref::<ArbitrageError>(), Some(ArbitrageError::FilterViolation(_))) => e,
+            placed => return Ok((order, placed)),
+        };
+        
+        warn!("{} rejected {} order on a filter ({}); refreshing rules and retrying once", exchange, order.symbol, reject);
+        let mut connectors = self.connectors.write().await;
+        let connector = connectors.get_mut(&exchange)
+            .ok_or_else(|| ArbitrageError::Trading(format!("No connector for {}", exchange)))?;
+        if let Err(e) = connector.refresh_symbol_rules().await {
+            warn!("Failed to refresh symbol rules for {}: {}", exchange, e);
+            return Ok((order, Err(reject)));
+        }
+        let conformed = match connector.symbol_rules(&order.symbol) {
+            Some(rules) => rules.conform(&order),
+            None => return Ok((order, Err(reject))),
+        };
+        info!("Retrying {} order at {} x {} (was {} x {})",
+              order.symbol, conformed.price, conformed.quantity, order.price, order.quantity);
+        self.check_min_notional(connector.as_ref(), &conformed)?;
+        let placed = connector.place_limit_order(&conformed).await;
+        Ok((conformed, placed))
+    }
+    
+    /// Place several orders on one exchange.
+    ///
+    /// With `execution.batch_orders` set they go out through the connector's
+    /// batch call; otherwise each is placed individually. Rejected batch items
+    /// are returned but not tracked as active.
+    pub async fn place_orders(&mut self, exchange: Exchange, orders: Vec<LimitOrder>) -> Result<Vec<OrderResponse>> {
+        if !self.config.execution.batch_orders {
+            let mut responses = Vec::with_capacity(orders.len());
+            for order in orders {
+                responses.push(self.place_order(exchange, order).await?);
+            }
+            return Ok(responses);
+        }
+        
+        if *self.emergency_shutdown.read().await {
+            return Err(ArbitrageError::Trading("System in emergency shutdown".to_string()).into());
+        }
+        for order in &orders {
+            self.check_risk_limits(order).await?;
+        }
+        
+        let start_time = Instant::now();
+        debug!("Placing batch of {} orders on {}", orders.len(), exchange);
+        
+        let connectors = self.connectors.read().await;
+        let connector = connectors.get(&exchange)
+            .ok_or_else(|| ArbitrageError::Trading(format!("No connector for {}", exchange)))?;
+        for order in &orders {
+            self.check_min_notional(connector.as_ref(), order)?;
+        }
+        
+        match connector.place_batch(&orders).await {
+            Ok(responses) => {
+                for (order, response) in orders.iter().zip(&responses) {
+                    let accepted = response.status != OrderStatus::Rejected;
+                    if accepted {
+                        self.active_orders.write().await
+                            .insert(response.order_id.clone(), (exchange, response.clone()));
+                        if let Some(store) = &self.trade_store {
+                            if let Err(e) = store.record_order(exchange, response) {
+                                warn!("Failed to persist order {}: {}", response.order_id, e);
+                            }
+                        }
+                        self.audit_placement(exchange, response);
+                        self.shadow_fill(exchange, order, response).await;
+                    }
+                    self.update_statistics(start_time.elapsed(), accepted, response).await;
+                }
+                self.persist_active_orders().await;
+                info!("Batch of {} orders placed on {}", responses.len(), exchange);
+                Ok(responses)
+            }
+            Err(e) => {
+                error!("Failed to place order batch on {}: {}", exchange, e);
+                self.update_statistics(start_time.elapsed(), false, &OrderResponse::default()).await;
+                Err(e)
+            }
+        }
+    }
+    
+    /// Refuse an order the exchange would reject for falling below its minimum notional
+    fn check_min_notional(&self, connector: &(dyn ExchangeConnector + Send + Sync), order: &LimitOrder) -> Result<()> {
+        if !self.config.execution.enforce_min_notional {
+            return Ok(());
+        }
+        match connector.symbol_rules(&order.symbol) {
+            Some(rules) if !rules.meets_min_notional(order.price, order.quantity, false) => {
+                Err(ArbitrageError::RiskManagement(format!(
+                    "Order notional {:.8} for {} is below the exchange minimum {}",
+                    rules.order_notional(order.price, order.quantity), order.symbol, rules.min_notional
+                )).into())
+            }
+            _ => Ok(()),
+        }
+    }
+    
+    /// Audit an accepted order, and its immediate fill if it traded on placement
+    fn audit_placement(&self, exchange: Exchange, response: &OrderResponse) {
+        self.audit(AuditEvent::Placed, exchange, response);
+        if response.filled_quantity > 0.0 {
+            self.audit(AuditEvent::Filled, exchange, response);
+        }
+    }
+    
+    fn audit(&self, event: AuditEvent, exchange: Exchange, response: &OrderResponse) {
+        if let Some(audit_log) = &self.audit_log {
+            if let Err(e) = audit_log.record(event, exchange, response) {
+                warn!("Failed to audit {:?} of order {}: {}", event, response.order_id, e);
+            }
+        }
+    }
+    
+    /// Compare a live fill with the shadow simulator's prediction
+    async fn shadow_fill(&self, exchange: Exchange, order: &LimitOrder, response: &OrderResponse) {
+        if let Some(shadow) = &self.shadow {
+            if let Err(e) = shadow.write().await.record_live_fill(exchange, order, response).await {
+                warn!("Shadow simulation failed for order {}: {}", response.order_id, e);
+            }
+        }
+    }
+    
+    /// Divergence between live fills and the shadow simulator, when enabled
+    pub async fn shadow_report(&self) -> Option<DivergenceReport> {
+        match &self.shadow {
+            Some(shadow) => Some(shadow.read().await.report()),
+            None => None,
+        }
+    }
+    
     /// Place order with retry mechanism
     pub async fn place_order_with_retry(&mut self, exchange: Exchange, order: LimitOrder) -> Result<OrderResponse> {
         let max_retries = self.config.execution.max_retry_attempts;
@@ -323,7 +639,10 @@ impl LiveTradingExecutor {
         Err(last_error.unwrap())
     }
     
-    /// Cancel an order
+    /// Cancel an order.
+    ///
+    /// The cancel may land after part or all of the order traded; any fill the
+    /// response reports beyond what was already known is folded into positions.
     pub async fn cancel_order(&mut self, exchange: Exchange, symbol: &str, order_id: &str) -> Result<OrderResponse> {
         debug!("Cancelling order {} on {}", order_id, exchange);
         
@@ -334,11 +653,21 @@ impl LiveTradingExecutor {
         match connector.cancel_order(symbol, order_id).await {
             Ok(response) => {
                 info!("Order cancelled successfully: {} on {}", order_id, exchange);
+                self.audit(AuditEvent::Cancelled, exchange, &response);
                 
                 // Remove from active orders
-                {
-                    let mut active_orders = self.active_orders.write().await;
-                    active_orders.remove(order_id);
+                let known_filled = self.active_orders.write().await
+                    .remove(order_id)
+                    .map_or(0.0, |(_, order)| order.filled_quantity);
+                self.persist_active_orders().await;
+                
+                let late_fill = response.filled_quantity - known_filled;
+                if late_fill > 0.0 {
+                    warn!("Order {} on {} filled {:.6} {} before the cancel landed ({:?}); recording residual position",
+                          order_id, exchange, late_fill, response.symbol, response.status);
+                    self.audit(AuditEvent::Filled, exchange, &response);
+                    let price = response.average_price.unwrap_or(response.price);
+                    self.apply_fill(exchange, &response.symbol, response.side, late_fill, price).await;
                 }
                 
                 Ok(response)
@@ -350,6 +679,40 @@ impl LiveTradingExecutor {
         }
     }
     
+    /// Fold a fill into the tracked position for its symbol
+    async fn apply_fill(&self, exchange: Exchange, symbol: &str, side: OrderSide, quantity: f64, price: f64) {
+        let signed = match side {
+            OrderSide::Buy => quantity,
+            OrderSide::Sell => -quantity,
+        };
+        let now = chrono::Utc::now().timestamp();
+        let mut positions = self.positions.write().await;
+        let position = positions.entry(symbol.to_string()).or_insert_with(|| Position {
+            exchange,
+            symbol: symbol.to_string(),
+            size: 0.0,
+            avg_price: price,
+            unrealized_pnl: 0.0,
+            last_update: now,
+            opened_at: now,
+        });
+        
+        let size = position.size + signed;
+        if position.size == 0.0 {
+            position.opened_at = now;
+        }
+        if position.size == 0.0 || position.size.signum() == signed.signum() {
+            // Adding to (or opening) the position moves the average entry
+            position.avg_price = (position.avg_price * position.size.abs() + price * quantity) / size.abs();
+        } else if size != 0.0 && size.signum() != position.size.signum() {
+            // Flipped through flat: the remainder opens at the fill price
+            position.avg_price = price;
+            position.opened_at = now;
+        }
+        position.size = size;
+        position.last_update = now;
+    }
+    
     /// Get order status
     pub async fn get_order_status(&self, exchange: Exchange, symbol: &str, order_id: &str) -> Result<OrderStatus> {
         let connectors = self.connectors.read().await;
@@ -359,11 +722,139 @@ impl LiveTradingExecutor {
         connector.get_order_status(symbol, order_id).await
     }
     
+    /// Get an order with its fill progress
+    pub async fn get_order(&self, exchange: Exchange, symbol: &str, order_id: &str) -> Result<OrderResponse> {
+        let connectors = self.connectors.read().await;
+        let connector = connectors.get(&exchange)
+            .ok_or_else(|| ArbitrageError::Trading(format!("No connector for {}", exchange)))?;
+        
+        connector.get_order(symbol, order_id).await
+    }
+    
     /// Get current positions
     pub async fn get_positions(&self) -> Result<HashMap<String, Position>> {
         Ok(self.positions.read().await.clone())
     }
     
+    /// Get positions flattened by the stop-loss
+    pub async fn get_stop_outs(&self) -> Vec<StopOut> {
+        self.stop_outs.read().await.clone()
+    }
+    
+    /// Build a market order flattening the position if its loss breaches `stop_loss_bps` of entry.
+    ///
+    /// The position is marked at the price it could be exited at (best bid for
+    /// longs, best ask for shorts).
+    pub fn stop_loss_order(&self, position: &Position, orderbook: &OrderBook) -> Option<MarketOrder> {
+        if position.size == 0.0 || position.avg_price <= 0.0 {
+            return None;
+        }
+        
+        let (side, exit_price) = if position.size > 0.0 {
+            (OrderSide::Sell, orderbook.best_bid()?)
+        } else {
+            (OrderSide::Buy, orderbook.best_ask()?)
+        };
+        
+        let pnl = (exit_price - position.avg_price) * position.size;
+        let loss_bps = -pnl / (position.size.abs() * position.avg_price) * 10000.0;
+        if loss_bps < self.config.risk_limits(&position.symbol).stop_loss_bps as f64 {
+            return None;
+        }
+        
+        Some(MarketOrder {
+            symbol: position.symbol.clone(),
+            side,
+            quantity: position.size.abs(),
+            client_order_id: Some(format!("stop_{}_{}", position.symbol, chrono::Utc::now().timestamp_millis())),
+        })
+    }
+    
+    /// Build a take-profit/stop OCO bracket for a position left directional,
+    /// e.g. a filled leg whose hedge never executed.
+    ///
+    /// Returns `None` unless both `bracket_take_profit_bps` and `bracket_stop_bps` are set.
+    pub fn bracket_order(&self, position: &Position) -> Option<OcoOrder> {
+        let take_profit_bps = self.config.risk.bracket_take_profit_bps;
+        let stop_bps = self.config.risk.bracket_stop_bps;
+        if take_profit_bps <= 0.0 || stop_bps <= 0.0 || position.size == 0.0 || position.avg_price <= 0.0 {
+            return None;
+        }
+        
+        let direction = position.size.signum();
+        Some(OcoOrder {
+            symbol: position.symbol.clone(),
+            side: if position.size > 0.0 { OrderSide::Sell } else { OrderSide::Buy },
+            quantity: position.size.abs(),
+            take_profit_price: position.avg_price * (1.0 + direction * take_profit_bps / 10000.0),
+            stop_price: position.avg_price * (1.0 - direction * stop_bps / 10000.0),
+            stop_limit_price: None,
+            client_order_id: Some(format!("bracket_{}_{}", position.symbol, chrono::Utc::now().timestamp_millis())),
+        })
+    }
+    
+    /// Protect a directional position with an OCO bracket on its exchange
+    pub async fn bracket_position(&self, position: &Position) -> Result<Option<OcoResponse>> {
+        let order = match self.bracket_order(position) {
+            Some(order) => order,
+            None => return Ok(None),
+        };
+        
+        let connectors = self.connectors.read().await;
+        let connector = connectors.get(&position.exchange)
+            .ok_or_else(|| ArbitrageError::Connection(format!("Exchange {} not connected", position.exchange)))?;
+        let response = connector.place_oco(&order).await?;
+        info!("Bracketed {} {} on {}: take profit {:.2}, stop {:.2} (list {})",
+              position.size, position.symbol, position.exchange, order.take_profit_price, order.stop_price, response.list_id);
+        Ok(Some(response))
+    }
+    
+    /// Build partial-close orders for profit targets newly crossed by the position.
+    ///
+    /// Each level closes its fraction of the position size seen when the first
+    /// level fired, and fires at most once until the position is closed.
+    pub async fn profit_taking_orders(&self, position: &Position, orderbook: &OrderBook) -> Vec<LimitOrder> {
+        let targets = &self.config.risk.profit_targets;
+        let mut ladder = self.profit_ladder.write().await;
+        
+        if position.size == 0.0 || position.avg_price <= 0.0 {
+            ladder.remove(&position.symbol);
+            return Vec::new();
+        }
+        
+        let exit = if position.size > 0.0 {
+            orderbook.best_bid().map(|price| (OrderSide::Sell, price))
+        } else {
+            orderbook.best_ask().map(|price| (OrderSide::Buy, price))
+        };
+        let (side, exit_price) = match exit {
+            Some(exit) => exit,
+            None => return Vec::new(),
+        };
+        
+        let profit_bps = (exit_price - position.avg_price) * position.size.signum() / position.avg_price * 10000.0;
+        let (levels_hit, initial_size) = ladder
+            .entry(position.symbol.clone())
+            .or_insert((0, position.size.abs()));
+        
+        let mut orders = Vec::new();
+        while *levels_hit < targets.len() && profit_bps >= targets[*levels_hit].profit_bps {
+            let target = &targets[*levels_hit];
+            let quantity = (*initial_size * target.fraction).min(position.size.abs());
+            info!("Profit target {:.1} bps hit for {}: closing {}", target.profit_bps, position.symbol, quantity);
+            orders.push(LimitOrder {
+                symbol: position.symbol.clone(),
+                side,
+                quantity,
+                price: exit_price,
+                time_in_force: TimeInForce::IOC,
+                client_order_id: Some(format!("tp{}_{}_{}", *levels_hit, position.symbol, chrono::Utc::now().timestamp_millis())),
+            });
+            *levels_hit += 1;
+        }
+        orders
+    }
+    
     /// Check risk limits for an order
     pub async fn check_risk_limits(&self, order: &LimitOrder) -> Result<()> {
         // Check position size limits
@@ -377,13 +868,39 @@ impl LiveTradingExecutor {
             OrderSide::Sell => current_position - order.quantity,
         };
         
-        if new_position.abs() > self.config.strategy.max_position_size {
+        let limits = self.config.risk_limits(&order.symbol);
+        if new_position.abs() > limits.max_position_size {
             return Err(ArbitrageError::RiskManagement(
                 format!("Position size {} exceeds limit {}", 
-                       new_position.abs(), self.config.strategy.max_position_size)
+                       new_position.abs(), limits.max_position_size)
             ).into());
         }
         
+        let position_value = new_position.abs() * order.price;
+        if limits.max_position_value_usd > 0.0 && position_value > limits.max_position_value_usd {
+            return Err(ArbitrageError::RiskManagement(
+                format!("Position value ${:.2} for {} exceeds limit ${:.2}",
+                       position_value, order.symbol, limits.max_position_value_usd)
+            ).into());
+        }
+        
+        // Check portfolio-wide notional, valuing the order's symbol at its post-trade size
+        let max_total_notional = self.config.risk.max_total_notional_usd;
+        if max_total_notional > 0.0 {
+            let other_notional: f64 = positions.values()
+                .filter(|p| p.symbol != order.symbol)
+                .map(|p| p.size.abs() * p.avg_price)
+                .sum();
+            let total_notional = other_notional + new_position.abs() * order.price;
+            
+            if total_notional > max_total_notional {
+                return Err(ArbitrageError::RiskManagement(
+                    format!("Total notional ${:.2} would exceed limit ${:.2}",
+                           total_notional, max_total_notional)
+                ).into());
+            }
+        }
+        
         // Check minimum order size
         if order.quantity < self.config.execution.min_order_size {
             return Err(ArbitrageError::RiskManagement(
@@ -395,8 +912,69 @@ impl LiveTradingExecutor {
         Ok(())
     }
     
-    /// Subscribe to market data for a symbol
+    /// Refuse an order that would spend into the exchange's configured minimum
+    /// reserve of the asset it pays with; balances are only fetched when one is set
+    async fn check_reserve(&self, exchange: Exchange, order: &LimitOrder) -> Result<()> {
+        let Some(quote) = self.config.strategy.quote_currency(&order.symbol) else {
+            return Ok(());
+        };
+        let (asset, required) = match order.side {
+            OrderSide::Buy => (quote, order.quantity * order.price),
+            OrderSide::Sell => (&order.symbol[..order.symbol.len() - quote.len()], order.quantity),
+        };
+        if self.config.risk.min_reserve(&exchange.to_string(), asset) <= 0.0 {
+            return Ok(());
+        }
+        
+        let balances = self.check_balances().await?;
+        let available = balances.get(&exchange)
+            .and_then(|balances| balances.get(asset))
+            .map(|balance| balance.free)
+            .unwrap_or(0.0);
+        self.config.risk.check_reserve(&exchange.to_string(), asset, available, required)
+    }
+    
+    /// Apply an operator command.
+    ///
+    /// Disabling a venue disconnects its feeds and refuses new orders to it;
+    /// enabling reconnects and replays its market data subscriptions.
+    pub async fn apply_control(&mut self, command: ControlCommand) -> Result<()> {
+        info!("Applying control command: {}", command);
+        match command {
+            ControlCommand::DisableExchange(exchange) => {
+                self.disabled_exchanges.write().await.insert(exchange);
+                if let Some(connector) = self.connectors.write().await.get_mut(&exchange) {
+                    connector.disconnect().await?;
+                }
+                self.market_data.write().await.remove(&exchange);
+                self.health.write().await.exchange_connections.insert(exchange, false);
+            }
+            ControlCommand::EnableExchange(exchange) => {
+                if let Some(connector) = self.connectors.write().await.get_mut(&exchange) {
+                    connector.connect().await?;
+                }
+                self.disabled_exchanges.write().await.remove(&exchange);
+                self.health.write().await.exchange_connections.insert(exchange, true);
+                let symbols = self.subscriptions.read().await.get(&exchange).cloned().unwrap_or_default();
+                for symbol in symbols {
+                    self.subscribe_market_data(exchange, &symbol).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+    
+    /// Subscribe to market data for a canonical symbol, using the exchange's ticker alias
     pub async fn subscribe_market_data(&mut self, exchange: Exchange, symbol: &str) -> Result<()> {
+        {
+            let mut subscriptions = self.subscriptions.write().await;
+            let symbols = subscriptions.entry(exchange).or_default();
+            if !symbols.iter().any(|subscribed| subscribed == symbol) {
+                symbols.push(symbol.to_string());
+            }
+        }
+        let exchange_symbol = self.config.exchanges.exchange_symbol(symbol, &exchange.to_string());
+        let symbol = exchange_symbol.as_str();
         debug!("Subscribing to market data for {} on {}", symbol, exchange);
         
         let mut connectors = self.connectors.write().await;
@@ -414,6 +992,9 @@ impl LiveTradingExecutor {
     /// Process incoming market data
     pub async fn process_market_data(&mut self, exchange: Exchange, orderbook: OrderBook) -> Result<()> {
         let symbol = orderbook.symbol.clone();
+        if let Some(shadow) = &self.shadow {
+            shadow.write().await.update_market_data(exchange, orderbook.clone()).await?;
+        }
         
         // Update market data cache
         {
@@ -440,18 +1021,40 @@ impl LiveTradingExecutor {
             health.recent_errors += 1;
         }
         
+        if let Err(e) = self.notifier.on_health_change(false, Some(exchange), "connection error").await {
+            warn!("Failed to send health notification: {}", e);
+        }
+        
+        // Skip the attempt while in a reconnect-storm cool-down
+        if let Some(remaining) = self.reconnect_cooldown_remaining(exchange).await {
+            return Err(ArbitrageError::Connection(format!(
+                "{} reconnects suspended for {}s after repeated failures", exchange, remaining.as_secs()
+            )).into());
+        }
+        
         // Attempt reconnection
         let mut connectors = self.connectors.write().await;
         if let Some(connector) = connectors.get_mut(&exchange) {
-            match connector.connect().await {
+            let result = connector.connect().await;
+            let mut guards = self.reconnect_guards.write().await;
+            let guard = guards
+                .entry(exchange)
+                .or_insert_with(|| ReconnectGuard::new(&self.config.exchanges.reconnect_guard));
+            
+            match result {
                 Ok(()) => {
                     info!("Successfully reconnected to {}", exchange);
+                    guard.record_success();
                     let mut health = self.health.write().await;
                     health.exchange_connections.insert(exchange, true);
                     health.is_healthy = health.exchange_connections.values().all(|&connected| connected);
                 }
                 Err(e) => {
                     error!("Failed to reconnect to {}: {}", exchange, e);
+                    if guard.record_failure(Instant::now()) {
+                        error!("Reconnect storm on {}: cooling down for {}s",
+                               exchange, self.config.exchanges.reconnect_guard.cooldown_secs);
+                    }
                     return Err(e);
                 }
             }
@@ -460,7 +1063,39 @@ impl LiveTradingExecutor {
         Ok(())
     }
     
-    /// Emergency shutdown - cancel all orders and disconnect
+    /// Remaining reconnect cool-down for an exchange, if one is active
+    pub async fn reconnect_cooldown_remaining(&self, exchange: Exchange) -> Option<Duration> {
+        self.reconnect_guards.read().await
+            .get(&exchange)
+            .and_then(|guard| guard.cooldown_remaining(Instant::now()))
+    }
+    
+    /// Connection status of an exchange; `Error` while in a reconnect cool-down
+    pub async fn exchange_status(&self, exchange: Exchange) -> ConnectionStatus {
+        if self.reconnect_cooldown_remaining(exchange).await.is_some() {
+            return ConnectionStatus::Error;
+        }
+        self.connectors.read().await
+            .get(&exchange)
+            .map(|connector| connector.connection_status())
+            .unwrap_or(ConnectionStatus::Disconnected)
+    }
+    
+    /// Clear an exchange's reconnect cool-down so the next error retries immediately
+    pub async fn reset_reconnect_guard(&self, exchange: Exchange) {
+        if let Some(guard) = self.reconnect_guards.write().await.get_mut(&exchange) {
+            guard.reset();
+            info!("Reconnect guard reset for {}", exchange);
+        }
+    }
+    
+    /// Emergency shutdown - cancel all orders, optionally flatten, and disconnect.
+    ///
+    /// Resting orders are cancelled before any position is flattened: a maker
+    /// left resting while its hedge is closed could fill afterwards and open
+    /// new unhedged exposure, and cancels report the late fills the flatten
+    /// step has to include. Makers go first, then other resting orders, each
+    /// oldest first. The webhook is notified after the cancels.
     pub async fn emergency_shutdown(&mut self) -> Result<()> {
         warn!("Initiating emergency shutdown");
         
@@ -470,14 +1105,28 @@ impl LiveTradingExecutor {
             *shutdown = true;
         }
         
-        // Cancel all active orders
-        let active_orders = self.active_orders.read().await.clone();
+        // Cancel all active orders in a deterministic, makers-first order
+        let mut active_orders: Vec<_> = self.active_orders.read().await.clone().into_iter().collect();
+        active_orders.sort_by_key(|(order_id, (_, order))| (shutdown_cancel_priority(order), order.timestamp, order_id.clone()));
         for (order_id, (exchange, order_response)) in active_orders {
             if let Err(e) = self.cancel_order(exchange, &order_response.symbol, &order_id).await {
                 error!("Failed to cancel order {} during emergency shutdown: {}", order_id, e);
             }
         }
         
+        // Notify only once nothing rests on the books, so a slow webhook cannot delay the cancels
+        if let Err(e) = self.notifier.notify(HealthEventType::EmergencyShutdown, None, "emergency shutdown initiated").await {
+            warn!("Failed to send health notification: {}", e);
+        }
+        
+        if self.config.risk.flatten_on_shutdown {
+            self.flatten_positions().await;
+        }
+
+        if let Some(store) = &self.trade_store {
+            store.shutdown().await;
+        }
+        
         // Disconnect from all exchanges
         let mut connectors = self.connectors.write().await;
         for (exchange, connector) in connectors.iter_mut() {
@@ -490,14 +1139,42 @@ impl LiveTradingExecutor {
         Ok(())
     }
     
+    /// Whether emergency shutdown has been initiated
+    pub async fn is_emergency_shutdown(&self) -> bool {
+        *self.emergency_shutdown.read().await
+    }
+    
+    /// Wait for the kill switch to fire, then run emergency shutdown
+    pub async fn run_kill_switch(&mut self, kill_switch: &KillSwitch) -> Result<()> {
+        kill_switch.triggered().await;
+        warn!("Kill switch triggered");
+        self.emergency_shutdown().await
+    }
+    
     /// Get health status
     pub async fn get_health_status(&self) -> Result<HealthStatus> {
         let mut health = self.health.write().await;
         health.uptime_seconds = self.start_time.elapsed().as_secs();
         health.active_orders = self.active_orders.read().await.len() as u64;
+        health.net_exposure = self.net_exposure().await;
         Ok(health.clone())
     }
     
+    /// Net position size per base asset, summed across exchanges
+    pub async fn net_exposure(&self) -> HashMap<String, f64> {
+        let positions = self.positions.read().await;
+        let mut exposure = HashMap::new();
+        for position in positions.values() {
+            let symbol = self.config.exchanges.canonical_symbol(&position.exchange.to_string(), &position.symbol);
+            let base = match self.config.strategy.quote_currency(&symbol) {
+                Some(quote) => symbol[..symbol.len() - quote.len()].to_string(),
+                None => symbol.clone(),
+            };
+            *exposure.entry(base).or_insert(0.0) += position.size;
+        }
+        exposure
+    }
+    
     /// Get execution statistics
     pub async fn get_execution_statistics(&self) -> Result<ExecutionStatistics> {
         Ok(self.statistics.read().await.clone())
@@ -557,11 +1234,375 @@ impl LiveTradingExecutor {
         stats.success_rate = (stats.successful_orders as f64 / stats.total_orders as f64) * 100.0;
     }
     
-    async fn update_position_pnl(&self, _exchange: Exchange, _symbol: &str) -> Result<()> {
-        // This would update position PnL based on current market prices
-        // Implementation would depend on position tracking logic
+    async fn update_position_pnl(&self, exchange: Exchange, symbol: &str) -> Result<()> {
+        let orderbook = {
+            let market_data = self.market_data.read().await;
+            match market_data.get(&exchange).and_then(|books| books.get(symbol)) {
+                Some(orderbook) => orderbook.clone(),
+                None => return Ok(()),
+            }
+        };
+        
+        // One stop at a time: further updates wait until the last one resolves
+        if self.stop_pending(symbol).await {
+            return Ok(());
+        }
+        
+        let (stop_order, position) = {
+            let mut positions = self.positions.write().await;
+            let position = match positions.get_mut(symbol) {
+                Some(position) if position.exchange == exchange => position,
+                _ => return Ok(()),
+            };
+            
+            let mark_price = if position.size > 0.0 { orderbook.best_bid() } else { orderbook.best_ask() };
+            if let Some(mark_price) = mark_price {
+                position.unrealized_pnl = (mark_price - position.avg_price) * position.size;
+                position.last_update = chrono::Utc::now().timestamp();
+            }
+            
+            (self.stop_loss_order(position, &orderbook), position.clone())
+        };
+        
+        let order = match stop_order {
+            Some(order) => order,
+            None => {
+                for order in self.profit_taking_orders(&position, &orderbook).await {
+                    self.submit_risk_order(exchange, &order).await?;
+                }
+                return Ok(());
+            }
+        };
+        
+        let loss_bps = -position.unrealized_pnl / (position.size.abs() * position.avg_price) * 10000.0;
+        warn!("Stop-loss triggered for {} on {}: loss {:.1} bps, flattening {} at market", symbol, exchange, loss_bps, order.quantity);
+        
+        let response = {
+            let connectors = self.connectors.read().await;
+            let connector = connectors.get(&exchange)
+                .ok_or_else(|| ArbitrageError::Trading(format!("No connector for {}", exchange)))?;
+            connector.place_market_order(&order).await?
+        };
+        self.profit_ladder.write().await.remove(symbol);
+        
+        let touch = if position.size > 0.0 { orderbook.best_bid() } else { orderbook.best_ask() };
+        let exit_price = response.average_price.or(touch).unwrap_or(position.avg_price);
+        self.stop_outs.write().await.push(StopOut {
+            exchange,
+            symbol: symbol.to_string(),
+            size: position.size,
+            entry_price: position.avg_price,
+            exit_price,
+            loss_bps,
+            order_id: response.order_id.clone(),
+            timestamp: now_ns(),
+        });
+        
+        if response.filled_quantity > 0.0 {
+            self.apply_fill(exchange, symbol, order.side, response.filled_quantity, exit_price).await;
+        }
+        if !is_terminal(response.status) {
+            self.active_orders.write().await.insert(response.order_id.clone(), (exchange, response.clone()));
+            self.persist_active_orders().await;
+            self.pending_stops.write().await.insert(symbol.to_string(), PendingExit::new(exchange, response));
+        }
+        
         Ok(())
     }
+    
+    /// Whether a stop-loss order for `symbol` is still working.
+    ///
+    /// A pending stop is re-queried at most once per `EXIT_POLL_INTERVAL`;
+    /// once it reaches a final state, whatever it filled beyond what was
+    /// already applied is folded into the position, whether it ended filled,
+    /// cancelled or expired, and it stops blocking new stops.
+    async fn stop_pending(&self, symbol: &str) -> bool {
+        let pending = {
+            let mut pending_stops = self.pending_stops.write().await;
+            match pending_stops.get_mut(symbol) {
+                Some(pending) if !pending.poll_due() => return true,
+                Some(pending) => {
+                    pending.checked_at = Some(Instant::now());
+                    pending.clone()
+                }
+                None => return false,
+            }
+        };
+        let (exchange, order) = (pending.exchange, pending.order);
+        let current = match self.get_order(exchange, symbol, &order.order_id).await {
+            Ok(current) => current,
+            Err(e) => {
+                warn!("Failed to check stop-loss order {} on {}: {}", order.order_id, exchange, e);
+                return true;
+            }
+        };
+        if !is_terminal(current.status) {
+            return true;
+        }
+        
+        let remaining = current.filled_quantity - order.filled_quantity;
+        if remaining > 0.0 {
+            let price = match current.average_price {
+                Some(price) => price,
+                None => self.stop_outs.read().await.iter().rev()
+                    .find(|stop_out| stop_out.order_id == order.order_id)
+                    .map_or(order.price, |stop_out| stop_out.exit_price),
+            };
+            self.apply_fill(exchange, symbol, order.side, remaining, price).await;
+        }
+        if current.status != OrderStatus::Filled {
+            warn!("Stop-loss order {} for {} on {} ended {:?} after filling {}; the stop can fire again for the rest",
+                  order.order_id, symbol, exchange, current.status, current.filled_quantity);
+        }
+        self.pending_stops.write().await.remove(symbol);
+        self.active_orders.write().await.remove(&order.order_id);
+        self.persist_active_orders().await;
+        false
+    }
+    
+    /// Flag positions open longer than `max_position_age_secs`, alerting once
+    /// per position and optionally flattening them. Positions with an exit
+    /// order still working are left alone until it resolves. Returns the
+    /// aged positions.
+    pub async fn check_position_ages(&self) -> Result<Vec<Position>> {
+        let max_age = self.config.risk.max_position_age_secs as i64;
+        if max_age == 0 {
+            return Ok(Vec::new());
+        }
+        
+        let mut exiting = std::collections::HashSet::new();
+        let pending: Vec<String> = self.pending_stops.read().await.keys().cloned().collect();
+        for symbol in pending {
+            if self.stop_pending(&symbol).await {
+                exiting.insert(symbol);
+            }
+        }
+        
+        let now = chrono::Utc::now().timestamp();
+        let aged: Vec<Position> = self.positions.read().await
+            .values()
+            .filter(|position| position.size != 0.0 && now - position.opened_at > max_age)
+            .filter(|position| !exiting.contains(&position.symbol))
+            .cloned()
+            .collect();
+        
+        for position in &aged {
+            let alerted = self.stale_alerts.write().await.insert(position.symbol.clone(), position.opened_at);
+            if alerted != Some(position.opened_at) {
+                let reason = format!("{} position on {} open for {} s (limit {} s)",
+                                     position.symbol, position.exchange, now - position.opened_at, max_age);
+                warn!("Stale position: {}", reason);
+                if let Err(e) = self.notifier.notify(HealthEventType::StalePosition, Some(position.exchange), &reason).await {
+                    warn!("Failed to send stale position notification: {}", e);
+                }
+            }
+            
+            if !self.config.risk.flatten_aged_positions {
+                continue;
+            }
+            let order = match self.exit_order(position, "stale").await {
+                Some(order) => order,
+                None => {
+                    warn!("No market data to flatten stale {} position on {}", position.symbol, position.exchange);
+                    continue;
+                }
+            };
+            let response = self.submit_risk_order(position.exchange, &order).await?;
+            if response.filled_quantity > 0.0 {
+                let price = response.average_price.unwrap_or(order.price);
+                self.apply_fill(position.exchange, &position.symbol, order.side, response.filled_quantity, price).await;
+            }
+            if !is_terminal(response.status) {
+                self.pending_stops.write().await.insert(position.symbol.clone(), PendingExit::new(position.exchange, response));
+            }
+        }
+        
+        Ok(aged)
+    }
+    
+    /// Check position ages every `POSITION_AGE_CHECK_INTERVAL` until aborted
+    pub async fn monitor_position_ages(self) {
+        let mut interval = tokio::time::interval(POSITION_AGE_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.check_position_ages().await {
+                warn!("Position age check failed: {}", e);
+            }
+        }
+    }
+    
+    /// Close every open position with an IOC order, by exchange then symbol
+    async fn flatten_positions(&self) {
+        let mut positions: Vec<Position> = self.positions.read().await
+            .values()
+            .filter(|position| position.size != 0.0)
+            .cloned()
+            .collect();
+        positions.sort_by_key(|position| (position.exchange.to_string(), position.symbol.clone()));
+        
+        for position in &positions {
+            let order = match self.exit_order(position, "shutdown").await {
+                Some(order) => order,
+                None => {
+                    warn!("No market data to flatten {} position on {} during shutdown", position.symbol, position.exchange);
+                    continue;
+                }
+            };
+            if let Err(e) = self.submit_risk_order(position.exchange, &order).await {
+                error!("Failed to flatten {} on {} during shutdown: {}", position.symbol, position.exchange, e);
+            }
+        }
+    }
+    
+    /// IOC order closing a position at the touch, or `None` without a book
+    async fn exit_order(&self, position: &Position, tag: &str) -> Option<LimitOrder> {
+        let exit_price = {
+            let market_data = self.market_data.read().await;
+            let orderbook = market_data.get(&position.exchange).and_then(|books| books.get(&position.symbol))?;
+            if position.size > 0.0 { orderbook.best_bid() } else { orderbook.best_ask() }
+        }?;
+        Some(LimitOrder {
+            symbol: position.symbol.clone(),
+            side: if position.size > 0.0 { OrderSide::Sell } else { OrderSide::Buy },
+            quantity: position.size.abs(),
+            price: exit_price,
+            time_in_force: TimeInForce::IOC,
+            client_order_id: Some(format!("{}_{}_{}", tag, position.symbol, chrono::Utc::now().timestamp_millis())),
+        })
+    }
+    
+    /// Place a risk-driven order (stop-loss or profit-taking) and track it as active
+    async fn submit_risk_order(&self, exchange: Exchange, order: &LimitOrder) -> Result<OrderResponse> {
+        let connectors = self.connectors.read().await;
+        let connector = connectors.get(&exchange)
+            .ok_or_else(|| ArbitrageError::Trading(format!("No connector for {}", exchange)))?;
+        let response = connector.place_limit_order(order).await?;
+        
+        self.active_orders.write().await.insert(response.order_id.clone(), (exchange, response.clone()));
+        self.persist_active_orders().await;
+        Ok(response)
+    }
+    
+    /// Write the tracked orders to the configured snapshot file, if any
+    async fn persist_active_orders(&self) {
+        let Some(path) = self.config.execution.active_orders_file.clone() else {
+            return;
+        };
+        // Taking the snapshot under the lock keeps a newer state from being
+        // overwritten by an older write that finishes later
+        let _guard = self.snapshot_lock.lock().await;
+        let mut orders: Vec<SnapshotOrder> = self.active_orders.read().await
+            .values()
+            .map(|(exchange, order)| SnapshotOrder { exchange: *exchange, order: order.clone() })
+            .collect();
+        orders.sort_by(|a, b| a.order.order_id.cmp(&b.order.order_id));
+        match tokio::task::spawn_blocking(move || order_snapshot::save(path, &orders)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!("Failed to persist active orders: {}", e),
+            Err(e) => warn!("Active order snapshot task failed: {}", e),
+        }
+    }
+    
+    /// Reload the tracked orders saved by a previous run and reconcile them
+    /// against each exchange's open orders.
+    ///
+    /// Saved orders still open are tracked again with their current state;
+    /// those no longer open filled or were cancelled while the process was
+    /// down and are dropped. Orders on a venue that cannot be queried are kept
+    /// as saved. Returns how many orders are tracked afterwards.
+    pub async fn restore_active_orders(&self) -> Result<usize> {
+        let Some(path) = &self.config.execution.active_orders_file else {
+            return Ok(0);
+        };
+        let saved = {
+            let path = path.clone();
+            tokio::task::spawn_blocking(move || order_snapshot::load(path)).await
+                .map_err(|e| ArbitrageError::Storage(format!("Active order snapshot task failed: {}", e)))??
+        };
+        
+        let mut open_orders: HashMap<Exchange, Option<HashMap<String, OrderResponse>>> = HashMap::new();
+        {
+            let connectors = self.connectors.read().await;
+            for exchange in saved.iter().map(|saved| saved.exchange) {
+                if open_orders.contains_key(&exchange) {
+                    continue;
+                }
+                let open = match connectors.get(&exchange) {
+                    Some(connector) => match connector.get_open_orders(None).await {
+                        Ok(orders) => Some(orders.into_iter().map(|order| (order.order_id.clone(), order)).collect()),
+                        Err(e) => {
+                            warn!("Failed to fetch open orders from {}, keeping saved orders: {}", exchange, e);
+                            None
+                        }
+                    },
+                    None => {
+                        warn!("No connector for {}, keeping saved orders unreconciled", exchange);
+                        None
+                    }
+                };
+                open_orders.insert(exchange, open);
+            }
+        }
+        
+        let restored = {
+            let mut active_orders = self.active_orders.write().await;
+            for SnapshotOrder { exchange, order } in saved {
+                match open_orders.get(&exchange) {
+                    Some(Some(open)) => match open.get(&order.order_id) {
+                        Some(current) => {
+                            active_orders.insert(order.order_id.clone(), (exchange, current.clone()));
+                        }
+                        None => info!("Saved order {} on {} is no longer open; dropping it", order.order_id, exchange),
+                    },
+                    _ => {
+                        active_orders.insert(order.order_id.clone(), (exchange, order));
+                    }
+                }
+            }
+            active_orders.len()
+        };
+        
+        info!("Restored {} active order(s) from {}", restored, path);
+        self.persist_active_orders().await;
+        Ok(restored)
+    }
+}
+
+/// Whether an order can no longer fill
+/// An exit order sent for a symbol and not yet resolved
+#[derive(Debug, Clone)]
+struct PendingExit {
+    exchange: Exchange,
+    /// The order as placed, with the fill already applied to the position
+    order: OrderResponse,
+    /// When the order was last queried
+    checked_at: Option<Instant>,
+}
+
+impl PendingExit {
+    fn new(exchange: Exchange, order: OrderResponse) -> Self {
+        Self { exchange, order, checked_at: Some(Instant::now()) }
+    }
+
+    /// Whether the order is due to be queried again
+    fn poll_due(&self) -> bool {
+        self.checked_at.is_none_or(|at| at.elapsed() >= EXIT_POLL_INTERVAL)
+    }
+}
+
+fn is_terminal(status: OrderStatus) -> bool {
+    matches!(status, OrderStatus::Filled | OrderStatus::Canceled | OrderStatus::Rejected | OrderStatus::Expired)
+}
+
+/// Shutdown cancel rank: resting makers, then other resting orders, then the rest
+fn shutdown_cancel_priority(order: &OrderResponse) -> u8 {
+    let resting = matches!(order.status, OrderStatus::New | OrderStatus::PartiallyFilled);
+    let maker = order.client_order_id.as_deref().is_some_and(|id| id.starts_with("maker_"));
+    match (resting, maker) {
+        (true, true) => 0,
+        (true, false) => 1,
+        (false, _) => 2,
+    }
 }
 
 // Default implementation for OrderResponse (used in error cases)
@@ -640,4 +1681,782 @@ mod tests {
         // Check that emergency flag is set
         assert!(*executor.emergency_shutdown.read().await);
     }
+
+    #[tokio::test]
+    async fn test_control_file_kill_switch_triggers_emergency_shutdown() {
+        let dir = tempfile::tempdir().unwrap();
+        let control_file = dir.path().join("KILL");
+        let mut config = create_test_config();
+        config.risk.kill_switch = crate::config::KillSwitchConfig {
+            control_file: Some(control_file.to_string_lossy().into_owned()),
+            sigusr1: false,
+            poll_interval_ms: 10,
+            command_file: None,
+        };
+        let kill_switch = KillSwitch::new(config.risk.kill_switch.clone());
+        let mut executor = LiveTradingExecutor::new(config).await.unwrap();
+        
+        let create_file = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            std::fs::write(&control_file, b"stop").unwrap();
+        };
+        let (result, ()) = tokio::time::timeout(
+            Duration::from_secs(2),
+            async { tokio::join!(executor.run_kill_switch(&kill_switch), create_file) },
+        ).await.expect("kill switch should fire once the control file exists");
+        
+        result.unwrap();
+        assert!(executor.is_emergency_shutdown().await);
+    }
+
+    #[tokio::test]
+    async fn test_stop_loss_generates_flattening_order() {
+        let mut config = create_test_config();
+        config.risk.stop_loss_bps = 50;
+        let executor = LiveTradingExecutor::new(config).await.unwrap();
+        
+        let position = Position {
+            exchange: Exchange::Binance,
+            symbol: "BTCUSDT".to_string(),
+            size: 0.5,
+            avg_price: 50000.0,
+            unrealized_pnl: 0.0,
+            last_update: 0,
+            opened_at: 0,
+        };
+        
+        // 20 bps against the long: within the stop
+        let mut orderbook = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        orderbook.update_bid(49900.0, 1.0);
+        orderbook.update_ask(49910.0, 1.0);
+        assert!(executor.stop_loss_order(&position, &orderbook).is_none());
+        
+        // 60 bps against the long: flatten with a market sell
+        orderbook.update_bid(49900.0, 0.0);
+        orderbook.update_bid(49700.0, 1.0);
+        let order = executor.stop_loss_order(&position, &orderbook).unwrap();
+        assert_eq!(order.side, OrderSide::Sell);
+        assert_eq!(order.quantity, 0.5);
+        assert!(order.client_order_id.unwrap().starts_with("stop_BTCUSDT_"));
+    }
+
+    #[tokio::test]
+    async fn test_bracket_order_straddles_short_entry() {
+        let mut config = create_test_config();
+        let executor = LiveTradingExecutor::new(config.clone()).await.unwrap();
+        let position = Position {
+            exchange: Exchange::Bybit,
+            symbol: "BTCUSDT".to_string(),
+            size: -0.2,
+            avg_price: 50000.0,
+            unrealized_pnl: 0.0,
+            last_update: 0,
+            opened_at: 0,
+        };
+        assert!(executor.bracket_order(&position).is_none());
+        
+        config.risk.bracket_take_profit_bps = 40.0;
+        config.risk.bracket_stop_bps = 100.0;
+        let executor = LiveTradingExecutor::new(config).await.unwrap();
+        let order = executor.bracket_order(&position).unwrap();
+        assert_eq!(order.side, OrderSide::Buy);
+        assert_eq!(order.quantity, 0.2);
+        assert!((order.take_profit_price - 49800.0).abs() < 1e-6);
+        assert!((order.stop_price - 50500.0).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_profit_ladder_generates_partial_closes() {
+        let mut config = create_test_config();
+        config.risk.profit_targets = vec![
+            crate::config::ProfitTarget { profit_bps: 20.0, fraction: 0.5 },
+            crate::config::ProfitTarget { profit_bps: 50.0, fraction: 0.25 },
+        ];
+        let executor = LiveTradingExecutor::new(config).await.unwrap();
+        
+        let position = Position {
+            exchange: Exchange::Bybit,
+            symbol: "BTCUSDT".to_string(),
+            size: -1.0,
+            avg_price: 50000.0,
+            unrealized_pnl: 0.0,
+            last_update: 0,
+            opened_at: 0,
+        };
+        let mut orderbook = OrderBook::new("BTCUSDT".to_string(), Exchange::Bybit);
+        orderbook.update_bid(49940.0, 1.0);
+        orderbook.update_ask(49950.0, 1.0);
+        
+        // 10 bps in profit: below the first level
+        assert!(executor.profit_taking_orders(&position, &orderbook).await.is_empty());
+        
+        // 30 bps: first level closes half
+        orderbook.update_ask(49950.0, 0.0);
+        orderbook.update_ask(49850.0, 1.0);
+        let orders = executor.profit_taking_orders(&position, &orderbook).await;
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].side, OrderSide::Buy);
+        assert_eq!(orders[0].quantity, 0.5);
+        
+        // 60 bps on the remaining half: second level closes a quarter of the initial size
+        let remaining = Position { size: -0.5, ..position.clone() };
+        orderbook.update_ask(49850.0, 0.0);
+        orderbook.update_ask(49700.0, 1.0);
+        let orders = executor.profit_taking_orders(&remaining, &orderbook).await;
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].quantity, 0.25);
+        assert_eq!(orders[0].price, 49700.0);
+        
+        assert!(executor.profit_taking_orders(&remaining, &orderbook).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_symbol_override_tightens_limits() {
+        let mut config = create_test_config();
+        config.strategy.max_position_size = 1.0;
+        config.risk.symbol_overrides.insert("ETHUSDT".to_string(), crate::config::SymbolRiskOverride {
+            max_position_size: Some(0.2),
+            stop_loss_bps: Some(10),
+            max_position_value_usd: None,
+        });
+        let executor = LiveTradingExecutor::new(config).await.unwrap();
+        
+        let order = |symbol: &str| LimitOrder {
+            symbol: symbol.to_string(),
+            side: OrderSide::Buy,
+            quantity: 0.5,
+            price: 3000.0,
+            time_in_force: TimeInForce::GTC,
+            client_order_id: None,
+        };
+        assert!(executor.check_risk_limits(&order("BTCUSDT")).await.is_ok());
+        let err = executor.check_risk_limits(&order("ETHUSDT")).await.unwrap_err();
+        assert!(err.to_string().contains("exceeds limit 0.2"), "{}", err);
+        
+        assert_eq!(executor.config.risk_limits("ETHUSDT").stop_loss_bps, 10);
+        assert_eq!(executor.config.risk_limits("BTCUSDT").stop_loss_bps, executor.config.risk.stop_loss_bps);
+    }
+    
+    #[tokio::test]
+    async fn test_total_notional_cap_refuses_order() {
+        let mut config = create_test_config();
+        config.risk.max_total_notional_usd = 100_000.0;
+        let executor = LiveTradingExecutor::new(config).await.unwrap();
+        
+        {
+            let mut positions = executor.positions.write().await;
+            for (symbol, size, price) in [("BTCUSDT", 0.5, 50000.0), ("ETHUSDT", 10.0, 3000.0), ("SOLUSDT", 150.0, 100.0)] {
+                positions.insert(symbol.to_string(), Position {
+                    exchange: Exchange::Binance,
+                    symbol: symbol.to_string(),
+                    size,
+                    avg_price: price,
+                    unrealized_pnl: 0.0,
+                    last_update: 0,
+                    opened_at: 0,
+                });
+            }
+        }
+        
+        // $70k open; $1k more fits, $40k more does not
+        let mut order = LimitOrder {
+            symbol: "XRPUSDT".to_string(),
+            side: OrderSide::Buy,
+            quantity: 0.5,
+            price: 2000.0,
+            time_in_force: TimeInForce::GTC,
+            client_order_id: None,
+        };
+        assert!(executor.check_risk_limits(&order).await.is_ok());
+        
+        order.price = 80_000.0;
+        let err = executor.check_risk_limits(&order).await.unwrap_err();
+        assert!(err.to_string().contains("Total notional"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_net_exposure_aggregates_base_asset_across_exchanges() {
+        let mut config = create_test_config();
+        config.strategy.quote_currencies = vec!["USDT".to_string(), "USDC".to_string()];
+        let executor = LiveTradingExecutor::new(config).await.unwrap();
+        
+        {
+            let mut positions = executor.positions.write().await;
+            for (exchange, symbol, size) in [
+                (Exchange::Binance, "BTCUSDT", 0.5),
+                (Exchange::Bybit, "BTCUSDC", -0.5),
+                (Exchange::Binance, "ETHUSDT", 2.0),
+                (Exchange::Bybit, "ETHUSDC", -0.5),
+            ] {
+                positions.insert(symbol.to_string(), Position {
+                    exchange,
+                    symbol: symbol.to_string(),
+                    size,
+                    avg_price: 1000.0,
+                    unrealized_pnl: 0.0,
+                    last_update: 0,
+                    opened_at: 0,
+                });
+            }
+        }
+        
+        let exposure = executor.net_exposure().await;
+        assert_eq!(exposure.len(), 2);
+        assert!(exposure["BTC"].abs() < 1e-9);
+        assert!((exposure["ETH"] - 1.5).abs() < 1e-9);
+        assert_eq!(executor.get_health_status().await.unwrap().net_exposure, exposure);
+    }
+
+    /// Connector stub whose behaviours all default to inert answers; tests set only
+    /// what they exercise. Clones share every recorded call and reported state.
+    #[derive(Clone)]
+    struct MockConnector {
+        /// Placements, cancels and disconnects, in order
+        journal: Arc<std::sync::Mutex<Vec<String>>>,
+        /// Limit orders placed; each fills in full
+        placed_orders: Arc<std::sync::Mutex<Vec<LimitOrder>>>,
+        /// Market orders placed; each rests until `order_state` says otherwise
+        market_orders: Arc<std::sync::Mutex<Vec<MarketOrder>>>,
+        /// Free balance reported per asset
+        balances: HashMap<String, f64>,
+        /// Barrier balance fetches wait on before answering
+        balance_barrier: Option<Arc<tokio::sync::Barrier>>,
+        /// Balance fetches made
+        balance_calls: Arc<std::sync::atomic::AtomicUsize>,
+        /// Quantity a cancel reports as already traded
+        filled_before_cancel: f64,
+        /// Status and filled quantity reported for queried orders
+        order_state: Arc<std::sync::Mutex<(OrderStatus, f64)>>,
+        /// Order queries made
+        queries: Arc<std::sync::atomic::AtomicUsize>,
+        /// Orders reported as resting on the exchange
+        open_orders: Vec<OrderResponse>,
+    }
+
+    impl Default for MockConnector {
+        fn default() -> Self {
+            Self {
+                journal: Arc::default(),
+                placed_orders: Arc::default(),
+                market_orders: Arc::default(),
+                balances: HashMap::new(),
+                balance_barrier: None,
+                balance_calls: Arc::default(),
+                filled_before_cancel: 0.0,
+                order_state: Arc::new(std::sync::Mutex::new((OrderStatus::New, 0.0))),
+                queries: Arc::default(),
+                open_orders: Vec::new(),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ExchangeConnector for MockConnector {
+        async fn connect(&mut self) -> Result<()> { Ok(()) }
+        async fn disconnect(&mut self) -> Result<()> {
+            self.journal.lock().unwrap().push("disconnect".to_string());
+            Ok(())
+        }
+        fn is_connected(&self) -> bool { true }
+        fn connection_status(&self) -> ConnectionStatus { ConnectionStatus::Connected }
+        async fn subscribe_orderbook(&mut self, _symbol: &str) -> Result<()> { Ok(()) }
+        async fn subscribe_trades(&mut self, _symbol: &str) -> Result<()> { Ok(()) }
+        async fn subscribe_ticker(&mut self, _symbol: &str) -> Result<()> { Ok(()) }
+        async fn get_orderbook(&self, symbol: &str) -> Result<OrderBook> {
+            Err(ArbitrageError::DataParsing(format!("No orderbook for {}", symbol)).into())
+        }
+        async fn get_balances(&self) -> Result<HashMap<String, Balance>> {
+            self.balance_calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if let Some(barrier) = &self.balance_barrier {
+                barrier.wait().await;
+            }
+            Ok(self.balances.iter()
+                .map(|(asset, free)| (asset.clone(), Balance { asset: asset.clone(), free: *free, locked: 0.0 }))
+                .collect())
+        }
+        async fn place_limit_order(&self, order: &LimitOrder) -> Result<OrderResponse> {
+            self.journal.lock().unwrap().push(format!("place {} {} {}", order.side, order.quantity, order.symbol));
+            let mut placed_orders = self.placed_orders.lock().unwrap();
+            placed_orders.push(order.clone());
+            Ok(OrderResponse {
+                order_id: format!("mock_{}", placed_orders.len()),
+                symbol: order.symbol.clone(),
+                side: order.side,
+                quantity: order.quantity,
+                price: order.price,
+                status: OrderStatus::Filled,
+                filled_quantity: order.quantity,
+                ..OrderResponse::default()
+            })
+        }
+        async fn place_market_order(&self, order: &MarketOrder) -> Result<OrderResponse> {
+            let mut market_orders = self.market_orders.lock().unwrap();
+            market_orders.push(order.clone());
+            Ok(OrderResponse {
+                order_id: format!("market_{}", market_orders.len()),
+                symbol: order.symbol.clone(),
+                side: order.side,
+                quantity: order.quantity,
+                status: OrderStatus::New,
+                ..OrderResponse::default()
+            })
+        }
+        async fn place_oco(&self, _order: &OcoOrder) -> Result<OcoResponse> {
+            Err(ArbitrageError::NotImplemented("place_oco".to_string()).into())
+        }
+        async fn cancel_order(&self, symbol: &str, order_id: &str) -> Result<OrderResponse> {
+            self.journal.lock().unwrap().push(format!("cancel {}", order_id));
+            if self.filled_before_cancel <= 0.0 {
+                return Ok(OrderResponse {
+                    order_id: order_id.to_string(),
+                    symbol: symbol.to_string(),
+                    status: OrderStatus::Canceled,
+                    ..OrderResponse::default()
+                });
+            }
+            Ok(OrderResponse {
+                order_id: order_id.to_string(),
+                symbol: symbol.to_string(),
+                side: OrderSide::Sell,
+                quantity: 1.0,
+                price: 50000.0,
+                status: OrderStatus::PartiallyFilled,
+                filled_quantity: self.filled_before_cancel,
+                average_price: Some(50010.0),
+                ..OrderResponse::default()
+            })
+        }
+        async fn get_order_status(&self, _symbol: &str, _order_id: &str) -> Result<OrderStatus> {
+            Ok(self.order_state.lock().unwrap().0)
+        }
+        async fn get_order(&self, symbol: &str, order_id: &str) -> Result<OrderResponse> {
+            self.queries.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let (status, filled_quantity) = *self.order_state.lock().unwrap();
+            let order = self.market_orders.lock().unwrap().last().cloned();
+            Ok(OrderResponse {
+                order_id: order_id.to_string(),
+                symbol: symbol.to_string(),
+                side: order.as_ref().map_or(OrderSide::Buy, |order| order.side),
+                quantity: order.as_ref().map_or(0.0, |order| order.quantity),
+                status,
+                filled_quantity,
+                average_price: (filled_quantity > 0.0).then_some(49690.0),
+                ..OrderResponse::default()
+            })
+        }
+        async fn get_open_orders(&self, _symbol: Option<&str>) -> Result<Vec<OrderResponse>> { Ok(self.open_orders.clone()) }
+        fn get_market_data_receiver(&self) -> Option<tokio::sync::mpsc::Receiver<crate::connectors::MarketDataUpdate>> { None }
+        fn get_order_update_receiver(&self) -> Option<tokio::sync::mpsc::Receiver<crate::connectors::OrderUpdate>> { None }
+        async fn refresh_symbol_rules(&mut self) -> Result<()> { Ok(()) }
+        fn symbol_rules(&self, _symbol: &str) -> Option<crate::connectors::SymbolRules> { None }
+    }
+
+    #[tokio::test]
+    async fn test_balances_fetched_in_parallel_and_cached() {
+        let executor = LiveTradingExecutor::new(create_test_config()).await.unwrap();
+        let connector = MockConnector {
+            balances: HashMap::from([("USDT".to_string(), 1000.0)]),
+            balance_barrier: Some(Arc::new(tokio::sync::Barrier::new(2))),
+            ..MockConnector::default()
+        };
+        let calls = connector.balance_calls.clone();
+        {
+            let mut connectors = executor.connectors.write().await;
+            for exchange in [Exchange::Binance, Exchange::Bybit] {
+                connectors.insert(exchange, Box::new(connector.clone()));
+            }
+        }
+        
+        // Sequential fetches would block forever on the barrier
+        let balances = tokio::time::timeout(Duration::from_secs(2), executor.check_balances())
+            .await
+            .expect("balance fetches should run concurrently")
+            .unwrap();
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances[&Exchange::Bybit]["USDT"].free, 1000.0);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+        
+        // Within the TTL the cache answers without touching the exchanges
+        executor.check_balances().await.unwrap();
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+        
+        // Forcing bypasses the cache
+        executor.refresh_balances(true).await.unwrap();
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_min_reserve_refuses_spot_order() {
+        let mut config = create_test_config();
+        config.risk.min_reserves.insert(
+            "bybit".to_string(),
+            HashMap::from([("USDT".to_string(), 900.0)]),
+        );
+        let mut executor = LiveTradingExecutor::new(config).await.unwrap();
+        let connector = MockConnector {
+            balances: HashMap::from([("USDT".to_string(), 1000.0)]),
+            ..MockConnector::default()
+        };
+        let placed_orders = connector.placed_orders.clone();
+        executor.connectors.write().await.insert(Exchange::Bybit, Box::new(connector));
+        
+        // 1000 USDT free with 900 held back: a $250 buy is refused, a $50 one fits
+        let mut order = LimitOrder {
+            symbol: "BTCUSDT".to_string(),
+            side: OrderSide::Buy,
+            quantity: 0.005,
+            price: 50000.0,
+            time_in_force: TimeInForce::GTC,
+            client_order_id: None,
+        };
+        let err = executor.place_order(Exchange::Bybit, order.clone()).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<ArbitrageError>(), Some(ArbitrageError::RiskManagement(_))), "{}", err);
+        assert!(placed_orders.lock().unwrap().is_empty());
+        
+        order.quantity = 0.001;
+        executor.place_order(Exchange::Bybit, order).await.unwrap();
+        assert_eq!(placed_orders.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_partially_filled_cancel_records_residual_position() {
+        let mut executor = LiveTradingExecutor::new(create_test_config()).await.unwrap();
+        executor.connectors.write().await
+            .insert(Exchange::Bybit, Box::new(MockConnector { filled_before_cancel: 0.4, ..MockConnector::default() }));
+        
+        // 0.1 had already been reported filled at placement
+        let placed = OrderResponse {
+            order_id: "resting".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            side: OrderSide::Sell,
+            quantity: 1.0,
+            price: 50000.0,
+            filled_quantity: 0.1,
+            ..OrderResponse::default()
+        };
+        executor.active_orders.write().await.insert("resting".to_string(), (Exchange::Bybit, placed));
+        
+        let response = executor.cancel_order(Exchange::Bybit, "BTCUSDT", "resting").await.unwrap();
+        assert_eq!(response.status, OrderStatus::PartiallyFilled);
+        assert!(executor.active_orders.read().await.is_empty());
+        
+        let position = executor.get_positions().await.unwrap()["BTCUSDT"].clone();
+        assert_eq!(position.exchange, Exchange::Bybit);
+        assert!((position.size + 0.3).abs() < 1e-9, "{}", position.size);
+        assert_eq!(position.avg_price, 50010.0);
+    }
+
+    #[tokio::test]
+    async fn test_stop_in_flight_not_resent_until_resolved() {
+        let mut config = create_test_config();
+        config.risk.stop_loss_bps = 50;
+        let mut executor = LiveTradingExecutor::new(config).await.unwrap();
+        let connector = MockConnector::default();
+        let (market_orders, status, queries) = (connector.market_orders.clone(), connector.order_state.clone(), connector.queries.clone());
+        executor.connectors.write().await.insert(Exchange::Binance, Box::new(connector));
+        executor.positions.write().await.insert("BTCUSDT".to_string(), Position {
+            exchange: Exchange::Binance,
+            symbol: "BTCUSDT".to_string(),
+            size: 0.5,
+            avg_price: 50000.0,
+            unrealized_pnl: 0.0,
+            last_update: 0,
+            opened_at: 0,
+        });
+        
+        // 60 bps against the long on every update
+        let mut orderbook = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        orderbook.update_bid(49700.0, 1.0);
+        orderbook.update_ask(49710.0, 1.0);
+        executor.process_market_data(Exchange::Binance, orderbook.clone()).await.unwrap();
+        executor.process_market_data(Exchange::Binance, orderbook.clone()).await.unwrap();
+        assert_eq!(market_orders.lock().unwrap().len(), 1);
+        assert_eq!(market_orders.lock().unwrap()[0].side, OrderSide::Sell);
+        assert_eq!(executor.stop_outs.read().await.len(), 1);
+        // The stop was just sent, so the update did not query it again
+        assert_eq!(queries.load(std::sync::atomic::Ordering::SeqCst), 0);
+        
+        // Once the stop fills the position is flat and nothing else is sent
+        *status.lock().unwrap() = (OrderStatus::Filled, 0.5);
+        executor.pending_stops.write().await.get_mut("BTCUSDT").unwrap().checked_at = None;
+        executor.process_market_data(Exchange::Binance, orderbook).await.unwrap();
+        assert_eq!(queries.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(market_orders.lock().unwrap().len(), 1);
+        assert!(executor.pending_stops.read().await.is_empty());
+        assert!(executor.active_orders.read().await.is_empty());
+        assert!(executor.get_positions().await.unwrap().get("BTCUSDT").is_none_or(|position| position.size.abs() < 1e-9));
+    }
+
+    #[tokio::test]
+    async fn test_expired_stop_applies_partial_fill_before_refiring() {
+        let mut config = create_test_config();
+        config.risk.stop_loss_bps = 50;
+        let mut executor = LiveTradingExecutor::new(config).await.unwrap();
+        let connector = MockConnector::default();
+        let (market_orders, status) = (connector.market_orders.clone(), connector.order_state.clone());
+        executor.connectors.write().await.insert(Exchange::Binance, Box::new(connector));
+        executor.positions.write().await.insert("BTCUSDT".to_string(), Position {
+            exchange: Exchange::Binance,
+            symbol: "BTCUSDT".to_string(),
+            size: 0.5,
+            avg_price: 50000.0,
+            unrealized_pnl: 0.0,
+            last_update: 0,
+            opened_at: 0,
+        });
+        
+        let mut orderbook = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        orderbook.update_bid(49700.0, 1.0);
+        orderbook.update_ask(49710.0, 1.0);
+        executor.process_market_data(Exchange::Binance, orderbook.clone()).await.unwrap();
+        assert_eq!(market_orders.lock().unwrap().len(), 1);
+        
+        // The stop expires after filling 0.2: that fill is kept and the next stop covers only the rest
+        *status.lock().unwrap() = (OrderStatus::Expired, 0.2);
+        executor.pending_stops.write().await.get_mut("BTCUSDT").unwrap().checked_at = None;
+        executor.process_market_data(Exchange::Binance, orderbook).await.unwrap();
+        let position = executor.get_positions().await.unwrap()["BTCUSDT"].clone();
+        assert!((position.size - 0.3).abs() < 1e-9, "{}", position.size);
+        let market_orders = market_orders.lock().unwrap();
+        assert_eq!(market_orders.len(), 2);
+        assert!((market_orders[1].quantity - 0.3).abs() < 1e-9, "{}", market_orders[1].quantity);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_cancels_makers_then_resting_then_flattens() {
+        let mut config = create_test_config();
+        config.risk.flatten_on_shutdown = true;
+        let mut executor = LiveTradingExecutor::new(config).await.unwrap();
+        let connector = MockConnector::default();
+        let journal = connector.journal.clone();
+        for exchange in [Exchange::Binance, Exchange::Bybit] {
+            executor.connectors.write().await.insert(exchange, Box::new(connector.clone()));
+        }
+
+        let order = |order_id: &str, client_order_id: &str, status: OrderStatus, timestamp: i64| OrderResponse {
+            order_id: order_id.to_string(),
+            client_order_id: Some(client_order_id.to_string()),
+            symbol: "BTCUSDT".to_string(),
+            status,
+            timestamp,
+            ..OrderResponse::default()
+        };
+        {
+            let mut active_orders = executor.active_orders.write().await;
+            active_orders.insert("hedge".to_string(), (Exchange::Binance, order("hedge", "taker_1", OrderStatus::Filled, 1)));
+            active_orders.insert("resting".to_string(), (Exchange::Binance, order("resting", "arb_buy_1", OrderStatus::New, 2)));
+            active_orders.insert("maker".to_string(), (Exchange::Bybit, order("maker", "maker_1", OrderStatus::PartiallyFilled, 3)));
+        }
+
+        // The hedge already filled long on Binance
+        let mut orderbook = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        orderbook.update_bid(49990.0, 1.0);
+        orderbook.update_ask(50000.0, 1.0);
+        executor.market_data.write().await
+            .entry(Exchange::Binance).or_default()
+            .insert("BTCUSDT".to_string(), orderbook);
+        executor.positions.write().await.insert("BTCUSDT".to_string(), Position {
+            exchange: Exchange::Binance,
+            symbol: "BTCUSDT".to_string(),
+            size: 0.5,
+            avg_price: 50000.0,
+            unrealized_pnl: 0.0,
+            last_update: 0,
+            opened_at: 0,
+        });
+
+        executor.emergency_shutdown().await.unwrap();
+        assert_eq!(journal.lock().unwrap()[..4], [
+            "cancel maker".to_string(),
+            "cancel resting".to_string(),
+            "cancel hedge".to_string(),
+            "place SELL 0.5 BTCUSDT".to_string(),
+        ]);
+        assert_eq!(journal.lock().unwrap()[4..], ["disconnect".to_string(), "disconnect".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_aged_position_flagged_and_flattened() {
+        let mut config = create_test_config();
+        config.risk.max_position_age_secs = 60;
+        config.risk.flatten_aged_positions = true;
+        let executor = LiveTradingExecutor::new(config).await.unwrap();
+        let connector = MockConnector::default();
+        let placed_orders = connector.placed_orders.clone();
+        executor.connectors.write().await.insert(Exchange::Bybit, Box::new(connector));
+        
+        let mut orderbook = OrderBook::new("ETHUSDT".to_string(), Exchange::Bybit);
+        orderbook.update_bid(2999.0, 10.0);
+        orderbook.update_ask(3001.0, 10.0);
+        executor.market_data.write().await
+            .entry(Exchange::Bybit).or_default()
+            .insert("ETHUSDT".to_string(), orderbook);
+        
+        let now = chrono::Utc::now().timestamp();
+        {
+            let mut positions = executor.positions.write().await;
+            for (symbol, age) in [("ETHUSDT", 300), ("BTCUSDT", 10), ("SOLUSDT", 300)] {
+                positions.insert(symbol.to_string(), Position {
+                    exchange: Exchange::Bybit,
+                    symbol: symbol.to_string(),
+                    size: -2.0,
+                    avg_price: 3000.0,
+                    unrealized_pnl: 0.0,
+                    last_update: now,
+                    opened_at: now - age,
+                });
+            }
+        }
+        
+        // SOLUSDT already has an exit working, which the mock keeps reporting as resting
+        let working = OrderResponse { order_id: "exit_1".to_string(), symbol: "SOLUSDT".to_string(), status: OrderStatus::New, ..OrderResponse::default() };
+        executor.pending_stops.write().await.insert("SOLUSDT".to_string(), PendingExit::new(Exchange::Bybit, working));
+        
+        let aged = executor.check_position_ages().await.unwrap();
+        assert_eq!(aged.len(), 1);
+        assert_eq!(aged[0].symbol, "ETHUSDT");
+        
+        let orders = placed_orders.lock().unwrap().clone();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].side, OrderSide::Buy);
+        assert_eq!(orders[0].quantity, 2.0);
+        assert_eq!(orders[0].price, 3001.0);
+        assert_eq!(orders[0].time_in_force, TimeInForce::IOC);
+        
+        // The filled exit closed the position, so the next check neither alerts nor flattens again
+        assert_eq!(executor.positions.read().await["ETHUSDT"].size, 0.0);
+        executor.pending_stops.write().await.get_mut("SOLUSDT").unwrap().checked_at = None;
+        assert!(executor.check_position_ages().await.unwrap().is_empty());
+        assert_eq!(placed_orders.lock().unwrap().len(), 1);
+    }
+    
+    #[tokio::test]
+    async fn test_filter_reject_refreshes_rules_and_retries_once() {
+        use crate::connectors::BinanceConnector;
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+        
+        let server = MockServer::start().await;
+        // A volatility band tightened the tick to 0.1 and the lot to 0.001
+        Mock::given(method("GET"))
+            .and(path("/api/v3/exchangeInfo"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "symbols": [{
+                    "symbol": "BTCUSDT",
+                    "filters": [
+                        {"filterType": "PRICE_FILTER", "tickSize": "0.10000000"},
+                        {"filterType": "LOT_SIZE", "minQty": "0.00100000", "stepSize": "0.00100000"},
+                        {"filterType": "NOTIONAL", "minNotional": "5.00000000", "applyMinToMarket": true}
+                    ]
+                }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/order"))
+            .and(query_param("price", "50000.05"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "code": -1013, "msg": "Filter failure: PRICE_FILTER"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/order"))
+            .and(query_param("price", "50000"))
+            .and(query_param("quantity", "0.012"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "symbol": "BTCUSDT", "orderId": 7, "clientOrderId": "retry_1", "transactTime": 1700000000000i64,
+                "price": "50000.00", "origQty": "0.012", "executedQty": "0", "cummulativeQuoteQty": "0",
+                "status": "NEW", "side": "BUY"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        
+        let mut executor = LiveTradingExecutor::new(create_test_config()).await.unwrap();
+        let connector = BinanceConnector::new(crate::connectors::test_exchange_config(&server.uri())).await.unwrap();
+        executor.connectors.write().await.insert(Exchange::Binance, Box::new(connector));
+        
+        let order = LimitOrder {
+            symbol: "BTCUSDT".to_string(),
+            side: OrderSide::Buy,
+            quantity: 0.0125,
+            price: 50000.05,
+            time_in_force: TimeInForce::GTC,
+            client_order_id: Some("retry_1".to_string()),
+        };
+        let response = executor.place_order(Exchange::Binance, order).await.unwrap();
+        assert_eq!(response.order_id, "7");
+        assert_eq!((response.price, response.quantity), (50000.0, 0.012));
+        assert_eq!(response.status, OrderStatus::New);
+    }
+
+    #[tokio::test]
+    async fn test_active_orders_restored_and_reconciled_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = create_test_config();
+        config.execution.active_orders_file = Some(dir.path().join("state/active_orders.json").to_string_lossy().into_owned());
+        let resting = |order_id: &str, client_id: &str| OrderResponse {
+            order_id: order_id.to_string(),
+            client_order_id: Some(client_id.to_string()),
+            symbol: "BTCUSDT".to_string(),
+            quantity: 1.0,
+            price: 50000.0,
+            status: OrderStatus::New,
+            ..OrderResponse::default()
+        };
+        
+        {
+            let executor = LiveTradingExecutor::new(config.clone()).await.unwrap();
+            let mut active_orders = executor.active_orders.write().await;
+            active_orders.insert("maker".to_string(), (Exchange::Binance, resting("maker", "maker_1")));
+            active_orders.insert("filled".to_string(), (Exchange::Binance, resting("filled", "arb_buy_1")));
+            active_orders.insert("hedge".to_string(), (Exchange::Bybit, resting("hedge", "taker_1")));
+            drop(active_orders);
+            executor.persist_active_orders().await;
+        }
+        
+        // Restart: the maker traded part while down, the other Binance order
+        // filled, and Bybit cannot be reached
+        let executor = LiveTradingExecutor::new(config.clone()).await.unwrap();
+        let mut maker = resting("maker", "maker_1");
+        maker.status = OrderStatus::PartiallyFilled;
+        maker.filled_quantity = 0.25;
+        executor.connectors.write().await
+            .insert(Exchange::Binance, Box::new(MockConnector { open_orders: vec![maker, resting("other", "manual")], ..MockConnector::default() }));
+        
+        assert_eq!(executor.restore_active_orders().await.unwrap(), 2);
+        let active_orders = executor.active_orders.read().await.clone();
+        let (exchange, maker) = &active_orders["maker"];
+        assert_eq!(*exchange, Exchange::Binance);
+        assert_eq!(maker.client_order_id.as_deref(), Some("maker_1"));
+        assert_eq!(maker.filled_quantity, 0.25);
+        assert_eq!(active_orders["hedge"].0, Exchange::Bybit);
+        assert!(!active_orders.contains_key("filled"));
+        assert!(!active_orders.contains_key("other"));
+        
+        // The reconciled set is what a further restart would see
+        let saved = order_snapshot::load(config.execution.active_orders_file.unwrap()).unwrap();
+        let ids: Vec<_> = saved.iter().map(|saved| saved.order.order_id.as_str()).collect();
+        assert_eq!(ids, ["hedge", "maker"]);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_persists_leave_a_complete_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("active_orders.json");
+        let mut config = create_test_config();
+        config.execution.active_orders_file = Some(path.to_string_lossy().into_owned());
+        let executor = LiveTradingExecutor::new(config).await.unwrap();
+        for i in 0..20 {
+            let order = OrderResponse { order_id: i.to_string(), symbol: "BTCUSDT".to_string(), ..OrderResponse::default() };
+            executor.active_orders.write().await.insert(i.to_string(), (Exchange::Binance, order));
+        }
+        
+        futures_util::future::join_all((0..20).map(|_| executor.persist_active_orders())).await;
+        assert_eq!(order_snapshot::load(&path).unwrap().len(), 20);
+        assert!(!path.with_extension("tmp").exists());
+    }
 }
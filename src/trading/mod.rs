@@ -1,14 +1,24 @@
 //! Trading execution modules
 
+pub mod control;
 pub mod dry_run;
+pub mod kill_switch;
 pub mod live_trading;
+pub mod order_snapshot;
+pub mod shadow;
+pub mod sweep;
 // pub mod executor; // Will be implemented later
 
-pub use dry_run::{DryRunExecutor, Portfolio, PerformanceMetrics};
+pub use control::ControlCommand;
+pub use dry_run::{DryRunExecutor, DryRunReport, LatencyModel, Portfolio, PerformanceMetrics};
+pub use kill_switch::KillSwitch;
+pub use order_snapshot::SnapshotOrder;
 pub use live_trading::{LiveTradingExecutor, HealthStatus, ExecutionStatistics, Position, ExchangeInfo};
+pub use shadow::{DivergenceReport, FillDivergence, ShadowChecker};
+pub use sweep::{ParameterSweep, SweepResult};
 
 /// Results structure for execution summary
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct ExecutionResults {
     /// Total trades executed
     pub total_trades: u64,
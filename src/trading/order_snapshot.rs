@@ -0,0 +1,49 @@
+//! On-disk snapshot of the orders the executor is tracking
+//!
+//! The snapshot is rewritten whole after every change to the tracked set,
+//! going through a temporary file and a rename so a crash mid-write leaves the
+//! previous snapshot intact. On startup it is read back and reconciled
+//! against each exchange's open orders.
+
+use crate::{
+    connectors::{Exchange, OrderResponse},
+    ArbitrageError, Result,
+};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// One tracked order as stored in the snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotOrder {
+    /// Exchange the order rests on
+    pub exchange: Exchange,
+    /// Order as last reported, including its symbol and client order ID
+    pub order: OrderResponse,
+}
+
+/// Replace the snapshot at `path` with `orders`
+pub fn save<P: AsRef<Path>>(path: P, orders: &[SnapshotOrder]) -> Result<()> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| ArbitrageError::Storage(format!("Failed to create order snapshot directory: {}", e)))?;
+    }
+    let json = serde_json::to_vec_pretty(orders)
+        .map_err(|e| ArbitrageError::Storage(format!("Failed to serialize order snapshot: {}", e)))?;
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, json)
+        .map_err(|e| ArbitrageError::Storage(format!("Failed to write order snapshot: {}", e)))?;
+    std::fs::rename(&tmp, path)
+        .map_err(|e| ArbitrageError::Storage(format!("Failed to replace order snapshot: {}", e)).into())
+}
+
+/// Orders stored at `path`; a missing file is an empty snapshot
+pub fn load<P: AsRef<Path>>(path: P) -> Result<Vec<SnapshotOrder>> {
+    let json = match std::fs::read(path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(ArbitrageError::Storage(format!("Failed to read order snapshot: {}", e)).into()),
+    };
+    serde_json::from_slice(&json)
+        .map_err(|e| ArbitrageError::Storage(format!("Malformed order snapshot: {}", e)).into())
+}
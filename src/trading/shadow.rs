@@ -0,0 +1,177 @@
+//! Shadow comparison of live fills against the dry-run simulator
+//!
+//! Every live fill is replayed through a [`DryRunExecutor`] fed the same market
+//! data, and the gap between the simulated and the real fill is recorded. A
+//! mean price divergence away from zero means the slippage or fill model is
+//! biased.
+
+use crate::{
+    config::ArbitrageConfig,
+    connectors::{Exchange, LimitOrder, OrderResponse, OrderSide},
+    data::OrderBook,
+    trading::DryRunExecutor,
+    Result,
+};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+/// One live fill compared with the simulator's prediction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FillDivergence {
+    /// Exchange the live order filled on
+    pub exchange: Exchange,
+    /// Symbol
+    pub symbol: String,
+    /// Order side
+    pub side: OrderSide,
+    /// Fill price the simulator predicted
+    pub predicted_price: f64,
+    /// Fill quantity the simulator predicted
+    pub predicted_quantity: f64,
+    /// Actual average fill price
+    pub live_price: f64,
+    /// Actual filled quantity
+    pub live_quantity: f64,
+    /// How much worse the live price was than predicted, in bps (negative when better)
+    pub price_divergence_bps: f64,
+}
+
+/// Aggregate divergence between live fills and the simulator
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DivergenceReport {
+    /// Fills compared
+    pub samples: usize,
+    /// Mean signed price divergence in bps; a persistent non-zero value is model bias
+    pub mean_price_divergence_bps: f64,
+    /// Largest absolute price divergence in bps
+    pub max_abs_price_divergence_bps: f64,
+    /// Mean live minus predicted fill quantity
+    pub mean_quantity_divergence: f64,
+}
+
+/// Replays live fills through the dry-run simulator and records the divergence
+pub struct ShadowChecker {
+    simulator: DryRunExecutor,
+    divergences: Vec<FillDivergence>,
+}
+
+impl ShadowChecker {
+    /// Create a checker with a fresh simulator for the given config
+    pub async fn new(config: ArbitrageConfig) -> Result<Self> {
+        Ok(Self::with_simulator(DryRunExecutor::new(config).await?))
+    }
+
+    /// Create a checker around an already configured simulator
+    pub fn with_simulator(simulator: DryRunExecutor) -> Self {
+        Self { simulator, divergences: Vec::new() }
+    }
+
+    /// Feed the simulator the same market data the live executor sees
+    pub async fn update_market_data(&mut self, exchange: Exchange, orderbook: OrderBook) -> Result<()> {
+        self.simulator.update_market_data(exchange, orderbook).await
+    }
+
+    /// Compare a live order's fill with the simulator's prediction for the same order.
+    ///
+    /// Orders that did not fill are skipped.
+    pub async fn record_live_fill(
+        &mut self,
+        exchange: Exchange,
+        order: &LimitOrder,
+        live: &OrderResponse,
+    ) -> Result<Option<FillDivergence>> {
+        let live_price = match live.average_price {
+            Some(price) if live.filled_quantity > 0.0 => price,
+            _ => return Ok(None),
+        };
+        let predicted = self.simulator.execute_order_on(exchange, order.clone()).await?;
+        let predicted_price = predicted.average_price.unwrap_or(order.price);
+
+        let adverse = match order.side {
+            OrderSide::Buy => live_price - predicted_price,
+            OrderSide::Sell => predicted_price - live_price,
+        };
+        let divergence = FillDivergence {
+            exchange,
+            symbol: order.symbol.clone(),
+            side: order.side,
+            predicted_price,
+            predicted_quantity: predicted.filled_quantity,
+            live_price,
+            live_quantity: live.filled_quantity,
+            price_divergence_bps: adverse / predicted_price * 10000.0,
+        };
+        debug!("Shadow fill {} {} on {}: live {:.8} vs predicted {:.8} ({:.2} bps)",
+               divergence.side, divergence.symbol, exchange, live_price, predicted_price, divergence.price_divergence_bps);
+        self.divergences.push(divergence.clone());
+        Ok(Some(divergence))
+    }
+
+    /// Every comparison recorded so far
+    pub fn divergences(&self) -> &[FillDivergence] {
+        &self.divergences
+    }
+
+    /// Summarize the recorded divergences
+    pub fn report(&self) -> DivergenceReport {
+        let samples = self.divergences.len();
+        if samples == 0 {
+            return DivergenceReport::default();
+        }
+        let count = samples as f64;
+        DivergenceReport {
+            samples,
+            mean_price_divergence_bps: self.divergences.iter().map(|d| d.price_divergence_bps).sum::<f64>() / count,
+            max_abs_price_divergence_bps: self.divergences.iter().map(|d| d.price_divergence_bps.abs()).fold(0.0, f64::max),
+            mean_quantity_divergence: self.divergences.iter().map(|d| d.live_quantity - d.predicted_quantity).sum::<f64>() / count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::{OrderStatus, TimeInForce};
+
+    #[tokio::test]
+    async fn test_biased_live_fills_show_in_report() {
+        let mut config = ArbitrageConfig::default();
+        config.execution.slippage_tolerance = 0.0;
+        let mut checker = ShadowChecker::with_simulator(DryRunExecutor::new(config).await.unwrap().with_seed(7));
+
+        let mut book = OrderBook::new("BTCUSDT".to_string(), Exchange::Binance);
+        book.update_bid(49990.0, 5.0);
+        book.update_ask(50000.0, 5.0);
+        checker.update_market_data(Exchange::Binance, book).await.unwrap();
+
+        let order = LimitOrder {
+            symbol: "BTCUSDT".to_string(),
+            side: OrderSide::Buy,
+            quantity: 1.0,
+            price: 50000.0,
+            time_in_force: TimeInForce::IOC,
+            client_order_id: None,
+        };
+        // The venue consistently fills 5 bps worse and only half the size
+        let live = OrderResponse {
+            symbol: "BTCUSDT".to_string(),
+            side: OrderSide::Buy,
+            quantity: 1.0,
+            price: 50000.0,
+            status: OrderStatus::PartiallyFilled,
+            filled_quantity: 0.5,
+            average_price: Some(50025.0),
+            ..OrderResponse::default()
+        };
+        for _ in 0..3 {
+            checker.record_live_fill(Exchange::Binance, &order, &live).await.unwrap().unwrap();
+        }
+        let unfilled = OrderResponse { filled_quantity: 0.0, average_price: None, ..live.clone() };
+        assert!(checker.record_live_fill(Exchange::Binance, &order, &unfilled).await.unwrap().is_none());
+
+        let report = checker.report();
+        assert_eq!(report.samples, 3);
+        assert!((report.mean_price_divergence_bps - 5.0).abs() < 1e-9, "{:?}", report);
+        assert!((report.mean_quantity_divergence + 0.5).abs() < 1e-9, "{:?}", report);
+    }
+}
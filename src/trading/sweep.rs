@@ -0,0 +1,193 @@
+//! Parameter sweeps over recorded market data
+//!
+//! Replays the same recorded session once per parameter combination through
+//! the futures strategy and the dry-run executor, so thresholds and sizing can
+//! be compared on identical data.
+
+use crate::{
+    config::ArbitrageConfig,
+    connectors::{Exchange, LimitOrder, TimeInForce},
+    data::OrderBook,
+    strategy::futures_arbitrage::FuturesArbitrageStrategy,
+    trading::{DryRunExecutor, ExecutionResults},
+    Result,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+/// Seed for the dry-run RNG so every combination sees the same simulated fills
+const SWEEP_SEED: u64 = 42;
+
+/// A recorded orderbook snapshot from one exchange
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedBook {
+    /// Exchange the snapshot came from
+    pub exchange: Exchange,
+    /// Orderbook at that point in the session
+    pub orderbook: OrderBook,
+}
+
+/// One combination of parameter overrides applied to the base config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SweepParams {
+    /// Minimum spread in basis points
+    pub min_spread_bps: f64,
+    /// Maximum position size per exchange
+    pub max_position_size: f64,
+}
+
+/// Outcome of replaying the session with one parameter combination
+#[derive(Debug)]
+pub struct SweepResult {
+    /// Parameters used for this run
+    pub params: SweepParams,
+    /// Opportunities executed during the replay
+    pub opportunities_executed: u64,
+    /// Dry-run execution results
+    pub results: ExecutionResults,
+}
+
+/// Replays a recorded session over a grid of parameter overrides
+pub struct ParameterSweep {
+    base_config: ArbitrageConfig,
+    min_spread_bps: Vec<f64>,
+    max_position_size: Vec<f64>,
+}
+
+impl ParameterSweep {
+    /// Create a sweep whose grid initially holds only the base config values
+    pub fn new(base_config: ArbitrageConfig) -> Self {
+        Self {
+            min_spread_bps: vec![base_config.strategy.min_spread_bps],
+            max_position_size: vec![base_config.strategy.max_position_size],
+            base_config,
+        }
+    }
+
+    /// Sweep over these `min_spread_bps` values
+    pub fn vary_min_spread_bps(mut self, values: Vec<f64>) -> Self {
+        self.min_spread_bps = values;
+        self
+    }
+
+    /// Sweep over these `max_position_size` values
+    pub fn vary_max_position_size(mut self, values: Vec<f64>) -> Self {
+        self.max_position_size = values;
+        self
+    }
+
+    /// Every parameter combination in the grid
+    pub fn combinations(&self) -> Vec<SweepParams> {
+        self.min_spread_bps
+            .iter()
+            .flat_map(|&min_spread_bps| {
+                self.max_position_size.iter().map(move |&max_position_size| SweepParams {
+                    min_spread_bps,
+                    max_position_size,
+                })
+            })
+            .collect()
+    }
+
+    /// Replay the session once per combination and return the results table
+    pub async fn run(&self, session: &[RecordedBook]) -> Result<Vec<SweepResult>> {
+        let mut table = Vec::new();
+        for params in self.combinations() {
+            let result = self.run_one(&params, session).await?;
+            info!("Sweep {:?}: {} opportunities, {} trades, PnL {:.2}",
+                  params, result.opportunities_executed, result.results.total_trades, result.results.total_pnl);
+            table.push(result);
+        }
+        Ok(table)
+    }
+
+    async fn run_one(&self, params: &SweepParams, session: &[RecordedBook]) -> Result<SweepResult> {
+        let mut config = self.base_config.clone();
+        config.strategy.min_spread_bps = params.min_spread_bps;
+        config.strategy.max_position_size = params.max_position_size;
+
+        let mut symbols: Vec<String> = session
+            .iter()
+            .map(|book| config.exchanges.canonical_symbol(&book.exchange.to_string(), &book.orderbook.symbol))
+            .collect();
+        symbols.sort();
+        symbols.dedup();
+
+        let strategy = FuturesArbitrageStrategy::new(config.clone(), symbols).await?;
+        let mut executor = DryRunExecutor::new(config).await?.with_seed(SWEEP_SEED);
+        let mut opportunities_executed = 0;
+
+        for recorded in session {
+            strategy.update_orderbook(recorded.exchange, recorded.orderbook.clone()).await?;
+            executor.update_market_data(recorded.exchange, recorded.orderbook.clone()).await?;
+
+            for opportunity in strategy.detect_opportunities().await? {
+                let maker = LimitOrder {
+                    symbol: opportunity.symbol.clone(),
+                    side: opportunity.maker_side,
+                    quantity: opportunity.quantity,
+                    price: opportunity.maker_price,
+                    time_in_force: TimeInForce::GTX,
+                    client_order_id: Some(format!("maker_{}", opportunity.correlation_id)),
+                };
+                let taker = LimitOrder {
+                    side: opportunity.taker_side,
+                    price: opportunity.taker_price,
+                    time_in_force: TimeInForce::IOC,
+                    client_order_id: Some(format!("taker_{}", opportunity.correlation_id)),
+                    ..maker.clone()
+                };
+                if executor.execute_order_on(opportunity.maker_exchange, maker).await.is_ok()
+                    && executor.execute_order_on(opportunity.taker_exchange, taker).await.is_ok()
+                {
+                    opportunities_executed += 1;
+                } else {
+                    debug!("Sweep replay failed to fill {}", opportunity.correlation_id);
+                }
+            }
+        }
+
+        Ok(SweepResult {
+            params: params.clone(),
+            opportunities_executed,
+            results: executor.get_results().await,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorded(exchange: Exchange, bid: f64, ask: f64) -> RecordedBook {
+        let mut orderbook = OrderBook::new("BTCUSDT".to_string(), exchange);
+        orderbook.update_bid(bid, 1.0);
+        orderbook.update_ask(ask, 1.0);
+        RecordedBook { exchange, orderbook }
+    }
+
+    #[tokio::test]
+    async fn test_higher_spread_threshold_trades_less() {
+        let mut config = ArbitrageConfig::default();
+        config.execution.min_order_size = 0.001;
+
+        // Bybit bid crosses the Binance ask by 6 bps, then by 20 bps
+        let session = vec![
+            recorded(Exchange::Binance, 49990.0, 50000.0),
+            recorded(Exchange::Bybit, 50030.0, 50040.0),
+            recorded(Exchange::Bybit, 50100.0, 50110.0),
+        ];
+
+        let table = ParameterSweep::new(config)
+            .vary_min_spread_bps(vec![5.0, 15.0])
+            .run(&session)
+            .await
+            .unwrap();
+
+        assert_eq!(table.len(), 2);
+        assert_eq!(table[0].params.min_spread_bps, 5.0);
+        assert_eq!(table[0].opportunities_executed, 2);
+        assert_eq!(table[1].opportunities_executed, 1);
+        assert!(table[1].results.total_trades < table[0].results.total_trades);
+    }
+}
@@ -0,0 +1,196 @@
+//! Append-only, hash-chained audit log of order activity
+//!
+//! Every entry carries the SHA-256 of its own contents together with the hash
+//! of the entry before it, so editing, removing or reordering any line breaks
+//! the chain from that point on.
+
+use crate::{
+    config::AuditLogConfig,
+    connectors::{Exchange, OrderResponse},
+    utils::time::now_ns,
+    ArbitrageError, Result,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Hash the first entry chains to
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Kind of order activity being audited
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEvent {
+    /// Order accepted by the exchange
+    Placed,
+    /// Order cancelled
+    Cancelled,
+    /// Order filled, fully or partially
+    Filled,
+}
+
+/// One audit log line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position in the log, starting at 0
+    pub sequence: u64,
+    /// When the entry was recorded, in nanoseconds
+    pub timestamp: i64,
+    /// What happened
+    pub event: AuditEvent,
+    /// Exchange the order is on
+    pub exchange: Exchange,
+    /// Order as reported by the exchange
+    pub order: OrderResponse,
+    /// Hash of the previous entry
+    pub prev_hash: String,
+    /// SHA-256 over this entry's contents and `prev_hash`
+    pub hash: String,
+}
+
+impl AuditEntry {
+    fn compute_hash(&self) -> String {
+        let body = serde_json::json!([self.sequence, self.timestamp, self.event, self.exchange, self.order]);
+        let mut hasher = Sha256::new();
+        hasher.update(self.prev_hash.as_bytes());
+        hasher.update(body.to_string().as_bytes());
+        hex::encode(hasher.finalize())
+    }
+}
+
+/// Hash-chained audit log, mirrored to a JSON-lines file when opened from a path
+pub struct AuditLog {
+    path: Option<PathBuf>,
+    entries: Mutex<Vec<AuditEntry>>,
+}
+
+impl AuditLog {
+    /// Open (or create) a log file, continuing the chain of any existing entries
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| ArbitrageError::Storage(format!("Failed to create audit log directory: {}", e)))?;
+        }
+        let mut entries = Vec::new();
+        if path.exists() {
+            let file = std::fs::File::open(path)
+                .map_err(|e| ArbitrageError::Storage(format!("Failed to open audit log: {}", e)))?;
+            for line in BufReader::new(file).lines() {
+                let line = line.map_err(|e| ArbitrageError::Storage(format!("Failed to read audit log: {}", e)))?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let entry = serde_json::from_str(&line)
+                    .map_err(|e| ArbitrageError::Storage(format!("Malformed audit log entry: {}", e)))?;
+                entries.push(entry);
+            }
+        }
+        Ok(Self { path: Some(path.to_path_buf()), entries: Mutex::new(entries) })
+    }
+
+    /// A log held only in memory, for tests and dry runs
+    pub fn in_memory() -> Self {
+        Self { path: None, entries: Mutex::new(Vec::new()) }
+    }
+
+    /// Open the configured log, or `None` when auditing is disabled
+    pub fn from_config(config: &AuditLogConfig) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        Self::open(&config.path).map(Some)
+    }
+
+    /// Append an entry chained to the last one
+    pub fn record(&self, event: AuditEvent, exchange: Exchange, order: &OrderResponse) -> Result<AuditEntry> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let mut entry = AuditEntry {
+            sequence: entries.len() as u64,
+            timestamp: now_ns(),
+            event,
+            exchange,
+            order: order.clone(),
+            prev_hash: entries.last().map_or_else(|| GENESIS_HASH.to_string(), |last| last.hash.clone()),
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash();
+
+        if let Some(path) = &self.path {
+            let line = serde_json::to_string(&entry)
+                .map_err(|e| ArbitrageError::Storage(format!("Failed to serialize audit entry: {}", e)))?;
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| ArbitrageError::Storage(format!("Failed to open audit log: {}", e)))?;
+            writeln!(file, "{}", line)
+                .map_err(|e| ArbitrageError::Storage(format!("Failed to append audit entry: {}", e)))?;
+        }
+        entries.push(entry.clone());
+        Ok(entry)
+    }
+
+    /// Whether every entry's hash matches its contents and chains to its predecessor
+    pub fn verify(&self) -> bool {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let mut prev_hash = GENESIS_HASH;
+        for (sequence, entry) in entries.iter().enumerate() {
+            if entry.sequence != sequence as u64 || entry.prev_hash != prev_hash || entry.hash != entry.compute_hash() {
+                return false;
+            }
+            prev_hash = &entry.hash;
+        }
+        true
+    }
+
+    /// All entries, oldest first
+    pub fn entries(&self) -> Vec<AuditEntry> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::{OrderSide, OrderStatus};
+
+    fn order(order_id: &str, status: OrderStatus) -> OrderResponse {
+        OrderResponse {
+            order_id: order_id.to_string(),
+            client_order_id: None,
+            symbol: "BTCUSDT".to_string(),
+            side: OrderSide::Buy,
+            quantity: 0.1,
+            price: 50000.0,
+            status,
+            filled_quantity: 0.0,
+            average_price: None,
+            timestamp: 1_700_000_000_000_000_000,
+        }
+    }
+
+    #[test]
+    fn test_tampered_entry_fails_verification() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+        let log = AuditLog::open(&path).unwrap();
+        log.record(AuditEvent::Placed, Exchange::Binance, &order("1", OrderStatus::New)).unwrap();
+        log.record(AuditEvent::Filled, Exchange::Binance, &order("1", OrderStatus::Filled)).unwrap();
+        log.record(AuditEvent::Cancelled, Exchange::Bybit, &order("2", OrderStatus::Canceled)).unwrap();
+        assert!(log.verify());
+
+        // Reopening continues the same chain
+        let reopened = AuditLog::open(&path).unwrap();
+        assert!(reopened.verify());
+        assert_eq!(reopened.record(AuditEvent::Placed, Exchange::Bybit, &order("3", OrderStatus::New)).unwrap().sequence, 3);
+        assert!(AuditLog::open(&path).unwrap().verify());
+
+        let tampered = std::fs::read_to_string(&path).unwrap().replacen("\"price\":50000.0", "\"price\":49000.0", 1);
+        std::fs::write(&path, tampered).unwrap();
+        assert!(!AuditLog::open(&path).unwrap().verify());
+    }
+}
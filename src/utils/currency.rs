@@ -0,0 +1,82 @@
+//! Conversion of amounts into a single reporting currency
+//!
+//! PnL is earned in each symbol's quote currency. Sessions trading several
+//! quotes convert every amount into the configured reporting currency with
+//! configured rates, which can be refreshed from a price feed at runtime.
+
+use crate::config::StrategyConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Converts amounts in any currency into the reporting currency
+#[derive(Debug, Clone)]
+pub struct CurrencyConverter {
+    reporting_currency: String,
+    /// Value of one unit of each currency in the reporting currency
+    rates: HashMap<String, f64>,
+}
+
+/// Per-currency amounts aggregated into the reporting currency
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CurrencyReport {
+    /// Currency `total` is expressed in
+    pub reporting_currency: String,
+    /// Sum of every convertible amount
+    pub total: f64,
+    /// Amounts in their own currency
+    pub by_currency: HashMap<String, f64>,
+    /// Currencies left out of `total` because no rate is known
+    pub unconverted: Vec<String>,
+}
+
+impl CurrencyConverter {
+    /// Create a converter with no rates besides the reporting currency itself
+    pub fn new(reporting_currency: &str) -> Self {
+        Self { reporting_currency: reporting_currency.to_string(), rates: HashMap::new() }
+    }
+
+    /// Create a converter from the configured reporting currency and rates
+    pub fn from_config(config: &StrategyConfig) -> Self {
+        Self { reporting_currency: config.reporting_currency.clone(), rates: config.conversion_rates.clone() }
+    }
+
+    /// Currency amounts are converted into
+    pub fn reporting_currency(&self) -> &str {
+        &self.reporting_currency
+    }
+
+    /// Set or refresh the reporting-currency value of one unit of `currency`
+    pub fn set_rate(&mut self, currency: &str, rate: f64) {
+        self.rates.insert(currency.to_string(), rate);
+    }
+
+    /// Reporting-currency value of one unit of `currency`, if known
+    pub fn rate(&self, currency: &str) -> Option<f64> {
+        if currency == self.reporting_currency {
+            return Some(1.0);
+        }
+        self.rates.get(currency).copied()
+    }
+
+    /// Convert an amount into the reporting currency, if a rate is known
+    pub fn convert(&self, amount: f64, currency: &str) -> Option<f64> {
+        self.rate(currency).map(|rate| amount * rate)
+    }
+
+    /// Aggregate per-currency amounts at the current rates
+    pub fn report(&self, amounts: &HashMap<String, f64>) -> CurrencyReport {
+        let mut report = CurrencyReport {
+            reporting_currency: self.reporting_currency.clone(),
+            by_currency: amounts.clone(),
+            ..CurrencyReport::default()
+        };
+        for (currency, amount) in amounts {
+            match self.convert(*amount, currency) {
+                Some(converted) => report.total += converted,
+                None => report.unconverted.push(currency.clone()),
+            }
+        }
+        report.unconverted.sort();
+        report
+    }
+}
@@ -0,0 +1,275 @@
+//! Runtime performance metrics
+
+use std::collections::VecDeque;
+
+/// Result of checking the profit rate against the configured floor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProfitRateStatus {
+    /// Not enough history to judge yet
+    WarmingUp,
+    /// Profit rate is at or above the floor
+    Healthy,
+    /// Profit rate is below the floor
+    BelowFloor {
+        /// Current realized profit per hour
+        rate_per_hour: f64,
+    },
+}
+
+/// Rolling realized-PnL-per-hour tracker
+#[derive(Debug, Clone)]
+pub struct ProfitRateMonitor {
+    /// Rolling window in milliseconds
+    window_ms: i64,
+    /// Minimum acceptable profit per hour (0 disables the floor)
+    floor_per_hour: f64,
+    /// Realized PnL samples (timestamp ms, pnl)
+    samples: VecDeque<(i64, f64)>,
+    /// Timestamp of the first sample ever recorded
+    started_at: Option<i64>,
+}
+
+impl ProfitRateMonitor {
+    /// Create a monitor over a rolling window
+    pub fn new(window_secs: u64, floor_per_hour: f64) -> Self {
+        Self {
+            window_ms: window_secs as i64 * 1000,
+            floor_per_hour,
+            samples: VecDeque::new(),
+            started_at: None,
+        }
+    }
+
+    /// Record realized PnL at a timestamp (milliseconds)
+    pub fn record(&mut self, timestamp_ms: i64, realized_pnl: f64) {
+        self.started_at.get_or_insert(timestamp_ms);
+        self.samples.push_back((timestamp_ms, realized_pnl));
+        self.evict(timestamp_ms);
+    }
+
+    /// Realized profit per hour over the rolling window ending at `now_ms`
+    pub fn rate_per_hour(&mut self, now_ms: i64) -> f64 {
+        self.evict(now_ms);
+        let window_pnl: f64 = self.samples.iter().map(|(_, pnl)| pnl).sum();
+        window_pnl * 3_600_000.0 / self.window_ms as f64
+    }
+
+    /// Compare the current rate to the floor; judged only once a full window has elapsed
+    pub fn check(&mut self, now_ms: i64) -> ProfitRateStatus {
+        let rate_per_hour = self.rate_per_hour(now_ms);
+        match self.started_at {
+            Some(started) if now_ms - started >= self.window_ms => {}
+            _ => return ProfitRateStatus::WarmingUp,
+        }
+
+        if self.floor_per_hour > 0.0 && rate_per_hour < self.floor_per_hour {
+            ProfitRateStatus::BelowFloor { rate_per_hour }
+        } else {
+            ProfitRateStatus::Healthy
+        }
+    }
+
+    fn evict(&mut self, now_ms: i64) {
+        while let Some((timestamp, _)) = self.samples.front() {
+            if now_ms - timestamp > self.window_ms {
+                self.samples.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+/// Result of checking how long it has been since the last detected opportunity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchdogStatus {
+    /// Disabled, or less than one window since monitoring started
+    WarmingUp,
+    /// Opportunities were detected within the window
+    Healthy,
+    /// No market data arrived within the window; a feed is likely down
+    NoMarketData {
+        /// Milliseconds since the last market data update
+        idle_ms: i64,
+    },
+    /// Market data is flowing but nothing was detected within the window
+    NoOpportunities {
+        /// Milliseconds since the last detected opportunity
+        idle_ms: i64,
+    },
+}
+
+/// Tracks whether opportunity detection has gone quiet while data keeps flowing
+#[derive(Debug, Clone)]
+pub struct OpportunityWatchdog {
+    /// Alert window in milliseconds (0 disables)
+    window_ms: i64,
+    /// Detected-opportunity count at the last check
+    last_count: u64,
+    /// When the detected-opportunity count last increased
+    last_opportunity_at: Option<i64>,
+    /// When market data was last received
+    last_data_at: Option<i64>,
+    /// Timestamp of the first observation
+    started_at: Option<i64>,
+}
+
+impl OpportunityWatchdog {
+    /// Create a watchdog alerting after `window_secs` without opportunities
+    pub fn new(window_secs: u64) -> Self {
+        Self {
+            window_ms: window_secs as i64 * 1000,
+            last_count: 0,
+            last_opportunity_at: None,
+            last_data_at: None,
+            started_at: None,
+        }
+    }
+
+    /// Record that market data arrived at a timestamp (milliseconds)
+    pub fn record_market_data(&mut self, timestamp_ms: i64) {
+        self.started_at.get_or_insert(timestamp_ms);
+        self.last_data_at = Some(timestamp_ms);
+    }
+
+    /// Compare the running opportunity count against the window ending at `now_ms`
+    pub fn check(&mut self, opportunities_detected: u64, now_ms: i64) -> WatchdogStatus {
+        let started = *self.started_at.get_or_insert(now_ms);
+        let last_opportunity_at = *self.last_opportunity_at.get_or_insert(started);
+        if opportunities_detected > self.last_count {
+            self.last_count = opportunities_detected;
+            self.last_opportunity_at = Some(now_ms);
+            return WatchdogStatus::Healthy;
+        }
+        if self.window_ms == 0 || now_ms - started < self.window_ms {
+            return WatchdogStatus::WarmingUp;
+        }
+
+        let data_idle_ms = now_ms - self.last_data_at.unwrap_or(started);
+        let opportunity_idle_ms = now_ms - last_opportunity_at;
+        if data_idle_ms >= self.window_ms {
+            WatchdogStatus::NoMarketData { idle_ms: data_idle_ms }
+        } else if opportunity_idle_ms >= self.window_ms {
+            WatchdogStatus::NoOpportunities { idle_ms: opportunity_idle_ms }
+        } else {
+            WatchdogStatus::Healthy
+        }
+    }
+}
+
+/// Caps how many lines a noisy event logs per interval
+#[derive(Debug, Clone)]
+pub struct LogSampler {
+    /// Lines allowed per interval (0 disables sampling)
+    limit: usize,
+    /// Interval length in milliseconds
+    interval_ms: i64,
+    /// Start of the current interval
+    window_start: Option<i64>,
+    /// Lines logged in the current interval
+    logged: usize,
+    /// Lines suppressed in the current interval
+    suppressed: u64,
+}
+
+/// Outcome of offering a batch of lines to a [`LogSampler`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SampleDecision {
+    /// How many of the offered lines to log, taken from the front of the batch
+    pub log: usize,
+    /// Lines suppressed in the interval that just closed, if one did
+    pub closed_suppressed: u64,
+}
+
+impl LogSampler {
+    /// Create a sampler allowing `limit` lines per `interval_ms`
+    pub fn new(limit: usize, interval_ms: u64) -> Self {
+        Self {
+            limit,
+            interval_ms: interval_ms.max(1) as i64,
+            window_start: None,
+            logged: 0,
+            suppressed: 0,
+        }
+    }
+
+    /// Offer `count` lines at `now_ms`, ordered most representative first
+    pub fn admit(&mut self, now_ms: i64, count: usize) -> SampleDecision {
+        if self.limit == 0 {
+            return SampleDecision { log: count, closed_suppressed: 0 };
+        }
+        let mut closed_suppressed = 0;
+        match self.window_start {
+            Some(start) if now_ms - start < self.interval_ms => {}
+            _ => {
+                closed_suppressed = std::mem::take(&mut self.suppressed);
+                self.window_start = Some(now_ms);
+                self.logged = 0;
+            }
+        }
+        let log = count.min(self.limit - self.logged);
+        self.logged += log;
+        self.suppressed += (count - log) as u64;
+        SampleDecision { log, closed_suppressed }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profit_rate_and_floor_alert() {
+        const MINUTE: i64 = 60_000;
+        // 30-minute window, $20/hour floor
+        let mut monitor = ProfitRateMonitor::new(1800, 20.0);
+
+        // $5 every 5 minutes = $60/hour
+        for i in 0..=6 {
+            monitor.record(i * 5 * MINUTE, 5.0);
+        }
+        assert!((monitor.rate_per_hour(30 * MINUTE) - 70.0).abs() < 1e-9);
+        assert_eq!(monitor.check(30 * MINUTE), ProfitRateStatus::Healthy);
+
+        // Profits dry up to $1 every 10 minutes
+        for i in 1..=3 {
+            monitor.record(30 * MINUTE + i * 10 * MINUTE, 1.0);
+        }
+        match monitor.check(61 * MINUTE) {
+            ProfitRateStatus::BelowFloor { rate_per_hour } => assert!((rate_per_hour - 6.0).abs() < 1e-9),
+            status => panic!("expected BelowFloor, got {:?}", status),
+        }
+    }
+
+    #[test]
+    fn test_no_alert_before_full_window() {
+        let mut monitor = ProfitRateMonitor::new(3600, 100.0);
+        monitor.record(0, 1.0);
+        assert_eq!(monitor.check(60_000), ProfitRateStatus::WarmingUp);
+    }
+
+    #[test]
+    fn test_watchdog_alerts_on_flowing_data_without_opportunities() {
+        const SECOND: i64 = 1000;
+        let mut watchdog = OpportunityWatchdog::new(60);
+
+        // Books update every second but no spread ever crosses the threshold
+        for i in 0..=59 {
+            watchdog.record_market_data(i * SECOND);
+        }
+        assert_eq!(watchdog.check(0, 59 * SECOND), WatchdogStatus::WarmingUp);
+
+        watchdog.record_market_data(60 * SECOND);
+        assert_eq!(watchdog.check(0, 60 * SECOND), WatchdogStatus::NoOpportunities { idle_ms: 60 * SECOND });
+
+        // An opportunity clears the alert
+        assert_eq!(watchdog.check(1, 61 * SECOND), WatchdogStatus::Healthy);
+    }
+
+    #[test]
+    fn test_watchdog_distinguishes_missing_data() {
+        let mut watchdog = OpportunityWatchdog::new(60);
+        watchdog.record_market_data(0);
+        assert_eq!(watchdog.check(0, 90_000), WatchdogStatus::NoMarketData { idle_ms: 90_000 });
+    }
+}
@@ -1,7 +1,18 @@
 //! Utility modules
 
+pub mod audit_log;
+pub mod currency;
 pub mod logger;
-// pub mod metrics; // Will be implemented later
+pub mod notifier;
+pub mod metrics;
+pub mod prometheus;
+pub mod time;
+pub mod trade_store;
 
+pub use audit_log::{AuditEntry, AuditEvent, AuditLog};
+pub use currency::{CurrencyConverter, CurrencyReport};
 pub use logger::*;
-// pub use metrics::*;
+pub use notifier::{HealthEvent, HealthEventType, WebhookNotifier};
+pub use metrics::*;
+pub use prometheus::{Histogram, MetricsExporter};
+pub use trade_store::{TradeRecord, TradeStore, TradeWriter};
@@ -0,0 +1,183 @@
+//! Health-transition webhook notifications
+
+use crate::{config::WebhookConfig, connectors::Exchange, ArbitrageError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+/// Kind of health event reported to the webhook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthEventType {
+    /// System transitioned from healthy to unhealthy
+    Unhealthy,
+    /// System transitioned from unhealthy to healthy
+    Recovered,
+    /// Emergency shutdown was initiated
+    EmergencyShutdown,
+    /// A position stayed open longer than the configured maximum age
+    StalePosition,
+    /// No market data arrived within the opportunity watchdog window
+    NoMarketData,
+    /// Market data is flowing but no opportunity was detected within the watchdog window
+    NoOpportunities,
+}
+
+/// JSON payload POSTed to the webhook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthEvent {
+    /// Event type
+    pub event_type: HealthEventType,
+    /// Exchange involved, if any
+    pub exchange: Option<Exchange>,
+    /// Human-readable reason
+    pub reason: String,
+    /// Event timestamp in milliseconds
+    pub timestamp: i64,
+}
+
+/// POSTs health events to a webhook on health transitions, with retry and rate limiting
+pub struct WebhookNotifier {
+    config: WebhookConfig,
+    client: reqwest::Client,
+    /// Last observed health, used to detect transitions
+    last_healthy: Mutex<Option<bool>>,
+    /// Last delivery time per event type
+    last_sent: Mutex<HashMap<HealthEventType, Instant>>,
+}
+
+impl WebhookNotifier {
+    /// Create a notifier; it does nothing when no URL is configured
+    pub fn new(config: WebhookConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.request_timeout_ms))
+            .build()
+            .unwrap_or_default();
+        Self {
+            config,
+            client,
+            last_healthy: Mutex::new(None),
+            last_sent: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Report the current health; a notification is sent only when it changes.
+    ///
+    /// The first observation sets the baseline and only notifies if unhealthy.
+    pub async fn on_health_change(&self, is_healthy: bool, exchange: Option<Exchange>, reason: &str) -> Result<()> {
+        let previous = {
+            let mut last_healthy = self.last_healthy.lock().await;
+            last_healthy.replace(is_healthy)
+        };
+
+        let event_type = match (previous, is_healthy) {
+            (Some(true), false) | (None, false) => HealthEventType::Unhealthy,
+            (Some(false), true) => HealthEventType::Recovered,
+            _ => return Ok(()),
+        };
+        self.notify(event_type, exchange, reason).await
+    }
+
+    /// Send an event unless one of the same type was sent within the rate-limit window
+    pub async fn notify(&self, event_type: HealthEventType, exchange: Option<Exchange>, reason: &str) -> Result<()> {
+        let url = match &self.config.url {
+            Some(url) => url,
+            None => return Ok(()),
+        };
+
+        {
+            let mut last_sent = self.last_sent.lock().await;
+            let min_interval = Duration::from_secs(self.config.min_interval_secs);
+            if let Some(sent_at) = last_sent.get(&event_type) {
+                if sent_at.elapsed() < min_interval {
+                    debug!("Suppressing {:?} webhook within rate-limit window", event_type);
+                    return Ok(());
+                }
+            }
+            last_sent.insert(event_type, Instant::now());
+        }
+
+        let event = HealthEvent {
+            event_type,
+            exchange,
+            reason: reason.to_string(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        };
+        self.deliver(url, &event).await
+    }
+
+    async fn deliver(&self, url: &str, event: &HealthEvent) -> Result<()> {
+        let mut last_error = String::new();
+        for attempt in 0..=self.config.max_retries {
+            if attempt > 0 {
+                tokio::time::sleep(Duration::from_millis(250 * attempt as u64)).await;
+            }
+            match self.client.post(url).json(event).send().await {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) => last_error = format!("HTTP {}", response.status()),
+                Err(e) => last_error = e.to_string(),
+            }
+            warn!("Health webhook delivery attempt {} failed: {}", attempt + 1, last_error);
+        }
+        Err(ArbitrageError::Connection(format!("Health webhook delivery failed: {}", last_error)).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_webhook_sent_only_on_unhealthy_transition() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(serde_json::json!({
+                "event_type": "unhealthy",
+                "exchange": "Bybit",
+                "reason": "websocket disconnected"
+            })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let notifier = WebhookNotifier::new(WebhookConfig {
+            url: Some(server.uri()),
+            max_retries: 0,
+            min_interval_secs: 60,
+            request_timeout_ms: 2000,
+        });
+
+        // Steady healthy state sends nothing
+        notifier.on_health_change(true, None, "ok").await.unwrap();
+        notifier.on_health_change(true, None, "ok").await.unwrap();
+
+        // Transition to unhealthy sends once; staying unhealthy does not resend
+        notifier.on_health_change(false, Some(Exchange::Bybit), "websocket disconnected").await.unwrap();
+        notifier.on_health_change(false, Some(Exchange::Bybit), "websocket disconnected").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_slow_webhook_times_out() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+            .mount(&server)
+            .await;
+
+        let notifier = WebhookNotifier::new(WebhookConfig {
+            url: Some(server.uri()),
+            max_retries: 0,
+            min_interval_secs: 60,
+            request_timeout_ms: 100,
+        });
+
+        let started = Instant::now();
+        assert!(notifier.notify(HealthEventType::EmergencyShutdown, None, "test").await.is_err());
+        assert!(started.elapsed() < Duration::from_secs(2), "{:?}", started.elapsed());
+    }
+}
@@ -0,0 +1,162 @@
+//! Prometheus text-format metrics endpoint
+//!
+//! Serves the realized spread-capture distribution of executed opportunities
+//! as a histogram on a plain HTTP `/metrics` endpoint for scraping.
+
+use crate::{config::PrometheusConfig, ArbitrageError, Result};
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::RwLock;
+use tracing::{debug, info};
+
+/// Cumulative Prometheus histogram with fixed upper bounds
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    bounds: Vec<f64>,
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    /// Create a histogram with the given bucket upper bounds; `+Inf` is implicit
+    pub fn new(mut bounds: Vec<f64>) -> Self {
+        bounds.retain(|bound| bound.is_finite());
+        bounds.sort_by(|a, b| a.total_cmp(b));
+        bounds.dedup();
+        Self { counts: vec![0; bounds.len()], bounds, sum: 0.0, count: 0 }
+    }
+
+    /// Record one observation
+    pub fn observe(&mut self, value: f64) {
+        for (bound, count) in self.bounds.iter().zip(self.counts.iter_mut()) {
+            if value <= *bound {
+                *count += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    /// Number of observations
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Append the histogram in Prometheus text exposition format
+    pub fn render(&self, name: &str, help: &str, out: &mut String) {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} histogram", name);
+        for (bound, count) in self.bounds.iter().zip(&self.counts) {
+            let _ = writeln!(out, "{}_bucket{{le=\"{}\"}} {}", name, bound, count);
+        }
+        let _ = writeln!(out, "{}_bucket{{le=\"+Inf\"}} {}", name, self.count);
+        let _ = writeln!(out, "{}_sum {}", name, self.sum);
+        let _ = writeln!(out, "{}_count {}", name, self.count);
+    }
+}
+
+/// Metrics registry served in Prometheus format
+pub struct MetricsExporter {
+    listen_addr: String,
+    spread_capture: RwLock<Histogram>,
+}
+
+impl MetricsExporter {
+    /// Build the exporter if enabled in config
+    pub fn from_config(config: &PrometheusConfig) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        Some(Self {
+            listen_addr: config.listen_addr.clone(),
+            spread_capture: RwLock::new(Histogram::new(config.spread_capture_buckets_bps.clone())),
+        })
+    }
+
+    /// Record the realized spread captured by an executed opportunity, in bps
+    pub async fn observe_spread_capture(&self, spread_bps: f64) {
+        self.spread_capture.write().await.observe(spread_bps);
+    }
+
+    /// Render every metric in Prometheus text exposition format
+    pub async fn render(&self) -> String {
+        let mut out = String::new();
+        self.spread_capture.read().await.render(
+            "arbitrage_spread_capture_bps",
+            "Realized spread captured per executed opportunity in basis points",
+            &mut out,
+        );
+        out
+    }
+
+    /// Bind the configured address and serve `/metrics` in the background; returns the bound address
+    pub async fn start_server(self: Arc<Self>) -> Result<SocketAddr> {
+        let listener = TcpListener::bind(&self.listen_addr)
+            .await
+            .map_err(|e| ArbitrageError::Connection(format!("Failed to bind metrics endpoint {}: {}", self.listen_addr, e)))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|e| ArbitrageError::Connection(format!("Metrics endpoint has no local address: {}", e)))?;
+        info!("Serving Prometheus metrics on http://{}/metrics", addr);
+
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, peer) = match listener.accept().await {
+                    Ok(accepted) => accepted,
+                    Err(e) => {
+                        debug!("Metrics endpoint accept failed: {}", e);
+                        continue;
+                    }
+                };
+                let exporter = self.clone();
+                tokio::spawn(async move {
+                    let mut request = [0u8; 1024];
+                    if stream.read(&mut request).await.is_err() {
+                        return;
+                    }
+                    let response = if request.starts_with(b"GET /metrics ") {
+                        let body = exporter.render().await;
+                        format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                            body.len(),
+                            body
+                        )
+                    } else {
+                        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+                    };
+                    if let Err(e) = stream.write_all(response.as_bytes()).await {
+                        debug!("Failed to answer metrics scrape from {}: {}", peer, e);
+                    }
+                });
+            }
+        });
+        Ok(addr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_buckets_are_cumulative() {
+        let mut histogram = Histogram::new(vec![10.0, 1.0, 5.0]);
+        for value in [0.5, 3.0, 7.0, 20.0] {
+            histogram.observe(value);
+        }
+        let mut out = String::new();
+        histogram.render("spread", "Spread", &mut out);
+
+        assert!(out.contains("# TYPE spread histogram"));
+        assert!(out.contains("spread_bucket{le=\"1\"} 1\n"));
+        assert!(out.contains("spread_bucket{le=\"5\"} 2\n"));
+        assert!(out.contains("spread_bucket{le=\"10\"} 3\n"));
+        assert!(out.contains("spread_bucket{le=\"+Inf\"} 4\n"));
+        assert!(out.contains("spread_sum 30.5\n"));
+        assert!(out.contains("spread_count 4\n"));
+    }
+}
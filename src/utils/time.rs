@@ -0,0 +1,160 @@
+//! Timestamp unit conversions and clocks
+//!
+//! Timestamps are nanoseconds since the Unix epoch internally. Exchanges report
+//! milliseconds (or occasionally seconds), so every ingestion boundary converts
+//! with these helpers rather than scaling inline.
+//!
+//! Wall-clock time can step when NTP corrects it, so durations, deadlines and
+//! latencies are measured on the monotonic side of a [`Clock`]; wall-clock time
+//! is for display, audit records and comparing against exchange timestamps.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// Nanoseconds per millisecond
+pub const NANOS_PER_MILLI: i64 = 1_000_000;
+
+/// Nanoseconds per second
+pub const NANOS_PER_SECOND: i64 = 1_000_000_000;
+
+/// Convert epoch milliseconds to nanoseconds
+pub fn ms_to_ns(ms: i64) -> i64 {
+    ms.saturating_mul(NANOS_PER_MILLI)
+}
+
+/// Convert epoch seconds to nanoseconds
+pub fn s_to_ns(s: i64) -> i64 {
+    s.saturating_mul(NANOS_PER_SECOND)
+}
+
+/// Convert epoch nanoseconds to milliseconds
+pub fn ns_to_ms(ns: i64) -> i64 {
+    ns / NANOS_PER_MILLI
+}
+
+/// Current time in epoch nanoseconds
+pub fn now_ns() -> i64 {
+    chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0)
+}
+
+/// Milliseconds from an epoch timestamp to `now_ns`, clamped at zero.
+///
+/// A timestamp ahead of the clock means the wall clock stepped backwards or the
+/// source is skewed; anything beyond `tolerance_ms` is logged.
+pub fn age_ms(now_ns: i64, then_ns: i64, tolerance_ms: u64) -> i64 {
+    let age_ms = ns_to_ms(now_ns.saturating_sub(then_ns));
+    if age_ms < 0 {
+        if -age_ms > tolerance_ms as i64 {
+            warn!("Timestamp is {} ms ahead of the wall clock; clock stepped or source is skewed", -age_ms);
+        }
+        return 0;
+    }
+    age_ms
+}
+
+/// Source of wall-clock and monotonic time
+pub trait Clock: Send + Sync {
+    /// Wall-clock time in epoch nanoseconds; may jump
+    fn wall_ns(&self) -> i64;
+
+    /// Monotonic nanoseconds since an arbitrary origin; never goes backwards
+    fn monotonic_ns(&self) -> i64;
+
+    /// Monotonic milliseconds since the clock's origin
+    fn monotonic_ms(&self) -> i64 {
+        ns_to_ms(self.monotonic_ns())
+    }
+}
+
+/// System wall clock paired with `Instant` for monotonic time
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    origin: Instant,
+}
+
+impl SystemClock {
+    /// Create a clock whose monotonic origin is now
+    pub fn new() -> Self {
+        Self { origin: Instant::now() }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn wall_ns(&self) -> i64 {
+        now_ns()
+    }
+
+    fn monotonic_ns(&self) -> i64 {
+        self.origin.elapsed().as_nanos().min(i64::MAX as u128) as i64
+    }
+}
+
+/// Manually driven clock for tests and replays
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    wall_ns: AtomicI64,
+    monotonic_ns: AtomicI64,
+}
+
+impl ManualClock {
+    /// Create a clock at the given wall time with its monotonic origin at zero
+    pub fn new(wall_ns: i64) -> Self {
+        Self { wall_ns: AtomicI64::new(wall_ns), monotonic_ns: AtomicI64::new(0) }
+    }
+
+    /// Let time pass on both the wall and monotonic clocks
+    pub fn advance(&self, elapsed: Duration) {
+        let elapsed_ns = elapsed.as_nanos().min(i64::MAX as u128) as i64;
+        self.wall_ns.fetch_add(elapsed_ns, Ordering::SeqCst);
+        self.monotonic_ns.fetch_add(elapsed_ns, Ordering::SeqCst);
+    }
+
+    /// Step the wall clock, as an NTP correction would, leaving monotonic time alone
+    pub fn set_wall_ns(&self, wall_ns: i64) {
+        self.wall_ns.store(wall_ns, Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn wall_ns(&self) -> i64 {
+        self.wall_ns.load(Ordering::SeqCst)
+    }
+
+    fn monotonic_ns(&self) -> i64 {
+        self.monotonic_ns.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_conversions() {
+        assert_eq!(ms_to_ns(1_700_000_000_123), 1_700_000_000_123_000_000);
+        assert_eq!(s_to_ns(1_700_000_000), 1_700_000_000_000_000_000);
+        assert_eq!(ns_to_ms(ms_to_ns(1_700_000_000_123)), 1_700_000_000_123);
+        assert_eq!(ms_to_ns(i64::MAX), i64::MAX);
+    }
+
+    #[test]
+    fn test_wall_step_leaves_monotonic_time_alone() {
+        let clock = ManualClock::new(ms_to_ns(1_700_000_000_000));
+        clock.advance(Duration::from_millis(250));
+        let seen_ns = clock.wall_ns();
+
+        clock.set_wall_ns(seen_ns - s_to_ns(3600));
+        assert_eq!(clock.monotonic_ms(), 250);
+        assert_eq!(age_ms(clock.wall_ns(), seen_ns, 1000), 0);
+
+        clock.advance(Duration::from_millis(10));
+        assert_eq!(clock.monotonic_ms(), 260);
+    }
+}
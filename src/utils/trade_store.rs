@@ -0,0 +1,413 @@
+//! SQLite persistence of opportunities and executed orders
+//!
+//! [`TradeWriter`] keeps the SQLite writes off the execution path: records are
+//! queued to a dedicated task that flushes them in one transaction per batch.
+
+use crate::{
+    config::TradeStoreConfig,
+    connectors::{Exchange, OrderResponse},
+    strategy::futures_arbitrage::FuturesArbitrageOpportunity,
+    ArbitrageError, Result,
+};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS opportunities (
+        correlation_id  TEXT PRIMARY KEY,
+        symbol          TEXT NOT NULL,
+        maker_exchange  TEXT NOT NULL,
+        taker_exchange  TEXT NOT NULL,
+        maker_side      TEXT NOT NULL,
+        taker_side      TEXT NOT NULL,
+        maker_price     REAL NOT NULL,
+        taker_price     REAL NOT NULL,
+        quantity        REAL NOT NULL,
+        spread_bps      REAL NOT NULL,
+        expected_profit REAL NOT NULL,
+        detected_at     INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS idx_opportunities_symbol ON opportunities (symbol, detected_at);
+
+    CREATE TABLE IF NOT EXISTS orders (
+        id              INTEGER PRIMARY KEY AUTOINCREMENT,
+        exchange        TEXT NOT NULL,
+        order_id        TEXT NOT NULL,
+        client_order_id TEXT,
+        symbol          TEXT NOT NULL,
+        side            TEXT NOT NULL,
+        quantity        REAL NOT NULL,
+        price           REAL NOT NULL,
+        status          TEXT NOT NULL,
+        filled_quantity REAL NOT NULL,
+        average_price   REAL,
+        timestamp       INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS idx_orders_timestamp ON orders (timestamp);
+    CREATE INDEX IF NOT EXISTS idx_orders_symbol ON orders (symbol, timestamp);
+";
+
+/// An executed order read back from the store
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeRecord {
+    /// Exchange the order was placed on
+    pub exchange: Exchange,
+    /// Order as reported by the exchange
+    pub order: OrderResponse,
+}
+
+/// SQLite sink for opportunities and executed orders
+pub struct TradeStore {
+    conn: Mutex<Connection>,
+}
+
+impl TradeStore {
+    /// Open (or create) a store at a file path
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        if let Some(parent) = path.as_ref().parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| ArbitrageError::Storage(format!("Failed to create trade store directory: {}", e)))?;
+        }
+        let conn = Connection::open(path)
+            .map_err(|e| ArbitrageError::Storage(format!("Failed to open trade store: {}", e)))?;
+        Self::with_connection(conn)
+    }
+
+    /// Open a store held in memory, for tests and dry runs
+    pub fn open_in_memory() -> Result<Self> {
+        let conn = Connection::open_in_memory()
+            .map_err(|e| ArbitrageError::Storage(format!("Failed to open in-memory trade store: {}", e)))?;
+        Self::with_connection(conn)
+    }
+
+    /// Open the configured store, or `None` when persistence is disabled
+    pub fn from_config(config: &TradeStoreConfig) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        Self::open(&config.path).map(Some)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)
+            .map_err(|e| ArbitrageError::Storage(format!("Failed to create trade store schema: {}", e)))?;
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    /// Record a detected opportunity; re-recording the same correlation ID is a no-op
+    pub fn record_opportunity(&self, opportunity: &FuturesArbitrageOpportunity) -> Result<()> {
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        insert_opportunity(&conn, opportunity)
+    }
+
+    /// Record an executed order
+    pub fn record_order(&self, exchange: Exchange, order: &OrderResponse) -> Result<()> {
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        insert_order(&conn, exchange, order)
+    }
+
+    /// Write a batch of records in a single transaction
+    fn write_batch(&self, writes: &[TradeWrite]) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        let tx = conn.transaction()
+            .map_err(|e| ArbitrageError::Storage(format!("Failed to begin trade store batch: {}", e)))?;
+        for write in writes {
+            match write {
+                TradeWrite::Opportunity(opportunity) => insert_opportunity(&tx, opportunity)?,
+                TradeWrite::Order(exchange, order) => insert_order(&tx, *exchange, order)?,
+            }
+        }
+        tx.commit()
+            .map_err(|e| ArbitrageError::Storage(format!("Failed to commit trade store batch: {}", e)).into())
+    }
+
+    /// Most recent executed orders, newest first
+    pub fn recent_trades(&self, limit: usize) -> Result<Vec<TradeRecord>> {
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        let mut statement = conn.prepare(
+            "SELECT exchange, order_id, client_order_id, symbol, side, quantity, price,
+                    status, filled_quantity, average_price, timestamp
+             FROM orders ORDER BY timestamp DESC, id DESC LIMIT ?1",
+        ).map_err(|e| ArbitrageError::Storage(format!("Failed to query trades: {}", e)))?;
+
+        let rows = statement
+            .query_map(params![limit as i64], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, String>(7)?,
+                    OrderResponse {
+                        order_id: row.get(1)?,
+                        client_order_id: row.get(2)?,
+                        symbol: row.get(3)?,
+                        side: crate::connectors::OrderSide::Buy,
+                        quantity: row.get(5)?,
+                        price: row.get(6)?,
+                        status: crate::connectors::OrderStatus::New,
+                        filled_quantity: row.get(8)?,
+                        average_price: row.get(9)?,
+                        timestamp: row.get(10)?,
+                    },
+                ))
+            })
+            .map_err(|e| ArbitrageError::Storage(format!("Failed to query trades: {}", e)))?;
+
+        let mut trades = Vec::new();
+        for row in rows {
+            let (exchange, side, status, mut order) =
+                row.map_err(|e| ArbitrageError::Storage(format!("Failed to read trade: {}", e)))?;
+            order.side = side.parse()?;
+            order.status = status.parse()?;
+            trades.push(TradeRecord { exchange: exchange.parse()?, order });
+        }
+        Ok(trades)
+    }
+}
+
+/// A record queued for the next batch
+enum TradeWrite {
+    Opportunity(FuturesArbitrageOpportunity),
+    Order(Exchange, OrderResponse),
+}
+
+/// Buffers trade store writes and flushes them in batches from a dedicated task.
+///
+/// A batch is written once `batch_size` records are queued or `flush_interval`
+/// has passed, whichever comes first; [`TradeWriter::shutdown`] flushes the rest.
+/// Records arriving after shutdown are written straight through.
+pub struct TradeWriter {
+    store: Arc<TradeStore>,
+    tx: Mutex<Option<mpsc::UnboundedSender<TradeWrite>>>,
+    worker: Mutex<Option<JoinHandle<()>>>,
+    batches_written: Arc<AtomicU64>,
+}
+
+impl TradeWriter {
+    /// Start a writer task over a store; must be called inside a Tokio runtime
+    pub fn new(store: TradeStore, batch_size: usize, flush_interval: Duration) -> Self {
+        let store = Arc::new(store);
+        let batches_written = Arc::new(AtomicU64::new(0));
+        if batch_size == 0 {
+            return Self { store, tx: Mutex::new(None), worker: Mutex::new(None), batches_written };
+        }
+        let (tx, rx) = mpsc::unbounded_channel();
+        let worker = tokio::spawn(run_writer(store.clone(), rx, batch_size, flush_interval, batches_written.clone()));
+        Self { store, tx: Mutex::new(Some(tx)), worker: Mutex::new(Some(worker)), batches_written }
+    }
+
+    /// Open the configured store and start its writer, or `None` when persistence is disabled
+    pub fn from_config(config: &TradeStoreConfig) -> Result<Option<Self>> {
+        Ok(TradeStore::from_config(config)?.map(|store| {
+            Self::new(store, config.batch_size, Duration::from_millis(config.flush_interval_ms.max(1)))
+        }))
+    }
+
+    /// Queue a detected opportunity
+    pub fn record_opportunity(&self, opportunity: &FuturesArbitrageOpportunity) -> Result<()> {
+        match self.queue(TradeWrite::Opportunity(opportunity.clone())) {
+            None => Ok(()),
+            Some(_) => self.store.record_opportunity(opportunity),
+        }
+    }
+
+    /// Queue an executed order
+    pub fn record_order(&self, exchange: Exchange, order: &OrderResponse) -> Result<()> {
+        match self.queue(TradeWrite::Order(exchange, order.clone())) {
+            None => Ok(()),
+            Some(_) => self.store.record_order(exchange, order),
+        }
+    }
+
+    /// Hand a record to the writer task; gives it back when there is no task to take it
+    fn queue(&self, write: TradeWrite) -> Option<TradeWrite> {
+        match self.tx.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+            Some(tx) => tx.send(write).err().map(|e| e.0),
+            None => Some(write),
+        }
+    }
+
+    /// Flush everything queued and stop the writer task
+    pub async fn shutdown(&self) {
+        self.tx.lock().unwrap_or_else(|e| e.into_inner()).take();
+        let worker = self.worker.lock().unwrap_or_else(|e| e.into_inner()).take();
+        if let Some(worker) = worker {
+            if let Err(e) = worker.await {
+                warn!("Trade store writer task failed: {}", e);
+            }
+        }
+    }
+
+    /// Batches flushed to the store so far
+    pub fn batches_written(&self) -> u64 {
+        self.batches_written.load(Ordering::Relaxed)
+    }
+
+    /// The underlying store, for reading records back
+    pub fn store(&self) -> &TradeStore {
+        &self.store
+    }
+}
+
+async fn run_writer(
+    store: Arc<TradeStore>,
+    mut rx: mpsc::UnboundedReceiver<TradeWrite>,
+    batch_size: usize,
+    flush_interval: Duration,
+    batches_written: Arc<AtomicU64>,
+) {
+    let mut buffer = Vec::with_capacity(batch_size);
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + flush_interval, flush_interval);
+    loop {
+        tokio::select! {
+            write = rx.recv() => match write {
+                Some(write) => {
+                    buffer.push(write);
+                    if buffer.len() >= batch_size {
+                        flush(&store, &mut buffer, &batches_written).await;
+                    }
+                }
+                None => {
+                    flush(&store, &mut buffer, &batches_written).await;
+                    return;
+                }
+            },
+            _ = interval.tick() => flush(&store, &mut buffer, &batches_written).await,
+        }
+    }
+}
+
+/// Write the buffered records on the blocking pool so SQLite I/O never stalls the runtime
+async fn flush(store: &Arc<TradeStore>, buffer: &mut Vec<TradeWrite>, batches_written: &AtomicU64) {
+    if buffer.is_empty() {
+        return;
+    }
+    let batch = std::mem::take(buffer);
+    let count = batch.len();
+    let store = store.clone();
+    match tokio::task::spawn_blocking(move || store.write_batch(&batch)).await {
+        Ok(Ok(())) => {
+            batches_written.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(Err(e)) => warn!("Failed to persist batch of {} trade records: {}", count, e),
+        Err(e) => warn!("Trade store flush task failed: {}", e),
+    }
+}
+
+/// Insert an opportunity row; a repeated correlation ID is ignored
+fn insert_opportunity(conn: &Connection, opportunity: &FuturesArbitrageOpportunity) -> Result<()> {
+    conn.execute(
+        "INSERT OR IGNORE INTO opportunities (
+            correlation_id, symbol, maker_exchange, taker_exchange, maker_side, taker_side,
+            maker_price, taker_price, quantity, spread_bps, expected_profit, detected_at
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+        params![
+            opportunity.correlation_id,
+            opportunity.symbol,
+            opportunity.maker_exchange.to_string(),
+            opportunity.taker_exchange.to_string(),
+            opportunity.maker_side.to_string(),
+            opportunity.taker_side.to_string(),
+            opportunity.maker_price,
+            opportunity.taker_price,
+            opportunity.quantity,
+            opportunity.spread_bps,
+            opportunity.expected_profit,
+            opportunity.timestamp,
+        ],
+    ).map_err(|e| ArbitrageError::Storage(format!("Failed to record opportunity: {}", e)))?;
+    Ok(())
+}
+
+/// Insert an executed order row
+fn insert_order(conn: &Connection, exchange: Exchange, order: &OrderResponse) -> Result<()> {
+    conn.execute(
+        "INSERT INTO orders (
+            exchange, order_id, client_order_id, symbol, side, quantity, price,
+            status, filled_quantity, average_price, timestamp
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+        params![
+            exchange.to_string(),
+            order.order_id,
+            order.client_order_id,
+            order.symbol,
+            order.side.to_string(),
+            order.quantity,
+            order.price,
+            order.status.to_string(),
+            order.filled_quantity,
+            order.average_price,
+            order.timestamp,
+        ],
+    ).map_err(|e| ArbitrageError::Storage(format!("Failed to record order: {}", e)))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::{OrderSide, OrderStatus};
+
+    fn filled_order(order_id: &str, side: OrderSide, timestamp: i64) -> OrderResponse {
+        OrderResponse {
+            order_id: order_id.to_string(),
+            client_order_id: Some(format!("maker_{}", order_id)),
+            symbol: "BTCUSDT".to_string(),
+            side,
+            quantity: 0.1,
+            price: 50000.0,
+            status: OrderStatus::Filled,
+            filled_quantity: 0.1,
+            average_price: Some(50000.0),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_recorded_trades_query_back_newest_first() {
+        let store = TradeStore::open_in_memory().unwrap();
+        store.record_order(Exchange::Binance, &filled_order("1", OrderSide::Buy, 1_000)).unwrap();
+        store.record_order(Exchange::Bybit, &filled_order("2", OrderSide::Sell, 2_000)).unwrap();
+        store.record_order(Exchange::Binance, &filled_order("3", OrderSide::Buy, 3_000)).unwrap();
+
+        let trades = store.recent_trades(2).unwrap();
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].order.order_id, "3");
+        assert_eq!(trades[1].exchange, Exchange::Bybit);
+        assert_eq!(trades[1].order.side, OrderSide::Sell);
+        assert_eq!(trades[1].order.status, OrderStatus::Filled);
+        assert_eq!(trades[1].order.client_order_id.as_deref(), Some("maker_2"));
+        assert_eq!(trades[1].order.average_price, Some(50000.0));
+    }
+
+    #[tokio::test]
+    async fn test_writer_batches_and_flushes_everything_on_shutdown() {
+        let writer = TradeWriter::new(TradeStore::open_in_memory().unwrap(), 10, Duration::from_secs(3600));
+        for i in 0..25 {
+            writer.record_order(Exchange::Binance, &filled_order(&i.to_string(), OrderSide::Buy, i)).unwrap();
+        }
+        // Two full batches go out on size; the last five wait for the interval or shutdown
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while writer.batches_written() < 2 {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        }).await.unwrap();
+        assert_eq!(writer.store().recent_trades(100).unwrap().len(), 20);
+
+        writer.shutdown().await;
+        assert_eq!(writer.batches_written(), 3);
+        let trades = writer.store().recent_trades(100).unwrap();
+        assert_eq!(trades.len(), 25);
+        assert_eq!(trades[0].order.order_id, "24");
+
+        // Late records are written straight through rather than dropped
+        writer.record_order(Exchange::Bybit, &filled_order("late", OrderSide::Sell, 100)).unwrap();
+        assert_eq!(writer.store().recent_trades(1).unwrap()[0].order.order_id, "late");
+    }
+}
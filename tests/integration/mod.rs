@@ -21,7 +21,7 @@ impl TestUtils {
     pub fn create_test_config() -> ArbitrageConfig {
         let mut config = ArbitrageConfig::default();
         config.strategy.symbol = "BTCUSDT".to_string();
-        config.strategy.min_spread_bps = 5; // Lower threshold for testing
+        config.strategy.min_spread_bps = 5.0; // Lower threshold for testing
         config.strategy.max_position_size = 0.1; // Smaller position for testing
         config.risk.max_drawdown = 0.1; // Higher tolerance for testing
         config
@@ -147,6 +147,14 @@ impl MockExchangeConnector {
 
 #[async_trait::async_trait]
 impl ExchangeConnector for MockExchangeConnector {
+    async fn refresh_symbol_rules(&mut self) -> Result<()> {
+        Ok(())
+    }
+    
+    fn symbol_rules(&self, _symbol: &str) -> Option<crate::connectors::SymbolRules> {
+        None
+    }
+    
     async fn connect(&mut self) -> Result<()> {
         self.connected = true;
         Ok(())
@@ -220,6 +228,14 @@ impl ExchangeConnector for MockExchangeConnector {
         })
     }
     
+    async fn place_oco(&self, order: &crate::connectors::OcoOrder) -> Result<crate::connectors::OcoResponse> {
+        // Return mock OCO response
+        Ok(crate::connectors::OcoResponse {
+            list_id: format!("mock_oco_{}", order.symbol),
+            order_ids: vec!["mock_tp".to_string(), "mock_stop".to_string()],
+        })
+    }
+    
     async fn cancel_order(&self, _symbol: &str, _order_id: &str) -> Result<crate::connectors::OrderResponse> {
         // Return mock cancel response
         Ok(crate::connectors::OrderResponse {
@@ -240,6 +256,10 @@ impl ExchangeConnector for MockExchangeConnector {
         Ok(crate::connectors::OrderStatus::Filled)
     }
     
+    async fn get_open_orders(&self, _symbol: Option<&str>) -> Result<Vec<crate::connectors::OrderResponse>> {
+        Ok(Vec::new())
+    }
+    
     fn get_market_data_receiver(&self) -> Option<mpsc::Receiver<crate::connectors::MarketDataUpdate>> {
         None // Mock implementation
     }
@@ -257,7 +277,7 @@ mod tests {
     fn test_create_test_config() {
         let config = TestUtils::create_test_config();
         assert_eq!(config.strategy.symbol, "BTCUSDT");
-        assert_eq!(config.strategy.min_spread_bps, 5);
+        assert_eq!(config.strategy.min_spread_bps, 5.0);
         assert!(config.validate().is_ok());
     }
 
@@ -21,6 +21,11 @@ fn create_test_binance_config() -> ExchangeConfig {
             connection_timeout_secs: 10,
             max_reconnect_attempts: 3,
             reconnect_delay_secs: 1,
+            max_rate_limit_retries: 3,
+            ws_compression: WsCompression::None,
+            ws_subscriptions_per_sec: 5.0,
+            ws_subscription_batch_size: 10,
+            ws_subscription_ack_timeout_ms: 5000,
         },
         auth: AuthConfig {
             api_key: "test_api_key".to_string(),
@@ -32,6 +37,7 @@ fn create_test_binance_config() -> ExchangeConfig {
         trading: TradingConfig {
             default_order_type: "LIMIT".to_string(),
             default_time_in_force: "GTC".to_string(),
+            order_types: Vec::new(),
             additional: HashMap::new(),
         },
         fees: FeeConfig {
@@ -58,6 +64,8 @@ fn create_test_binance_config() -> ExchangeConfig {
             streams: vec!["depth@100ms".to_string(), "trade".to_string()],
             topics: vec![],
             depth_levels: 20,
+            resync_on_gap: true,
+            snapshot_bootstrap: true,
             additional: HashMap::new(),
         },
         monitoring: MonitoringConfig {
@@ -66,6 +74,11 @@ fn create_test_binance_config() -> ExchangeConfig {
             enable_trade_logging: true,
             log_rotation_size_mb: 100,
             health_check_interval_secs: 30,
+            webhook: WebhookConfig::default(),
+            trade_store: TradeStoreConfig::default(),
+            prometheus: PrometheusConfig::default(),
+            audit_log: AuditLogConfig::default(),
+            book_integrity_interval_ms: 0,
         },
     }
 }
@@ -82,6 +95,11 @@ fn create_test_bybit_config() -> ExchangeConfig {
             connection_timeout_secs: 10,
             max_reconnect_attempts: 3,
             reconnect_delay_secs: 1,
+            max_rate_limit_retries: 3,
+            ws_compression: WsCompression::None,
+            ws_subscriptions_per_sec: 5.0,
+            ws_subscription_batch_size: 10,
+            ws_subscription_ack_timeout_ms: 5000,
         },
         auth: AuthConfig {
             api_key: "test_api_key".to_string(),
@@ -93,6 +111,7 @@ fn create_test_bybit_config() -> ExchangeConfig {
         trading: TradingConfig {
             default_order_type: "Limit".to_string(),
             default_time_in_force: "GTC".to_string(),
+            order_types: Vec::new(),
             additional: HashMap::new(),
         },
         fees: FeeConfig {
@@ -119,6 +138,8 @@ fn create_test_bybit_config() -> ExchangeConfig {
             streams: vec![],
             topics: vec!["orderbook.1".to_string(), "publicTrade".to_string()],
             depth_levels: 50,
+            resync_on_gap: true,
+            snapshot_bootstrap: true,
             additional: HashMap::new(),
         },
         monitoring: MonitoringConfig {
@@ -127,6 +148,11 @@ fn create_test_bybit_config() -> ExchangeConfig {
             enable_trade_logging: true,
             log_rotation_size_mb: 100,
             health_check_interval_secs: 30,
+            webhook: WebhookConfig::default(),
+            trade_store: TradeStoreConfig::default(),
+            prometheus: PrometheusConfig::default(),
+            audit_log: AuditLogConfig::default(),
+            book_integrity_interval_ms: 0,
         },
     }
 }
@@ -14,7 +14,7 @@ use tokio::time::timeout;
 fn create_test_config() -> ArbitrageConfig {
     let mut config = ArbitrageConfig::default();
     config.strategy.symbol = "BTCUSDT".to_string();
-    config.strategy.min_spread_bps = 5;
+    config.strategy.min_spread_bps = 5.0;
     config.strategy.max_position_size = 1.0;
     config.execution.slippage_tolerance = 0.001;
     config.execution.order_timeout_ms = 1000;
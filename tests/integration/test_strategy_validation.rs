@@ -15,7 +15,7 @@ use tokio::time::timeout;
 fn create_strategy_config() -> ArbitrageConfig {
     let mut config = ArbitrageConfig::default();
     config.strategy.symbol = "BTCUSDT".to_string();
-    config.strategy.min_spread_bps = 5; // 0.05% minimum spread
+    config.strategy.min_spread_bps = 5.0; // 0.05% minimum spread
     config.strategy.max_position_size = 1.0; // 1 BTC max position
     config.execution.min_order_size = 0.001; // 0.001 BTC minimum
     config.execution.slippage_tolerance = 0.001; // 0.1% slippage